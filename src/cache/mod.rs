@@ -167,6 +167,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             started_at: None,
             closed_at: None,
+            updated_at: None,
             assignee: None,
             tasks: vec![],
             metadata: HashMap::new(),
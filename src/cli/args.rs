@@ -0,0 +1,880 @@
+//! Command arguments shared by the CLI and the MCP server
+//!
+//! clap parses these structs from the command line, and the MCP server
+//! deserializes them from tool arguments and derives the tool input schemas
+//! from them. Doc comments double as `--help` text and schema descriptions,
+//! so both interfaces always accept the same arguments.
+
+use crate::cli::OutputFormatter;
+use crate::cli::handlers;
+use crate::error::Result;
+use clap::Args;
+use serde::Deserialize;
+
+/// Arguments of `board`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct BoardArgs {
+    /// Filter by assignee
+    #[arg(short, long)]
+    pub assignee: Option<String>,
+
+    /// Show only active tickets
+    #[arg(long)]
+    #[serde(default)]
+    pub active_only: bool,
+
+    /// Compact view (less spacing)
+    #[arg(short, long)]
+    #[serde(default)]
+    pub compact: bool,
+}
+
+impl BoardArgs {
+    /// Run `board`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_board_command(
+            self.assignee.as_deref(),
+            self.active_only,
+            self.compact,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `review`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct ReviewArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    pub ticket: Option<String>,
+
+    /// Review notes/comments
+    #[arg(short, long)]
+    pub notes: Option<String>,
+}
+
+impl ReviewArgs {
+    /// Run `review`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_review_command(self.ticket, self.notes.as_deref(), project_dir, output)
+    }
+}
+
+/// Arguments of `approve`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct ApproveArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    pub ticket: Option<String>,
+
+    /// Approval message
+    #[arg(short, long)]
+    pub message: Option<String>,
+}
+
+impl ApproveArgs {
+    /// Run `approve`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_approve_command(self.ticket, self.message.as_deref(), project_dir, output)
+    }
+}
+
+/// Arguments of `request-changes`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct RequestChangesArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    pub ticket: Option<String>,
+
+    /// Description of requested changes
+    #[arg(short, long)]
+    pub changes: String,
+}
+
+impl RequestChangesArgs {
+    /// Run `request-changes`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_request_changes_command(self.ticket, &self.changes, project_dir, output)
+    }
+}
+
+/// Arguments of `handoff`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct HandoffArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    pub ticket: Option<String>,
+
+    /// New assignee (agent or person name)
+    pub assignee: String,
+
+    /// Handoff notes
+    #[arg(short, long)]
+    pub notes: Option<String>,
+}
+
+impl HandoffArgs {
+    /// Run `handoff`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_handoff_command(
+            self.ticket,
+            &self.assignee,
+            self.notes.as_deref(),
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `archive`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct ArchiveArgs {
+    /// Ticket ID or slug (specification ID with --spec)
+    #[arg(required_unless_present_any = ["older_than", "status"])]
+    pub ticket: Option<String>,
+
+    /// Unarchive instead of archive
+    #[arg(short, long)]
+    #[serde(default)]
+    pub unarchive: bool,
+
+    /// Archive or restore a specification instead of a ticket
+    #[arg(long)]
+    #[serde(default)]
+    pub spec: bool,
+
+    /// Archive every ticket without activity for this long (e.g. 90d, 12w)
+    #[arg(long, conflicts_with_all = ["ticket", "unarchive", "spec"])]
+    pub older_than: Option<String>,
+
+    /// Archive every ticket with this status
+    #[arg(long, conflicts_with_all = ["ticket", "unarchive", "spec"])]
+    pub status: Option<String>,
+
+    /// Show what would be archived without changing anything
+    #[arg(long)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ArchiveArgs {
+    /// Run `archive`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        let params = handlers::ArchiveParams {
+            target: self.ticket,
+            unarchive: self.unarchive,
+            spec: self.spec,
+            older_than: self.older_than,
+            status: self.status,
+            dry_run: self.dry_run,
+        };
+        handlers::handle_archive_command(&params, project_dir, output)
+    }
+}
+
+/// Arguments of `task uncomplete`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct TaskUncompleteArgs {
+    /// Task ID
+    pub task: String,
+
+    /// Ticket ID or slug (defaults to active ticket)
+    #[arg(short, long)]
+    pub ticket: Option<String>,
+}
+
+impl TaskUncompleteArgs {
+    /// Run `task uncomplete`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_task_uncomplete(
+            self.task,
+            self.ticket,
+            project_dir.map(str::to_string),
+            output,
+        )
+    }
+}
+
+/// Arguments of `worktree create`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct WorktreeCreateArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    pub ticket: Option<String>,
+
+    /// Custom branch name (default: branch prefix and ticket slug)
+    #[arg(long)]
+    pub branch_name: Option<String>,
+}
+
+impl WorktreeCreateArgs {
+    /// Run `worktree create`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_worktree_create(self.ticket, self.branch_name, project_dir, output)
+    }
+}
+
+/// Arguments of `bulk update`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct BulkUpdateArgs {
+    /// Filter expression (e.g., "status:todo priority:high")
+    #[arg(short, long)]
+    pub filter: String,
+
+    /// New status to set
+    #[arg(long)]
+    pub status: Option<String>,
+
+    /// New priority to set
+    #[arg(long)]
+    pub priority: Option<String>,
+
+    /// New assignee to set
+    #[arg(long)]
+    pub assignee: Option<String>,
+
+    /// Dry run - show what would be updated
+    #[arg(short, long)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl BulkUpdateArgs {
+    /// Run `bulk update`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_bulk_update(
+            self.filter,
+            self.status,
+            self.priority,
+            self.assignee,
+            self.dry_run,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `bulk tag`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct BulkTagArgs {
+    /// Filter expression (e.g., "status:doing")
+    #[arg(short, long)]
+    pub filter: String,
+
+    /// Tags to add (comma-separated)
+    #[arg(long)]
+    pub add: Option<String>,
+
+    /// Tags to remove (comma-separated)
+    #[arg(long)]
+    pub remove: Option<String>,
+
+    /// Dry run - show what would be updated
+    #[arg(short, long)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl BulkTagArgs {
+    /// Run `bulk tag`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_bulk_tag(
+            self.filter,
+            self.add,
+            self.remove,
+            self.dry_run,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `bulk close`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct BulkCloseArgs {
+    /// Filter expression (e.g., "status:review")
+    #[arg(short, long)]
+    pub filter: String,
+
+    /// Close message
+    #[arg(short, long)]
+    pub message: Option<String>,
+
+    /// Archive the tickets
+    #[arg(short, long)]
+    #[serde(default)]
+    pub archive: bool,
+
+    /// Dry run - show what would be closed
+    #[arg(short, long)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl BulkCloseArgs {
+    /// Run `bulk close`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_bulk_close(
+            self.filter,
+            self.message,
+            self.archive,
+            self.dry_run,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `bulk archive`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct BulkArchiveArgs {
+    /// Filter expression
+    #[arg(short, long)]
+    pub filter: String,
+
+    /// Dry run - show what would be archived
+    #[arg(short, long)]
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl BulkArchiveArgs {
+    /// Run `bulk archive`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_bulk_archive(self.filter, self.dry_run, project_dir, output)
+    }
+}
+
+/// Arguments of `filter create`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct FilterCreateArgs {
+    /// Filter name
+    pub name: String,
+
+    /// Filter expression (e.g., "status:todo priority:high")
+    pub expression: String,
+
+    /// Filter description
+    #[arg(short, long)]
+    pub description: Option<String>,
+}
+
+impl FilterCreateArgs {
+    /// Run `filter create`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_filter_create(
+            self.name,
+            self.expression,
+            self.description,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `filter show`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct FilterShowArgs {
+    /// Filter name
+    pub name: String,
+}
+
+impl FilterShowArgs {
+    /// Run `filter show`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_filter_show(self.name, project_dir, output)
+    }
+}
+
+/// Arguments of `filter delete`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct FilterDeleteArgs {
+    /// Filter name
+    pub name: String,
+
+    /// Force deletion without confirmation
+    #[arg(short, long)]
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl FilterDeleteArgs {
+    /// Run `filter delete`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_filter_delete(self.name, self.force, project_dir, output)
+    }
+}
+
+/// Arguments of `filter apply`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct FilterApplyArgs {
+    /// Filter name
+    pub name: String,
+
+    /// Additional filter expression to combine
+    #[arg(short, long)]
+    pub additional: Option<String>,
+}
+
+impl FilterApplyArgs {
+    /// Run `filter apply`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_filter_apply(self.name, self.additional, project_dir, output)
+    }
+}
+
+/// Arguments of `alias create`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct AliasCreateArgs {
+    /// Alias name
+    pub name: String,
+
+    /// Command to execute (e.g., "list --status todo --priority high")
+    pub command: String,
+
+    /// Alias description
+    #[arg(short, long)]
+    pub description: Option<String>,
+}
+
+impl AliasCreateArgs {
+    /// Run `alias create`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_alias_create(
+            self.name,
+            self.command,
+            self.description,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `alias delete`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct AliasDeleteArgs {
+    /// Alias name
+    pub name: String,
+}
+
+impl AliasDeleteArgs {
+    /// Run `alias delete`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_alias_delete(self.name, project_dir, output)
+    }
+}
+
+/// Arguments of `time log`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct TimeLogArgs {
+    /// Time spent (e.g., "1h30m", "2h", "45m")
+    pub time: String,
+
+    /// Ticket ID or slug (defaults to active ticket)
+    #[arg(short, long)]
+    pub ticket: Option<String>,
+
+    /// Notes about the work done
+    #[arg(short, long)]
+    pub notes: Option<String>,
+
+    /// Date of work (defaults to today)
+    #[arg(short, long)]
+    pub date: Option<String>,
+}
+
+impl TimeLogArgs {
+    /// Run `time log`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_time_log(
+            self.time,
+            self.ticket,
+            self.notes,
+            self.date,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `time start`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct TimeStartArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    #[arg(short, long)]
+    pub ticket: Option<String>,
+
+    /// Notes about the work
+    #[arg(short, long)]
+    pub notes: Option<String>,
+}
+
+impl TimeStartArgs {
+    /// Run `time start`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_time_start(self.ticket, self.notes, project_dir, output)
+    }
+}
+
+/// Arguments of `time stop`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct TimeStopArgs {
+    /// Notes about the work done
+    #[arg(short, long)]
+    pub notes: Option<String>,
+}
+
+impl TimeStopArgs {
+    /// Run `time stop`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_time_stop(self.notes, project_dir, output)
+    }
+}
+
+/// Arguments of `time report`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct TimeReportArgs {
+    /// Ticket ID or slug (defaults to active ticket)
+    #[arg(short, long)]
+    pub ticket: Option<String>,
+
+    /// Show summary for all tickets
+    #[arg(short, long)]
+    #[serde(default)]
+    pub all: bool,
+
+    /// Date range start (e.g., "2024-11-01", "last week")
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Date range end
+    #[arg(long)]
+    pub until: Option<String>,
+}
+
+impl TimeReportArgs {
+    /// Run `time report`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_time_report(
+            self.ticket,
+            self.all,
+            self.since,
+            self.until,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `hook create`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct HookCreateArgs {
+    /// Hook name
+    pub name: String,
+
+    /// Event that triggers the hook
+    #[arg(short, long)]
+    pub event: String,
+
+    /// Command to execute
+    #[arg(short, long)]
+    pub command: String,
+
+    /// Description of the hook
+    #[arg(short, long)]
+    pub description: Option<String>,
+
+    /// Abort operation if hook fails (only for pre-* events)
+    #[arg(long)]
+    #[serde(default)]
+    pub abort_on_failure: bool,
+}
+
+impl HookCreateArgs {
+    /// Run `hook create`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_hook_create(
+            self.name,
+            self.event,
+            self.command,
+            self.description,
+            self.abort_on_failure,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of the `hook` commands that act on one hook
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct HookNameArgs {
+    /// Hook name
+    pub name: String,
+}
+
+impl HookNameArgs {
+    /// Run `hook delete`
+    pub fn delete(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_hook_delete(self.name, project_dir, output)
+    }
+
+    /// Run `hook enable`
+    pub fn enable(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_hook_enable(self.name, project_dir, output)
+    }
+
+    /// Run `hook disable`
+    pub fn disable(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_hook_disable(self.name, project_dir, output)
+    }
+
+    /// Run `hook test`
+    pub fn test(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_hook_test(self.name, project_dir, output)
+    }
+}
+
+/// Arguments of `spec status`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecStatusArgs {
+    /// Specification ID (defaults to active spec)
+    #[arg(short, long)]
+    pub spec: Option<String>,
+
+    /// Show detailed progress
+    #[arg(short, long)]
+    #[serde(default)]
+    pub detailed: bool,
+
+    /// Show validation status
+    #[arg(long)]
+    #[serde(default)]
+    pub validation: bool,
+}
+
+impl SpecStatusArgs {
+    /// Run `spec status`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        // The validation flag is handled within the status handler
+        handlers::handle_spec_status(
+            self.spec,
+            self.detailed || self.validation,
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `spec list`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecListArgs {
+    /// Filter by status (draft, `in_progress`, completed, approved)
+    #[arg(short, long)]
+    pub status: Option<String>,
+
+    /// Filter by phase (requirements, design, tasks)
+    #[arg(long)]
+    pub phase: Option<String>,
+
+    /// Show archived specs
+    #[arg(long)]
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl SpecListArgs {
+    /// Run `spec list`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_list(self.status, self.phase, self.archived, project_dir, output)
+    }
+}
+
+/// Arguments of `spec show`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecShowArgs {
+    /// Specification ID
+    pub spec: String,
+
+    /// Show all documents
+    #[arg(short, long)]
+    #[serde(default)]
+    pub all: bool,
+
+    /// Show in markdown format
+    #[arg(short, long)]
+    #[serde(default)]
+    pub markdown: bool,
+}
+
+impl SpecShowArgs {
+    /// Run `spec show`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_show(self.spec, self.all, self.markdown, project_dir, output)
+    }
+}
+
+/// Arguments of `spec delete`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecDeleteArgs {
+    /// Specification ID
+    pub spec: String,
+
+    /// Force deletion without confirmation
+    #[arg(short, long)]
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl SpecDeleteArgs {
+    /// Run `spec delete`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_delete(self.spec, self.force, project_dir, output)
+    }
+}
+
+/// Arguments of `spec activate`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecActivateArgs {
+    /// Specification ID
+    pub spec: String,
+}
+
+impl SpecActivateArgs {
+    /// Run `spec activate`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_activate(self.spec, project_dir, output)
+    }
+}
+
+/// Arguments of `spec approve`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecApproveArgs {
+    /// Specification ID
+    pub spec: String,
+
+    /// Phase to approve (requirements, design, tasks)
+    pub phase: String,
+
+    /// Approval message
+    #[arg(short, long)]
+    pub message: Option<String>,
+
+    /// Reviewer signing off (defaults to git user.name, then $USER)
+    #[arg(long)]
+    pub reviewer: Option<String>,
+}
+
+impl SpecApproveArgs {
+    /// Run `spec approve`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_approve(
+            &self.spec,
+            &self.phase,
+            self.message,
+            self.reviewer.as_deref(),
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `spec request-changes`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecRequestChangesArgs {
+    /// Specification ID
+    pub spec: String,
+
+    /// Phase to request changes on (requirements, design, tasks)
+    pub phase: String,
+
+    /// Description of the requested changes
+    #[arg(short, long)]
+    pub message: String,
+
+    /// Reviewer signing off (defaults to git user.name, then $USER)
+    #[arg(long)]
+    pub reviewer: Option<String>,
+}
+
+impl SpecRequestChangesArgs {
+    /// Run `spec request-changes`
+    pub fn run(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_request_changes(
+            &self.spec,
+            &self.phase,
+            &self.message,
+            self.reviewer.as_deref(),
+            project_dir,
+            output,
+        )
+    }
+}
+
+/// Arguments of `spec reviewers add` and `spec reviewers remove`
+#[derive(Args, Debug, Deserialize)]
+#[cfg_attr(feature = "mcp", derive(schemars::JsonSchema))]
+pub struct SpecReviewersArgs {
+    /// Phase (requirements, design, tasks)
+    pub phase: String,
+
+    /// Reviewer names
+    #[arg(required = true)]
+    pub names: Vec<String>,
+
+    /// Specification ID (defaults to active spec)
+    #[arg(short, long)]
+    pub spec: Option<String>,
+}
+
+impl SpecReviewersArgs {
+    /// Run `spec reviewers add`
+    pub fn add(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_reviewers(
+            self.spec.as_deref(),
+            &self.phase,
+            &self.names,
+            &[],
+            project_dir,
+            output,
+        )
+    }
+
+    /// Run `spec reviewers remove`
+    pub fn remove(self, project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
+        handlers::handle_spec_reviewers(
+            self.spec.as_deref(),
+            &self.phase,
+            &[],
+            &self.names,
+            project_dir,
+            output,
+        )
+    }
+}
@@ -1,3 +1,12 @@
+use super::args::{
+    AliasCreateArgs, AliasDeleteArgs, ApproveArgs, ArchiveArgs, BoardArgs, BulkArchiveArgs,
+    BulkCloseArgs, BulkTagArgs, BulkUpdateArgs, FilterApplyArgs, FilterCreateArgs,
+    FilterDeleteArgs, FilterShowArgs, HandoffArgs, HookCreateArgs, HookNameArgs,
+    RequestChangesArgs, ReviewArgs, SpecActivateArgs, SpecApproveArgs, SpecDeleteArgs,
+    SpecListArgs, SpecRequestChangesArgs, SpecReviewersArgs, SpecShowArgs, SpecStatusArgs,
+    TaskUncompleteArgs, TimeLogArgs, TimeReportArgs, TimeStartArgs, TimeStopArgs,
+    WorktreeCreateArgs,
+};
 use clap::{Parser, Subcommand};
 
 /// vibe-ticket: A high-performance ticket management system for Vide Coding
@@ -22,6 +31,10 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub project: Option<String>,
 
+    /// Override a configuration value for this run (e.g., ui.emoji=false)
+    #[arg(long, global = true, value_name = "KEY=VALUE")]
+    pub set: Vec<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -45,6 +58,10 @@ pub enum Commands {
         /// Generate CLAUDE.md for AI assistance
         #[arg(long = "claude-md", alias = "claude")]
         claude_md: bool,
+
+        /// Register `merge-driver` for ticket files in .gitattributes and .git/config
+        #[arg(long)]
+        install_merge_driver: bool,
     },
 
     /// Create a new ticket
@@ -121,18 +138,25 @@ pub enum Commands {
     },
 
     /// Show tickets in kanban board view
-    Board {
-        /// Filter by assignee
-        #[arg(short, long)]
-        assignee: Option<String>,
+    Board(BoardArgs),
 
-        /// Show only active tickets
-        #[arg(long)]
-        active_only: bool,
+    /// Triage tickets on a live, full-screen kanban board
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Saved filter to apply on start
+        #[arg(short, long)]
+        filter: Option<String>,
+    },
 
-        /// Compact view (less spacing)
+    /// Stream changes to ticket files as JSON lines
+    Watch {
+        /// Filter expression (e.g. "status:doing tag:bug"), or @name for a saved filter
         #[arg(short, long)]
-        compact: bool,
+        filter: Option<String>,
+
+        /// Shell command to run for each change, which is passed in `$VIBE_TICKET_CONTEXT`
+        #[arg(long)]
+        exec: Option<String>,
     },
 
     /// Start working on a ticket
@@ -188,50 +212,23 @@ pub enum Commands {
         /// Create a merge/pull request
         #[arg(long)]
         pr: bool,
+
+        /// Open the pull request as a draft
+        #[arg(long, requires = "pr")]
+        draft: bool,
     },
 
     /// Mark a ticket for review
-    Review {
-        /// Ticket ID or slug (defaults to active ticket)
-        ticket: Option<String>,
-
-        /// Review notes/comments
-        #[arg(short, long)]
-        notes: Option<String>,
-    },
+    Review(ReviewArgs),
 
     /// Approve a ticket and mark as done
-    Approve {
-        /// Ticket ID or slug (defaults to active ticket)
-        ticket: Option<String>,
-
-        /// Approval message
-        #[arg(short, long)]
-        message: Option<String>,
-    },
+    Approve(ApproveArgs),
 
     /// Request changes on a ticket
-    RequestChanges {
-        /// Ticket ID or slug (defaults to active ticket)
-        ticket: Option<String>,
-
-        /// Description of requested changes
-        #[arg(short, long)]
-        changes: String,
-    },
+    RequestChanges(RequestChangesArgs),
 
     /// Hand off a ticket to another agent/person
-    Handoff {
-        /// Ticket ID or slug (defaults to active ticket)
-        ticket: Option<String>,
-
-        /// New assignee (agent or person name)
-        assignee: String,
-
-        /// Handoff notes
-        #[arg(short, long)]
-        notes: Option<String>,
-    },
+    Handoff(HandoffArgs),
 
     /// Check the current status
     Check {
@@ -302,15 +299,8 @@ pub enum Commands {
         command: TaskCommands,
     },
 
-    /// Archive or unarchive tickets
-    Archive {
-        /// Ticket ID or slug
-        ticket: String,
-
-        /// Unarchive instead of archive
-        #[arg(short, long)]
-        unarchive: bool,
-    },
+    /// Move tickets or specifications into compressed cold storage, or restore them
+    Archive(ArchiveArgs),
 
     /// Search tickets
     Search {
@@ -332,11 +322,15 @@ pub enum Commands {
         /// Use regex
         #[arg(short, long)]
         regex: bool,
+
+        /// Also search archived tickets
+        #[arg(long)]
+        archived: bool,
     },
 
     /// Export tickets
     Export {
-        /// Output format (json, yaml, csv, markdown)
+        /// Output format (json, yaml, csv, markdown, html, dot, mermaid, svg)
         #[arg(short, long, default_value = "json")]
         format: String,
 
@@ -349,15 +343,73 @@ pub enum Commands {
         include_archived: bool,
     },
 
+    /// Visualize ticket and spec dependencies as a graph
+    Graph {
+        /// Output format (dot, mermaid, svg)
+        #[arg(short, long, default_value = "dot")]
+        format: String,
+
+        /// Group nodes by (status, spec, tag, assignee)
+        #[arg(short, long, default_value = "status")]
+        group_by: String,
+
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Include archived tickets
+        #[arg(long)]
+        include_archived: bool,
+    },
+
+    /// Synchronize tickets with GitHub issues
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommands,
+    },
+
+    /// Check project files for corruption and leftovers from crashed processes
+    Doctor {
+        /// Repair the problems found, moving bad files to .vibe-ticket/quarantine/
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Upgrade the project files to the format of this version
+    Migrate {
+        /// Show the changes without making them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Merge two versions of a ticket file (run by git as a merge driver)
+    MergeDriver {
+        /// Common ancestor version (%O)
+        base: String,
+
+        /// Our version, overwritten with the result (%A)
+        ours: String,
+
+        /// Their version (%B)
+        theirs: String,
+
+        /// Path of the file in the repository (%P)
+        path: Option<String>,
+    },
+
     /// Import tickets
     Import {
         /// Input file
         file: String,
 
-        /// Input format (json, yaml, csv)
+        /// Input format (json, yaml, csv, github, gitlab, jira)
         #[arg(short, long)]
         format: Option<String>,
 
+        /// YAML file mapping external states and priorities
+        #[arg(long)]
+        mapping: Option<String>,
+
         /// Skip validation
         #[arg(long)]
         skip_validation: bool,
@@ -480,11 +532,15 @@ pub enum ConfigCommands {
     Show {
         /// Show specific key
         key: Option<String>,
+
+        /// Show where each value comes from
+        #[arg(long)]
+        origin: bool,
     },
 
-    /// Set configuration value
+    /// Set configuration value in the project configuration file
     Set {
-        /// Configuration key (e.g., project.name, ui.emoji)
+        /// Configuration key (e.g., `project.name`, `git.worktree_prefix`)
         key: String,
 
         /// Value to set
@@ -543,14 +599,7 @@ pub enum TaskCommands {
     },
 
     /// Uncomplete a task
-    Uncomplete {
-        /// Task ID
-        task: String,
-
-        /// Ticket ID or slug (defaults to active ticket)
-        #[arg(short, long)]
-        ticket: Option<String>,
-    },
+    Uncomplete(TaskUncompleteArgs),
 
     /// List tasks in a ticket
     List {
@@ -728,76 +777,90 @@ pub enum SpecCommands {
     },
 
     /// Show specification status
-    Status {
-        /// Specification ID (defaults to active spec)
-        #[arg(short, long)]
-        spec: Option<String>,
+    Status(SpecStatusArgs),
 
-        /// Show detailed progress
-        #[arg(short, long)]
-        detailed: bool,
+    /// List all specifications
+    List(SpecListArgs),
 
-        /// Show validation status
-        #[arg(long)]
-        validation: bool,
-    },
+    /// Show specification details
+    Show(SpecShowArgs),
 
-    /// List all specifications
-    List {
-        /// Filter by status (draft, `in_progress`, completed, approved)
-        #[arg(short, long)]
-        status: Option<String>,
+    /// Delete a specification
+    Delete(SpecDeleteArgs),
 
-        /// Filter by phase (requirements, design, tasks)
-        #[arg(long)]
-        phase: Option<String>,
+    /// Approve a specification phase
+    Approve(SpecApproveArgs),
 
-        /// Show archived specs
-        #[arg(long)]
-        archived: bool,
+    /// Request changes on a specification phase
+    RequestChanges(SpecRequestChangesArgs),
+
+    /// Manage the required reviewers of a specification
+    Reviewers {
+        #[command(subcommand)]
+        command: SpecReviewersCommands,
     },
 
-    /// Show specification details
-    Show {
-        /// Specification ID
-        spec: String,
+    /// Set active specification
+    Activate(SpecActivateArgs),
 
-        /// Show all documents
+    /// List the recorded revisions of a specification
+    History {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+    },
+
+    /// Show differences between two revisions of a specification
+    Diff {
+        /// Older revision number (or "current")
+        from: String,
+
+        /// Newer revision number (or "current")
+        #[arg(default_value = "current")]
+        to: String,
+
+        /// Specification ID (defaults to active spec)
         #[arg(short, long)]
-        all: bool,
+        spec: Option<String>,
 
-        /// Show in markdown format
+        /// Only diff one document (requirements, design, tasks)
         #[arg(short, long)]
-        markdown: bool,
+        document: Option<String>,
     },
 
-    /// Delete a specification
-    Delete {
-        /// Specification ID
-        spec: String,
+    /// Restore a specification to a recorded revision
+    Restore {
+        /// Revision number to restore
+        revision: String,
 
-        /// Force deletion without confirmation
+        /// Specification ID (defaults to active spec)
         #[arg(short, long)]
-        force: bool,
+        spec: Option<String>,
     },
 
-    /// Approve a specification phase
-    Approve {
-        /// Specification ID
-        spec: String,
+    /// Sync task checkboxes in tasks.md with the status of exported tickets
+    Sync {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+    },
 
-        /// Phase to approve (requirements, design, tasks)
-        phase: String,
+    /// Lint specification documents for ambiguous wording
+    Lint {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
 
-        /// Approval message
-        #[arg(short, long)]
-        message: Option<String>,
+        /// Output format (text, json, sarif)
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
-    /// Set active specification
-    Activate {
-        /// Specification ID
-        spec: String,
+    /// Show the requirement traceability matrix of a specification
+    Trace {
+        /// Specification ID (defaults to active spec)
+        spec: Option<String>,
+
+        /// Render the matrix as a Markdown table
+        #[arg(long)]
+        markdown: bool,
     },
 
     /// Generate templates for spec-driven development
@@ -816,6 +879,16 @@ pub enum SpecCommands {
     },
 }
 
+/// Spec reviewer management commands
+#[derive(Subcommand, Debug)]
+pub enum SpecReviewersCommands {
+    /// Require reviewers to approve a phase
+    Add(SpecReviewersArgs),
+
+    /// Stop requiring reviewers to approve a phase
+    Remove(SpecReviewersArgs),
+}
+
 #[cfg(feature = "mcp")]
 #[derive(Subcommand, Debug)]
 pub enum McpCommands {
@@ -835,8 +908,39 @@ pub enum McpCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum SyncCommands {
+    /// Push tickets to GitHub issues
+    Push {
+        /// Ticket ID or slug (pushes all tickets if not specified)
+        ticket: Option<String>,
+
+        /// Overwrite issues that changed remotely since the last sync
+        #[arg(long)]
+        force: bool,
+
+        /// Show what would be pushed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Pull GitHub issues into tickets
+    Pull {
+        /// Take the remote version even if tickets have unpushed changes
+        #[arg(long)]
+        force: bool,
+
+        /// Show what would be pulled without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum WorktreeCommands {
+    /// Create a worktree for a ticket without starting it
+    Create(WorktreeCreateArgs),
+
     /// List all worktrees for vibe-ticket
     List {
         /// Show worktrees for all tickets
@@ -885,146 +989,46 @@ pub enum WorktreeCommands {
 #[derive(Subcommand, Debug)]
 pub enum BulkCommands {
     /// Update multiple tickets at once
-    Update {
-        /// Filter expression (e.g., "status:todo priority:high")
-        #[arg(short, long)]
-        filter: String,
-
-        /// New status to set
-        #[arg(long)]
-        status: Option<String>,
-
-        /// New priority to set
-        #[arg(long)]
-        priority: Option<String>,
-
-        /// New assignee to set
-        #[arg(long)]
-        assignee: Option<String>,
-
-        /// Dry run - show what would be updated
-        #[arg(short, long)]
-        dry_run: bool,
-    },
+    Update(BulkUpdateArgs),
 
     /// Add or remove tags from multiple tickets
-    Tag {
-        /// Filter expression (e.g., "status:doing")
-        #[arg(short, long)]
-        filter: String,
-
-        /// Tags to add (comma-separated)
-        #[arg(long)]
-        add: Option<String>,
-
-        /// Tags to remove (comma-separated)
-        #[arg(long)]
-        remove: Option<String>,
-
-        /// Dry run - show what would be updated
-        #[arg(short, long)]
-        dry_run: bool,
-    },
+    Tag(BulkTagArgs),
 
     /// Close multiple tickets at once
-    Close {
-        /// Filter expression (e.g., "status:review")
-        #[arg(short, long)]
-        filter: String,
-
-        /// Close message
-        #[arg(short, long)]
-        message: Option<String>,
-
-        /// Archive the tickets
-        #[arg(short, long)]
-        archive: bool,
-
-        /// Dry run - show what would be closed
-        #[arg(short, long)]
-        dry_run: bool,
-    },
+    Close(BulkCloseArgs),
 
     /// Archive multiple tickets at once
-    Archive {
-        /// Filter expression
-        #[arg(short, long)]
-        filter: String,
-
-        /// Dry run - show what would be archived
-        #[arg(short, long)]
-        dry_run: bool,
-    },
+    Archive(BulkArchiveArgs),
 }
 
 #[derive(Subcommand, Debug)]
 pub enum FilterCommands {
     /// Create a new saved filter
-    Create {
-        /// Filter name
-        name: String,
-
-        /// Filter expression (e.g., "status:todo priority:high")
-        expression: String,
-
-        /// Filter description
-        #[arg(short, long)]
-        description: Option<String>,
-    },
+    Create(FilterCreateArgs),
 
     /// List saved filters
     List,
 
     /// Show filter details
-    Show {
-        /// Filter name
-        name: String,
-    },
+    Show(FilterShowArgs),
 
     /// Delete a saved filter
-    Delete {
-        /// Filter name
-        name: String,
-
-        /// Force deletion without confirmation
-        #[arg(short, long)]
-        force: bool,
-    },
+    Delete(FilterDeleteArgs),
 
     /// Apply a saved filter (alias for `list --filter @name`)
-    Apply {
-        /// Filter name
-        name: String,
-
-        /// Additional filter expression to combine
-        #[arg(short, long)]
-        additional: Option<String>,
-    },
+    Apply(FilterApplyArgs),
 }
 
 #[derive(Subcommand, Debug)]
 pub enum AliasCommands {
     /// Create a new alias
-    Create {
-        /// Alias name
-        name: String,
-
-        /// Command to execute (e.g., "list --status todo --priority high")
-        command: String,
-
-        /// Alias description
-        #[arg(short, long)]
-        description: Option<String>,
-    },
+    Create(AliasCreateArgs),
 
     /// List all aliases
     List,
 
     /// Delete an alias
-    Delete {
-        /// Alias name
-        name: String,
-    },
+    Delete(AliasDeleteArgs),
 
     /// Run an alias
     Run {
@@ -1040,114 +1044,40 @@ pub enum AliasCommands {
 #[derive(Subcommand, Debug)]
 pub enum TimeCommands {
     /// Log time spent on a ticket
-    Log {
-        /// Time spent (e.g., "1h30m", "2h", "45m")
-        time: String,
-
-        /// Ticket ID or slug (defaults to active ticket)
-        #[arg(short, long)]
-        ticket: Option<String>,
-
-        /// Notes about the work done
-        #[arg(short, long)]
-        notes: Option<String>,
-
-        /// Date of work (defaults to today)
-        #[arg(short, long)]
-        date: Option<String>,
-    },
+    Log(TimeLogArgs),
 
     /// Start a timer for the current ticket
-    Start {
-        /// Ticket ID or slug (defaults to active ticket)
-        #[arg(short, long)]
-        ticket: Option<String>,
-
-        /// Notes about the work
-        #[arg(short, long)]
-        notes: Option<String>,
-    },
+    Start(TimeStartArgs),
 
     /// Stop the current timer and log time
-    Stop {
-        /// Notes about the work done
-        #[arg(short, long)]
-        notes: Option<String>,
-    },
+    Stop(TimeStopArgs),
 
     /// Show current timer status
     Status,
 
     /// Show time report for a ticket
-    Report {
-        /// Ticket ID or slug (defaults to active ticket)
-        #[arg(short, long)]
-        ticket: Option<String>,
-
-        /// Show summary for all tickets
-        #[arg(short, long)]
-        all: bool,
-
-        /// Date range start (e.g., "2024-11-01", "last week")
-        #[arg(long)]
-        since: Option<String>,
-
-        /// Date range end
-        #[arg(long)]
-        until: Option<String>,
-    },
+    Report(TimeReportArgs),
 }
 
 #[derive(Subcommand, Debug)]
 pub enum HookCommands {
     /// Create a new hook
-    Create {
-        /// Hook name
-        name: String,
-
-        /// Event that triggers the hook
-        #[arg(short, long)]
-        event: String,
-
-        /// Command to execute
-        #[arg(short, long)]
-        command: String,
-
-        /// Description of the hook
-        #[arg(short, long)]
-        description: Option<String>,
-
-        /// Abort operation if hook fails (only for pre-* events)
-        #[arg(long)]
-        abort_on_failure: bool,
-    },
+    Create(HookCreateArgs),
 
     /// List all hooks
     List,
 
     /// Delete a hook
-    Delete {
-        /// Hook name
-        name: String,
-    },
+    Delete(HookNameArgs),
 
     /// Enable a hook
-    Enable {
-        /// Hook name
-        name: String,
-    },
+    Enable(HookNameArgs),
 
     /// Disable a hook
-    Disable {
-        /// Hook name
-        name: String,
-    },
+    Disable(HookNameArgs),
 
     /// Test a hook with sample data
-    Test {
-        /// Hook name
-        name: String,
-    },
+    Test(HookNameArgs),
 }
 
 #[cfg(test)]
@@ -1158,7 +1088,10 @@ mod tests {
     /// Test basic CLI structure parsing
     #[test]
     fn test_cli_parse_basic() {
-        let cli = Cli::parse_from(["vibe-ticket", "--version"]);
+        let err = Cli::try_parse_from(["vibe-ticket", "--version"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::DisplayVersion);
+
+        let cli = Cli::parse_from(["vibe-ticket", "list"]);
         assert!(!cli.verbose);
         assert!(!cli.json);
         assert!(!cli.no_color);
@@ -1193,11 +1126,13 @@ mod tests {
                 description,
                 force,
                 claude_md,
+                install_merge_driver,
             } => {
                 assert!(name.is_none());
                 assert!(description.is_none());
                 assert!(!force);
                 assert!(!claude_md);
+                assert!(!install_merge_driver);
             },
             _ => panic!("Expected Init command"),
         }
@@ -1211,6 +1146,7 @@ mod tests {
             "Test description",
             "--force",
             "--claude-md",
+            "--install-merge-driver",
         ]);
         match cli.command {
             Commands::Init {
@@ -1218,11 +1154,13 @@ mod tests {
                 description,
                 force,
                 claude_md,
+                install_merge_driver,
             } => {
                 assert_eq!(name, Some("test-project".to_string()));
                 assert_eq!(description, Some("Test description".to_string()));
                 assert!(force);
                 assert!(claude_md);
+                assert!(install_merge_driver);
             },
             _ => panic!("Expected Init command"),
         }
@@ -1455,7 +1393,7 @@ mod tests {
         let cli = Cli::parse_from(["vibe-ticket", "config", "show"]);
         match cli.command {
             Commands::Config { command } => match command {
-                ConfigCommands::Show { key } => {
+                ConfigCommands::Show { key, .. } => {
                     assert!(key.is_none());
                 },
                 _ => panic!("Expected Config Show command"),
@@ -1635,6 +1573,30 @@ mod tests {
             },
             _ => panic!("Expected Export command"),
         }
+
+        // Test graph with grouping
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "graph",
+            "--format",
+            "svg",
+            "--group-by",
+            "spec",
+        ]);
+        match cli.command {
+            Commands::Graph {
+                format,
+                group_by,
+                output,
+                include_archived,
+            } => {
+                assert_eq!(format, "svg");
+                assert_eq!(group_by, "spec");
+                assert!(output.is_none());
+                assert!(!include_archived);
+            },
+            _ => panic!("Expected Graph command"),
+        }
     }
 
     /// Test command aliases
@@ -1662,6 +1624,7 @@ mod tests {
             "Completed feature",
             "--archive",
             "--pr",
+            "--draft",
         ]);
         match cli.command {
             Commands::Close {
@@ -1669,11 +1632,13 @@ mod tests {
                 message,
                 archive,
                 pr,
+                draft,
             } => {
                 assert_eq!(ticket, Some("feature-123".to_string()));
                 assert_eq!(message, Some("Completed feature".to_string()));
                 assert!(archive);
                 assert!(pr);
+                assert!(draft);
             },
             _ => panic!("Expected Close command"),
         }
@@ -1749,11 +1714,13 @@ mod tests {
             Commands::Import {
                 file,
                 format,
+                mapping,
                 skip_validation,
                 dry_run,
             } => {
                 assert_eq!(file, "data.json");
                 assert!(format.is_none());
+                assert!(mapping.is_none());
                 assert!(!skip_validation);
                 assert!(!dry_run);
             },
@@ -1773,11 +1740,13 @@ mod tests {
             Commands::Import {
                 file,
                 format,
+                mapping,
                 skip_validation,
                 dry_run,
             } => {
                 assert_eq!(file, "tickets.csv");
                 assert_eq!(format, Some("csv".to_string()));
+                assert!(mapping.is_none());
                 assert!(skip_validation);
                 assert!(dry_run);
             },
@@ -1855,21 +1824,42 @@ mod tests {
     fn test_archive_command() {
         let cli = Cli::parse_from(["vibe-ticket", "archive", "old-ticket"]);
         match cli.command {
-            Commands::Archive { ticket, unarchive } => {
-                assert_eq!(ticket, "old-ticket");
-                assert!(!unarchive);
+            Commands::Archive(args) => {
+                assert_eq!(args.ticket.as_deref(), Some("old-ticket"));
+                assert!(!args.unarchive);
             },
             _ => panic!("Expected Archive command"),
         }
 
         let cli = Cli::parse_from(["vibe-ticket", "archive", "ticket-123", "--unarchive"]);
         match cli.command {
-            Commands::Archive { ticket, unarchive } => {
-                assert_eq!(ticket, "ticket-123");
-                assert!(unarchive);
+            Commands::Archive(args) => {
+                assert_eq!(args.ticket.as_deref(), Some("ticket-123"));
+                assert!(args.unarchive);
             },
             _ => panic!("Expected Archive command"),
         }
+
+        let cli = Cli::parse_from([
+            "vibe-ticket",
+            "archive",
+            "--older-than",
+            "90d",
+            "--status",
+            "done",
+        ]);
+        match cli.command {
+            Commands::Archive(args) => {
+                assert_eq!(args.ticket, None);
+                assert_eq!(args.older_than.as_deref(), Some("90d"));
+                assert_eq!(args.status.as_deref(), Some("done"));
+            },
+            _ => panic!("Expected Archive command"),
+        }
+        assert!(Cli::try_parse_from(["vibe-ticket", "archive"]).is_err());
+        assert!(
+            Cli::try_parse_from(["vibe-ticket", "archive", "t-1", "--older-than", "90d"]).is_err()
+        );
     }
 
     /// Test search command filters
@@ -1931,14 +1921,10 @@ mod tests {
         ]);
         match cli.command {
             Commands::Spec { command } => match command {
-                SpecCommands::List {
-                    status,
-                    phase,
-                    archived,
-                } => {
-                    assert_eq!(status, Some("draft".to_string()));
-                    assert_eq!(phase, Some("requirements".to_string()));
-                    assert!(archived);
+                SpecCommands::List(args) => {
+                    assert_eq!(args.status, Some("draft".to_string()));
+                    assert_eq!(args.phase, Some("requirements".to_string()));
+                    assert!(args.archived);
                 },
                 _ => panic!("Expected Spec List command"),
             },
@@ -1956,14 +1942,11 @@ mod tests {
         ]);
         match cli.command {
             Commands::Spec { command } => match command {
-                SpecCommands::Approve {
-                    spec,
-                    phase,
-                    message,
-                } => {
-                    assert_eq!(spec, "spec-123");
-                    assert_eq!(phase, "design");
-                    assert_eq!(message, Some("LGTM".to_string()));
+                SpecCommands::Approve(args) => {
+                    assert_eq!(args.reviewer, None);
+                    assert_eq!(args.spec, "spec-123");
+                    assert_eq!(args.phase, "design");
+                    assert_eq!(args.message, Some("LGTM".to_string()));
                 },
                 _ => panic!("Expected Spec Approve command"),
             },
@@ -1,12 +1,32 @@
 //! Command alias handler for creating custom shortcuts
-
+//!
+//! Aliases are expanded in-process before the command line is parsed, so
+//! `vibe-ticket <alias> [args]` runs the aliased command with the same binary.
+//! Alias commands are split like a shell would split them and may use
+//! placeholders for the arguments given to the alias:
+//!
+//! - `$1`, `$2`, ... take the first, second, ... argument
+//! - `${name}` takes a named argument; names are bound, in order of first
+//!   appearance, to the arguments after those taken by numbered placeholders
+//! - `${1:-default}` and `${name:-default}` fall back to `default`
+//! - `$@` takes every argument not bound to a placeholder
+//!
+//! Arguments not bound to a placeholder are appended when the alias has no
+//! `$@`. Aliases may expand to other aliases. User aliases in
+//! `~/.config/vibe-ticket/aliases.yaml` are available in every project;
+//! project aliases override them.
+
+use crate::cli::Cli;
 use crate::cli::output::OutputFormatter;
-use crate::cli::utils::find_project_root;
+use crate::cli::utils::{find_project_root, user_config_dir};
 use crate::error::{Result, VibeTicketError};
+use crate::storage::write_project_file;
+use clap::CommandFactory;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// A command alias definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,14 +48,38 @@ pub struct Aliases {
 }
 
 impl Aliases {
-    /// Load aliases from file
+    /// Load project aliases from file
     pub fn load(project_dir: Option<&str>) -> Result<Self> {
-        let path = Self::aliases_path(project_dir)?;
+        Self::read(&Self::aliases_path(project_dir)?)
+    }
+
+    /// Load user aliases from the user configuration directory
+    pub fn load_user() -> Result<Self> {
+        user_config_dir().map_or_else(
+            || Ok(Self::default()),
+            |dir| Self::read(&dir.join("aliases.yaml")),
+        )
+    }
+
+    /// Load user aliases overridden by project aliases
+    ///
+    /// Outside a project only user aliases are loaded.
+    pub fn load_merged(project_dir: Option<&str>) -> Result<Self> {
+        let mut aliases = Self::load_user()?;
+        match Self::load(project_dir) {
+            Ok(project) => aliases.aliases.extend(project.aliases),
+            Err(VibeTicketError::ProjectNotInitialized) => {},
+            Err(e) => return Err(e),
+        }
+        Ok(aliases)
+    }
+
+    fn read(path: &Path) -> Result<Self> {
         if !path.exists() {
             return Ok(Self::default());
         }
 
-        let content = fs::read_to_string(&path)
+        let content = fs::read_to_string(path)
             .map_err(|e| VibeTicketError::custom(format!("Failed to read aliases file: {e}")))?;
         let aliases: Self = serde_yaml::from_str(&content)
             .map_err(|e| VibeTicketError::custom(format!("Failed to parse aliases file: {e}")))?;
@@ -47,8 +91,7 @@ impl Aliases {
         let path = Self::aliases_path(project_dir)?;
         let content = serde_yaml::to_string(self)
             .map_err(|e| VibeTicketError::custom(format!("Failed to serialize aliases: {e}")))?;
-        fs::write(&path, content)
-            .map_err(|e| VibeTicketError::custom(format!("Failed to write aliases file: {e}")))?;
+        write_project_file(&path, content)?;
         Ok(())
     }
 
@@ -69,9 +112,252 @@ impl Aliases {
     }
 
     /// Get an alias by name
+    #[must_use]
     pub fn get(&self, name: &str) -> Option<&CommandAlias> {
         self.aliases.get(name)
     }
+
+    /// Expand an alias and any aliases it expands to
+    ///
+    /// Returns the command line to run, without the program name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the alias is not found, its command cannot be
+    /// split, an argument without default is missing, or the aliases form
+    /// a cycle.
+    pub fn expand(&self, name: &str, args: &[String]) -> Result<Vec<String>> {
+        let mut chain = vec![name.to_string()];
+        let mut words = self.expand_once(name, args)?;
+        while let Some(next) = words
+            .first()
+            .filter(|word| !is_builtin_command(word) && self.get(word).is_some())
+            .cloned()
+        {
+            if chain.contains(&next) {
+                return Err(VibeTicketError::custom(format!(
+                    "Alias cycle: {} -> {next}",
+                    chain.join(" -> ")
+                )));
+            }
+            words = self.expand_once(&next, &words[1..])?;
+            chain.push(next);
+        }
+        Ok(words)
+    }
+
+    fn expand_once(&self, name: &str, args: &[String]) -> Result<Vec<String>> {
+        let alias = self
+            .get(name)
+            .ok_or_else(|| VibeTicketError::custom(format!("Alias '{name}' not found")))?;
+        let words = shell_words::split(&alias.command).map_err(|e| {
+            VibeTicketError::InvalidInput(format!("Alias '{name}' has an invalid command: {e}"))
+        })?;
+        substitute_placeholders(name, &words, args)
+    }
+}
+
+/// A part of a word in an alias command
+#[derive(Debug, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Arg {
+        key: String,
+        default: Option<String>,
+    },
+}
+
+/// Split a word into text and placeholders
+fn parse_word(alias: &str, word: &str) -> Result<Vec<Piece>> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = word.chars().peekable();
+    while let Some(c) = chars.next() {
+        let placeholder = match chars.peek() {
+            Some('{') if c == '$' => {
+                chars.next();
+                let mut body = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => body.push(c),
+                        None => {
+                            return Err(VibeTicketError::InvalidInput(format!(
+                                "Alias '{alias}' has an unterminated placeholder in '{word}'"
+                            )));
+                        },
+                    }
+                }
+                let (key, default) = match body.split_once(":-") {
+                    Some((key, default)) => (key.to_string(), Some(default.to_string())),
+                    None => (body, None),
+                };
+                if key.is_empty() {
+                    return Err(VibeTicketError::InvalidInput(format!(
+                        "Alias '{alias}' has an empty placeholder in '{word}'"
+                    )));
+                }
+                Piece::Arg { key, default }
+            },
+            Some(d) if c == '$' && d.is_ascii_digit() => {
+                let mut key = String::new();
+                while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                    key.push(d);
+                }
+                Piece::Arg { key, default: None }
+            },
+            _ => {
+                text.push(c);
+                continue;
+            },
+        };
+        if !text.is_empty() {
+            pieces.push(Piece::Text(std::mem::take(&mut text)));
+        }
+        pieces.push(placeholder);
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+/// Replace the placeholders of an alias command with the alias arguments
+fn substitute_placeholders(alias: &str, words: &[String], args: &[String]) -> Result<Vec<String>> {
+    let parsed = words
+        .iter()
+        .map(|word| parse_word(alias, word))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Named placeholders take the arguments after the numbered ones, in
+    // order of first appearance
+    let mut numbered = 0;
+    let mut names: Vec<&str> = Vec::new();
+    for piece in parsed.iter().flatten() {
+        if let Piece::Arg { key, .. } = piece {
+            match key.parse::<usize>() {
+                Ok(0) => {
+                    return Err(VibeTicketError::InvalidInput(format!(
+                        "Alias '{alias}' uses $0; arguments start at $1"
+                    )));
+                },
+                Ok(position) => numbered = numbered.max(position),
+                Err(_) if !names.contains(&key.as_str()) => names.push(key),
+                Err(_) => {},
+            }
+        }
+    }
+    let position_of = |key: &str| {
+        key.parse::<usize>().unwrap_or_else(|_| {
+            numbered + names.iter().position(|name| *name == key).unwrap_or(0) + 1
+        })
+    };
+    let bound = numbered + names.len();
+    let rest = args.get(bound..).unwrap_or_default();
+
+    let mut expanded = Vec::new();
+    let mut rest_used = false;
+    for (word, pieces) in words.iter().zip(&parsed) {
+        if word == "$@" {
+            expanded.extend_from_slice(rest);
+            rest_used = true;
+            continue;
+        }
+        let mut value = String::new();
+        let mut only_placeholders = true;
+        for piece in pieces {
+            match piece {
+                Piece::Text(text) => {
+                    value.push_str(text);
+                    only_placeholders = false;
+                },
+                Piece::Arg { key, default } => {
+                    let position = position_of(key);
+                    let arg = args.get(position - 1).or(default.as_ref()).ok_or_else(|| {
+                        VibeTicketError::InvalidInput(format!(
+                            "Alias '{alias}' needs argument {position} ({key})"
+                        ))
+                    })?;
+                    value.push_str(arg);
+                },
+            }
+        }
+        // Empty placeholders drop the word, so `${1:-}` makes an argument optional
+        if !(value.is_empty() && only_placeholders && !pieces.is_empty()) {
+            expanded.push(value);
+        }
+    }
+    if !rest_used {
+        expanded.extend_from_slice(rest);
+    }
+    Ok(expanded)
+}
+
+/// Whether `name` is a built-in command, which aliases cannot shadow
+fn is_builtin_command(name: &str) -> bool {
+    name == "help" || Cli::command().find_subcommand(name).is_some()
+}
+
+/// Expand an alias used as the command of a command line
+///
+/// `args` is the full command line including the program name. Global
+/// options before the command are kept, and `--project` selects the project
+/// whose aliases are used. Command lines that start with a built-in command
+/// or an unknown name are returned unchanged.
+///
+/// # Errors
+///
+/// Returns an error if the aliases cannot be loaded or expanded.
+pub fn expand_command_line(args: Vec<OsString>) -> Result<Vec<OsString>> {
+    let mut project = None;
+    let mut index = 1;
+    while let Some(arg) = args.get(index).and_then(|arg| arg.to_str()) {
+        match arg {
+            "-v" | "--verbose" | "-j" | "--json" | "--no-color" => index += 1,
+            "-p" | "--project" => {
+                project = args.get(index + 1).and_then(|arg| arg.to_str());
+                index += 2;
+            },
+            _ => {
+                if let Some(dir) = arg
+                    .strip_prefix("--project=")
+                    .or_else(|| arg.strip_prefix("-p").filter(|dir| !dir.is_empty()))
+                {
+                    project = Some(dir);
+                    index += 1;
+                } else {
+                    break;
+                }
+            },
+        }
+    }
+
+    let Some(name) = args.get(index).and_then(|arg| arg.to_str()) else {
+        return Ok(args);
+    };
+    if name.starts_with('-') || is_builtin_command(name) {
+        return Ok(args);
+    }
+    let aliases = Aliases::load_merged(project)?;
+    if aliases.get(name).is_none() {
+        return Ok(args);
+    }
+    let Some(alias_args) = args[index + 1..]
+        .iter()
+        .map(|arg| arg.to_str().map(str::to_string))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "Arguments of alias '{name}' must be valid UTF-8"
+        )));
+    };
+
+    let expanded = aliases.expand(name, &alias_args)?;
+    Ok(args[..index]
+        .iter()
+        .cloned()
+        .chain(expanded.into_iter().map(OsString::from))
+        .collect())
 }
 
 /// Handle alias create command
@@ -89,13 +375,8 @@ pub fn handle_alias_create(
         ));
     }
 
-    // Check for reserved names
-    let reserved = [
-        "init", "new", "list", "show", "edit", "close", "start", "check", "task", "search",
-        "export", "import", "config", "spec", "worktree", "mcp", "bulk", "filter", "alias", "time",
-        "board", "review", "approve", "handoff", "archive", "open",
-    ];
-    if reserved.contains(&name.as_str()) {
+    // Built-in commands always win, so an alias with their name would never run
+    if is_builtin_command(&name) {
         return Err(VibeTicketError::custom(format!(
             "Cannot use reserved command name '{name}' as an alias"
         )));
@@ -132,7 +413,7 @@ pub fn handle_alias_create(
         output.info(&format!("Command: {command}"));
         output.info("");
         output.info("Usage:");
-        output.info(&format!("  vibe-ticket alias run {name}"));
+        output.info(&format!("  vibe-ticket {name}"));
     }
 
     Ok(())
@@ -140,7 +421,22 @@ pub fn handle_alias_create(
 
 /// Handle alias list command
 pub fn handle_alias_list(project_dir: Option<&str>, output: &OutputFormatter) -> Result<()> {
-    let aliases = Aliases::load(project_dir)?;
+    let project = Aliases::load(project_dir)?;
+    let mut aliases = Aliases::load_user()?;
+    let user_names: Vec<String> = aliases
+        .aliases
+        .keys()
+        .filter(|name| project.get(name).is_none())
+        .cloned()
+        .collect();
+    aliases.aliases.extend(project.aliases);
+    let scope = |alias: &CommandAlias| {
+        if user_names.contains(&alias.name) {
+            "user"
+        } else {
+            "project"
+        }
+    };
 
     if aliases.aliases.is_empty() {
         output.info("No aliases defined");
@@ -155,7 +451,15 @@ pub fn handle_alias_list(project_dir: Option<&str>, output: &OutputFormatter) ->
     }
 
     if output.is_json() {
-        let alias_list: Vec<_> = aliases.aliases.values().collect();
+        let alias_list: Vec<_> = aliases
+            .aliases
+            .values()
+            .map(|alias| {
+                let mut value = serde_json::json!(alias);
+                value["scope"] = serde_json::json!(scope(alias));
+                value
+            })
+            .collect();
         output.print_json(&serde_json::json!({
             "aliases": alias_list,
             "count": alias_list.len(),
@@ -168,7 +472,11 @@ pub fn handle_alias_list(project_dir: Option<&str>, output: &OutputFormatter) ->
         alias_list.sort_by(|a, b| a.name.cmp(&b.name));
 
         for alias in alias_list {
-            output.info(&format!("  {}", alias.name));
+            if scope(alias) == "user" {
+                output.info(&format!("  {} (user)", alias.name));
+            } else {
+                output.info(&format!("  {}", alias.name));
+            }
             output.info(&format!("    Command: {}", alias.command));
             if let Some(desc) = &alias.description {
                 output.info(&format!("    Description: {desc}"));
@@ -189,6 +497,15 @@ pub fn handle_alias_delete(
     let mut aliases = Aliases::load(project_dir)?;
 
     if aliases.get(&name).is_none() {
+        if Aliases::load_user()?.get(&name).is_some() {
+            return Err(VibeTicketError::custom(format!(
+                "Alias '{name}' is a user alias. Remove it from {}",
+                user_config_dir()
+                    .unwrap_or_default()
+                    .join("aliases.yaml")
+                    .display()
+            )));
+        }
         return Err(VibeTicketError::custom(format!("Alias '{name}' not found")));
     }
 
@@ -207,56 +524,24 @@ pub fn handle_alias_delete(
     Ok(())
 }
 
-/// Handle alias run command
+/// Expand an alias for the `alias run` command
+///
+/// Returns the command line to run, without the program name. The caller
+/// parses and dispatches it, so the alias runs in this process.
+///
+/// # Errors
+///
+/// Returns an error if the aliases cannot be loaded or expanded.
 pub fn handle_alias_run(
-    name: String,
-    args: Vec<String>,
+    name: &str,
+    args: &[String],
     project_dir: Option<&str>,
-    output: &OutputFormatter,
-) -> Result<()> {
-    let aliases = Aliases::load(project_dir)?;
-
-    let alias = aliases
-        .get(&name)
-        .ok_or_else(|| VibeTicketError::custom(format!("Alias '{name}' not found")))?;
-
-    // Build the full command
-    let full_command = if args.is_empty() {
-        alias.command.clone()
-    } else {
-        format!("{} {}", alias.command, args.join(" "))
-    };
-
-    output.info(&format!("Running: vibe-ticket {full_command}"));
-    output.info("");
-
-    // Parse and execute the command
-    // Note: In a real implementation, we'd re-parse and dispatch to the appropriate handler
-    // For now, we'll spawn a subprocess to run the command
-    let status = std::process::Command::new("vibe-ticket")
-        .args(full_command.split_whitespace())
-        .status()
-        .map_err(|e| VibeTicketError::custom(format!("Failed to run command: {e}")))?;
-
-    if !status.success() {
-        return Err(VibeTicketError::custom(format!(
-            "Command exited with status: {}",
-            status.code().unwrap_or(-1)
-        )));
-    }
-
-    Ok(())
-}
-
-/// Get an alias if it exists (for use during command dispatch)
-#[allow(dead_code)]
-pub fn get_alias(name: &str, project_dir: Option<&str>) -> Option<String> {
-    Aliases::load(project_dir)
-        .ok()
-        .and_then(|aliases| aliases.get(name).map(|a| a.command.clone()))
+) -> Result<Vec<String>> {
+    Aliases::load_merged(project_dir)?.expand(name, args)
 }
 
 #[cfg(test)]
+#[allow(clippy::literal_string_with_formatting_args)] // Alias placeholders
 mod tests {
     use super::*;
 
@@ -274,4 +559,106 @@ mod tests {
         assert_eq!(parsed.name, alias.name);
         assert_eq!(parsed.command, alias.command);
     }
+
+    fn aliases(defs: &[(&str, &str)]) -> Aliases {
+        let mut aliases = Aliases::default();
+        for (name, command) in defs {
+            aliases.add(CommandAlias {
+                name: (*name).to_string(),
+                command: (*command).to_string(),
+                description: None,
+                created_at: chrono::Utc::now(),
+            });
+        }
+        aliases
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_expand_quoting_and_placeholders() {
+        let aliases = aliases(&[
+            ("fix", r#"new "$1" --title "Fix ${what:-the bug}""#),
+            ("urgent", "list --priority high $@ --open"),
+            ("look", "show ${ticket:-active}"),
+            ("maybe", "show ${1:-}"),
+        ]);
+
+        assert_eq!(
+            aliases.expand("fix", &args(&["crash"])).unwrap(),
+            args(&["new", "crash", "--title", "Fix the bug"])
+        );
+        assert_eq!(
+            aliases
+                .expand("fix", &args(&["crash", "the crash on start", "--start"]))
+                .unwrap(),
+            args(&[
+                "new",
+                "crash",
+                "--title",
+                "Fix the crash on start",
+                "--start"
+            ])
+        );
+        assert_eq!(
+            aliases.expand("urgent", &args(&["--json"])).unwrap(),
+            args(&["list", "--priority", "high", "--json", "--open"])
+        );
+        assert_eq!(
+            aliases.expand("look", &[]).unwrap(),
+            args(&["show", "active"])
+        );
+        assert_eq!(aliases.expand("maybe", &[]).unwrap(), args(&["show"]));
+
+        let error = aliases.expand("fix", &[]).unwrap_err().to_string();
+        assert!(error.contains("needs argument 1"), "{error}");
+    }
+
+    #[test]
+    fn test_expand_chain_and_cycle() {
+        let aliases = aliases(&[
+            ("todo", "list --status todo"),
+            ("mytodo", "todo --assignee me"),
+            ("ping", "pong"),
+            ("pong", "ping"),
+            ("list", "board"),
+        ]);
+
+        // Built-in commands are never expanded, even when an alias shadows them
+        assert_eq!(
+            aliases.expand("mytodo", &args(&["--json"])).unwrap(),
+            args(&["list", "--status", "todo", "--assignee", "me", "--json"])
+        );
+        let error = aliases.expand("ping", &[]).unwrap_err().to_string();
+        assert!(error.contains("ping -> pong -> ping"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_word_errors() {
+        assert!(parse_word("a", "${1").is_err());
+        assert!(parse_word("a", "${}").is_err());
+        assert_eq!(
+            parse_word("a", "v$2.x$").unwrap(),
+            vec![
+                Piece::Text("v".to_string()),
+                Piece::Arg {
+                    key: "2".to_string(),
+                    default: None
+                },
+                Piece::Text(".x$".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_command_line() {
+        let line = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+        // Built-in commands and options pass through without loading aliases
+        let builtin = line(&["vibe-ticket", "--json", "list", "--open"]);
+        assert_eq!(expand_command_line(builtin.clone()).unwrap(), builtin);
+        let help = line(&["vibe-ticket", "--help"]);
+        assert_eq!(expand_command_line(help.clone()).unwrap(), help);
+    }
 }
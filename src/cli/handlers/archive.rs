@@ -1,23 +1,46 @@
 //! Handler for the `archive` command
 //!
-//! This module implements the logic for archiving and unarchiving tickets.
+//! This module implements the logic for archiving and unarchiving tickets
+//! and specifications. Archived items are moved into the compressed
+//! [`ArchiveStore`] so regular loads no longer parse them.
 
 use crate::cli::handlers::common::resolve_ticket_ref;
 use crate::cli::{OutputFormatter, find_project_root};
+use crate::core::{Status, Ticket};
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use crate::specs::SpecManager;
+use crate::storage::archive::is_archived;
+use crate::storage::{ActiveTicketRepository, ArchiveStore, FileStorage, TicketRepository};
+use chrono::{DateTime, Duration, Utc};
+use std::path::Path;
+
+/// Parameters for the `archive` command
+pub struct ArchiveParams {
+    /// Ticket ID or slug, or specification ID with `spec`
+    pub target: Option<String>,
+    /// Restore from the archive instead of archiving
+    pub unarchive: bool,
+    /// Whether `target` is a specification
+    pub spec: bool,
+    /// Archive every ticket without activity for this long (e.g. `90d`)
+    pub older_than: Option<String>,
+    /// Archive every ticket with this status
+    pub status: Option<String>,
+    /// Only show what would be archived
+    pub dry_run: bool,
+}
 
 /// Handler for the `archive` command
 ///
-/// Archives or unarchives a ticket:
-/// 1. Archives completed tickets to clean up the active list
-/// 2. Adds metadata to mark the ticket as archived
-/// 3. Can unarchive tickets to restore them to the active list
+/// Archives or unarchives a ticket or specification, or archives every
+/// ticket matching `older_than` and `status`:
+/// 1. Moves archived items into `.vibe-ticket/archive/`, compressed
+/// 2. Keeps the active ticket out of the archive
+/// 3. Restores archived items to where they came from
 ///
 /// # Arguments
 ///
-/// * `ticket_ref` - Ticket ID or slug to archive/unarchive
-/// * `unarchive` - Whether to unarchive instead of archive
+/// * `params` - What to archive or restore
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
 ///
@@ -25,11 +48,10 @@ use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 ///
 /// Returns an error if:
 /// - The project is not initialized
-/// - The ticket is not found
-/// - Trying to archive an active ticket
+/// - The ticket or specification is not found
+/// - Trying to archive the active ticket
 pub fn handle_archive_command(
-    ticket_ref: &str,
-    unarchive: bool,
+    params: &ArchiveParams,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -39,127 +61,293 @@ pub fn handle_archive_command(
 
     // Initialize storage
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let archive = ArchiveStore::new(&vibe_ticket_dir);
+
+    match params.target.as_deref() {
+        Some(spec_id) if params.spec => {
+            archive_spec(&archive, &vibe_ticket_dir, spec_id, params, output)
+        },
+        Some(ticket_ref) if params.unarchive => {
+            unarchive_ticket(&storage, &archive, ticket_ref, output)
+        },
+        Some(ticket_ref) => archive_ticket(&storage, &archive, ticket_ref, output),
+        None => archive_matching(&storage, &archive, params, output),
+    }
+}
+
+fn archive_ticket(
+    storage: &FileStorage,
+    archive: &ArchiveStore,
+    ticket_ref: &str,
+    output: &OutputFormatter,
+) -> Result<()> {
+    // Resolve and load the ticket
+    let ticket = match resolve_ticket_ref(storage, ticket_ref).and_then(|id| storage.load(&id)) {
+        Ok(ticket) => ticket,
+        Err(e) => {
+            if archive.find_ticket(ticket_ref)?.is_some() {
+                return Err(VibeTicketError::custom("Ticket is already archived"));
+            }
+            return Err(e);
+        },
+    };
 
-    // Resolve ticket ID
-    let ticket_id = resolve_ticket_ref(&storage, ticket_ref)?;
+    // Check if ticket is the active ticket
+    if storage.get_all_active()?.contains(&ticket.id) {
+        return Err(VibeTicketError::custom(
+            "Cannot archive the active ticket. Close or switch to another ticket first.",
+        ));
+    }
+
+    // Check if ticket is in progress
+    if ticket.status == Status::Doing || ticket.status == Status::Review {
+        output.warning(&format!(
+            "Warning: Archiving a ticket with status '{}'. Consider closing it first.",
+            ticket.status
+        ));
+    }
 
-    // Load the ticket
-    let mut ticket = storage.load(&ticket_id)?;
+    let ticket = archive.archive_ticket(storage, ticket)?;
 
-    // Check if already in desired state
-    let is_archived = ticket
-        .metadata
-        .get("archived")
-        .and_then(serde_json::Value::as_bool)
-        .unwrap_or(false);
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "action": "archived",
+            "ticket": ticket_json(&ticket),
+        }))?;
+    } else {
+        output.success(&format!("Archived ticket: {}", ticket.slug));
+        output.info(&format!("Title: {}", ticket.title));
+        output.info(&format!("Status: {}", ticket.status));
+        output.info("\nThe ticket has been moved to .vibe-ticket/archive/.");
+        output.info("Use search --archived to find archived tickets.");
+        output.info("Use --unarchive flag to restore this ticket.");
+    }
+    Ok(())
+}
 
-    if unarchive {
-        // Unarchiving
-        if !is_archived {
+fn unarchive_ticket(
+    storage: &FileStorage,
+    archive: &ArchiveStore,
+    ticket_ref: &str,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let ticket = if let Some(archived) = archive.find_ticket(ticket_ref)? {
+        archive.restore_ticket(storage, &archived.id)?
+    } else {
+        // Tickets archived by older versions only carry the flag
+        let ticket_id = resolve_ticket_ref(storage, ticket_ref)?;
+        let mut ticket = storage.load(&ticket_id)?;
+        if !is_archived(&ticket) {
             return Err(VibeTicketError::custom("Ticket is not archived"));
         }
-
-        // Remove archive metadata
         ticket.metadata.remove("archived");
         ticket.metadata.remove("archived_at");
-
-        // Save the updated ticket
         storage.save(&ticket)?;
+        ticket
+    };
 
-        // Output results
-        if output.is_json() {
-            output.print_json(&serde_json::json!({
-                "status": "success",
-                "action": "unarchived",
-                "ticket": {
-                    "id": ticket.id.to_string(),
-                    "slug": ticket.slug,
-                    "title": ticket.title,
-                    "status": ticket.status.to_string(),
-                }
-            }))?;
-        } else {
-            output.success(&format!("Unarchived ticket: {}", ticket.slug));
-            output.info(&format!("Title: {}", ticket.title));
-            output.info(&format!("Status: {}", ticket.status));
-        }
+    // Output results
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "action": "unarchived",
+            "ticket": ticket_json(&ticket),
+        }))?;
     } else {
-        // Archiving
-        if is_archived {
-            return Err(VibeTicketError::custom("Ticket is already archived"));
-        }
-
-        // Check if ticket is the active ticket
-        if let Ok(Some(active_id)) = storage.get_active() {
-            if active_id == ticket.id {
-                return Err(VibeTicketError::custom(
-                    "Cannot archive the active ticket. Close or switch to another ticket first.",
-                ));
-            }
-        }
+        output.success(&format!("Unarchived ticket: {}", ticket.slug));
+        output.info(&format!("Title: {}", ticket.title));
+        output.info(&format!("Status: {}", ticket.status));
+    }
+    Ok(())
+}
 
-        // Check if ticket is in progress
-        if ticket.status == crate::core::Status::Doing
-            || ticket.status == crate::core::Status::Review
-        {
-            output.warning(&format!(
-                "Warning: Archiving a ticket with status '{}'. Consider closing it first.",
-                ticket.status
-            ));
-        }
+fn archive_matching(
+    storage: &FileStorage,
+    archive: &ArchiveStore,
+    params: &ArchiveParams,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let cutoff = params
+        .older_than
+        .as_deref()
+        .map(parse_age)
+        .transpose()?
+        .map(|age| Utc::now() - age);
+    let status = params
+        .status
+        .as_deref()
+        .map(str::parse::<Status>)
+        .transpose()
+        .map_err(VibeTicketError::InvalidInput)?;
+    let mut transaction = storage.begin("archive")?;
+    let active = storage.get_all_active()?;
 
-        // Add archive metadata
-        ticket
-            .metadata
-            .insert("archived".to_string(), serde_json::Value::Bool(true));
-        ticket.metadata.insert(
-            "archived_at".to_string(),
-            serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
-        );
+    let mut matching: Vec<Ticket> = storage
+        .load_all()?
+        .into_iter()
+        .filter(|t| !active.contains(&t.id))
+        .filter(|t| status.is_none_or(|status| t.status == status))
+        .filter(|t| cutoff.is_none_or(|cutoff| last_activity(t) < cutoff))
+        .collect();
+    matching.sort_by_key(last_activity);
 
-        // Save the updated ticket
-        storage.save(&ticket)?;
+    if !params.dry_run {
+        matching = matching
+            .into_iter()
+            .map(|ticket| archive.stage_ticket(&mut transaction, ticket))
+            .collect::<Result<_>>()?;
+        transaction.commit()?;
+    }
 
-        // Output results
-        if output.is_json() {
-            output.print_json(&serde_json::json!({
-                "status": "success",
-                "action": "archived",
-                "ticket": {
-                    "id": ticket.id.to_string(),
-                    "slug": ticket.slug,
-                    "title": ticket.title,
-                    "status": ticket.status.to_string(),
-                }
-            }))?;
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "action": if params.dry_run { "dry_run" } else { "archived" },
+            "count": matching.len(),
+            "tickets": matching.iter().map(ticket_json).collect::<Vec<_>>(),
+        }))?;
+    } else if matching.is_empty() {
+        output.info("No tickets to archive");
+    } else {
+        if params.dry_run {
+            output.info(&format!("Would archive {} ticket(s):", matching.len()));
         } else {
-            output.success(&format!("Archived ticket: {}", ticket.slug));
-            output.info(&format!("Title: {}", ticket.title));
-            output.info(&format!("Status: {}", ticket.status));
-            output.info("\nThe ticket has been archived and will not appear in regular listings.");
-            output.info("Use --archived flag with list command to see archived tickets.");
-            output.info("Use --unarchive flag to restore this ticket.");
+            output.success(&format!("Archived {} ticket(s):", matching.len()));
+        }
+        for ticket in &matching {
+            output.info(&format!(
+                "  - {} ({}, last activity {})",
+                ticket.slug,
+                ticket.status,
+                last_activity(ticket).format("%Y-%m-%d")
+            ));
         }
     }
+    Ok(())
+}
+
+fn archive_spec(
+    archive: &ArchiveStore,
+    vibe_ticket_dir: &Path,
+    spec_id: &str,
+    params: &ArchiveParams,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let specs_dir = vibe_ticket_dir.join("specs");
+    let bundle = if params.unarchive {
+        archive.restore_spec(&specs_dir, spec_id)?
+    } else {
+        let manager = SpecManager::new(specs_dir.clone());
+        let bundle = archive.archive_spec(&specs_dir, spec_id)?;
+        if manager.get_active_spec()?.as_deref() == Some(spec_id) {
+            manager.clear_active_spec()?;
+        }
+        bundle
+    };
+    let action = if params.unarchive {
+        "unarchived"
+    } else {
+        "archived"
+    };
 
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "action": action,
+            "spec": {
+                "id": bundle.metadata.id,
+                "title": bundle.metadata.title,
+                "files": bundle.files.len(),
+            }
+        }))?;
+    } else {
+        output.success(&format!(
+            "{} specification: {}",
+            if params.unarchive {
+                "Unarchived"
+            } else {
+                "Archived"
+            },
+            bundle.metadata.title
+        ));
+        output.info(&format!("ID: {}", bundle.metadata.id));
+    }
     Ok(())
 }
 
+fn ticket_json(ticket: &Ticket) -> serde_json::Value {
+    serde_json::json!({
+        "id": ticket.id.to_string(),
+        "slug": ticket.slug,
+        "title": ticket.title,
+        "status": ticket.status.to_string(),
+    })
+}
+
+/// When a ticket was last worked on: updated, closed, started or created
+fn last_activity(ticket: &Ticket) -> DateTime<Utc> {
+    ticket
+        .updated_at
+        .or(ticket.closed_at)
+        .or(ticket.started_at)
+        .unwrap_or(ticket.created_at)
+}
+
+/// Parse an age like `90d`, `12w` or `36h`
+fn parse_age(age: &str) -> Result<Duration> {
+    let invalid = || {
+        VibeTicketError::InvalidInput(format!(
+            "Invalid age: {age}. Use a number with h, d or w, like '90d'"
+        ))
+    };
+    let age = age.trim();
+    let unit = age.chars().last().ok_or_else(invalid)?;
+    let count: i64 = age[..age.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    match unit.to_ascii_lowercase() {
+        'h' => Ok(Duration::hours(count)),
+        'd' => Ok(Duration::days(count)),
+        'w' => Ok(Duration::weeks(count)),
+        _ => Err(invalid()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_archive_metadata() {
-        use serde_json::json;
-
-        let mut metadata = std::collections::HashMap::new();
-        metadata.insert("archived".to_string(), json!(true));
+        let mut ticket = Ticket::new("t", "T");
+        assert!(!is_archived(&ticket));
+        ticket
+            .metadata
+            .insert("archived".to_string(), serde_json::json!(true));
+        assert!(is_archived(&ticket));
+    }
 
-        let is_archived = metadata
-            .get("archived")
-            .and_then(serde_json::Value::as_bool)
-            .unwrap_or(false);
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_age("2W").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("36h").unwrap(), Duration::hours(36));
+        assert!(parse_age("90").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("").is_err());
+    }
 
-        assert!(is_archived);
+    #[test]
+    fn test_last_activity() {
+        let mut ticket = Ticket::new("t", "T");
+        assert_eq!(last_activity(&ticket), ticket.created_at);
+        let closed = Utc::now() + Duration::days(1);
+        ticket.closed_at = Some(closed);
+        assert_eq!(last_activity(&ticket), closed);
+        let updated = closed + Duration::days(1);
+        ticket.updated_at = Some(updated);
+        assert_eq!(last_activity(&ticket), updated);
     }
 }
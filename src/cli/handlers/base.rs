@@ -65,19 +65,11 @@ impl HandlerContext {
     /// - Failed to read the active ticket file
     /// - No active ticket is set
     pub fn get_active_ticket_id(&self) -> Result<TicketId> {
-        let active_ticket_path = self.tickets_dir.join("active_ticket");
-
-        if !active_ticket_path.exists() {
-            return Err(VibeTicketError::Custom(
+        self.storage.get_active_ticket()?.ok_or_else(|| {
+            VibeTicketError::Custom(
                 "No active ticket. Use 'vibe-ticket work-on' to select a ticket.".to_string(),
-            ));
-        }
-
-        let content = std::fs::read_to_string(&active_ticket_path)?;
-        let id_str = content.trim();
-
-        TicketId::parse_str(id_str)
-            .map_err(|_| VibeTicketError::Custom(format!("Invalid active ticket ID: {id_str}")))
+            )
+        })
     }
 
     /// Set the active ticket
@@ -86,9 +78,7 @@ impl HandlerContext {
     ///
     /// Returns an error if failed to write the active ticket file
     pub fn set_active_ticket(&self, ticket_id: &TicketId) -> Result<()> {
-        let active_ticket_path = self.tickets_dir.join("active_ticket");
-        std::fs::write(&active_ticket_path, ticket_id.to_string())?;
-        Ok(())
+        self.storage.set_active_ticket(ticket_id)
     }
 
     /// Clear the active ticket
@@ -97,11 +87,7 @@ impl HandlerContext {
     ///
     /// Returns an error if failed to remove the active ticket file
     pub fn clear_active_ticket(&self) -> Result<()> {
-        let active_ticket_path = self.tickets_dir.join("active_ticket");
-        if active_ticket_path.exists() {
-            std::fs::remove_file(&active_ticket_path)?;
-        }
-        Ok(())
+        self.storage.clear_active_ticket()
     }
 
     /// Resolve a ticket reference (ID, slug, or active)
@@ -120,11 +106,8 @@ impl HandlerContext {
             }
 
             // Try to find by slug
-            let tickets = self.storage.load_all()?;
-            for ticket in tickets {
-                if ticket.slug == ref_str {
-                    return Ok(ticket.id);
-                }
+            if let Some(id) = self.storage.find_ticket_id_by_slug(ref_str)? {
+                return Ok(id);
             }
 
             Err(VibeTicketError::Custom(format!(
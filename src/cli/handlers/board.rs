@@ -4,9 +4,9 @@
 //! displaying them in columns organized by status.
 
 use crate::cli::{OutputFormatter, find_project_root};
-use crate::core::{Status, Ticket};
+use crate::core::Status;
 use crate::error::Result;
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketSummary};
 use std::collections::HashMap;
 
 /// Handler for the `board` command
@@ -37,8 +37,8 @@ pub fn handle_board_command(
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
 
-    // Load tickets
-    let mut tickets = storage.load_all()?;
+    // Load ticket summaries from the index
+    let mut tickets = storage.load_summaries()?;
 
     // Filter by assignee if specified
     if let Some(assignee_filter) = assignee {
@@ -56,7 +56,7 @@ pub fn handle_board_command(
     }
 
     // Group tickets by status
-    let mut by_status: HashMap<Status, Vec<Ticket>> = HashMap::new();
+    let mut by_status: HashMap<Status, Vec<TicketSummary>> = HashMap::new();
     for ticket in tickets {
         by_status.entry(ticket.status).or_default().push(ticket);
     }
@@ -72,7 +72,10 @@ pub fn handle_board_command(
 }
 
 /// Output board as JSON
-fn output_json(by_status: &HashMap<Status, Vec<Ticket>>, output: &OutputFormatter) -> Result<()> {
+fn output_json(
+    by_status: &HashMap<Status, Vec<TicketSummary>>,
+    output: &OutputFormatter,
+) -> Result<()> {
     let mut board = HashMap::new();
 
     for (status, tickets) in by_status {
@@ -86,8 +89,8 @@ fn output_json(by_status: &HashMap<Status, Vec<Ticket>>, output: &OutputFormatte
                     "priority": t.priority.to_string(),
                     "assignee": t.assignee,
                     "tasks": {
-                        "total": t.tasks.len(),
-                        "completed": t.tasks.iter().filter(|task| task.completed).count(),
+                        "total": t.tasks_total,
+                        "completed": t.tasks_completed,
                     },
                 })
             })
@@ -100,7 +103,11 @@ fn output_json(by_status: &HashMap<Status, Vec<Ticket>>, output: &OutputFormatte
 }
 
 /// Output board as text
-fn output_text(by_status: &HashMap<Status, Vec<Ticket>>, compact: bool, output: &OutputFormatter) {
+fn output_text(
+    by_status: &HashMap<Status, Vec<TicketSummary>>,
+    compact: bool,
+    output: &OutputFormatter,
+) {
     let spacing = if compact { "" } else { "\n" };
 
     // Define column order
@@ -209,7 +216,7 @@ fn format_status_header(status: Status) -> String {
 }
 
 /// Format a ticket for display in a cell
-fn format_ticket_cell(ticket: &Ticket, _width: usize) -> String {
+fn format_ticket_cell(ticket: &TicketSummary, _width: usize) -> String {
     // Truncate title if too long
     let title = if ticket.title.len() > 12 {
         format!("{}...", &ticket.title[..9])
@@ -235,7 +242,7 @@ mod tests {
     #[test]
     fn test_format_ticket_cell() {
         let ticket = crate::core::Ticket::new("test".to_string(), "Test Title".to_string());
-        let cell = format_ticket_cell(&ticket, 20);
+        let cell = format_ticket_cell(&TicketSummary::from(&ticket), 20);
         assert!(!cell.is_empty());
     }
 
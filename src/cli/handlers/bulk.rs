@@ -2,19 +2,11 @@
 
 use crate::cli::output::OutputFormatter;
 use crate::cli::utils::find_project_root;
-use crate::core::{Priority, Status, Ticket};
+use crate::core::{Priority, Status, Ticket, TicketId};
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{FileStorage, TicketRepository};
-use chrono::Utc;
-
-/// Check if a ticket is archived (stored in metadata)
-fn is_archived(ticket: &Ticket) -> bool {
-    ticket
-        .metadata
-        .get("archived")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false)
-}
+use crate::specs::TaskSync;
+use crate::storage::archive::is_archived;
+use crate::storage::{ActiveTicketRepository, ArchiveStore, FileStorage, TicketRepository};
 
 /// Parse a filter expression into key-value pairs
 /// Format: "key:value key2:value2" or "key:value,value2"
@@ -85,6 +77,7 @@ pub fn handle_bulk_update(
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut transaction = storage.begin("bulk update")?;
 
     let filters = parse_filter_expression(&filter);
     let tickets = storage.load_all()?;
@@ -124,18 +117,13 @@ pub fn handle_bulk_update(
         return Ok(());
     }
 
-    let mut updated_count = 0;
+    // Tickets changing status are kept apart so their spec tasks follow
+    let mut updated = Vec::new();
+    let mut moved = Vec::new();
     for ticket in matching {
         let mut updated_ticket = ticket.clone();
         let mut changed = false;
 
-        if let Some(s) = new_status {
-            if updated_ticket.status != s {
-                updated_ticket.status = s;
-                changed = true;
-            }
-        }
-
         if let Some(p) = new_priority {
             if updated_ticket.priority != p {
                 updated_ticket.priority = p;
@@ -155,20 +143,33 @@ pub fn handle_bulk_update(
             }
         }
 
-        if changed {
-            storage.save(&updated_ticket)?;
-            updated_count += 1;
+        if new_status.is_some_and(|s| updated_ticket.status != s) {
+            moved.push(updated_ticket);
+        } else if changed {
+            updated.push(updated_ticket);
         }
     }
+    let sync = TaskSync::new(&vibe_ticket_dir);
+    let specs = match new_status {
+        Some(s) => sync.stage_statuses(&mut moved, s, &mut transaction)?,
+        None => Vec::new(),
+    };
+    for ticket in updated.iter().chain(&moved) {
+        transaction.save(ticket)?;
+    }
+    let report = transaction.commit()?;
+    sync.complete_specs(&specs)?;
+    let updated = applied_slugs(&report.saved, &tickets);
 
     if output.is_json() {
         output.print_json(&serde_json::json!({
             "status": "success",
-            "updated_count": updated_count,
+            "updated_count": updated.len(),
+            "updated": updated,
             "filter": filter,
         }))?;
     } else {
-        output.success(&format!("Updated {updated_count} ticket(s)"));
+        output.success(&format!("Updated {} ticket(s)", updated.len()));
     }
 
     Ok(())
@@ -186,6 +187,7 @@ pub fn handle_bulk_tag(
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut transaction = storage.begin("bulk tag")?;
 
     let filters = parse_filter_expression(&filter);
     let tickets = storage.load_all()?;
@@ -233,7 +235,6 @@ pub fn handle_bulk_tag(
         return Ok(());
     }
 
-    let mut updated_count = 0;
     for ticket in matching {
         let mut updated_ticket = ticket.clone();
         let mut changed = false;
@@ -255,21 +256,23 @@ pub fn handle_bulk_tag(
         }
 
         if changed {
-            storage.save(&updated_ticket)?;
-            updated_count += 1;
+            transaction.save(&updated_ticket)?;
         }
     }
+    let report = transaction.commit()?;
+    let updated = applied_slugs(&report.saved, &tickets);
 
     if output.is_json() {
         output.print_json(&serde_json::json!({
             "status": "success",
-            "updated_count": updated_count,
+            "updated_count": updated.len(),
+            "updated": updated,
             "filter": filter,
             "tags_added": tags_to_add,
             "tags_removed": tags_to_remove,
         }))?;
     } else {
-        output.success(&format!("Updated tags on {updated_count} ticket(s)"));
+        output.success(&format!("Updated tags on {} ticket(s)", updated.len()));
     }
 
     Ok(())
@@ -287,6 +290,7 @@ pub fn handle_bulk_close(
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut transaction = storage.begin("bulk close")?;
 
     let filters = parse_filter_expression(&filter);
     let tickets = storage.load_all()?;
@@ -317,12 +321,10 @@ pub fn handle_bulk_close(
         return Ok(());
     }
 
-    let mut closed_count = 0;
-    for ticket in matching {
-        let mut updated_ticket = ticket.clone();
-        updated_ticket.status = Status::Done;
-        updated_ticket.closed_at = Some(Utc::now());
-
+    let sync = TaskSync::new(&vibe_ticket_dir);
+    let mut closing: Vec<Ticket> = matching.into_iter().cloned().collect();
+    let specs = sync.stage_statuses(&mut closing, Status::Done, &mut transaction)?;
+    for mut updated_ticket in closing {
         if let Some(msg) = &message {
             // Add close message to metadata
             updated_ticket
@@ -337,14 +339,17 @@ pub fn handle_bulk_close(
                 .insert("archived".to_string(), serde_json::json!(true));
         }
 
-        storage.save(&updated_ticket)?;
-        closed_count += 1;
+        transaction.save(&updated_ticket)?;
     }
+    let report = transaction.commit()?;
+    sync.complete_specs(&specs)?;
+    let closed = applied_slugs(&report.saved, &tickets);
 
     if output.is_json() {
         output.print_json(&serde_json::json!({
             "status": "success",
-            "closed_count": closed_count,
+            "closed_count": closed.len(),
+            "closed": closed,
             "filter": filter,
             "archived": archive,
         }))?;
@@ -354,7 +359,7 @@ pub fn handle_bulk_close(
         } else {
             "Closed"
         };
-        output.success(&format!("{action} {closed_count} ticket(s)"));
+        output.success(&format!("{action} {} ticket(s)", closed.len()));
     }
 
     Ok(())
@@ -370,6 +375,7 @@ pub fn handle_bulk_archive(
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut transaction = storage.begin("bulk archive")?;
 
     let filters = parse_filter_expression(&filter);
     let tickets = storage.load_all()?;
@@ -397,30 +403,41 @@ pub fn handle_bulk_archive(
         return Ok(());
     }
 
-    let mut archived_count = 0;
+    let archive = ArchiveStore::new(&vibe_ticket_dir);
+    let active = storage.get_all_active()?;
     for ticket in matching {
-        let mut updated_ticket = ticket.clone();
-        // Store archived status in metadata
-        updated_ticket
-            .metadata
-            .insert("archived".to_string(), serde_json::json!(true));
-        storage.save(&updated_ticket)?;
-        archived_count += 1;
+        if active.contains(&ticket.id) {
+            output.warning(&format!("Skipping active ticket {}", ticket.slug));
+            continue;
+        }
+        archive.stage_ticket(&mut transaction, ticket.clone())?;
     }
+    let report = transaction.commit()?;
+    let archived = applied_slugs(&report.deleted, &tickets);
 
     if output.is_json() {
         output.print_json(&serde_json::json!({
             "status": "success",
-            "archived_count": archived_count,
+            "archived_count": archived.len(),
+            "archived": archived,
             "filter": filter,
         }))?;
     } else {
-        output.success(&format!("Archived {archived_count} ticket(s)"));
+        output.success(&format!("Archived {} ticket(s)", archived.len()));
     }
 
     Ok(())
 }
 
+/// Slugs of the tickets a transaction changed, in the order it changed them
+fn applied_slugs(applied: &[TicketId], tickets: &[Ticket]) -> Vec<String> {
+    applied
+        .iter()
+        .filter_map(|id| tickets.iter().find(|ticket| &ticket.id == id))
+        .map(|ticket| ticket.slug.clone())
+        .collect()
+}
+
 fn parse_status(s: &str) -> Result<Status> {
     match s.to_lowercase().as_str() {
         "todo" => Ok(Status::Todo),
@@ -481,4 +498,93 @@ mod tests {
         assert!(parse_priority("CRITICAL").is_ok());
         assert!(parse_priority("invalid").is_err());
     }
+
+    #[test]
+    fn test_bulk_update_reports_applied_tickets() {
+        let project = crate::test_utils::TestProject::new();
+        let login = project.create_ticket("Login");
+        let logout = project.create_ticket("Logout");
+        let mut done = logout.clone();
+        done.status = Status::Done;
+        project.storage.save(&done).unwrap();
+
+        let output = OutputFormatter::capture();
+        handle_bulk_update(
+            "status:todo".to_string(),
+            None,
+            Some("high".to_string()),
+            None,
+            false,
+            Some(project.root_path_str()),
+            &output,
+        )
+        .unwrap();
+
+        let report = &output.take_captured().json[0];
+        assert_eq!(report["updated"], serde_json::json!([login.slug]));
+        assert_eq!(
+            project.storage.load(&login.id).unwrap().priority,
+            Priority::High
+        );
+        assert_ne!(
+            project.storage.load(&logout.id).unwrap().priority,
+            Priority::High
+        );
+        assert!(!project.tickets_dir.join("local/journal").exists());
+    }
+
+    #[test]
+    fn test_bulk_close_syncs_spec_tasks() {
+        use crate::specs::{SpecDocumentType, SpecManager, SpecPhase};
+
+        let project = crate::test_utils::TestProject::new();
+        let manager = SpecManager::new(project.tickets_dir.join("specs"));
+        let spec_id = manager
+            .create_spec("Auth".to_string(), String::new())
+            .unwrap()
+            .id;
+        manager
+            .save_document(
+                &spec_id,
+                SpecDocumentType::Tasks,
+                "- [ ] T001 Login\n- [ ] T002 Logout\n",
+            )
+            .unwrap();
+        TaskSync::new(&project.tickets_dir)
+            .export(&manager.load_spec(&spec_id).unwrap())
+            .unwrap();
+
+        let output = OutputFormatter::capture();
+        handle_bulk_close(
+            "status:todo".to_string(),
+            None,
+            false,
+            false,
+            Some(project.root_path_str()),
+            &output,
+        )
+        .unwrap();
+
+        let tasks =
+            std::fs::read_to_string(manager.get_document_path(&spec_id, SpecDocumentType::Tasks))
+                .unwrap();
+        assert_eq!(tasks, "- [x] T001 Login\n- [x] T002 Logout\n");
+        assert_eq!(
+            manager
+                .load_spec(&spec_id)
+                .unwrap()
+                .metadata
+                .progress
+                .current_phase(),
+            SpecPhase::Completed
+        );
+        assert!(
+            project
+                .storage
+                .load_all()
+                .unwrap()
+                .iter()
+                .all(|ticket| ticket.closed_at.is_some())
+        );
+    }
 }
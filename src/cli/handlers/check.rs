@@ -6,7 +6,7 @@
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::{Status, Ticket};
 use crate::error::Result;
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository, TicketSummary};
 use chrono::{DateTime, Local, Utc};
 
 /// Handler for the `check` command
@@ -56,7 +56,7 @@ struct CheckData {
     active_ticket: Option<Ticket>,
     current_branch: Option<String>,
     statistics: Option<Statistics>,
-    recent_tickets: Vec<Ticket>,
+    recent_tickets: Vec<TicketSummary>,
 }
 
 /// Gather all data needed for check command
@@ -74,14 +74,15 @@ fn gather_check_data(detailed: bool, stats: bool, project_dir: Option<&str>) ->
     };
 
     let current_branch = get_current_git_branch(&project_root);
-    let statistics = if stats || detailed {
-        Some(calculate_statistics(&storage)?)
+    let summaries = if stats || detailed {
+        storage.load_summaries()?
     } else {
-        None
+        vec![]
     };
+    let statistics = (stats || detailed).then(|| calculate_statistics(&summaries));
 
     let recent_tickets = if detailed {
-        get_recent_tickets(&storage, 5)?
+        get_recent_tickets(summaries, 5)
     } else {
         vec![]
     };
@@ -205,7 +206,7 @@ fn display_statistics(stats: &Statistics, detailed: bool, output: &OutputFormatt
 }
 
 /// Display recent tickets
-fn display_recent_tickets(tickets: &[Ticket], output: &OutputFormatter) {
+fn display_recent_tickets(tickets: &[TicketSummary], output: &OutputFormatter) {
     output.info("");
     output.info("Recent tickets:");
     for ticket in tickets {
@@ -239,9 +240,7 @@ struct Statistics {
 }
 
 /// Calculate project statistics
-fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
-    let tickets = storage.load_all()?;
-
+fn calculate_statistics(tickets: &[TicketSummary]) -> Statistics {
     let mut stats = Statistics {
         total: tickets.len(),
         todo: 0,
@@ -255,7 +254,7 @@ fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
         low: 0,
     };
 
-    for ticket in &tickets {
+    for ticket in tickets {
         // Count by status
         match ticket.status {
             Status::Todo => stats.todo += 1,
@@ -274,20 +273,18 @@ fn calculate_statistics(storage: &FileStorage) -> Result<Statistics> {
         }
     }
 
-    Ok(stats)
+    stats
 }
 
 /// Get recent tickets sorted by creation date
-fn get_recent_tickets(storage: &FileStorage, limit: usize) -> Result<Vec<Ticket>> {
-    let mut tickets = storage.load_all()?;
-
+fn get_recent_tickets(mut tickets: Vec<TicketSummary>, limit: usize) -> Vec<TicketSummary> {
     // Sort by creation date (descending)
-    tickets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    tickets.sort_by_key(|t| std::cmp::Reverse(t.created_at));
 
     // Take the specified limit
     tickets.truncate(limit);
 
-    Ok(tickets)
+    tickets
 }
 
 /// Get current Git branch name
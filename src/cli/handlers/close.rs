@@ -3,12 +3,20 @@
 //! This module implements the logic for closing tickets,
 //! including status updates and optional archiving.
 
+use super::time::TimeTracking;
 use crate::cli::handlers::common::resolve_ticket_ref;
 use crate::cli::{OutputFormatter, find_project_root};
-use crate::core::Status;
+use crate::config::Config;
+use crate::core::{Status, Ticket};
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
-use chrono::Utc;
+use crate::forge::{
+    PullRequest, PullRequestContext, PullRequestDraft, PullRequestTemplate,
+    repository_from_remote_url,
+};
+use crate::specs::{SpecManager, SpecMetadata, TaskSync};
+use crate::storage::{ActiveTicketRepository, ArchiveStore, FileStorage, TicketRepository};
+use std::path::Path;
+use std::process::Command;
 
 /// Handler for the `close` command
 ///
@@ -18,7 +26,7 @@ use chrono::Utc;
 /// 3. Sets the `closed_at` timestamp
 /// 4. Clears the active ticket if it was the one being closed
 /// 5. Optionally archives the ticket
-/// 6. Optionally creates a pull request
+/// 6. Optionally opens a pull request on the configured forge
 ///
 /// # Arguments
 ///
@@ -26,6 +34,7 @@ use chrono::Utc;
 /// * `message` - Optional close message
 /// * `archive` - Whether to archive the ticket
 /// * `create_pr` - Whether to create a pull request
+/// * `draft` - Open the pull request as a draft
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
 ///
@@ -36,11 +45,13 @@ use chrono::Utc;
 /// - No ticket is specified and there's no active ticket
 /// - The ticket is not found
 /// - The ticket is already closed
+/// - The pull request could not be created (the ticket is still closed)
 pub fn handle_close_command(
     ticket_ref: Option<String>,
     message: Option<String>,
     archive: bool,
     create_pr: bool,
+    draft: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -72,10 +83,10 @@ pub fn handle_close_command(
         )));
     }
 
-    // Update ticket status and close time
+    // Update ticket status and close time, keeping the spec task this
+    // ticket was exported from in sync
     let previous_status = ticket.status;
-    ticket.status = Status::Done;
-    ticket.closed_at = Some(Utc::now());
+    TaskSync::new(&vibe_ticket_dir).set_status(&mut ticket, Status::Done)?;
 
     // Add close message to metadata if provided
     if let Some(msg) = &message {
@@ -104,23 +115,15 @@ pub fn handle_close_command(
         }
     }
 
-    // Create pull request if requested
-    if create_pr {
-        create_pull_request(&project_root, &ticket, output)?;
-    }
+    // Create pull request if requested. A failure is reported after the
+    // ticket has been closed so the close itself is not lost.
+    let pull_request = create_pr
+        .then(|| create_pull_request(&project_root, &storage, &mut ticket, draft))
+        .transpose();
 
-    // Archive if requested (for now, just add a flag to metadata)
+    // Move the closed ticket into the archive store if requested
     if archive {
-        // In a real implementation, we might move the ticket to an archive directory
-        let mut archived_ticket = ticket.clone();
-        archived_ticket
-            .metadata
-            .insert("archived".to_string(), serde_json::Value::Bool(true));
-        archived_ticket.metadata.insert(
-            "archived_at".to_string(),
-            serde_json::Value::String(Utc::now().to_rfc3339()),
-        );
-        storage.save(&archived_ticket)?;
+        ArchiveStore::new(&vibe_ticket_dir).archive_ticket(&storage, ticket.clone())?;
     }
 
     // Output results
@@ -136,7 +139,8 @@ pub fn handle_close_command(
                 "archived": archive,
             },
             "message": message,
-            "pr_created": create_pr,
+            "pr_created": matches!(pull_request, Ok(Some(_))),
+            "pull_request": pull_request.as_ref().ok().and_then(Option::as_ref),
         }))?;
     } else {
         output.success(&format!("Closed ticket: {}", ticket.slug));
@@ -151,8 +155,11 @@ pub fn handle_close_command(
             output.info("Ticket has been archived");
         }
 
-        if create_pr {
-            output.info("Pull request creation initiated");
+        if let Ok(Some(pull_request)) = &pull_request {
+            output.success(&format!("Created pull request: {}", pull_request.url));
+            for warning in &pull_request.warnings {
+                output.warning(warning);
+            }
         }
 
         // Calculate duration if started_at is available
@@ -166,86 +173,127 @@ pub fn handle_close_command(
         }
     }
 
-    Ok(())
+    pull_request.map(|_| ()).map_err(|e| {
+        VibeTicketError::custom(format!(
+            "Ticket closed, but the pull request could not be created: {e}"
+        ))
+    })
 }
 
-/// Create a pull request for the ticket
+/// Open a pull request for the ticket on the configured forge
+///
+/// The title and body are rendered from `.vibe-ticket/templates/pr.md` (or the
+/// built-in template), and the pull request URL is stored on the ticket.
 fn create_pull_request(
-    project_root: &std::path::Path,
-    ticket: &crate::core::Ticket,
-    output: &OutputFormatter,
-) -> Result<()> {
-    use std::process::Command;
+    project_root: &Path,
+    storage: &FileStorage,
+    ticket: &mut Ticket,
+    draft: bool,
+) -> Result<PullRequest> {
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let config = Config::load_from_path(vibe_ticket_dir.join("config.yaml")).unwrap_or_default();
+    let forge = config.forge;
+    let kind = forge.kind;
 
-    // Get current branch name
-    let current_branch = Command::new("git")
-        .arg("rev-parse")
-        .arg("--abbrev-ref")
-        .arg("HEAD")
-        .current_dir(project_root)
-        .output()
-        .map_err(|e| VibeTicketError::custom(format!("Failed to get current branch: {e}")))?;
+    let branch = git_output(project_root, &["symbolic-ref", "--short", "HEAD"]).map_err(|_| {
+        VibeTicketError::custom("Cannot create a pull request without a checked-out branch")
+    })?;
+    let repository = match forge.repository {
+        Some(repository) => repository,
+        None => {
+            let url = git_output(project_root, &["remote", "get-url", "origin"])?;
+            repository_from_remote_url(&url).ok_or_else(|| {
+                VibeTicketError::custom(format!(
+                    "Cannot determine the repository from remote '{url}'. \
+                     Run: vibe-ticket config set forge.repository <owner/repo>"
+                ))
+            })?
+        },
+    };
+    let api_url = forge
+        .api_url
+        .or_else(|| kind.default_api_url().map(str::to_string))
+        .ok_or_else(|| {
+            VibeTicketError::custom(format!(
+                "No API URL configured for {kind}. Run: vibe-ticket config set forge.api_url <url>"
+            ))
+        })?;
+    let token_env = forge
+        .token_env
+        .unwrap_or_else(|| kind.default_token_env().to_string());
+    let token = std::env::var(&token_env)
+        .ok()
+        .filter(|token| !token.is_empty());
 
-    if !current_branch.status.success() {
-        return Err(VibeTicketError::custom("Failed to get current branch name"));
-    }
+    let project_dir = project_root.to_str();
+    let time_spent = TimeTracking::load(project_dir)?.total_time_for_ticket(&ticket.id.to_string());
+    let specs = SpecManager::new(vibe_ticket_dir.join("specs"))
+        .list_specs()
+        .unwrap_or_default();
+    let spec = linked_spec(&specs, ticket);
 
-    let branch_name = String::from_utf8_lossy(&current_branch.stdout)
-        .trim()
-        .to_string();
+    let context = PullRequestContext::new(ticket, &branch, &forge.base_branch)
+        .with_time_spent(time_spent)
+        .with_spec(spec);
+    let (title, body) = PullRequestTemplate::load(&vibe_ticket_dir)?.render(&context)?;
 
-    // Check if we have the GitHub CLI installed
-    let gh_check = Command::new("gh").arg("--version").output();
+    // Reviewers come from config; the assignee authored the change
+    let reviewers = forge
+        .reviewers
+        .into_iter()
+        .filter(|reviewer| ticket.assignee.as_ref() != Some(reviewer))
+        .collect();
 
-    if gh_check.is_err() || !gh_check.unwrap().status.success() {
-        output.warning(
-            "GitHub CLI (gh) not found. Please install it to create pull requests automatically.",
-        );
-        output.info(&format!(
-            "You can create a pull request manually for branch: {branch_name}"
-        ));
-        return Ok(());
-    }
+    let pull_request = kind
+        .connect(&api_url, &repository, token)?
+        .create_pull_request(&PullRequestDraft {
+            title,
+            body,
+            head: branch,
+            base: forge.base_branch,
+            draft: draft || forge.draft,
+            reviewers,
+        })?;
 
-    // Create PR using GitHub CLI
-    let pr_title = format!("[{}] {}", ticket.slug, ticket.title);
-    let pr_body = format!(
-        "## Ticket: {}\n\n{}\n\n**Status:** {} → Done\n**Priority:** {}\n",
-        ticket.slug,
-        ticket.description,
-        if ticket.started_at.is_some() {
-            "Doing"
-        } else {
-            "Todo"
-        },
-        ticket.priority
+    ticket.metadata.insert(
+        "pr_url".to_string(),
+        serde_json::Value::String(pull_request.url.clone()),
     );
+    ticket
+        .metadata
+        .insert("pr_number".to_string(), pull_request.number.into());
+    storage.save(ticket)?;
+
+    Ok(pull_request)
+}
+
+/// Find the specification a ticket belongs to
+///
+/// A spec is linked either through its `ticket_id` or by the ticket being
+/// tagged with the spec ID.
+fn linked_spec<'a>(specs: &'a [SpecMetadata], ticket: &Ticket) -> Option<&'a SpecMetadata> {
+    let ticket_id = ticket.id.to_string();
+    specs
+        .iter()
+        .find(|spec| spec.ticket_id.as_deref() == Some(ticket_id.as_str()))
+        .or_else(|| specs.iter().find(|spec| ticket.tags.contains(&spec.id)))
+}
 
-    let create_pr = Command::new("gh")
-        .arg("pr")
-        .arg("create")
-        .arg("--title")
-        .arg(&pr_title)
-        .arg("--body")
-        .arg(&pr_body)
-        .arg("--head")
-        .arg(&branch_name)
+/// Run a git command in the project and return its trimmed output
+fn git_output(project_root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
         .current_dir(project_root)
         .output()
-        .map_err(|e| VibeTicketError::custom(format!("Failed to create PR: {e}")))?;
-
-    if create_pr.status.success() {
-        let pr_url = String::from_utf8_lossy(&create_pr.stdout)
-            .trim()
-            .to_string();
-        output.success(&format!("Created pull request: {pr_url}"));
-    } else {
-        let error_msg = String::from_utf8_lossy(&create_pr.stderr);
-        output.warning(&format!("Failed to create pull request: {error_msg}"));
-        output.info("You can create the pull request manually");
+        .map_err(|e| VibeTicketError::custom(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(VibeTicketError::custom(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
     }
-
-    Ok(())
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
 #[cfg(test)]
@@ -58,22 +58,7 @@ impl TicketOperation for HandlerContext {
     }
 
     fn resolve_ticket_ref(&self, ticket_ref: &str) -> Result<TicketId> {
-        // Try to parse as UUID first
-        if let Ok(id) = Uuid::parse_str(ticket_ref) {
-            return Ok(TicketId::from_uuid(id));
-        }
-
-        // Try to find by slug
-        let tickets = self.storage.load_all_tickets()?;
-        for ticket in tickets {
-            if ticket.slug == ticket_ref {
-                return Ok(ticket.id);
-            }
-        }
-
-        Err(VibeTicketError::TicketNotFound {
-            id: ticket_ref.to_string(),
-        })
+        resolve_ticket_ref(&self.storage, ticket_ref)
     }
 
     fn get_active_ticket_id(&self) -> Result<TicketId> {
@@ -91,11 +76,13 @@ pub fn resolve_ticket_ref(storage: &FileStorage, ticket_ref: &str) -> Result<Tic
     }
 
     // Try to find by slug
-    let tickets = storage.load_all_tickets()?;
-    for ticket in tickets {
-        if ticket.slug == ticket_ref {
-            return Ok(ticket.id);
-        }
+    if let Some(id) = storage.find_ticket_id_by_slug(ticket_ref)? {
+        return Ok(id);
+    }
+
+    // `active` names the active ticket unless a ticket has that slug
+    if ticket_ref == "active" {
+        return storage.get_active()?.ok_or(VibeTicketError::NoActiveTicket);
     }
 
     Err(VibeTicketError::TicketNotFound {
@@ -3,7 +3,8 @@
 //! This module implements the logic for managing project configuration.
 
 use crate::cli::{ConfigCommands, OutputFormatter, find_project_root};
-use crate::config::Config;
+use crate::config::layers::for_each_leaf;
+use crate::config::{Config, Origin};
 use crate::error::{Result, VibeTicketError};
 
 /// Handler for the `config` subcommands
@@ -29,7 +30,7 @@ pub fn handle_config_command(
     let config_path = project_root.join(".vibe-ticket/config.yaml");
 
     match command {
-        ConfigCommands::Show { key } => handle_show(key, &config_path, output),
+        ConfigCommands::Show { key, origin } => handle_show(key, origin, &config_path, output),
         ConfigCommands::Set { key, value } => handle_set(&key, &value, &config_path, output),
         ConfigCommands::Get { key } => handle_get(&key, &config_path, output),
         ConfigCommands::Reset { force } => handle_reset(force, &config_path, output),
@@ -51,68 +52,79 @@ pub fn handle_config_command(
 /// Show configuration values
 fn handle_show(
     key: Option<String>,
+    show_origin: bool,
     config_path: &std::path::Path,
     output: &OutputFormatter,
 ) -> Result<()> {
-    let config = Config::load_from_path(config_path)?;
+    let layered = Config::load_layered(config_path)?;
+    let origin_of = |key: &str| {
+        layered
+            .origins
+            .get(key)
+            .map_or_else(|| Origin::Default.to_string(), ToString::to_string)
+    };
 
-    if let Some(key) = key {
-        // Show specific key
-        let value = get_config_value(&config, &key)?;
-        if output.is_json() {
+    // Leaves below the requested key, or all of them
+    let tree = serde_json::to_value(&layered.config)?;
+    let root = match &key {
+        Some(key) => layered.config.get(key)?,
+        None => tree,
+    };
+    let mut values = Vec::new();
+    for_each_leaf(
+        &root,
+        key.as_deref().unwrap_or_default(),
+        &mut |leaf, value| {
+            values.push((leaf.to_string(), value.clone()));
+        },
+    );
+
+    if output.is_json() {
+        if show_origin {
+            let entries: serde_json::Map<_, _> = values
+                .iter()
+                .map(|(leaf, value)| {
+                    (
+                        leaf.clone(),
+                        serde_json::json!({ "value": value, "origin": origin_of(leaf) }),
+                    )
+                })
+                .collect();
+            output.print_json(&serde_json::Value::Object(entries))?;
+        } else if let Some(key) = key {
             output.print_json(&serde_json::json!({
                 "key": key,
-                "value": value,
+                "value": root,
             }))?;
         } else {
-            output.info(&format!("{}: {}", key, format_value(&value)));
+            output.print_json(&root)?;
         }
-    } else {
-        // Show all configuration
-        if output.is_json() {
-            output.print_json(&serde_json::to_value(&config)?)?;
-        } else {
-            output.success("Project Configuration:");
-            output.info("");
-
-            // Project section
-            output.info("[project]");
-            output.info(&format!("  name: {}", config.project.name));
-            if let Some(desc) = &config.project.description {
-                output.info(&format!("  description: {desc}"));
-            }
-            if let Some(assignee) = &config.project.default_assignee {
-                output.info(&format!("  default_assignee: {assignee}"));
-            }
-            output.info(&format!(
-                "  default_priority: {}",
-                config.project.default_priority
-            ));
-            output.info("");
-
-            // UI section
-            output.info("[ui]");
-            output.info(&format!("  theme: {}", config.ui.theme));
-            output.info(&format!("  emoji: {}", config.ui.emoji));
-            output.info(&format!("  page_size: {}", config.ui.page_size));
-            output.info(&format!("  date_format: {}", config.ui.date_format));
-            output.info("");
+        return Ok(());
+    }
 
-            // Git section
-            output.info("[git]");
-            output.info(&format!("  enabled: {}", config.git.enabled));
-            output.info(&format!("  branch_prefix: {}", config.git.branch_prefix));
-            output.info(&format!("  auto_branch: {}", config.git.auto_branch));
-            if let Some(template) = &config.git.commit_template {
-                output.info(&format!("  commit_template: {template}"));
-            }
+    if key.is_none() {
+        output.success("Project Configuration:");
+    }
+    let mut section = "";
+    for (leaf, value) in &values {
+        let line = format!("{leaf}: {}", format_value(value));
+        let line = if show_origin {
+            format!("{line}  ({})", origin_of(leaf))
+        } else {
+            line
+        };
+        if key.is_some() {
+            output.info(&line);
+            continue;
+        }
+        // Group by top-level section
+        let (leaf_section, _) = leaf.split_once('.').unwrap_or((leaf, ""));
+        if leaf_section != section {
             output.info("");
-
-            // Plugins section
-            output.info("[plugins]");
-            output.info(&format!("  enabled: {:?}", config.plugins.enabled));
-            output.info(&format!("  directory: {}", config.plugins.directory));
+            output.info(&format!("[{leaf_section}]"));
+            section = leaf_section;
         }
+        output.info(&format!("  {}", &line[leaf_section.len() + 1..]));
     }
 
     Ok(())
@@ -125,22 +137,16 @@ fn handle_set(
     config_path: &std::path::Path,
     output: &OutputFormatter,
 ) -> Result<()> {
-    let mut config = Config::load_from_path(config_path)?;
-
-    // Parse and set the value
-    set_config_value(&mut config, key, value)?;
-
-    // Save the configuration
-    config.save_to_path(config_path)?;
+    let stored = Config::set_in_file(config_path, key, value)?;
 
     if output.is_json() {
         output.print_json(&serde_json::json!({
             "status": "success",
             "key": key,
-            "value": value,
+            "value": stored,
         }))?;
     } else {
-        output.success(&format!("Set {key} = {value}"));
+        output.success(&format!("Set {key} = {}", format_value(&stored)));
     }
 
     Ok(())
@@ -149,7 +155,7 @@ fn handle_set(
 /// Get a specific configuration value
 fn handle_get(key: &str, config_path: &std::path::Path, output: &OutputFormatter) -> Result<()> {
     let config = Config::load_from_path(config_path)?;
-    let value = get_config_value(&config, key)?;
+    let value = config.get(key)?;
 
     if output.is_json() {
         output.print_json(&value)?;
@@ -190,87 +196,6 @@ fn handle_reset(
     Ok(())
 }
 
-/// Get a configuration value by key path
-fn get_config_value(config: &Config, key: &str) -> Result<serde_json::Value> {
-    // Convert config to JSON for easy path access
-    let json = serde_json::to_value(config)?;
-
-    // Split the key path
-    let parts: Vec<&str> = key.split('.').collect();
-    let mut current = &json;
-
-    for part in parts {
-        match current.get(part) {
-            Some(value) => current = value,
-            None => {
-                return Err(VibeTicketError::custom(format!(
-                    "Configuration key '{key}' not found"
-                )));
-            },
-        }
-    }
-
-    Ok(current.clone())
-}
-
-/// Set a configuration value by key path
-fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
-    match key {
-        "project.name" => config.project.name = value.to_string(),
-        "project.description" => config.project.description = Some(value.to_string()),
-        "project.default_assignee" => config.project.default_assignee = Some(value.to_string()),
-        "project.default_priority" => {
-            // Validate priority
-            if !["low", "medium", "high", "critical"].contains(&value) {
-                return Err(VibeTicketError::custom(
-                    "Invalid priority. Must be one of: low, medium, high, critical",
-                ));
-            }
-            config.project.default_priority = value.to_string();
-        },
-        "ui.theme" => {
-            // Validate theme
-            if !["light", "dark", "auto"].contains(&value) {
-                return Err(VibeTicketError::custom(
-                    "Invalid theme. Must be one of: light, dark, auto",
-                ));
-            }
-            config.ui.theme = value.to_string();
-        },
-        "ui.emoji" => {
-            config.ui.emoji = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
-        },
-        "ui.page_size" => {
-            config.ui.page_size = value
-                .parse::<usize>()
-                .map_err(|_| VibeTicketError::custom("Value must be a positive number"))?;
-        },
-        "ui.date_format" => config.ui.date_format = value.to_string(),
-        "git.enabled" => {
-            config.git.enabled = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
-        },
-        "git.branch_prefix" => config.git.branch_prefix = value.to_string(),
-        "git.auto_branch" => {
-            config.git.auto_branch = value
-                .parse::<bool>()
-                .map_err(|_| VibeTicketError::custom("Value must be true or false"))?;
-        },
-        "git.commit_template" => config.git.commit_template = Some(value.to_string()),
-        "plugins.directory" => config.plugins.directory = value.to_string(),
-        _ => {
-            return Err(VibeTicketError::custom(format!(
-                "Configuration key '{key}' cannot be set or doesn't exist"
-            )));
-        },
-    }
-
-    Ok(())
-}
-
 /// Handle the claude subcommand for generating CLAUDE.md
 fn handle_claude(
     append: bool,
@@ -543,32 +468,25 @@ fn format_value(value: &serde_json::Value) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
-    fn test_get_config_value() {
-        let config = Config::default();
-
-        // Test valid keys
-        assert!(get_config_value(&config, "project.name").is_ok());
-        assert!(get_config_value(&config, "ui.emoji").is_ok());
-
-        // Test invalid key
-        assert!(get_config_value(&config, "invalid.key").is_err());
-    }
-
-    #[test]
-    fn test_set_config_value() {
-        let mut config = Config::default();
-
-        // Test setting valid values
-        assert!(set_config_value(&mut config, "project.name", "Test Project").is_ok());
-        assert_eq!(config.project.name, "Test Project");
-
-        assert!(set_config_value(&mut config, "ui.emoji", "false").is_ok());
+    fn test_set_and_get() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        Config::default().save_to_path(&config_path).unwrap();
+        let output = OutputFormatter::new(false, true);
+
+        handle_set("git.worktree_prefix", "../wt-", &config_path, &output).unwrap();
+        handle_set("ui.emoji", "false", &config_path, &output).unwrap();
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(config.git.worktree_prefix, "../wt-");
         assert!(!config.ui.emoji);
+        assert_eq!(config.get("ui.emoji").unwrap(), serde_json::json!(false));
 
         // Test invalid values
-        assert!(set_config_value(&mut config, "project.default_priority", "invalid").is_err());
-        assert!(set_config_value(&mut config, "ui.emoji", "not_a_bool").is_err());
+        assert!(handle_set("project.default_priority", "invalid", &config_path, &output).is_err());
+        assert!(handle_set("ui.emoji", "not_a_bool", &config_path, &output).is_err());
+        assert!(handle_get("invalid.key", &config_path, &output).is_err());
     }
 }
@@ -0,0 +1,585 @@
+//! Handler for the `doctor` command
+//!
+//! Checks the `.vibe-ticket` directory for problems that the other commands
+//! work around or stumble over: files that no longer parse, lock and
+//! temporary files left behind by crashed processes, active tickets that no
+//! longer exist, tickets sharing a slug and a project format older than the
+//! current one. With `--fix`, bad files are moved to `.vibe-ticket/quarantine/`
+//! rather than deleted, so nothing is lost, and old formats are migrated.
+//!
+//! A project in a newer format than this version supports is reported and
+//! nothing else is checked, as its files would look damaged.
+
+use crate::cli::handlers::{Aliases, Hooks, SavedFilters, TimeTracking};
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::{Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+use crate::storage::{
+    FORMAT_VERSION, FileStorage, LOCK_TIMEOUT, LockInfo, ProjectState, is_temp_file,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Kind of problem found by `doctor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// A file that is empty, truncated or not valid YAML or JSON
+    Corrupt,
+    /// A file that parses but does not match the format this version reads
+    SchemaMismatch,
+    /// A lock file whose holder is gone
+    OrphanedLock,
+    /// A temporary file left by an interrupted write
+    InterruptedWrite,
+    /// An active ticket ID with no ticket file
+    DanglingActiveTicket,
+    /// Tickets sharing a slug
+    DuplicateSlug,
+    /// A project in an older format than this version writes
+    OutdatedFormat,
+}
+
+/// A problem found by `doctor`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Problem {
+    /// What is wrong
+    pub kind: ProblemKind,
+    /// The file concerned, relative to the project root
+    pub path: PathBuf,
+    /// Details of the problem
+    pub message: String,
+    /// How the problem was fixed, if it was
+    pub fix: Option<String>,
+}
+
+/// Formats of the files checked by `doctor`
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Ticket,
+    ProjectState,
+    Config,
+    Hooks,
+    Filters,
+    Aliases,
+    TimeTracking,
+    Spec,
+}
+
+impl Format {
+    /// Check `content` against the format, returning the problem if any
+    fn check(self, content: &str) -> Option<(ProblemKind, String)> {
+        match self {
+            Self::Ticket => check_yaml::<Ticket>(content, "ticket"),
+            Self::ProjectState => check_yaml::<ProjectState>(content, "project state"),
+            Self::Config => check_yaml::<Config>(content, "configuration"),
+            Self::Hooks => check_yaml::<Hooks>(content, "hooks"),
+            Self::Filters => check_yaml::<SavedFilters>(content, "saved filters"),
+            Self::Aliases => check_yaml::<Aliases>(content, "aliases"),
+            Self::TimeTracking => check_yaml::<TimeTracking>(content, "time tracking"),
+            Self::Spec => check_json::<SpecMetadata>(content, "specification"),
+        }
+    }
+}
+
+/// Handler for the `doctor` command
+///
+/// # Arguments
+///
+/// * `fix` - Repair the problems found
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for the report
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project is not initialized
+/// - Problems remain after the command finishes
+pub fn handle_doctor_command(
+    fix: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let mut doctor = Doctor {
+        root: project_root.clone(),
+        vibe_ticket_dir: project_root.join(".vibe-ticket"),
+        storage: FileStorage::new(project_root.join(".vibe-ticket")),
+        quarantine: None,
+        fix,
+        problems: Vec::new(),
+    };
+
+    if doctor.check_format() {
+        let tickets = doctor.check_files()?;
+        doctor.check_leftovers()?;
+        doctor.check_active_tickets(&tickets);
+        doctor.check_slugs(tickets);
+    }
+
+    let unfixed = doctor.problems.iter().filter(|p| p.fix.is_none()).count();
+    if output.is_json() {
+        output.json(&serde_json::json!({
+            "problems": doctor.problems,
+            "unfixed": unfixed,
+        }))?;
+    } else if doctor.problems.is_empty() {
+        output.success("No problems found");
+    } else {
+        for problem in &doctor.problems {
+            let line = format!("{}: {}", problem.path.display(), problem.message);
+            match &problem.fix {
+                Some(fix) => output.success(&format!("{line} ({fix})")),
+                None => output.warning(&line),
+            }
+        }
+    }
+
+    match unfixed {
+        0 => Ok(()),
+        _ if fix => Err(VibeTicketError::custom(format!(
+            "{unfixed} problem(s) could not be fixed"
+        ))),
+        _ => Err(VibeTicketError::custom(format!(
+            "{unfixed} problem(s) found; run `vibe-ticket doctor --fix` to repair them"
+        ))),
+    }
+}
+
+struct Doctor {
+    root: PathBuf,
+    vibe_ticket_dir: PathBuf,
+    storage: FileStorage,
+    /// Directory bad files are moved to, created on first use
+    quarantine: Option<PathBuf>,
+    fix: bool,
+    problems: Vec<Problem>,
+}
+
+impl Doctor {
+    /// Record a problem, applying `repair` when fixing
+    fn report(
+        &mut self,
+        kind: ProblemKind,
+        path: &Path,
+        message: String,
+        repair: impl FnOnce(&mut Self) -> Result<String>,
+    ) {
+        let fix = if self.fix {
+            match repair(self) {
+                Ok(fix) => Some(fix),
+                Err(error) => {
+                    self.problems.push(Problem {
+                        kind,
+                        path: self.relative(path),
+                        message: format!("{message}; fixing it failed: {error}"),
+                        fix: None,
+                    });
+                    return;
+                },
+            }
+        } else {
+            None
+        };
+        self.problems.push(Problem {
+            kind,
+            path: self.relative(path),
+            message,
+            fix,
+        });
+    }
+
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+
+    /// Compare the format version of the project with the current one
+    ///
+    /// Returns false for a newer format, which this version cannot check or
+    /// repair. A state file that does not parse is left to `check_files`.
+    fn check_format(&mut self) -> bool {
+        let path = self.vibe_ticket_dir.join("state.yaml");
+        let Ok(version) = self.storage.format_version() else {
+            return true;
+        };
+        if version > FORMAT_VERSION {
+            self.problems.push(Problem {
+                kind: ProblemKind::SchemaMismatch,
+                path: self.relative(&path),
+                message: format!(
+                    "format version {version} is newer than this version of vibe-ticket \
+                     supports ({FORMAT_VERSION}); upgrade vibe-ticket"
+                ),
+                fix: None,
+            });
+            return false;
+        }
+        if version < FORMAT_VERSION {
+            let message = format!("format version {version} is older than {FORMAT_VERSION}");
+            self.report(ProblemKind::OutdatedFormat, &path, message, |doctor| {
+                let report = doctor.storage.migrate(false)?;
+                Ok(format!("migrated to format version {}", report.to))
+            });
+        }
+        true
+    }
+
+    /// Parse every data file, returning the tickets that parse
+    fn check_files(&mut self) -> Result<Vec<(PathBuf, Ticket)>> {
+        let dir = &self.vibe_ticket_dir;
+        let mut files = vec![
+            (dir.join("state.yaml"), Format::ProjectState),
+            (dir.join("config.yaml"), Format::Config),
+            (dir.join("hooks.yaml"), Format::Hooks),
+            (dir.join("filters.yaml"), Format::Filters),
+            (dir.join("aliases.yaml"), Format::Aliases),
+            (dir.join("time_tracking.yaml"), Format::TimeTracking),
+        ];
+        files.extend(
+            list_dir(&dir.join("tickets"))?
+                .into_iter()
+                .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+                .map(|path| (path, Format::Ticket)),
+        );
+        files.extend(
+            list_dir(&dir.join("specs"))?
+                .into_iter()
+                .map(|spec_dir| (spec_dir.join("spec.json"), Format::Spec)),
+        );
+
+        let mut tickets = Vec::new();
+        for (path, format) in files {
+            if !path.is_file() {
+                continue;
+            }
+            let content =
+                fs::read(&path).map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+            let content = String::from_utf8_lossy(&content);
+            if let Some((kind, message)) = format.check(&content) {
+                self.report(kind, &path, message, |doctor| doctor.quarantine(&path));
+            } else if matches!(format, Format::Ticket) {
+                if let Ok(ticket) = serde_yaml::from_str(&content) {
+                    tickets.push((path, ticket));
+                }
+            }
+        }
+        Ok(tickets)
+    }
+
+    /// Find lock and temporary files left behind by processes that died
+    ///
+    /// The lock files in `local/locks/` stay by design; the operating system
+    /// releases their locks when the holder exits. Lock files elsewhere are
+    /// left by the polling locks of older versions.
+    fn check_leftovers(&mut self) -> Result<()> {
+        let locks_dir = self.storage.locks_dir();
+        let mut pending = vec![self.vibe_ticket_dir.clone()];
+        while let Some(dir) = pending.pop() {
+            if dir.ends_with("quarantine") || dir == locks_dir {
+                continue;
+            }
+            for path in list_dir(&dir)? {
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let kind = if path.extension().is_some_and(|ext| ext == "lock") {
+                    ProblemKind::OrphanedLock
+                } else if is_temp_file(&path) {
+                    ProblemKind::InterruptedWrite
+                } else {
+                    continue;
+                };
+                // Locks are held and temporary files renamed within moments
+                let message = match (kind, LockInfo::read(&path)) {
+                    (ProblemKind::OrphanedLock, Some(info)) if info.is_holder_alive() => continue,
+                    (ProblemKind::OrphanedLock, Some(info)) => format!(
+                        "lock file left by process {}, which is no longer running",
+                        info.pid
+                    ),
+                    _ if !is_older_than(&path, LOCK_TIMEOUT) => continue,
+                    (ProblemKind::OrphanedLock, None) => {
+                        "lock file left by a process that is gone".to_string()
+                    },
+                    _ => "temporary file left by an interrupted write".to_string(),
+                };
+                self.report(kind, &path, message, |_| {
+                    fs::remove_file(&path)
+                        .map_err(|e| VibeTicketError::io_error("remove", &path, e))?;
+                    Ok("removed".to_string())
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Find active tickets whose ticket file is gone
+    fn check_active_tickets(&mut self, tickets: &[(PathBuf, Ticket)]) {
+        let exists = |id: &TicketId| tickets.iter().any(|(_, ticket)| &ticket.id == id);
+        let local = self.vibe_ticket_dir.join("local");
+
+        match self.storage.get_active_ticket() {
+            Ok(Some(id)) if !exists(&id) => self.report(
+                ProblemKind::DanglingActiveTicket,
+                &local.join("active_ticket"),
+                format!("active ticket {id} does not exist"),
+                |doctor| {
+                    doctor.storage.clear_active_ticket()?;
+                    Ok("cleared the active ticket".to_string())
+                },
+            ),
+            Ok(_) => {},
+            Err(error) => self.report(
+                ProblemKind::Corrupt,
+                &local.join("active_ticket"),
+                error.to_string(),
+                |doctor| {
+                    doctor.storage.clear_active_ticket()?;
+                    Ok("cleared the active ticket".to_string())
+                },
+            ),
+        }
+
+        let active = match self.storage.get_all_active_tickets() {
+            Ok(active) => active,
+            Err(error) => {
+                let path = local.join("active_tickets.yaml");
+                self.report(ProblemKind::Corrupt, &path, error.to_string(), |doctor| {
+                    doctor.quarantine(&path)
+                });
+                return;
+            },
+        };
+        for id in active.into_iter().filter(|id| !exists(id)) {
+            self.report(
+                ProblemKind::DanglingActiveTicket,
+                &local.join("active_tickets.yaml"),
+                format!("active ticket {id} does not exist"),
+                |doctor| {
+                    doctor.storage.remove_active_ticket(&id)?;
+                    Ok("removed it from the active tickets".to_string())
+                },
+            );
+        }
+    }
+
+    /// Find tickets sharing a slug; all but the oldest get a unique slug
+    fn check_slugs(&mut self, tickets: Vec<(PathBuf, Ticket)>) {
+        let mut by_slug: HashMap<String, Vec<(PathBuf, Ticket)>> = HashMap::new();
+        for (path, ticket) in tickets {
+            by_slug
+                .entry(ticket.slug.clone())
+                .or_default()
+                .push((path, ticket));
+        }
+        let mut duplicates: Vec<_> = by_slug
+            .into_values()
+            .filter(|tickets| tickets.len() > 1)
+            .collect();
+        duplicates.sort_by(|a, b| a[0].1.slug.cmp(&b[0].1.slug));
+
+        for mut tickets in duplicates {
+            tickets.sort_by_key(|(_, ticket)| ticket.created_at);
+            let (_, original) = tickets.remove(0);
+            for (path, mut ticket) in tickets {
+                let message = format!(
+                    "slug '{}' is also used by ticket {}",
+                    ticket.slug, original.id
+                );
+                self.report(ProblemKind::DuplicateSlug, &path, message, |doctor| {
+                    let short_id: String = ticket.id.to_string().chars().take(8).collect();
+                    ticket.slug = format!("{}-{short_id}", ticket.slug);
+                    doctor.storage.save_ticket(&ticket)?;
+                    Ok(format!("renamed to '{}'", ticket.slug))
+                });
+            }
+        }
+    }
+
+    /// Move a bad file into the quarantine directory, keeping its path
+    fn quarantine(&mut self, path: &Path) -> Result<String> {
+        let quarantine = self
+            .quarantine
+            .get_or_insert_with(|| {
+                self.vibe_ticket_dir
+                    .join("quarantine")
+                    .join(chrono::Utc::now().format("%Y%m%d-%H%M%S").to_string())
+            })
+            .clone();
+        let target = quarantine.join(path.strip_prefix(&self.vibe_ticket_dir).unwrap_or(path));
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| VibeTicketError::io_error("create", parent, e))?;
+        }
+        fs::rename(path, &target).map_err(|e| VibeTicketError::io_error("move", path, e))?;
+        Ok(format!("moved to {}", self.relative(&target).display()))
+    }
+}
+
+fn check_yaml<T: DeserializeOwned>(content: &str, what: &str) -> Option<(ProblemKind, String)> {
+    match serde_yaml::from_str::<serde_yaml::Value>(content) {
+        Ok(serde_yaml::Value::Null) => Some((ProblemKind::Corrupt, "file is empty".to_string())),
+        Ok(_) => serde_yaml::from_str::<T>(content).err().map(|e| {
+            (
+                ProblemKind::SchemaMismatch,
+                format!("not a {what} file this version can read: {e}"),
+            )
+        }),
+        Err(e) => Some((ProblemKind::Corrupt, format!("invalid YAML: {e}"))),
+    }
+}
+
+fn check_json<T: DeserializeOwned>(content: &str, what: &str) -> Option<(ProblemKind, String)> {
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(_) => serde_json::from_str::<T>(content).err().map(|e| {
+            (
+                ProblemKind::SchemaMismatch,
+                format!("not a {what} file this version can read: {e}"),
+            )
+        }),
+        Err(e) => Some((ProblemKind::Corrupt, format!("invalid JSON: {e}"))),
+    }
+}
+
+/// Entries of `dir`, or none if it does not exist
+fn list_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| VibeTicketError::io_error("read", dir, e))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| VibeTicketError::io_error("read", dir, e))?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn is_older_than(path: &Path, age: std::time::Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use crate::test_utils::TestProject;
+
+    fn kinds(problems: &[Problem]) -> Vec<ProblemKind> {
+        problems.iter().map(|problem| problem.kind).collect()
+    }
+
+    fn run(project: &TestProject, fix: bool) -> (Result<()>, Vec<Problem>) {
+        let output = OutputFormatter::capture();
+        let result = handle_doctor_command(fix, Some(project.root_path_str()), &output);
+        let report = output.take_captured();
+        let problems = report
+            .json
+            .last()
+            .map(|json| serde_json::from_value(json["problems"].clone()).unwrap())
+            .unwrap_or_default();
+        (result, problems)
+    }
+
+    #[test]
+    fn test_doctor_finds_and_fixes_problems() {
+        let project = TestProject::new();
+        let dir = project.tickets_dir.clone();
+        fs::create_dir_all(dir.join("tickets")).unwrap();
+        let first = project.create_ticket("Login");
+        let mut second = Ticket::new(first.slug.clone(), "Login again");
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        project.storage.save(&second).unwrap();
+        fs::write(
+            dir.join("tickets/truncated.yaml"),
+            "id: 1234\ntitle: \"Half",
+        )
+        .unwrap();
+        fs::write(dir.join("filters.yaml"), "filters: [1, 2]\n").unwrap();
+        project.storage.add_active_ticket(&TicketId::new()).unwrap();
+        let an_hour_ago = SystemTime::now() - std::time::Duration::from_secs(3600);
+        for leftover in ["tickets/abc.yaml.lock", "tickets/.abc.yaml.42.0.tmp"] {
+            let file = fs::File::create(dir.join(leftover)).unwrap();
+            file.set_modified(an_hour_ago).unwrap();
+        }
+        // A lock held right now is left alone
+        fs::write(dir.join("state.yaml.lock"), "{}").unwrap();
+        let holder = |pid: u32| format!(r#"{{"pid": {pid}, "acquired_at": 0, "operation": null}}"#);
+        fs::write(dir.join("config.yaml.lock"), holder(std::process::id())).unwrap();
+        fs::write(dir.join("filters.yaml.lock"), holder(u32::MAX)).unwrap();
+        // Lock files of the current locks are never reported
+        let _lock = project.storage.lock_store("test").unwrap();
+
+        let (result, problems) = run(&project, false);
+        assert!(result.is_err());
+        assert_eq!(
+            kinds(&problems),
+            [
+                ProblemKind::SchemaMismatch,
+                ProblemKind::Corrupt,
+                ProblemKind::OrphanedLock,
+                ProblemKind::InterruptedWrite,
+                ProblemKind::OrphanedLock,
+                ProblemKind::DanglingActiveTicket,
+                ProblemKind::DuplicateSlug,
+            ]
+        );
+        assert!(problems.iter().all(|problem| problem.fix.is_none()));
+
+        let (result, problems) = run(&project, true);
+        result.unwrap();
+        assert_eq!(problems.len(), 7);
+        assert!(!dir.join("tickets/truncated.yaml").exists());
+        assert!(!dir.join("tickets/abc.yaml.lock").exists());
+        assert!(!dir.join("filters.yaml.lock").exists());
+        assert!(dir.join("state.yaml.lock").exists());
+        assert!(dir.join("config.yaml.lock").exists());
+        let quarantined: Vec<_> = list_dir(&dir.join("quarantine")).unwrap();
+        assert!(quarantined[0].join("tickets/truncated.yaml").exists());
+        assert!(quarantined[0].join("filters.yaml").exists());
+        assert!(project.storage.get_all_active_tickets().unwrap().is_empty());
+        let renamed = project.storage.load(&second.id).unwrap();
+        assert_ne!(renamed.slug, first.slug);
+
+        let (result, problems) = run(&project, false);
+        result.unwrap();
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_doctor_migrates_old_formats_and_leaves_newer_ones_alone() {
+        let project = TestProject::new();
+        let state_path = project.tickets_dir.join("state.yaml");
+        let state = |version: u32| {
+            format!(
+                "name: test\ndescription: null\ncreated_at: 2024-01-01T00:00:00Z\n\
+                 updated_at: 2024-01-01T00:00:00Z\nticket_count: 0\nformat_version: {version}\n"
+            )
+        };
+
+        fs::write(&state_path, state(0)).unwrap();
+        let (result, problems) = run(&project, true);
+        result.unwrap();
+        assert_eq!(kinds(&problems), [ProblemKind::OutdatedFormat]);
+        assert_eq!(project.storage.format_version().unwrap(), FORMAT_VERSION);
+
+        // Files in a newer format are not quarantined as unreadable
+        fs::write(&state_path, state(FORMAT_VERSION + 1)).unwrap();
+        fs::write(project.tickets_dir.join("filters.yaml"), "filters: [1]\n").unwrap();
+        let (result, problems) = run(&project, true);
+        assert!(result.is_err());
+        assert_eq!(kinds(&problems), [ProblemKind::SchemaMismatch]);
+        assert!(problems[0].fix.is_none());
+        assert!(project.tickets_dir.join("filters.yaml").exists());
+    }
+}
@@ -7,6 +7,7 @@ use crate::cli::handlers::common::resolve_ticket_ref;
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::{Priority, Status};
 use crate::error::{Result, VibeTicketError};
+use crate::specs::TaskSync;
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 
 /// Parameters for updating a ticket
@@ -100,7 +101,8 @@ pub fn handle_edit_command(
         add_tags: add_tags.as_deref(),
         remove_tags: remove_tags.as_deref(),
     };
-    apply_ticket_updates(&mut ticket, &mut changes, &update_params)?;
+    let sync = TaskSync::new(&vibe_ticket_dir);
+    apply_ticket_updates(&mut ticket, &mut changes, &update_params, &sync)?;
 
     // Check if any changes were made
     if changes.is_empty() {
@@ -126,6 +128,7 @@ fn apply_ticket_updates(
     ticket: &mut crate::core::Ticket,
     changes: &mut Vec<String>,
     params: &UpdateParams<'_>,
+    sync: &TaskSync,
 ) -> Result<()> {
     // Update title if provided
     if let Some(new_title) = params.title {
@@ -158,19 +161,9 @@ fn apply_ticket_updates(
                 status: status_str.to_string(),
             })?;
         let old_status = ticket.status;
-        ticket.status = new_status;
+        // Keep the spec task this ticket was exported from in sync
+        sync.set_status(ticket, new_status)?;
         changes.push(format!("Status: {old_status} → {new_status}"));
-
-        // Update timestamps based on status changes
-        match (old_status, new_status) {
-            (Status::Todo, Status::Doing) => {
-                ticket.started_at = Some(chrono::Utc::now());
-            },
-            (_, Status::Done) if old_status != Status::Done => {
-                ticket.closed_at = Some(chrono::Utc::now());
-            },
-            _ => {},
-        }
     }
 
     // Handle tags
@@ -0,0 +1,62 @@
+//! Graphviz DOT rendering
+
+use super::{NodeKind, TicketGraph, fill_color};
+use std::fmt::Write as FmtWrite;
+
+/// Render the graph as a Graphviz DOT digraph
+#[must_use]
+pub fn render_dot(graph: &TicketGraph) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "digraph tickets {{").unwrap();
+    writeln!(output, "  rankdir=LR;").unwrap();
+    writeln!(output, "  node [shape=box, style=\"rounded,filled\"];").unwrap();
+
+    for (cluster, (group, members)) in graph.groups().into_iter().enumerate() {
+        writeln!(output, "\n  subgraph cluster_{cluster} {{").unwrap();
+        writeln!(output, "    label=\"{}\";", escape(group)).unwrap();
+        for index in members {
+            let node = &graph.nodes[index];
+            writeln!(
+                output,
+                "    {} [label=\"{}\", {}];",
+                node.key,
+                escape(&node.label),
+                node_style(node.kind)
+            )
+            .unwrap();
+        }
+        writeln!(output, "  }}").unwrap();
+    }
+
+    if !graph.edges.is_empty() {
+        writeln!(output).unwrap();
+    }
+    for edge in &graph.edges {
+        writeln!(
+            output,
+            "  {} -> {} [label=\"{}\"];",
+            graph.nodes[edge.from].key,
+            graph.nodes[edge.to].key,
+            escape(&edge.label)
+        )
+        .unwrap();
+    }
+
+    writeln!(output, "}}").unwrap();
+    output
+}
+
+/// DOT attributes for a node kind
+fn node_style(kind: NodeKind) -> String {
+    let shape = match kind {
+        NodeKind::Spec => "note",
+        NodeKind::Ticket(_) => "box",
+    };
+    format!("shape={shape}, fillcolor=\"{}\"", fill_color(kind))
+}
+
+/// Escape a string for use inside a quoted DOT identifier
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
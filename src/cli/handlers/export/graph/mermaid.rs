@@ -0,0 +1,80 @@
+//! Mermaid flowchart rendering
+
+use super::{NodeKind, TicketGraph, fill_color};
+use crate::core::Status;
+use std::fmt::Write as FmtWrite;
+
+/// Render the graph as a Mermaid flowchart
+#[must_use]
+pub fn render_mermaid(graph: &TicketGraph) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "flowchart LR").unwrap();
+
+    for (cluster, (group, members)) in graph.groups().into_iter().enumerate() {
+        writeln!(output, "  subgraph group_{cluster}[\"{}\"]", escape(group)).unwrap();
+        for index in members {
+            let node = &graph.nodes[index];
+            let label = escape(&node.label);
+            match node.kind {
+                NodeKind::Spec => writeln!(output, "    {}[[\"{label}\"]]", node.key),
+                NodeKind::Ticket(_) => writeln!(output, "    {}[\"{label}\"]", node.key),
+            }
+            .unwrap();
+        }
+        writeln!(output, "  end").unwrap();
+    }
+
+    for edge in &graph.edges {
+        writeln!(
+            output,
+            "  {} -->|{}| {}",
+            graph.nodes[edge.from].key,
+            escape(&edge.label),
+            graph.nodes[edge.to].key
+        )
+        .unwrap();
+    }
+
+    // Style nodes by kind
+    writeln!(
+        output,
+        "  classDef spec fill:{}",
+        fill_color(NodeKind::Spec)
+    )
+    .unwrap();
+    for status in Status::all() {
+        writeln!(
+            output,
+            "  classDef {} fill:{}",
+            status_class(status),
+            fill_color(NodeKind::Ticket(status))
+        )
+        .unwrap();
+    }
+    for node in &graph.nodes {
+        let class = match node.kind {
+            NodeKind::Spec => "spec",
+            NodeKind::Ticket(status) => status_class(status),
+        };
+        writeln!(output, "  class {} {class}", node.key).unwrap();
+    }
+
+    output
+}
+
+/// Mermaid class name for a status
+const fn status_class(status: Status) -> &'static str {
+    match status {
+        Status::Todo => "todo",
+        Status::Doing => "doing",
+        Status::Review => "review",
+        Status::Blocked => "blocked",
+        Status::Done => "done",
+    }
+}
+
+/// Escape characters that Mermaid treats specially inside quoted labels
+fn escape(value: &str) -> String {
+    value.replace('"', "#quot;").replace('|', "#124;")
+}
@@ -0,0 +1,446 @@
+//! Dependency graph export
+//!
+//! This module builds a graph of how tickets and specifications relate to each
+//! other and renders it as Graphviz DOT, Mermaid or a self-contained SVG.
+//!
+//! Edges are derived from:
+//! - `SpecMetadata.ticket_id` (spec → its associated ticket)
+//! - Tickets tagged with a spec ID, as produced by `spec tasks --export-tickets`
+//! - Ticket-to-ticket references stored in ticket metadata under one of the
+//!   [`REFERENCE_KEYS`], given either as a ticket ID/slug or a list of them
+
+mod dot;
+mod mermaid;
+mod svg;
+
+use super::Exporter;
+use crate::core::{Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+
+pub use self::dot::render_dot;
+pub use self::mermaid::render_mermaid;
+pub use self::svg::render_svg;
+
+/// Ticket metadata keys that are interpreted as references to other tickets
+pub const REFERENCE_KEYS: &[&str] = &["depends_on", "blocked_by", "blocks", "related", "parent"];
+
+/// Output format of a rendered graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// Standalone SVG rendered with the built-in layout engine
+    Svg,
+}
+
+impl GraphFormat {
+    /// Get the format name for display
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Dot => "Graphviz DOT",
+            Self::Mermaid => "Mermaid",
+            Self::Svg => "SVG",
+        }
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "mermaid" | "mmd" => Ok(Self::Mermaid),
+            "svg" => Ok(Self::Svg),
+            _ => Err(VibeTicketError::custom(format!(
+                "Unsupported graph format: {s}. Supported formats: dot, mermaid, svg"
+            ))),
+        }
+    }
+}
+
+/// How nodes are clustered in the rendered graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// Group tickets by their status
+    #[default]
+    Status,
+    /// Group tickets by the specification they belong to
+    Spec,
+    /// Group tickets by their first tag
+    Tag,
+    /// Group tickets by assignee
+    Assignee,
+}
+
+impl FromStr for GroupBy {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "status" => Ok(Self::Status),
+            "spec" => Ok(Self::Spec),
+            "tag" | "tags" => Ok(Self::Tag),
+            "assignee" => Ok(Self::Assignee),
+            _ => Err(VibeTicketError::custom(format!(
+                "Invalid group: {s}. Valid groups: status, spec, tag, assignee"
+            ))),
+        }
+    }
+}
+
+/// Kind of a graph node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A ticket with its current status
+    Ticket(Status),
+    /// A specification
+    Spec,
+}
+
+/// A node in the ticket graph
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    /// Identifier that is safe to use in DOT and Mermaid
+    pub key: String,
+    /// Human-readable label
+    pub label: String,
+    /// Node kind
+    pub kind: NodeKind,
+    /// Name of the group (cluster) this node belongs to
+    pub group: String,
+}
+
+/// An edge between two nodes, referencing them by index
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphEdge {
+    /// Index of the source node
+    pub from: usize,
+    /// Index of the target node
+    pub to: usize,
+    /// Relation label (e.g. `spec`, `depends_on`)
+    pub label: String,
+}
+
+/// Graph of tickets, specifications and their relations
+#[derive(Debug, Clone, Default)]
+pub struct TicketGraph {
+    /// All nodes, specs first, followed by tickets
+    pub nodes: Vec<GraphNode>,
+    /// All edges
+    pub edges: Vec<GraphEdge>,
+}
+
+impl TicketGraph {
+    /// Build a graph from tickets and specifications
+    #[must_use]
+    pub fn build(tickets: &[Ticket], specs: &[SpecMetadata], group_by: GroupBy) -> Self {
+        let mut graph = Self::default();
+        let mut spec_index = HashMap::new();
+        let mut ticket_index = HashMap::new();
+
+        for spec in specs {
+            spec_index.insert(spec.id.as_str(), graph.nodes.len());
+            graph.nodes.push(GraphNode {
+                key: node_key("s", &spec.id),
+                label: spec.title.clone(),
+                kind: NodeKind::Spec,
+                group: String::new(),
+            });
+        }
+
+        for ticket in tickets {
+            let index = graph.nodes.len();
+            ticket_index.insert(ticket.id.to_string(), index);
+            ticket_index.entry(ticket.slug.clone()).or_insert(index);
+            graph.nodes.push(GraphNode {
+                key: node_key("t", &ticket.id.to_string()),
+                label: format!("{} {}", ticket.status.emoji(), ticket.slug),
+                kind: NodeKind::Ticket(ticket.status),
+                group: String::new(),
+            });
+        }
+
+        let mut seen = HashSet::new();
+        let mut add_edge = |graph: &mut Self, from: usize, to: usize, label: &str| {
+            let edge = GraphEdge {
+                from,
+                to,
+                label: label.to_string(),
+            };
+            if from != to && seen.insert(edge.clone()) {
+                graph.edges.push(edge);
+            }
+        };
+
+        // Spec → ticket edges
+        for spec in specs {
+            let from = spec_index[spec.id.as_str()];
+            if let Some(to) = spec
+                .ticket_id
+                .as_deref()
+                .and_then(|id| ticket_index.get(id))
+            {
+                add_edge(&mut graph, from, *to, "spec");
+            }
+        }
+        for ticket in tickets {
+            let to = ticket_index[&ticket.id.to_string()];
+            for tag in &ticket.tags {
+                if let Some(from) = spec_index.get(tag.as_str()) {
+                    add_edge(&mut graph, *from, to, "task");
+                }
+            }
+        }
+
+        // Ticket → ticket references from metadata
+        for ticket in tickets {
+            let from = ticket_index[&ticket.id.to_string()];
+            for key in REFERENCE_KEYS {
+                for reference in metadata_references(ticket, key) {
+                    if let Some(to) = ticket_index.get(reference.as_str()) {
+                        add_edge(&mut graph, from, *to, key);
+                    }
+                }
+            }
+        }
+
+        graph.assign_groups(tickets, specs.len(), group_by);
+        graph
+    }
+
+    /// Assign every node to a group according to `group_by`
+    fn assign_groups(&mut self, tickets: &[Ticket], spec_count: usize, group_by: GroupBy) {
+        // Spec membership of each ticket node, used when grouping by spec
+        let mut ticket_spec: HashMap<usize, usize> = HashMap::new();
+        for edge in &self.edges {
+            if edge.from < spec_count && edge.to >= spec_count {
+                ticket_spec.entry(edge.to).or_insert(edge.from);
+            }
+        }
+
+        for index in 0..spec_count {
+            self.nodes[index].group = match group_by {
+                GroupBy::Spec => self.nodes[index].label.clone(),
+                _ => "Specifications".to_string(),
+            };
+        }
+
+        for (offset, ticket) in tickets.iter().enumerate() {
+            let index = spec_count + offset;
+            self.nodes[index].group = match group_by {
+                GroupBy::Status => ticket.status.to_string(),
+                GroupBy::Spec => ticket_spec
+                    .get(&index)
+                    .map_or_else(|| "No spec".to_string(), |s| self.nodes[*s].label.clone()),
+                GroupBy::Tag => ticket
+                    .tags
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "Untagged".to_string()),
+                GroupBy::Assignee => ticket
+                    .assignee
+                    .clone()
+                    .unwrap_or_else(|| "Unassigned".to_string()),
+            };
+        }
+    }
+
+    /// Group node indices by group name, in a stable order
+    #[must_use]
+    pub fn groups(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut groups: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            groups.entry(node.group.as_str()).or_default().push(index);
+        }
+        groups
+    }
+
+    /// Render the graph in the given format
+    #[must_use]
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => render_dot(self),
+            GraphFormat::Mermaid => render_mermaid(self),
+            GraphFormat::Svg => render_svg(self),
+        }
+    }
+}
+
+/// Fill color used for a node in every rendering
+const fn fill_color(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Spec => "#ede7f6",
+        NodeKind::Ticket(status) => match status {
+            Status::Todo => "#e3f2fd",
+            Status::Doing => "#fff8e1",
+            Status::Review => "#e0f7fa",
+            Status::Blocked => "#ffebee",
+            Status::Done => "#e8f5e9",
+        },
+    }
+}
+
+/// Build an identifier made of ASCII alphanumerics and underscores
+fn node_key(prefix: &str, id: &str) -> String {
+    let sanitized: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{prefix}_{sanitized}")
+}
+
+/// Read ticket references stored under a metadata key
+fn metadata_references(ticket: &Ticket, key: &str) -> Vec<String> {
+    match ticket.metadata.get(key) {
+        Some(serde_json::Value::String(s)) => s
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Some(serde_json::Value::Array(values)) => values
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Graph exporter implementation
+///
+/// Unlike the other exporters it can also take the project's specifications
+/// into account, so spec-to-ticket relations show up in the output.
+pub struct GraphExporter {
+    format: GraphFormat,
+    group_by: GroupBy,
+    specs: Vec<SpecMetadata>,
+}
+
+impl GraphExporter {
+    /// Create a new graph exporter
+    #[must_use]
+    pub const fn new(format: GraphFormat, group_by: GroupBy) -> Self {
+        Self {
+            format,
+            group_by,
+            specs: Vec::new(),
+        }
+    }
+
+    /// Include specifications in the graph
+    #[must_use]
+    pub fn with_specs(mut self, specs: Vec<SpecMetadata>) -> Self {
+        self.specs = specs;
+        self
+    }
+}
+
+impl Exporter for GraphExporter {
+    fn export(&self, tickets: &[Ticket]) -> Result<String> {
+        Ok(TicketGraph::build(tickets, &self.specs, self.group_by).render(self.format))
+    }
+
+    fn format_name(&self) -> &'static str {
+        self.format.display_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TicketBuilder;
+
+    fn fixture() -> (Vec<Ticket>, Vec<SpecMetadata>) {
+        let spec = SpecMetadata::new("Auth".to_string(), String::new());
+        let api = TicketBuilder::new()
+            .slug("auth-api")
+            .title("Auth API")
+            .tags(vec![spec.id.clone()])
+            .build();
+        let mut ui = TicketBuilder::new()
+            .slug("auth-ui")
+            .title("Auth UI")
+            .status(Status::Doing)
+            .build();
+        ui.metadata.insert(
+            "depends_on".to_string(),
+            serde_json::json!(["auth-api", "missing"]),
+        );
+        (vec![api, ui], vec![spec])
+    }
+
+    #[test]
+    fn test_build_edges() {
+        let (tickets, specs) = fixture();
+        let graph = TicketGraph::build(&tickets, &specs, GroupBy::Status);
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(graph.edges.contains(&GraphEdge {
+            from: 0,
+            to: 1,
+            label: "task".to_string(),
+        }));
+        assert!(graph.edges.contains(&GraphEdge {
+            from: 2,
+            to: 1,
+            label: "depends_on".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_group_by_spec() {
+        let (tickets, specs) = fixture();
+        let graph = TicketGraph::build(&tickets, &specs, GroupBy::Spec);
+        let groups = graph.groups();
+
+        assert_eq!(groups["Auth"], vec![0, 1]);
+        assert_eq!(groups["No spec"], vec![2]);
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let (tickets, specs) = fixture();
+        let graph = TicketGraph::build(&tickets, &specs, GroupBy::Status);
+
+        let dot = graph.render(GraphFormat::Dot);
+        assert!(dot.starts_with("digraph tickets {"));
+        assert!(dot.contains("subgraph cluster_"));
+        assert!(dot.contains("[label=\"depends_on\"]"));
+
+        let mermaid = graph.render(GraphFormat::Mermaid);
+        assert!(mermaid.starts_with("flowchart LR"));
+        assert!(mermaid.contains("-->|depends_on|"));
+
+        let svg = graph.render(GraphFormat::Svg);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("auth-ui"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_graph_exporter() {
+        let (tickets, specs) = fixture();
+        let exporter = GraphExporter::new(GraphFormat::Mermaid, GroupBy::Tag).with_specs(specs);
+
+        let output = exporter.export(&tickets).unwrap();
+        assert!(output.contains("subgraph"));
+        assert_eq!(exporter.format_name(), "Mermaid");
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!("DOT".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert_eq!("svg".parse::<GraphFormat>().unwrap(), GraphFormat::Svg);
+        assert!("png".parse::<GraphFormat>().is_err());
+        assert_eq!("assignee".parse::<GroupBy>().unwrap(), GroupBy::Assignee);
+        assert!("priority".parse::<GroupBy>().is_err());
+    }
+}
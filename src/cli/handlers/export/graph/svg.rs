@@ -0,0 +1,332 @@
+//! SVG rendering with a built-in layered layout
+//!
+//! The layout is a small Sugiyama-style pipeline so the SVG output does not
+//! depend on Graphviz being installed:
+//! 1. Break cycles by ignoring back edges found during a depth-first search
+//! 2. Assign each node a rank (column) using the longest path from a source
+//! 3. Order nodes within each rank by group, then reduce edge crossings with
+//!    a few barycenter sweeps
+//! 4. Place nodes on a grid and draw edges as cubic curves
+
+use super::{TicketGraph, fill_color};
+use std::collections::BTreeMap;
+use std::fmt::Write as FmtWrite;
+
+const NODE_WIDTH: usize = 200;
+const NODE_HEIGHT: usize = 36;
+const RANK_GAP: usize = 80;
+const ROW_GAP: usize = 20;
+const MARGIN: usize = 20;
+const LEGEND_ROW: usize = 22;
+const MAX_LABEL_CHARS: usize = 26;
+const CROSSING_SWEEPS: usize = 4;
+
+/// Stroke colors cycled through for groups
+const GROUP_COLORS: &[&str] = &[
+    "#1e88e5", "#f4511e", "#43a047", "#8e24aa", "#fdd835", "#00acc1", "#6d4c41", "#546e7a",
+];
+
+/// Render the graph as a standalone SVG document
+#[must_use]
+pub fn render_svg(graph: &TicketGraph) -> String {
+    let layout = Layout::compute(graph);
+    let groups = graph.groups();
+    let group_colors: BTreeMap<&str, &str> = groups
+        .keys()
+        .enumerate()
+        .map(|(i, group)| (*group, GROUP_COLORS[i % GROUP_COLORS.len()]))
+        .collect();
+
+    let legend_height = groups.len() * LEGEND_ROW + MARGIN;
+    let width = (MARGIN * 2 + layout.ranks * (NODE_WIDTH + RANK_GAP)).saturating_sub(RANK_GAP);
+    let width = width.max(NODE_WIDTH + MARGIN * 2);
+    let height = legend_height + MARGIN * 2 + layout.rows * (NODE_HEIGHT + ROW_GAP);
+
+    let mut output = String::new();
+    writeln!(
+        output,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"12\">"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+         markerWidth=\"8\" markerHeight=\"8\" orient=\"auto-start-reverse\">\
+         <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#555\"/></marker></defs>"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "  <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>"
+    )
+    .unwrap();
+
+    // Legend
+    for (row, (group, color)) in group_colors.iter().enumerate() {
+        let y = MARGIN + row * LEGEND_ROW;
+        writeln!(
+            output,
+            "  <rect x=\"{MARGIN}\" y=\"{y}\" width=\"14\" height=\"14\" fill=\"none\" \
+             stroke=\"{color}\" stroke-width=\"3\"/>"
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "  <text x=\"{}\" y=\"{}\">{}</text>",
+            MARGIN + 22,
+            y + 12,
+            escape(group)
+        )
+        .unwrap();
+    }
+
+    let origin_y = legend_height + MARGIN;
+    let position = |index: usize| {
+        let (rank, row) = layout.positions[index];
+        (
+            MARGIN + rank * (NODE_WIDTH + RANK_GAP),
+            origin_y + row * (NODE_HEIGHT + ROW_GAP),
+        )
+    };
+
+    // Edges are drawn first so nodes are painted on top of them
+    for edge in &graph.edges {
+        let (fx, fy) = position(edge.from);
+        let (tx, ty) = position(edge.to);
+        let (x1, y1) = (fx + NODE_WIDTH, fy + NODE_HEIGHT / 2);
+        let (x2, y2) = (tx, ty + NODE_HEIGHT / 2);
+        let bend = RANK_GAP / 2 + x1.abs_diff(x2) / 4;
+        writeln!(
+            output,
+            "  <path d=\"M {x1} {y1} C {} {y1}, {} {y2}, {x2} {y2}\" fill=\"none\" \
+             stroke=\"#555\" marker-end=\"url(#arrow)\"/>",
+            x1 + bend,
+            x2.saturating_sub(bend),
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "  <text x=\"{}\" y=\"{}\" font-size=\"10\" fill=\"#555\" \
+             text-anchor=\"middle\">{}</text>",
+            x1.midpoint(x2),
+            y1.midpoint(y2) - 4,
+            escape(&edge.label)
+        )
+        .unwrap();
+    }
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        let (x, y) = position(index);
+        writeln!(
+            output,
+            "  <g><title>{}</title><rect x=\"{x}\" y=\"{y}\" width=\"{NODE_WIDTH}\" \
+             height=\"{NODE_HEIGHT}\" rx=\"6\" fill=\"{}\" stroke=\"{}\" stroke-width=\"2\"/>\
+             <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></g>",
+            escape(&node.label),
+            fill_color(node.kind),
+            group_colors[node.group.as_str()],
+            x + NODE_WIDTH / 2,
+            y + NODE_HEIGHT / 2 + 4,
+            escape(&truncate(&node.label))
+        )
+        .unwrap();
+    }
+
+    writeln!(output, "</svg>").unwrap();
+    output
+}
+
+/// Node placement on a grid of ranks (columns) and rows
+struct Layout {
+    /// `(rank, row)` for every node
+    positions: Vec<(usize, usize)>,
+    /// Number of ranks
+    ranks: usize,
+    /// Number of rows in the tallest rank
+    rows: usize,
+}
+
+impl Layout {
+    fn compute(graph: &TicketGraph) -> Self {
+        let count = graph.nodes.len();
+        let mut successors = vec![Vec::new(); count];
+        for edge in &graph.edges {
+            successors[edge.from].push(edge.to);
+        }
+
+        let order = Self::acyclic_order(&successors);
+
+        // Longest-path ranking over the forward edges of the DFS order
+        let mut position_in_order = vec![0; count];
+        for (position, node) in order.iter().enumerate() {
+            position_in_order[*node] = position;
+        }
+        let mut rank = vec![0usize; count];
+        for node in &order {
+            for next in &successors[*node] {
+                if position_in_order[*next] > position_in_order[*node] {
+                    rank[*next] = rank[*next].max(rank[*node] + 1);
+                }
+            }
+        }
+
+        let ranks = rank.iter().max().map_or(0, |r| r + 1);
+        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); ranks];
+        for node in 0..count {
+            layers[rank[node]].push(node);
+        }
+        for layer in &mut layers {
+            layer.sort_by(|a, b| {
+                (&graph.nodes[*a].group, &graph.nodes[*a].label)
+                    .cmp(&(&graph.nodes[*b].group, &graph.nodes[*b].label))
+            });
+        }
+
+        let mut predecessors = vec![Vec::new(); count];
+        for edge in &graph.edges {
+            predecessors[edge.to].push(edge.from);
+        }
+        let mut row = vec![0usize; count];
+        Self::assign_rows(&layers, &mut row);
+        for _ in 0..CROSSING_SWEEPS {
+            for layer in layers.iter_mut().skip(1) {
+                Self::sort_by_barycenter(layer, &predecessors, &row);
+            }
+            Self::assign_rows(&layers, &mut row);
+        }
+
+        Self {
+            positions: (0..count).map(|node| (rank[node], row[node])).collect(),
+            ranks,
+            rows: layers.iter().map(Vec::len).max().unwrap_or(0),
+        }
+    }
+
+    /// Topological order of nodes, ignoring edges that would close a cycle
+    fn acyclic_order(successors: &[Vec<usize>]) -> Vec<usize> {
+        let count = successors.len();
+        let mut visited = vec![false; count];
+        let mut finished = Vec::with_capacity(count);
+
+        for start in 0..count {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut stack = vec![(start, 0usize)];
+            while let Some((node, next_child)) = stack.pop() {
+                if let Some(child) = successors[node].get(next_child) {
+                    stack.push((node, next_child + 1));
+                    if !visited[*child] {
+                        visited[*child] = true;
+                        stack.push((*child, 0));
+                    }
+                } else {
+                    finished.push(node);
+                }
+            }
+        }
+
+        finished.reverse();
+        finished
+    }
+
+    /// Store each node's index within its layer
+    fn assign_rows(layers: &[Vec<usize>], row: &mut [usize]) {
+        for layer in layers {
+            for (index, node) in layer.iter().enumerate() {
+                row[*node] = index;
+            }
+        }
+    }
+
+    /// Reorder a layer by the mean row of each node's predecessors
+    fn sort_by_barycenter(layer: &mut [usize], predecessors: &[Vec<usize>], row: &[usize]) {
+        #[allow(clippy::cast_precision_loss)]
+        let barycenter = |node: usize| -> f64 {
+            let parents = &predecessors[node];
+            if parents.is_empty() {
+                row[node] as f64
+            } else {
+                parents.iter().map(|p| row[*p] as f64).sum::<f64>() / parents.len() as f64
+            }
+        };
+        layer.sort_by(|a, b| barycenter(*a).total_cmp(&barycenter(*b)));
+    }
+}
+
+/// Shorten a label so it fits inside a node box
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        label.to_string()
+    } else {
+        let mut short: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Escape text for use in XML content and attributes
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::handlers::export::graph::{GraphEdge, GraphNode, NodeKind};
+    use crate::core::Status;
+
+    fn chain(len: usize, cyclic: bool) -> TicketGraph {
+        let nodes = (0..len)
+            .map(|i| GraphNode {
+                key: format!("t_{i}"),
+                label: format!("ticket-{i}"),
+                kind: NodeKind::Ticket(Status::Todo),
+                group: "Todo".to_string(),
+            })
+            .collect();
+        let mut edges: Vec<GraphEdge> = (1..len)
+            .map(|i| GraphEdge {
+                from: i - 1,
+                to: i,
+                label: "depends_on".to_string(),
+            })
+            .collect();
+        if cyclic {
+            edges.push(GraphEdge {
+                from: len - 1,
+                to: 0,
+                label: "blocks".to_string(),
+            });
+        }
+        TicketGraph { nodes, edges }
+    }
+
+    #[test]
+    fn test_layout_ranks_chain() {
+        let layout = Layout::compute(&chain(3, false));
+        assert_eq!(layout.ranks, 3);
+        assert_eq!(layout.rows, 1);
+        assert_eq!(layout.positions, vec![(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_layout_handles_cycles() {
+        let layout = Layout::compute(&chain(3, true));
+        assert_eq!(layout.ranks, 3);
+        assert!(render_svg(&chain(3, true)).contains("blocks"));
+    }
+
+    #[test]
+    fn test_escape_and_truncate() {
+        assert_eq!(escape("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert_eq!(truncate("short"), "short");
+        assert_eq!(truncate(&"x".repeat(40)).chars().count(), MAX_LABEL_CHARS);
+    }
+}
@@ -0,0 +1,511 @@
+//! HTML static site export implementation
+//!
+//! Produces a self-contained site that can be published as a CI artifact:
+//! - `index.html` with a kanban board mirroring the `board` command
+//! - `tickets/<id>.html` with the rendered description, tasks and time
+//! - `specs/<id>.html` with the rendered requirements, design and tasks
+//! - `search.json` (and `search-index.js` for `file://` browsing) for
+//!   client-side search
+
+use super::Exporter;
+use crate::cli::utils::format_duration;
+use crate::core::{Status, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::{SpecDocumentType, Specification};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tera::{Context, Tera};
+
+/// Board columns in display order, matching the `board` command
+const COLUMNS: [Status; 5] = [
+    Status::Todo,
+    Status::Doing,
+    Status::Review,
+    Status::Blocked,
+    Status::Done,
+];
+
+/// A file of the generated site
+#[derive(Debug, Clone)]
+pub struct SiteFile {
+    /// Path relative to the site root
+    pub path: PathBuf,
+    /// File contents
+    pub content: String,
+}
+
+/// HTML exporter implementation
+#[derive(Default)]
+pub struct HtmlExporter {
+    project: String,
+    specs: Vec<Specification>,
+    time_spent: HashMap<String, i64>,
+}
+
+impl HtmlExporter {
+    /// Create a new HTML exporter for the named project
+    #[must_use]
+    pub fn new(project: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Include specifications as per-spec pages
+    #[must_use]
+    pub fn with_specs(mut self, specs: Vec<Specification>) -> Self {
+        self.specs = specs;
+        self
+    }
+
+    /// Include logged time, in minutes per ticket ID
+    #[must_use]
+    pub fn with_time_spent(mut self, time_spent: HashMap<String, i64>) -> Self {
+        self.time_spent = time_spent;
+        self
+    }
+
+    /// Render every page of the site
+    pub fn render_site(&self, tickets: &[Ticket]) -> Result<Vec<SiteFile>> {
+        let tera = Self::templates()?;
+        let views: Vec<TicketView> = tickets
+            .iter()
+            .map(|t| TicketView::new(t, self.time_spent.get(&t.id.to_string()).copied()))
+            .collect();
+
+        let mut files = vec![SiteFile {
+            path: PathBuf::from("index.html"),
+            content: self.render_index(&tera, &views)?,
+        }];
+
+        for view in &views {
+            let mut context = self.base_context("../");
+            context.insert("ticket", view);
+            files.push(SiteFile {
+                path: Path::new("tickets").join(format!("{}.html", view.id)),
+                content: render(&tera, "ticket.html", &context)?,
+            });
+        }
+
+        for spec in &self.specs {
+            let mut context = self.base_context("../");
+            context.insert("spec", &SpecView::new(spec, &views));
+            files.push(SiteFile {
+                path: Path::new("specs").join(format!("{}.html", spec.metadata.id)),
+                content: render(&tera, "spec.html", &context)?,
+            });
+        }
+
+        let search = serde_json::to_string(&self.search_index(&views))
+            .map_err(|e| VibeTicketError::custom(format!("Failed to build search index: {e}")))?;
+        files.push(SiteFile {
+            path: PathBuf::from("search-index.js"),
+            content: format!("window.SEARCH_INDEX = {search};\n"),
+        });
+        files.push(SiteFile {
+            path: PathBuf::from("search.json"),
+            content: search,
+        });
+
+        Ok(files)
+    }
+
+    /// Write the site into a directory, creating it if needed
+    pub fn write_site(&self, tickets: &[Ticket], dir: &Path) -> Result<usize> {
+        let files = self.render_site(tickets)?;
+        for file in &files {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| VibeTicketError::io_error("create", parent, e))?;
+            }
+            std::fs::write(&path, &file.content)
+                .map_err(|e| VibeTicketError::io_error("write", &path, e))?;
+        }
+        Ok(files.len())
+    }
+
+    /// Load the embedded page templates
+    fn templates() -> Result<Tera> {
+        let mut tera = Tera::default();
+        tera.add_raw_templates(vec![
+            (
+                "base.html",
+                include_str!("../../../../templates/site/base.html"),
+            ),
+            (
+                "index.html",
+                include_str!("../../../../templates/site/index.html"),
+            ),
+            (
+                "ticket.html",
+                include_str!("../../../../templates/site/ticket.html"),
+            ),
+            (
+                "spec.html",
+                include_str!("../../../../templates/site/spec.html"),
+            ),
+        ])
+        .map_err(|e| VibeTicketError::custom(format!("Invalid site template: {e}")))?;
+        Ok(tera)
+    }
+
+    fn base_context(&self, root: &str) -> Context {
+        let mut context = Context::new();
+        context.insert("project", &self.project);
+        context.insert("root", root);
+        context.insert(
+            "exported_at",
+            &Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+        );
+        context
+    }
+
+    fn render_index(&self, tera: &Tera, views: &[TicketView]) -> Result<String> {
+        let columns: Vec<_> = COLUMNS
+            .iter()
+            .map(|status| {
+                serde_json::json!({
+                    "name": status.to_string(),
+                    "emoji": status.emoji(),
+                    "tickets": views
+                        .iter()
+                        .filter(|v| v.status == status.to_string())
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let specs: Vec<_> = self
+            .specs
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "id": s.metadata.id,
+                    "title": s.metadata.title,
+                    "phase": s.metadata.progress.current_phase.to_string(),
+                })
+            })
+            .collect();
+
+        let mut context = self.base_context("");
+        context.insert("columns", &columns);
+        context.insert("specs", &specs);
+        render(tera, "index.html", &context)
+    }
+
+    fn search_index(&self, views: &[TicketView]) -> Vec<SearchEntry> {
+        let tickets = views.iter().map(|v| SearchEntry {
+            kind: "ticket",
+            title: v.title.clone(),
+            url: format!("tickets/{}.html", v.id),
+            status: v.status.clone(),
+            text: format!(
+                "{} {} {} {}",
+                v.slug,
+                v.title,
+                v.tags.join(" "),
+                v.description
+            ),
+        });
+        let specs = self.specs.iter().map(|s| SearchEntry {
+            kind: "spec",
+            title: s.metadata.title.clone(),
+            url: format!("specs/{}.html", s.metadata.id),
+            status: s.metadata.progress.current_phase.to_string(),
+            text: format!("{} {}", s.metadata.title, s.metadata.description),
+        });
+        tickets.chain(specs).collect()
+    }
+}
+
+impl Exporter for HtmlExporter {
+    /// Render the board page only; use [`HtmlExporter::write_site`] for the full site
+    fn export(&self, tickets: &[Ticket]) -> Result<String> {
+        let views: Vec<TicketView> = tickets
+            .iter()
+            .map(|t| TicketView::new(t, self.time_spent.get(&t.id.to_string()).copied()))
+            .collect();
+        self.render_index(&Self::templates()?, &views)
+    }
+
+    fn format_name(&self) -> &'static str {
+        "HTML"
+    }
+}
+
+/// Template view of a ticket
+#[derive(Debug, Serialize)]
+struct TicketView {
+    id: String,
+    slug: String,
+    title: String,
+    description: String,
+    description_html: String,
+    status: String,
+    status_emoji: &'static str,
+    priority: String,
+    priority_emoji: &'static str,
+    assignee: Option<String>,
+    tags: Vec<String>,
+    tasks: Vec<TaskView>,
+    tasks_total: usize,
+    tasks_completed: usize,
+    completion: u32,
+    created_at: String,
+    started_at: Option<String>,
+    closed_at: Option<String>,
+    time_spent: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskView {
+    title: String,
+    completed: bool,
+}
+
+impl TicketView {
+    fn new(ticket: &Ticket, minutes: Option<i64>) -> Self {
+        let timestamp = |t: chrono::DateTime<Utc>| t.format("%Y-%m-%d %H:%M").to_string();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let completion = ticket.completion_percentage().round() as u32;
+        Self {
+            id: ticket.id.to_string(),
+            slug: ticket.slug.clone(),
+            title: ticket.title.clone(),
+            description: ticket.description.clone(),
+            description_html: markdown_to_html(&ticket.description),
+            status: ticket.status.to_string(),
+            status_emoji: ticket.status.emoji(),
+            priority: ticket.priority.to_string(),
+            priority_emoji: ticket.priority.emoji(),
+            assignee: ticket.assignee.clone(),
+            tags: ticket.tags.clone(),
+            tasks: ticket
+                .tasks
+                .iter()
+                .map(|t| TaskView {
+                    title: t.title.clone(),
+                    completed: t.completed,
+                })
+                .collect(),
+            tasks_total: ticket.total_tasks_count(),
+            tasks_completed: ticket.completed_tasks_count(),
+            completion,
+            created_at: timestamp(ticket.created_at),
+            started_at: ticket.started_at.map(timestamp),
+            closed_at: ticket.closed_at.map(timestamp),
+            time_spent: minutes
+                .filter(|m| *m > 0)
+                .map(|m| format_duration(chrono::Duration::minutes(m))),
+        }
+    }
+}
+
+/// Template view of a specification
+#[derive(Debug, Serialize)]
+struct SpecView<'a> {
+    id: &'a str,
+    title: &'a str,
+    description: &'a str,
+    phase: String,
+    version: String,
+    documents: Vec<DocumentView>,
+    tickets: Vec<&'a TicketView>,
+}
+
+#[derive(Debug, Serialize)]
+struct DocumentView {
+    name: &'static str,
+    html: String,
+}
+
+impl<'a> SpecView<'a> {
+    fn new(spec: &'a Specification, tickets: &'a [TicketView]) -> Self {
+        let documents = [
+            (SpecDocumentType::Requirements, &spec.requirements),
+            (SpecDocumentType::Design, &spec.design),
+            (SpecDocumentType::Tasks, &spec.tasks),
+        ]
+        .into_iter()
+        .filter_map(|(doc_type, content)| {
+            content.as_ref().map(|content| DocumentView {
+                name: doc_type.display_name(),
+                html: markdown_to_html(content),
+            })
+        })
+        .collect();
+
+        let meta = &spec.metadata;
+        Self {
+            id: &meta.id,
+            title: &meta.title,
+            description: &meta.description,
+            phase: meta.progress.current_phase.to_string(),
+            version: meta.version.to_string(),
+            documents,
+            tickets: tickets
+                .iter()
+                .filter(|t| {
+                    t.tags.contains(&meta.id)
+                        || meta.ticket_id.as_deref().is_some_and(|id| id == t.id)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Entry of the client-side search index
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    kind: &'static str,
+    title: String,
+    url: String,
+    status: String,
+    text: String,
+}
+
+/// Render Markdown to HTML, dropping raw HTML and unsafe link targets from
+/// the source
+fn markdown_to_html(markdown: &str) -> String {
+    use pulldown_cmark::{Event, Options, Parser, Tag, html};
+
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS).map(
+        |event| match event {
+            Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Link {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            }),
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => Event::Start(Tag::Image {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            }),
+            other => other,
+        },
+    );
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// Keep relative URLs and http, https and mailto links; replace anything
+/// else, such as `javascript:`, with `#`
+fn safe_url(url: pulldown_cmark::CowStr<'_>) -> pulldown_cmark::CowStr<'_> {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+        .collect();
+    let scheme = cleaned
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|scheme| !scheme.contains(['/', '?', '#']));
+    match scheme {
+        None => url,
+        Some(scheme)
+            if ["http", "https", "mailto"].contains(&scheme.to_ascii_lowercase().as_str()) =>
+        {
+            url
+        },
+        Some(_) => "#".into(),
+    }
+}
+
+fn render(tera: &Tera, template: &str, context: &Context) -> Result<String> {
+    tera.render(template, context)
+        .map_err(|e| VibeTicketError::custom(format!("Failed to render {template}: {e:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TicketBuilder;
+    use tempfile::TempDir;
+
+    fn fixture() -> (Vec<Ticket>, Specification) {
+        let mut spec = Specification::new("Login".to_string(), String::new(), None, vec![]);
+        spec.requirements = Some("# Requirements\n\n- REQ-001: users log in".to_string());
+        let mut ticket = TicketBuilder::new()
+            .slug("login-form")
+            .title("Login form")
+            .description(
+                "Render **form** <script>alert(1)</script> [docs](javascript:alert(1)) \
+                 ![logo](JaVaScript:alert(2)) [site](https://example.com)",
+            )
+            .status(Status::Doing)
+            .tags(vec![spec.metadata.id.clone()])
+            .build();
+        ticket.add_task("Add fields");
+        (vec![ticket], spec)
+    }
+
+    #[test]
+    fn test_render_site() {
+        let (tickets, spec) = fixture();
+        let ticket_id = tickets[0].id.to_string();
+        let spec_id = spec.metadata.id.clone();
+        let mut time = HashMap::new();
+        time.insert(ticket_id.clone(), 90);
+
+        let files = HtmlExporter::new("demo")
+            .with_specs(vec![spec])
+            .with_time_spent(time)
+            .render_site(&tickets)
+            .unwrap();
+        let file = |path: &str| {
+            files
+                .iter()
+                .find(|f| f.path == Path::new(path))
+                .unwrap_or_else(|| panic!("missing {path}"))
+        };
+
+        let index = &file("index.html").content;
+        assert!(index.contains("Doing (1)"));
+        assert!(index.contains(&format!("tickets/{ticket_id}.html")));
+
+        let page = &file(&format!("tickets/{ticket_id}.html")).content;
+        assert!(page.contains("<strong>form</strong>"));
+        assert!(!page.contains("<script>alert"));
+        assert!(!page.to_lowercase().contains("script:alert"));
+        assert!(page.contains(r#"<a href="https://example.com">site</a>"#));
+        assert!(page.contains("Add fields"));
+        assert!(page.contains("1h 30m"));
+
+        let spec_page = &file(&format!("specs/{spec_id}.html")).content;
+        assert!(spec_page.contains("REQ-001"));
+        assert!(spec_page.contains("Login form"));
+
+        assert!(file("search.json").content.contains("login-form"));
+    }
+
+    #[test]
+    fn test_write_site() {
+        let (tickets, _) = fixture();
+        let temp_dir = TempDir::new().unwrap();
+
+        let written = HtmlExporter::new("demo")
+            .write_site(&tickets, temp_dir.path())
+            .unwrap();
+
+        assert_eq!(written, 4);
+        assert!(temp_dir.path().join("index.html").exists());
+        assert!(temp_dir.path().join("tickets").is_dir());
+    }
+}
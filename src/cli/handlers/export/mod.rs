@@ -4,6 +4,8 @@
 //! to various formats using the Exporter trait.
 
 mod csv;
+pub mod graph;
+mod html;
 mod json;
 mod markdown;
 mod yaml;
@@ -11,11 +13,15 @@ mod yaml;
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::Ticket;
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{FileStorage, TicketRepository};
+use crate::specs::{SpecManager, SpecMetadata};
+use crate::storage::archive::is_archived;
+use crate::storage::{ArchiveStore, FileStorage, TicketRepository};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 pub use self::csv::CsvExporter;
+pub use self::graph::{GraphExporter, GraphFormat, GroupBy};
+pub use self::html::HtmlExporter;
 pub use self::json::JsonExporter;
 pub use self::markdown::MarkdownExporter;
 pub use self::yaml::YamlExporter;
@@ -59,15 +65,24 @@ pub fn handle_export_command(
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
+    // The HTML exporter writes a whole site rather than a single document
+    if matches!(format.to_lowercase().as_str(), "html" | "site") {
+        return export_site(output_path, include_archived, project_dir, output);
+    }
+
     // Get exporter for the format
     let exporter: Box<dyn Exporter> = match format.to_lowercase().as_str() {
         "json" => Box::new(JsonExporter),
         "yaml" => Box::new(YamlExporter),
         "csv" => Box::new(CsvExporter),
         "markdown" | "md" => Box::new(MarkdownExporter),
+        "dot" | "graphviz" | "mermaid" | "svg" => Box::new(
+            GraphExporter::new(format.parse()?, GroupBy::Status)
+                .with_specs(load_specs(project_dir)?),
+        ),
         _ => {
             return Err(VibeTicketError::custom(format!(
-                "Unsupported export format: {format}. Supported formats: json, yaml, csv, markdown"
+                "Unsupported export format: {format}. Supported formats: json, yaml, csv, markdown, html, dot, mermaid, svg"
             )));
         },
     };
@@ -90,29 +105,100 @@ pub fn handle_export_command(
 }
 
 /// Load tickets from storage
-fn load_tickets(project_dir: Option<&str>, include_archived: bool) -> Result<Vec<Ticket>> {
+pub fn load_tickets(project_dir: Option<&str>, include_archived: bool) -> Result<Vec<Ticket>> {
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
     let storage = FileStorage::new(&vibe_ticket_dir);
 
     let mut tickets = storage.load_all()?;
 
-    // Filter out archived tickets if not included
-    if !include_archived {
-        tickets.retain(|t| {
-            !t.metadata
-                .get("archived")
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or(false)
-        });
+    // Archived tickets live in the archive store; older versions only
+    // flagged them in place
+    if include_archived {
+        tickets.extend(ArchiveStore::new(&vibe_ticket_dir).load_all_tickets()?);
+    } else {
+        tickets.retain(|t| !is_archived(t));
     }
 
     // Sort tickets by creation date
-    tickets.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    tickets.sort_by_key(|t| t.created_at);
 
     Ok(tickets)
 }
 
+/// Export tickets and specs as a static HTML site into `output_path`
+fn export_site(
+    output_path: Option<String>,
+    include_archived: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    use super::time::TimeTracking;
+    use crate::config::Config;
+
+    let Some(dir) = output_path else {
+        return Err(VibeTicketError::custom(
+            "HTML export writes a site directory. Use --output <DIR> to choose where",
+        ));
+    };
+
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let tickets = load_tickets(project_dir, include_archived)?;
+
+    let config = Config::load_from_path(vibe_ticket_dir.join("config.yaml")).unwrap_or_default();
+    let spec_manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let specs = load_specs(project_dir)?
+        .iter()
+        .filter_map(|meta| spec_manager.load_spec(&meta.id).ok())
+        .collect();
+    let time_spent = TimeTracking::load(project_dir)?
+        .entries
+        .iter()
+        .map(|(ticket_id, entries)| {
+            (
+                ticket_id.clone(),
+                entries.iter().map(|e| e.duration_minutes).sum(),
+            )
+        })
+        .collect();
+
+    let exporter = HtmlExporter::new(config.project.name)
+        .with_specs(specs)
+        .with_time_spent(time_spent);
+    let written = exporter.write_site(&tickets, std::path::Path::new(&dir))?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "output": dir,
+            "format": exporter.format_name(),
+            "tickets": tickets.len(),
+            "files": written,
+        }))?;
+    } else {
+        output.success(&format!(
+            "Exported {} tickets as a static site to {dir} ({written} files)",
+            tickets.len()
+        ));
+        output.info(&format!("Open {dir}/index.html to browse the board"));
+    }
+
+    Ok(())
+}
+
+/// Load specification metadata for graph exports
+///
+/// Projects without a specs directory simply have no specifications.
+pub fn load_specs(project_dir: Option<&str>) -> Result<Vec<SpecMetadata>> {
+    let project_root = find_project_root(project_dir)?;
+    let specs_dir = project_root.join(".vibe-ticket").join("specs");
+    if !specs_dir.exists() {
+        return Ok(Vec::new());
+    }
+    SpecManager::new(specs_dir).list_specs()
+}
+
 /// Output export results
 fn output_results(
     content: String,
@@ -162,6 +248,7 @@ mod tests {
             created_at: Utc::now(),
             started_at: None,
             closed_at: None,
+            updated_at: None,
         }
     }
 
@@ -188,4 +275,5 @@ mod tests {
     test_exporter!(test_csv_exporter, CsvExporter, "test-ticket");
     test_exporter!(test_yaml_exporter, YamlExporter, "total: 1");
     test_exporter!(test_markdown_exporter, MarkdownExporter, "# Ticket Export");
+    test_exporter!(test_html_exporter, HtmlExporter::new("demo"), "Todo (1)");
 }
@@ -2,7 +2,9 @@
 
 use crate::cli::output::OutputFormatter;
 use crate::cli::utils::find_project_root;
+use crate::core::{Priority, Status, Ticket};
 use crate::error::{Result, VibeTicketError};
+use crate::storage::write_project_file;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -21,6 +23,31 @@ pub struct SavedFilter {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl SavedFilter {
+    /// Whether a ticket matches the filter expression
+    ///
+    /// Supports `status:`, `priority:`, `assignee:` and `tag:` terms, each
+    /// taking a comma-separated list of alternatives. Other words must occur
+    /// in the title or slug. Unknown keys are ignored.
+    #[must_use]
+    pub fn matches(&self, ticket: &Ticket) -> bool {
+        self.expression.split_whitespace().all(|term| {
+            let Some((key, value)) = term.split_once(':') else {
+                let term = term.to_lowercase();
+                return ticket.title.to_lowercase().contains(&term) || ticket.slug.contains(&term);
+            };
+            let any = |matches: &dyn Fn(&str) -> bool| value.split(',').any(matches);
+            match key.to_lowercase().as_str() {
+                "status" => any(&|v| Status::try_from(v).is_ok_and(|s| s == ticket.status)),
+                "priority" => any(&|v| Priority::try_from(v).is_ok_and(|p| p == ticket.priority)),
+                "assignee" => any(&|v| ticket.assignee.as_deref() == Some(v)),
+                "tag" => any(&|v| ticket.tags.iter().any(|tag| tag == v)),
+                _ => true,
+            }
+        })
+    }
+}
+
 /// Collection of saved filters
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SavedFilters {
@@ -47,8 +74,7 @@ impl SavedFilters {
         let path = Self::filters_path(project_dir)?;
         let content = serde_yaml::to_string(self)
             .map_err(|e| VibeTicketError::custom(format!("Failed to serialize filters: {e}")))?;
-        fs::write(&path, content)
-            .map_err(|e| VibeTicketError::custom(format!("Failed to write filters file: {e}")))?;
+        write_project_file(&path, content)?;
         Ok(())
     }
 
@@ -69,6 +95,7 @@ impl SavedFilters {
     }
 
     /// Get a filter by name
+    #[must_use]
     pub fn get(&self, name: &str) -> Option<&SavedFilter> {
         self.filters.get(name)
     }
@@ -300,4 +327,24 @@ mod tests {
         assert_eq!(parsed.name, filter.name);
         assert_eq!(parsed.expression, filter.expression);
     }
+
+    #[test]
+    fn test_saved_filter_matches() {
+        let filter = |expression: &str| SavedFilter {
+            name: "test".to_string(),
+            expression: expression.to_string(),
+            description: None,
+            created_at: chrono::Utc::now(),
+        };
+        let mut ticket = Ticket::new("fix-login", "Fix login crash");
+        ticket.priority = Priority::High;
+        ticket.tags = vec!["bug".to_string()];
+
+        assert!(filter("status:todo,doing priority:high").matches(&ticket));
+        assert!(filter("tag:bug login").matches(&ticket));
+        assert!(filter("unknown:key").matches(&ticket));
+        assert!(!filter("status:done").matches(&ticket));
+        assert!(!filter("assignee:alice").matches(&ticket));
+        assert!(!filter("signup").matches(&ticket));
+    }
 }
@@ -7,11 +7,11 @@ use crate::cli::output::OutputFormatter;
 use crate::cli::utils;
 use crate::core::{Status, Ticket, TicketId};
 use crate::error::{Result, VibeTicketError};
+use crate::specs::TaskSync;
 use crate::storage::{FileStorage, TicketRepository};
 use chrono::Utc;
 use dialoguer::{Confirm, Editor, Input, MultiSelect, theme::ColorfulTheme};
 use std::env;
-use std::fs;
 use std::path::Path;
 
 /// Handle the intent-focused finish command
@@ -48,7 +48,7 @@ pub fn handle_finish_command(
         t
     } else {
         // Try to get active ticket
-        get_active_ticket(&tickets_dir)?
+        get_active_ticket(&storage)?
     };
 
     // Parse ticket ID
@@ -81,9 +81,8 @@ pub fn handle_finish_command(
         get_closing_message(&ticket, formatter)?
     };
 
-    // Update ticket
-    ticket.status = Status::Done;
-    ticket.closed_at = Some(Utc::now());
+    // Update ticket, keeping the spec task it was exported from in sync
+    TaskSync::new(&tickets_dir).set_status(&mut ticket, Status::Done)?;
 
     // Add closing message to metadata
     ticket.metadata.insert(
@@ -95,10 +94,7 @@ pub fn handle_finish_command(
     storage.save(&ticket)?;
 
     // Clear active ticket
-    let active_ticket_path = tickets_dir.join("active_ticket");
-    if active_ticket_path.exists() {
-        fs::remove_file(&active_ticket_path)?;
-    }
+    storage.clear_active_ticket()?;
 
     // Handle worktree cleanup
     if !keep_worktree {
@@ -124,17 +120,16 @@ pub fn handle_finish_command(
 }
 
 /// Get the currently active ticket
-fn get_active_ticket(tickets_dir: &Path) -> Result<String> {
-    let active_ticket_path = tickets_dir.join("active_ticket");
-
-    if active_ticket_path.exists() {
-        let content = fs::read_to_string(&active_ticket_path)?;
-        Ok(content.trim().to_string())
-    } else {
-        Err(VibeTicketError::Custom(
-            "No active ticket. Specify a ticket ID or use 'vibe-ticket work-on' first.".to_string(),
-        ))
-    }
+fn get_active_ticket(storage: &FileStorage) -> Result<String> {
+    storage
+        .get_active_ticket()?
+        .map(|id| id.to_string())
+        .ok_or_else(|| {
+            VibeTicketError::Custom(
+                "No active ticket. Specify a ticket ID or use 'vibe-ticket work-on' first."
+                    .to_string(),
+            )
+        })
 }
 
 /// Handle incomplete tasks
@@ -0,0 +1,70 @@
+//! Handler for the `graph` command
+//!
+//! Renders the relations between tickets and specifications so planning
+//! sessions can see how work is connected.
+
+use super::export::graph::TicketGraph;
+use super::export::{GraphFormat, GroupBy, load_specs, load_tickets};
+use crate::cli::OutputFormatter;
+use crate::error::{Result, VibeTicketError};
+
+/// Handler for the `graph` command
+///
+/// # Arguments
+///
+/// * `format` - Output format (dot, mermaid, svg)
+/// * `group_by` - How to cluster nodes (status, spec, tag, assignee)
+/// * `output_path` - Optional file to write the graph to instead of stdout
+/// * `include_archived` - Whether archived tickets are part of the graph
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The format or grouping is unknown
+/// - The project is not initialized
+/// - The output file cannot be written
+pub fn handle_graph_command(
+    format: &str,
+    group_by: &str,
+    output_path: Option<String>,
+    include_archived: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let format: GraphFormat = format.parse()?;
+    let group_by: GroupBy = group_by.parse()?;
+
+    let tickets = load_tickets(project_dir, include_archived)?;
+    let specs = load_specs(project_dir)?;
+    let graph = TicketGraph::build(&tickets, &specs, group_by);
+    let content = graph.render(format);
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(&path, content)
+                .map_err(|e| VibeTicketError::io_error("write", std::path::Path::new(&path), e))?;
+
+            if output.is_json() {
+                output.print_json(&serde_json::json!({
+                    "status": "success",
+                    "output": path,
+                    "format": format.display_name(),
+                    "nodes": graph.nodes.len(),
+                    "edges": graph.edges.len(),
+                }))?;
+            } else {
+                output.success(&format!(
+                    "Wrote graph with {} nodes and {} edges to {path}",
+                    graph.nodes.len(),
+                    graph.edges.len()
+                ));
+                output.info(&format!("Format: {}", format.display_name()));
+            }
+        },
+        None => println!("{content}"),
+    }
+
+    Ok(())
+}
@@ -6,6 +6,7 @@
 use crate::cli::output::OutputFormatter;
 use crate::cli::utils::find_project_root;
 use crate::error::{Result, VibeTicketError};
+use crate::storage::write_project_file;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -142,8 +143,7 @@ impl Hooks {
         let path = Self::hooks_path(project_dir)?;
         let content = serde_yaml::to_string(self)
             .map_err(|e| VibeTicketError::custom(format!("Failed to serialize hooks: {e}")))?;
-        fs::write(&path, content)
-            .map_err(|e| VibeTicketError::custom(format!("Failed to write hooks file: {e}")))?;
+        write_project_file(&path, content)?;
         Ok(())
     }
 
@@ -164,6 +164,7 @@ impl Hooks {
     }
 
     /// Get a hook by name
+    #[must_use]
     pub fn get(&self, name: &str) -> Option<&Hook> {
         self.hooks.get(name)
     }
@@ -174,6 +175,7 @@ impl Hooks {
     }
 
     /// Get all hooks for a specific event
+    #[must_use]
     pub fn get_for_event(&self, event: HookEvent) -> Vec<&Hook> {
         self.hooks
             .values()
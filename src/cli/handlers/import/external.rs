@@ -0,0 +1,300 @@
+//! Common representation of issues exported from other trackers
+//!
+//! Each tracker parser produces [`ExternalIssue`] values, which are then
+//! converted to tickets using an [`ImportMapping`]. The tracker's own
+//! identifier is stored in the ticket metadata under [`EXTERNAL_ID_KEY`] so
+//! re-importing the same export updates tickets instead of duplicating them.
+
+use super::mapping::ImportMapping;
+use crate::cli::utils::slugify;
+use crate::core::{Status, Ticket, TicketId};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Metadata key holding the external tracker identifier
+pub const EXTERNAL_ID_KEY: &str = "external_id";
+
+/// Maximum length of the title part of generated slugs
+const MAX_SLUG_TITLE: usize = 40;
+
+/// A comment on an external issue
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalComment {
+    /// Comment author
+    pub author: Option<String>,
+    /// Comment text
+    pub body: String,
+    /// When the comment was written
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// An issue read from another tracker's export
+#[derive(Debug, Clone, Default)]
+pub struct ExternalIssue {
+    /// Tracker name (`github`, `gitlab`, `jira`)
+    pub source: &'static str,
+    /// Stable identifier within the tracker, e.g. `owner/repo#12` or `PROJ-7`
+    pub key: String,
+    /// Short reference used in the slug, e.g. `12` or `proj-7`
+    pub reference: String,
+    /// Issue title
+    pub title: String,
+    /// Issue body
+    pub description: String,
+    /// Workflow state as named by the tracker
+    pub state: String,
+    /// Priority as named by the tracker, if it has a priority field
+    pub priority: Option<String>,
+    /// Labels
+    pub labels: Vec<String>,
+    /// Assignee user names
+    pub assignees: Vec<String>,
+    /// Reporter or author
+    pub author: Option<String>,
+    /// Comments in chronological order
+    pub comments: Vec<ExternalComment>,
+    /// Creation time
+    pub created_at: Option<DateTime<Utc>>,
+    /// Close or resolution time
+    pub closed_at: Option<DateTime<Utc>>,
+    /// Link to the issue in the tracker
+    pub url: Option<String>,
+}
+
+impl ExternalIssue {
+    /// Identifier stored in ticket metadata, e.g. `github:owner/repo#12`
+    #[must_use]
+    pub fn external_id(&self) -> String {
+        format!("{}:{}", self.source, self.key)
+    }
+
+    /// Convert the issue into a new ticket
+    #[must_use]
+    pub fn into_ticket(self, mapping: &ImportMapping) -> Ticket {
+        let status = mapping.status(&self.state);
+        let priority = mapping.resolve_priority(self.priority.as_deref(), &self.labels);
+        let created_at = self.created_at.unwrap_or_else(Utc::now);
+
+        let mut slug = format!("{}-{}", self.source, slugify(&self.reference));
+        let title_slug = slugify(&self.title);
+        let title_slug = title_slug
+            .get(..MAX_SLUG_TITLE)
+            .unwrap_or(&title_slug)
+            .trim_end_matches('-');
+        if !title_slug.is_empty() {
+            slug.push('-');
+            slug.push_str(title_slug);
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert(EXTERNAL_ID_KEY.to_string(), self.external_id().into());
+        metadata.insert("external_source".to_string(), self.source.into());
+        if let Some(url) = &self.url {
+            metadata.insert("external_url".to_string(), url.as_str().into());
+        }
+        if let Some(author) = &self.author {
+            metadata.insert("reporter".to_string(), author.as_str().into());
+        }
+        if self.assignees.len() > 1 {
+            metadata.insert("assignees".to_string(), self.assignees.clone().into());
+        }
+        if !self.comments.is_empty() {
+            metadata.insert(
+                "comments".to_string(),
+                serde_json::to_value(&self.comments).unwrap_or_default(),
+            );
+        }
+
+        Ticket {
+            id: TicketId::new(),
+            slug,
+            title: self.title,
+            description: self.description,
+            priority,
+            status,
+            tags: self.labels,
+            created_at,
+            started_at: matches!(status, Status::Doing | Status::Review | Status::Done)
+                .then_some(created_at),
+            closed_at: if status == Status::Done {
+                self.closed_at.or(Some(created_at))
+            } else {
+                None
+            },
+            updated_at: None,
+            assignee: self.assignees.into_iter().next(),
+            tasks: Vec::new(),
+            metadata,
+        }
+    }
+}
+
+/// Extract the external ID of a ticket, if it was imported from a tracker
+#[must_use]
+pub fn external_id(ticket: &Ticket) -> Option<&str> {
+    ticket
+        .metadata
+        .get(EXTERNAL_ID_KEY)
+        .and_then(serde_json::Value::as_str)
+}
+
+/// Apply a re-imported issue to the ticket created by an earlier import
+///
+/// The ticket keeps its ID, slug, tasks and any local metadata; tracker
+/// fields are refreshed. Returns `None` when nothing changed.
+#[must_use]
+pub fn merge_reimport(existing: Ticket, incoming: Ticket) -> Option<Ticket> {
+    let mut metadata = existing.metadata.clone();
+    metadata.extend(incoming.metadata);
+
+    let merged = Ticket {
+        title: incoming.title,
+        description: incoming.description,
+        priority: incoming.priority,
+        status: incoming.status,
+        tags: incoming.tags,
+        started_at: existing.started_at.or(incoming.started_at),
+        // Exports without a close date get one at import time; keep the first
+        closed_at: if existing.status == Status::Done && incoming.status == Status::Done {
+            existing.closed_at.or(incoming.closed_at)
+        } else {
+            incoming.closed_at
+        },
+        assignee: incoming.assignee,
+        metadata,
+        ..existing.clone()
+    };
+
+    let changed = merged.title != existing.title
+        || merged.description != existing.description
+        || merged.priority != existing.priority
+        || merged.status != existing.status
+        || merged.tags != existing.tags
+        || merged.started_at != existing.started_at
+        || merged.closed_at != existing.closed_at
+        || merged.assignee != existing.assignee
+        || merged.metadata != existing.metadata;
+    changed.then_some(merged)
+}
+
+/// Build a stable issue key from a web URL, falling back to the bare number
+///
+/// `https://github.com/owner/repo/issues/12` becomes `owner/repo#12` and
+/// `https://gitlab.com/group/project/-/issues/3` becomes `group/project#3`.
+#[must_use]
+pub fn issue_key(url: Option<&str>, number: &str) -> String {
+    let project = url
+        .and_then(|url| url.split_once("://").map(|(_, rest)| rest))
+        .and_then(|rest| rest.split_once('/').map(|(_, path)| path))
+        .and_then(|path| {
+            path.rsplit_once("/issues/")
+                .map(|(project, _)| project.trim_end_matches("/-"))
+        });
+    match project {
+        Some(project) if !project.is_empty() => format!("{project}#{number}"),
+        _ => format!("#{number}"),
+    }
+}
+
+/// Parse a timestamp in any of the formats used by tracker exports
+#[must_use]
+pub fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    // GitLab CSV ("2024-01-15 10:00:00 UTC") and Jira CSV ("15/Jan/24 10:00 AM")
+    let value = value.trim_end_matches(" UTC");
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%d/%b/%y %I:%M %p",
+        "%d/%b/%Y %I:%M %p",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|naive| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Priority;
+
+    #[test]
+    fn test_issue_key_from_url() {
+        assert_eq!(
+            issue_key(Some("https://github.com/owner/repo/issues/12"), "12"),
+            "owner/repo#12"
+        );
+        assert_eq!(
+            issue_key(Some("https://gitlab.com/group/sub/project/-/issues/3"), "3"),
+            "group/sub/project#3"
+        );
+        assert_eq!(issue_key(None, "5"), "#5");
+    }
+
+    #[test]
+    fn test_parse_datetime_formats() {
+        assert!(parse_datetime("2024-01-15T10:00:00Z").is_some());
+        assert!(parse_datetime("Mon, 15 Jan 2024 10:00:00 +0000").is_some());
+        assert!(parse_datetime("2024-01-15 10:00:00 UTC").is_some());
+        assert!(parse_datetime("15/Jan/24 10:00 AM").is_some());
+        assert!(parse_datetime("").is_none());
+    }
+
+    #[test]
+    fn test_into_ticket() {
+        let issue = ExternalIssue {
+            source: "github",
+            key: "owner/repo#12".to_string(),
+            reference: "12".to_string(),
+            title: "Fix the login page!".to_string(),
+            state: "CLOSED".to_string(),
+            labels: vec!["bug".to_string(), "priority: high".to_string()],
+            assignees: vec!["alice".to_string(), "bob".to_string()],
+            ..ExternalIssue::default()
+        };
+        let ticket = issue.into_ticket(&ImportMapping::default());
+
+        assert_eq!(ticket.slug, "github-12-fix-the-login-page");
+        assert_eq!(ticket.status, Status::Done);
+        assert_eq!(ticket.priority, Priority::High);
+        assert_eq!(ticket.assignee.as_deref(), Some("alice"));
+        assert!(ticket.closed_at.is_some());
+        assert_eq!(external_id(&ticket), Some("github:owner/repo#12"));
+    }
+
+    #[test]
+    fn test_merge_reimport() {
+        let issue = ExternalIssue {
+            source: "jira",
+            key: "PROJ-1".to_string(),
+            reference: "PROJ-1".to_string(),
+            title: "Original".to_string(),
+            state: "To Do".to_string(),
+            ..ExternalIssue::default()
+        };
+        let mapping = ImportMapping::default();
+        let mut existing = issue.clone().into_ticket(&mapping);
+        existing.slug = "renamed-locally".to_string();
+
+        assert!(merge_reimport(existing.clone(), issue.clone().into_ticket(&mapping)).is_none());
+
+        let changed = ExternalIssue {
+            title: "Updated".to_string(),
+            ..issue
+        };
+        let merged = merge_reimport(existing.clone(), changed.into_ticket(&mapping)).unwrap();
+        assert_eq!(merged.id, existing.id);
+        assert_eq!(merged.slug, "renamed-locally");
+        assert_eq!(merged.title, "Updated");
+    }
+}
@@ -0,0 +1,164 @@
+//! GitHub issues importer
+//!
+//! Reads the JSON produced by
+//! `gh issue list --json number,title,body,state,labels,assignees,author,comments,createdAt,closedAt,url`
+//! and also accepts the REST API shape (`html_url`, `created_at`, `user`, ...).
+//! Pull requests returned by the REST API are skipped.
+
+use super::external::{ExternalComment, ExternalIssue, issue_key, parse_datetime};
+use crate::error::{Result, VibeTicketError};
+use serde_json::Value;
+
+/// Whether a JSON document looks like a GitHub issue list
+#[must_use]
+pub fn detect(json: &Value) -> bool {
+    json.as_array()
+        .and_then(|items| items.first())
+        .is_some_and(|item| {
+            item.get("number").is_some() && item.get("state").is_some() && item.get("iid").is_none()
+        })
+}
+
+/// Parse a GitHub issue list
+pub fn parse(content: &str) -> Result<Vec<ExternalIssue>> {
+    let json: Value = serde_json::from_str(content)
+        .map_err(|e| VibeTicketError::deserialization_error("GitHub issues JSON", e))?;
+    let items = json
+        .as_array()
+        .ok_or_else(|| VibeTicketError::custom("GitHub export must be a JSON array of issues"))?;
+
+    items
+        .iter()
+        .filter(|item| item.get("pull_request").is_none())
+        .map(parse_issue)
+        .collect()
+}
+
+fn parse_issue(item: &Value) -> Result<ExternalIssue> {
+    let number = item
+        .get("number")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| VibeTicketError::custom("GitHub issue is missing its number"))?
+        .to_string();
+    let url = str_field(item, &["url", "html_url"]).filter(|url| url.contains("/issues/"));
+
+    let mut assignees: Vec<String> = item
+        .get("assignees")
+        .and_then(Value::as_array)
+        .map(|list| list.iter().filter_map(login).collect())
+        .unwrap_or_default();
+    if assignees.is_empty() {
+        assignees.extend(item.get("assignee").and_then(login));
+    }
+
+    Ok(ExternalIssue {
+        source: "github",
+        key: issue_key(url.as_deref(), &number),
+        reference: number,
+        title: str_field(item, &["title"]).unwrap_or_default(),
+        description: str_field(item, &["body"]).unwrap_or_default(),
+        state: str_field(item, &["state"]).unwrap_or_default(),
+        priority: None,
+        labels: item
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| {
+                labels
+                    .iter()
+                    .filter_map(|label| label.get("name").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        assignees,
+        author: item
+            .get("author")
+            .or_else(|| item.get("user"))
+            .and_then(login),
+        comments: item
+            .get("comments")
+            .and_then(Value::as_array)
+            .map(|comments| {
+                comments
+                    .iter()
+                    .map(|comment| ExternalComment {
+                        author: comment
+                            .get("author")
+                            .or_else(|| comment.get("user"))
+                            .and_then(login),
+                        body: str_field(comment, &["body"]).unwrap_or_default(),
+                        created_at: str_field(comment, &["createdAt", "created_at"])
+                            .and_then(|value| parse_datetime(&value)),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        created_at: str_field(item, &["createdAt", "created_at"])
+            .and_then(|value| parse_datetime(&value)),
+        closed_at: str_field(item, &["closedAt", "closed_at"])
+            .and_then(|value| parse_datetime(&value)),
+        url,
+    })
+}
+
+/// First string value among the given field names
+fn str_field(value: &Value, names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|name| value.get(name).and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+fn login(user: &Value) -> Option<String> {
+    user.get("login")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gh_cli_output() {
+        let content = r#"[{
+            "number": 12,
+            "title": "Login fails",
+            "body": "Steps to reproduce",
+            "state": "OPEN",
+            "url": "https://github.com/owner/repo/issues/12",
+            "labels": [{"name": "bug"}],
+            "assignees": [{"login": "alice"}],
+            "author": {"login": "bob"},
+            "comments": [{"author": {"login": "carol"}, "body": "Confirmed", "createdAt": "2024-01-16T09:00:00Z"}],
+            "createdAt": "2024-01-15T10:00:00Z",
+            "closedAt": null
+        }]"#;
+        let json: Value = serde_json::from_str(content).unwrap();
+        assert!(detect(&json));
+
+        let issues = parse(content).unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.external_id(), "github:owner/repo#12");
+        assert_eq!(issue.labels, vec!["bug"]);
+        assert_eq!(issue.assignees, vec!["alice"]);
+        assert_eq!(issue.author.as_deref(), Some("bob"));
+        assert_eq!(issue.comments[0].body, "Confirmed");
+        assert!(issue.closed_at.is_none());
+    }
+
+    #[test]
+    fn test_rest_api_skips_pull_requests() {
+        let content = r#"[
+            {"number": 1, "title": "Issue", "state": "closed", "html_url": "https://github.com/o/r/issues/1",
+             "assignee": {"login": "alice"}, "comments": 3},
+            {"number": 2, "title": "PR", "state": "open", "html_url": "https://github.com/o/r/pull/2",
+             "pull_request": {}}
+        ]"#;
+        let issues = parse(content).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].assignees, vec!["alice"]);
+        assert!(issues[0].comments.is_empty());
+    }
+}
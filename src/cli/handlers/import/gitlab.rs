@@ -0,0 +1,234 @@
+//! GitLab issues importer
+//!
+//! Accepts both the CSV produced by "Export as CSV" on an issue list and the
+//! JSON returned by the issues API. The API does not embed comments, but an
+//! optional `notes` array on each issue is imported when present.
+
+use super::external::{ExternalComment, ExternalIssue, issue_key, parse_datetime};
+use crate::error::{Result, VibeTicketError};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Whether a JSON document looks like a GitLab issue list
+#[must_use]
+pub fn detect_json(json: &Value) -> bool {
+    json.as_array()
+        .and_then(|items| items.first())
+        .is_some_and(|item| item.get("iid").is_some())
+}
+
+/// Whether a CSV header row looks like a GitLab issue export
+#[must_use]
+pub fn detect_csv(header: &str) -> bool {
+    header.contains("Issue ID") && header.contains("URL") && header.contains("Author Username")
+}
+
+/// Parse a GitLab export, choosing JSON or CSV from the content
+pub fn parse(content: &str) -> Result<Vec<ExternalIssue>> {
+    if content.trim_start().starts_with('[') {
+        parse_json(content)
+    } else {
+        parse_csv(content)
+    }
+}
+
+/// Parse the issues API JSON
+pub fn parse_json(content: &str) -> Result<Vec<ExternalIssue>> {
+    let json: Value = serde_json::from_str(content)
+        .map_err(|e| VibeTicketError::deserialization_error("GitLab issues JSON", e))?;
+    let items = json
+        .as_array()
+        .ok_or_else(|| VibeTicketError::custom("GitLab export must be a JSON array of issues"))?;
+
+    items
+        .iter()
+        .map(|item| {
+            let iid = item
+                .get("iid")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| VibeTicketError::custom("GitLab issue is missing its iid"))?
+                .to_string();
+            let url = str_field(item, "web_url");
+            let mut assignees: Vec<String> = item
+                .get("assignees")
+                .and_then(Value::as_array)
+                .map(|list| list.iter().filter_map(username).collect())
+                .unwrap_or_default();
+            if assignees.is_empty() {
+                assignees.extend(item.get("assignee").and_then(username));
+            }
+
+            Ok(ExternalIssue {
+                source: "gitlab",
+                key: issue_key(url.as_deref(), &iid),
+                reference: iid,
+                title: str_field(item, "title").unwrap_or_default(),
+                description: str_field(item, "description").unwrap_or_default(),
+                state: str_field(item, "state").unwrap_or_default(),
+                priority: None,
+                labels: item
+                    .get("labels")
+                    .and_then(Value::as_array)
+                    .map(|labels| {
+                        labels
+                            .iter()
+                            .filter_map(|label| {
+                                label
+                                    .as_str()
+                                    .or_else(|| label.get("name").and_then(Value::as_str))
+                            })
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                assignees,
+                author: item.get("author").and_then(username),
+                comments: item
+                    .get("notes")
+                    .and_then(Value::as_array)
+                    .map(|notes| {
+                        notes
+                            .iter()
+                            .filter(|note| {
+                                note.get("system").and_then(Value::as_bool) != Some(true)
+                            })
+                            .map(|note| ExternalComment {
+                                author: note.get("author").and_then(username),
+                                body: str_field(note, "body").unwrap_or_default(),
+                                created_at: str_field(note, "created_at")
+                                    .and_then(|value| parse_datetime(&value)),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                created_at: str_field(item, "created_at").and_then(|value| parse_datetime(&value)),
+                closed_at: str_field(item, "closed_at").and_then(|value| parse_datetime(&value)),
+                url,
+            })
+        })
+        .collect()
+}
+
+/// Parse the issue list CSV export
+pub fn parse_csv(content: &str) -> Result<Vec<ExternalIssue>> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers: HashMap<String, usize> = reader
+        .headers()
+        .map_err(|e| VibeTicketError::deserialization_error("GitLab CSV header", e))?
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.trim().to_string(), index))
+        .collect();
+
+    let mut issues = Vec::new();
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| VibeTicketError::deserialization_error("GitLab CSV record", e))?;
+        let field = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|index| record.get(*index))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let iid = field("Issue ID")
+            .ok_or_else(|| VibeTicketError::custom("GitLab CSV row is missing 'Issue ID'"))?
+            .to_string();
+        let url = field("URL").map(str::to_string);
+        let assignees = field("Assignee Username")
+            .map(|names| {
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        issues.push(ExternalIssue {
+            source: "gitlab",
+            key: issue_key(url.as_deref(), &iid),
+            reference: iid,
+            title: field("Title").unwrap_or_default().to_string(),
+            description: field("Description").unwrap_or_default().to_string(),
+            state: field("State").unwrap_or_default().to_string(),
+            priority: None,
+            labels: field("Labels")
+                .map(|labels| {
+                    labels
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|label| !label.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            assignees,
+            author: field("Author Username").map(str::to_string),
+            comments: Vec::new(),
+            created_at: field("Created At (UTC)").and_then(parse_datetime),
+            closed_at: field("Closed At (UTC)").and_then(parse_datetime),
+            url,
+        });
+    }
+
+    Ok(issues)
+}
+
+fn str_field(value: &Value, name: &str) -> Option<String> {
+    value.get(name).and_then(Value::as_str).map(str::to_string)
+}
+
+fn username(user: &Value) -> Option<String> {
+    user.get("username")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_api_json() {
+        let content = r#"[{
+            "iid": 3,
+            "title": "Crash on save",
+            "description": "Stack trace attached",
+            "state": "opened",
+            "web_url": "https://gitlab.com/group/project/-/issues/3",
+            "labels": ["bug", "priority::high"],
+            "assignees": [{"username": "alice"}],
+            "author": {"username": "bob"},
+            "notes": [
+                {"body": "added label", "system": true},
+                {"body": "Looking into it", "author": {"username": "alice"}}
+            ],
+            "created_at": "2024-01-15T10:00:00.000Z"
+        }]"#;
+        let json: Value = serde_json::from_str(content).unwrap();
+        assert!(detect_json(&json));
+
+        let issues = parse(content).unwrap();
+        assert_eq!(issues[0].external_id(), "gitlab:group/project#3");
+        assert_eq!(issues[0].labels, vec!["bug", "priority::high"]);
+        assert_eq!(issues[0].comments.len(), 1);
+        assert!(issues[0].created_at.is_some());
+    }
+
+    #[test]
+    fn test_parse_csv_export() {
+        let content = "Title,Description,Issue ID,URL,State,Author,Author Username,Assignee,Assignee Username,Created At (UTC),Closed At (UTC),Labels\n\
+            Crash on save,Trace,3,https://gitlab.com/group/project/-/issues/3,Closed,Bob,bob,Alice,alice,2024-01-15 10:00:00 UTC,2024-01-16 10:00:00 UTC,\"bug,ui\"\n";
+        assert!(detect_csv(content.lines().next().unwrap()));
+
+        let issues = parse(content).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].external_id(), "gitlab:group/project#3");
+        assert_eq!(issues[0].labels, vec!["bug", "ui"]);
+        assert_eq!(issues[0].assignees, vec!["alice"]);
+        assert!(issues[0].closed_at.is_some());
+    }
+}
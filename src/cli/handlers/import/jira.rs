@@ -0,0 +1,313 @@
+//! Jira issues importer
+//!
+//! Supports the two file exports available from the Jira issue navigator:
+//! - CSV, where multi-valued fields such as `Labels` and `Comment` are
+//!   repeated as several columns with the same header
+//! - XML (the RSS feed), with one `<item>` per issue
+
+use super::external::{ExternalComment, ExternalIssue, parse_datetime};
+use crate::error::{Result, VibeTicketError};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+/// Whether a CSV header row looks like a Jira export
+#[must_use]
+pub fn detect_csv(header: &str) -> bool {
+    header.contains("Issue key") && header.contains("Summary")
+}
+
+/// Whether an XML document looks like a Jira RSS export
+#[must_use]
+pub fn detect_xml(content: &str) -> bool {
+    let trimmed = content.trim_start();
+    trimmed.starts_with('<') && trimmed.contains("<rss") && trimmed.contains("<item>")
+}
+
+/// Parse a Jira export, choosing XML or CSV from the content
+pub fn parse(content: &str) -> Result<Vec<ExternalIssue>> {
+    if content.trim_start().starts_with('<') {
+        parse_xml(content)
+    } else {
+        parse_csv(content)
+    }
+}
+
+/// Parse a Jira CSV export
+pub fn parse_csv(content: &str) -> Result<Vec<ExternalIssue>> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| VibeTicketError::deserialization_error("Jira CSV header", e))?
+        .iter()
+        .map(|name| name.trim().to_string())
+        .collect();
+
+    let mut issues = Vec::new();
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| VibeTicketError::deserialization_error("Jira CSV record", e))?;
+        // All non-empty values of a (possibly repeated) column
+        let values = |name: &str| -> Vec<String> {
+            headers
+                .iter()
+                .zip(record.iter())
+                .filter(|(header, value)| header.as_str() == name && !value.trim().is_empty())
+                .map(|(_, value)| value.trim().to_string())
+                .collect()
+        };
+        let field = |name: &str| values(name).into_iter().next();
+
+        let key = field("Issue key")
+            .ok_or_else(|| VibeTicketError::custom("Jira CSV row is missing 'Issue key'"))?;
+
+        issues.push(ExternalIssue {
+            source: "jira",
+            reference: key.clone(),
+            title: field("Summary").unwrap_or_default(),
+            description: field("Description").unwrap_or_default(),
+            state: field("Status").unwrap_or_default(),
+            priority: field("Priority"),
+            labels: values("Labels"),
+            assignees: field("Assignee").into_iter().collect(),
+            author: field("Reporter"),
+            comments: values("Comment")
+                .iter()
+                .map(|comment| parse_csv_comment(comment))
+                .collect(),
+            created_at: field("Created").as_deref().and_then(parse_datetime),
+            closed_at: field("Resolved").as_deref().and_then(parse_datetime),
+            url: None,
+            key,
+        });
+    }
+
+    Ok(issues)
+}
+
+/// Parse a CSV comment cell of the form `date;author;body`
+fn parse_csv_comment(cell: &str) -> ExternalComment {
+    let mut parts = cell.splitn(3, ';');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(date), Some(author), Some(body)) if parse_datetime(date).is_some() => {
+            ExternalComment {
+                author: Some(author.to_string()),
+                body: body.to_string(),
+                created_at: parse_datetime(date),
+            }
+        },
+        _ => ExternalComment {
+            author: None,
+            body: cell.to_string(),
+            created_at: None,
+        },
+    }
+}
+
+/// Parse a Jira XML (RSS) export
+pub fn parse_xml(content: &str) -> Result<Vec<ExternalIssue>> {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut issues = Vec::new();
+    let mut current: Option<ExternalIssue> = None;
+    let mut comment_author: Option<String> = None;
+    let mut comment_created: Option<String> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| VibeTicketError::custom(format!("Invalid Jira XML: {e}")))?;
+        match event {
+            Event::Start(start) => {
+                let name = String::from_utf8_lossy(start.name().as_ref()).to_string();
+                text.clear();
+                if name == "item" {
+                    current = Some(ExternalIssue {
+                        source: "jira",
+                        ..ExternalIssue::default()
+                    });
+                } else if current.is_some() {
+                    if name == "comment" {
+                        comment_author = attribute(&start, "author");
+                        comment_created = attribute(&start, "created");
+                    } else if name == "assignee" || name == "reporter" {
+                        // Prefer the account name over the display name
+                        if let (Some(issue), Some(user)) =
+                            (current.as_mut(), attribute(&start, "username"))
+                        {
+                            set_user(issue, &name, user);
+                        }
+                    }
+                }
+            },
+            Event::Text(value) => {
+                let value = value
+                    .unescape()
+                    .map_err(|e| VibeTicketError::custom(format!("Invalid Jira XML: {e}")))?;
+                text.push_str(&value);
+            },
+            Event::CData(value) => text.push_str(&String::from_utf8_lossy(&value)),
+            Event::End(end) => {
+                let name = String::from_utf8_lossy(end.name().as_ref()).to_string();
+                if name == "item" {
+                    if let Some(issue) = current.take() {
+                        issues.push(issue);
+                    }
+                } else if let Some(issue) = current.as_mut() {
+                    apply_xml_field(
+                        issue,
+                        &name,
+                        std::mem::take(&mut text),
+                        &mut comment_author,
+                        &mut comment_created,
+                    );
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    if issues.iter().any(|issue| issue.key.is_empty()) {
+        return Err(VibeTicketError::custom(
+            "Jira XML item is missing its <key>",
+        ));
+    }
+    Ok(issues)
+}
+
+fn apply_xml_field(
+    issue: &mut ExternalIssue,
+    name: &str,
+    text: String,
+    comment_author: &mut Option<String>,
+    comment_created: &mut Option<String>,
+) {
+    match name {
+        "key" => {
+            issue.reference.clone_from(&text);
+            issue.key = text;
+        },
+        "summary" => issue.title = text,
+        "description" => issue.description = strip_html(&text),
+        "status" => issue.state = text,
+        "priority" => issue.priority = Some(text),
+        "link" => issue.url = Some(text),
+        "label" => issue.labels.push(text),
+        "created" => issue.created_at = parse_datetime(&text),
+        "resolved" => issue.closed_at = parse_datetime(&text),
+        "assignee" | "reporter" if !text.is_empty() && text != "Unassigned" => {
+            set_user(issue, name, text);
+        },
+        "comment" => issue.comments.push(ExternalComment {
+            author: comment_author.take(),
+            body: strip_html(&text),
+            created_at: comment_created.take().as_deref().and_then(parse_datetime),
+        }),
+        _ => {},
+    }
+}
+
+/// Record an assignee or reporter unless one was already taken from attributes
+fn set_user(issue: &mut ExternalIssue, field: &str, user: String) {
+    if field == "assignee" {
+        if issue.assignees.is_empty() {
+            issue.assignees.push(user);
+        }
+    } else if issue.author.is_none() {
+        issue.author = Some(user);
+    }
+}
+
+fn attribute(start: &BytesStart<'_>, name: &str) -> Option<String> {
+    start
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+/// Reduce the rendered HTML Jira puts in descriptions and comments to text
+fn strip_html(html: &str) -> String {
+    let html = html
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("</p>", "\n\n")
+        .replace("</li>", "\n");
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_with_repeated_columns() {
+        let content = "Summary,Issue key,Issue id,Status,Priority,Assignee,Reporter,Created,Resolved,Description,Labels,Labels,Comment\n\
+            Broken export,PROJ-7,10007,In Progress,Highest,alice,bob,15/Jan/24 10:00 AM,,Export fails,backend,urgent,16/Jan/24 9:30 AM;carol;Seen it too\n";
+        assert!(detect_csv(content.lines().next().unwrap()));
+
+        let issues = parse(content).unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.external_id(), "jira:PROJ-7");
+        assert_eq!(issue.labels, vec!["backend", "urgent"]);
+        assert_eq!(issue.priority.as_deref(), Some("Highest"));
+        assert_eq!(issue.comments[0].author.as_deref(), Some("carol"));
+        assert_eq!(issue.comments[0].body, "Seen it too");
+        assert!(issue.created_at.is_some());
+    }
+
+    #[test]
+    fn test_parse_xml_export() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="0.92"><channel><title>Jira</title>
+<item>
+  <title>[PROJ-8] Slow search</title>
+  <link>https://jira.example.com/browse/PROJ-8</link>
+  <key id="10008">PROJ-8</key>
+  <summary>Slow search</summary>
+  <description>&lt;p&gt;Search takes &lt;b&gt;10s&lt;/b&gt;&lt;/p&gt;</description>
+  <priority id="3">Medium</priority>
+  <status id="6">Done</status>
+  <assignee username="alice">Alice Example</assignee>
+  <reporter username="bob">Bob Example</reporter>
+  <created>Mon, 15 Jan 2024 10:00:00 +0000</created>
+  <resolved>Tue, 16 Jan 2024 10:00:00 +0000</resolved>
+  <labels><label>perf</label></labels>
+  <comments><comment id="1" author="carol" created="Mon, 15 Jan 2024 12:00:00 +0000">&lt;p&gt;Reproduced&lt;/p&gt;</comment></comments>
+</item>
+</channel></rss>"#;
+        assert!(detect_xml(content));
+
+        let issues = parse(content).unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.external_id(), "jira:PROJ-8");
+        assert_eq!(issue.title, "Slow search");
+        assert_eq!(issue.description, "Search takes 10s");
+        assert_eq!(issue.assignees, vec!["alice"]);
+        assert_eq!(issue.author.as_deref(), Some("bob"));
+        assert_eq!(issue.labels, vec!["perf"]);
+        assert_eq!(issue.comments[0].body, "Reproduced");
+        assert!(issue.closed_at.is_some());
+    }
+}
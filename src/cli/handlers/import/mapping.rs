@@ -0,0 +1,154 @@
+//! Status and priority mapping for tracker imports
+//!
+//! Every tracker names its workflow states and priorities differently. The
+//! built-in rules cover the common defaults of GitHub, GitLab and Jira, and a
+//! `--mapping` YAML file can override them:
+//!
+//! ```yaml
+//! status:
+//!   "In QA": review
+//!   "Won't Do": done
+//! priority:
+//!   "P0": critical
+//!   "Nice to have": low
+//! ```
+//!
+//! Keys are matched case-insensitively. Priority keys are also matched
+//! against labels, so label-based priorities like `priority::high` work.
+
+use crate::core::{Priority, Status};
+use crate::error::{Result, VibeTicketError};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Custom status and priority mapping loaded from a YAML file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportMapping {
+    /// External state name to status
+    pub status: HashMap<String, Status>,
+    /// External priority or label name to priority
+    pub priority: HashMap<String, Priority>,
+}
+
+impl ImportMapping {
+    /// Load a mapping file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| VibeTicketError::io_error("read", path, e))?;
+        Self::parse(&content)
+    }
+
+    /// Parse a mapping from YAML
+    pub fn parse(content: &str) -> Result<Self> {
+        let mapping: Self = serde_yaml::from_str(content)
+            .map_err(|e| VibeTicketError::deserialization_error("mapping file", e))?;
+        Ok(Self {
+            status: lowercase_keys(mapping.status),
+            priority: lowercase_keys(mapping.priority),
+        })
+    }
+
+    /// Map an external state to a status
+    #[must_use]
+    pub fn status(&self, state: &str) -> Status {
+        let key = normalize(state);
+        if let Some(status) = self.status.get(&key) {
+            return *status;
+        }
+        match key.as_str() {
+            "in progress" | "in development" | "started" | "doing" => Status::Doing,
+            "in review" | "code review" | "review" | "reviewing" => Status::Review,
+            "closed" | "done" | "resolved" | "completed" | "merged" => Status::Done,
+            "blocked" | "on hold" | "impeded" => Status::Blocked,
+            _ => Status::try_from(key.as_str()).unwrap_or(Status::Todo),
+        }
+    }
+
+    /// Map an external priority name to a priority, if it is recognized
+    #[must_use]
+    pub fn priority(&self, name: &str) -> Option<Priority> {
+        let key = normalize(name);
+        if let Some(priority) = self.priority.get(&key) {
+            return Some(*priority);
+        }
+        // Labels such as "priority: high", "priority::high" or "priority/high"
+        let key = key.strip_prefix("priority").map_or(key.as_str(), |rest| {
+            rest.trim_start_matches([':', '/', '-', ' '])
+        });
+        match key {
+            "highest" | "blocker" | "critical" | "urgent" | "p0" => Some(Priority::Critical),
+            "high" | "major" | "p1" => Some(Priority::High),
+            "medium" | "normal" | "p2" => Some(Priority::Medium),
+            "low" | "lowest" | "minor" | "trivial" | "p3" | "p4" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+
+    /// Resolve the priority of an issue from its priority field or labels
+    #[must_use]
+    pub fn resolve_priority(&self, field: Option<&str>, labels: &[String]) -> Priority {
+        field
+            .and_then(|name| self.priority(name))
+            .or_else(|| {
+                labels
+                    .iter()
+                    .filter(|label| {
+                        self.priority.contains_key(&normalize(label))
+                            || normalize(label).starts_with("priority")
+                    })
+                    .find_map(|label| self.priority(label))
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn normalize(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+fn lowercase_keys<V>(map: HashMap<String, V>) -> HashMap<String, V> {
+    map.into_iter().map(|(k, v)| (normalize(&k), v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_status_mapping() {
+        let mapping = ImportMapping::default();
+        assert_eq!(mapping.status("OPEN"), Status::Todo);
+        assert_eq!(mapping.status("opened"), Status::Todo);
+        assert_eq!(mapping.status("In Progress"), Status::Doing);
+        assert_eq!(mapping.status("CLOSED"), Status::Done);
+        assert_eq!(mapping.status("Something else"), Status::Todo);
+    }
+
+    #[test]
+    fn test_custom_mapping_overrides_defaults() {
+        let mapping = ImportMapping::parse(
+            "status:\n  \"In QA\": review\n  Closed: blocked\npriority:\n  Nice to have: low\n",
+        )
+        .unwrap();
+        assert_eq!(mapping.status("in qa"), Status::Review);
+        assert_eq!(mapping.status("closed"), Status::Blocked);
+        assert_eq!(
+            mapping.resolve_priority(None, &["nice to have".to_string()]),
+            Priority::Low
+        );
+    }
+
+    #[test]
+    fn test_priority_from_labels() {
+        let mapping = ImportMapping::default();
+        let labels = vec!["bug".to_string(), "priority::high".to_string()];
+        assert_eq!(mapping.resolve_priority(None, &labels), Priority::High);
+        assert_eq!(
+            mapping.resolve_priority(Some("Highest"), &labels),
+            Priority::Critical
+        );
+        assert_eq!(mapping.resolve_priority(None, &[]), Priority::Medium);
+    }
+}
@@ -1,13 +1,23 @@
 //! Handler for the `import` command
 //!
 //! This module implements the logic for importing tickets
-//! from various formats (JSON, YAML, CSV).
+//! from various formats (JSON, YAML, CSV) and from the issue exports of
+//! other trackers (GitHub, GitLab, Jira).
+
+mod external;
+mod github;
+mod gitlab;
+mod jira;
+mod mapping;
+
+use external::ExternalIssue;
+use mapping::ImportMapping;
 
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::{Priority, Status, Ticket, TicketId};
 use crate::error::{Result, VibeTicketError};
 use crate::storage::{FileStorage, TicketRepository};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Handler for the `import` command
 ///
@@ -15,11 +25,19 @@ use std::collections::HashMap;
 /// 1. JSON - Full structured data
 /// 2. YAML - Human-readable structured data
 /// 3. CSV - Spreadsheet format
+/// 4. GitHub - `gh issue list --json ...` output or REST API JSON
+/// 5. GitLab - Issue list CSV export or issues API JSON
+/// 6. Jira - Issue navigator CSV or XML export
+///
+/// Tickets imported from another tracker keep its identifier in the
+/// `external_id` metadata field, so importing the same export again updates
+/// those tickets instead of creating duplicates.
 ///
 /// # Arguments
 ///
 /// * `file_path` - Path to the import file
 /// * `format` - Optional format (auto-detected if not specified)
+/// * `mapping_path` - Optional YAML file with custom status/priority mapping
 /// * `skip_validation` - Whether to skip validation
 /// * `dry_run` - Whether to perform a dry run (don't actually import)
 /// * `project_dir` - Optional project directory path
@@ -27,6 +45,7 @@ use std::collections::HashMap;
 pub fn handle_import_command(
     file_path: &str,
     format: Option<&str>,
+    mapping_path: Option<&str>,
     skip_validation: bool,
     dry_run: bool,
     project_dir: Option<&str>,
@@ -36,8 +55,9 @@ pub fn handle_import_command(
     let project_root = find_project_root(project_dir)?;
     let vibe_ticket_dir = project_root.join(".vibe-ticket");
 
-    // Initialize storage
+    // Initialize storage; tickets are written all together or not at all
     let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut transaction = storage.begin("import")?;
 
     // Read file content
     let content = std::fs::read_to_string(file_path)
@@ -50,14 +70,23 @@ pub fn handle_import_command(
         detect_format(file_path, &content)?
     };
 
+    let mapping = match mapping_path {
+        Some(path) => ImportMapping::load(std::path::Path::new(path))?,
+        None => ImportMapping::default(),
+    };
+
     // Parse tickets based on format
     let tickets = match format.to_lowercase().as_str() {
         "json" => import_json(&content)?,
         "yaml" => import_yaml(&content)?,
         "csv" => import_csv(&content)?,
+        "github" => convert(github::parse(&content)?, &mapping),
+        "gitlab" => convert(gitlab::parse(&content)?, &mapping),
+        "jira" => convert(jira::parse(&content)?, &mapping),
         _ => {
             return Err(VibeTicketError::custom(format!(
-                "Unsupported import format: {format}. Supported formats: json, yaml, csv"
+                "Unsupported import format: {format}. \
+                 Supported formats: json, yaml, csv, github, gitlab, jira"
             )));
         },
     };
@@ -101,13 +130,36 @@ pub fn handle_import_command(
 
     // Perform the import if not dry run
     if !dry_run {
-        let mut imported = 0;
+        let mut imported = Vec::new();
+        let mut updated = Vec::new();
         let mut skipped = 0;
-        let mut errors = Vec::new();
+
+        // Tickets from earlier imports of the same tracker, by external ID
+        let mut existing_external: HashMap<String, Ticket> = storage
+            .load_all()?
+            .into_iter()
+            .filter_map(|t| Some((external::external_id(&t)?.to_string(), t)))
+            .collect();
+        let mut staged_slugs = HashSet::new();
 
         for ticket in tickets {
+            // Re-imported tracker issues update the ticket created the first time
+            if let Some(existing) =
+                external::external_id(&ticket).and_then(|id| existing_external.remove(id))
+            {
+                let Some(merged) = external::merge_reimport(existing, ticket) else {
+                    skipped += 1;
+                    continue;
+                };
+                transaction.save(&merged)?;
+                updated.push(merged.slug);
+                continue;
+            }
+
             // Check if ticket with same slug already exists
-            if storage.find_ticket_by_slug(&ticket.slug)?.is_some() {
+            if staged_slugs.contains(&ticket.slug)
+                || storage.find_ticket_by_slug(&ticket.slug)?.is_some()
+            {
                 skipped += 1;
                 if !output.is_json() {
                     output.warning(&format!(
@@ -118,43 +170,78 @@ pub fn handle_import_command(
                 continue;
             }
 
-            // Save the ticket
-            match storage.save(&ticket) {
-                Ok(()) => imported += 1,
-                Err(e) => {
-                    errors.push(format!("Failed to import '{}': {}", ticket.slug, e));
-                },
-            }
+            transaction.save(&ticket)?;
+            staged_slugs.insert(ticket.slug.clone());
+            imported.push(ticket.slug);
         }
 
+        // Nothing is written unless every ticket is
+        transaction.commit()?;
+
         // Report results
         if output.is_json() {
             output.print_json(&serde_json::json!({
                 "status": "completed",
-                "imported": imported,
+                "imported": imported.len(),
+                "updated": updated.len(),
                 "skipped": skipped,
-                "errors": errors,
+                "imported_tickets": imported,
+                "updated_tickets": updated,
             }))?;
         } else {
             output.info("");
             output.success(&format!(
-                "Import completed: {imported} imported, {skipped} skipped"
+                "Import completed: {} imported, {} updated, {skipped} skipped",
+                imported.len(),
+                updated.len()
             ));
-
-            if !errors.is_empty() {
-                output.error("Errors occurred during import:");
-                for error in errors {
-                    output.error(&format!("  • {error}"));
-                }
-            }
         }
     }
 
     Ok(())
 }
 
+/// Convert tracker issues into tickets
+fn convert(issues: Vec<ExternalIssue>, mapping: &ImportMapping) -> Vec<Ticket> {
+    issues
+        .into_iter()
+        .map(|issue| issue.into_ticket(mapping))
+        .collect()
+}
+
+/// Detect a tracker export from its content
+fn detect_tracker(content: &str) -> Option<&'static str> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('[') {
+        let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+        if github::detect(&json) {
+            return Some("github");
+        }
+        if gitlab::detect_json(&json) {
+            return Some("gitlab");
+        }
+        return None;
+    }
+    if jira::detect_xml(trimmed) {
+        return Some("jira");
+    }
+    let header = trimmed.lines().next()?;
+    if jira::detect_csv(header) {
+        Some("jira")
+    } else if gitlab::detect_csv(header) {
+        Some("gitlab")
+    } else {
+        None
+    }
+}
+
 /// Detect format from file extension or content
 fn detect_format(file_path: &str, content: &str) -> Result<String> {
+    // Tracker exports reuse generic extensions, so check their shape first
+    if let Some(tracker) = detect_tracker(content) {
+        return Ok(tracker.to_string());
+    }
+
     // Try to detect from file extension
     if let Some(extension) = file_path.split('.').next_back() {
         match extension.to_lowercase().as_str() {
@@ -297,6 +384,7 @@ fn import_csv(content: &str) -> Result<Vec<Ticket>> {
             created_at,
             started_at,
             closed_at,
+            updated_at: None,
             assignee,
             tasks: Vec::new(), // CSV doesn't include task details
             metadata: HashMap::new(),
@@ -354,4 +442,24 @@ mod tests {
         assert_eq!(detect_format("unknown", "[{\"test\": 1}]").unwrap(), "json");
         assert_eq!(detect_format("unknown", "---\ntickets:").unwrap(), "yaml");
     }
+
+    #[test]
+    fn test_tracker_format_detection() {
+        assert_eq!(
+            detect_format("issues.json", r#"[{"number": 1, "state": "OPEN"}]"#).unwrap(),
+            "github"
+        );
+        assert_eq!(
+            detect_format("issues.json", r#"[{"iid": 1, "state": "opened"}]"#).unwrap(),
+            "gitlab"
+        );
+        assert_eq!(
+            detect_format("export.csv", "Summary,Issue key,Status\nA,P-1,Done").unwrap(),
+            "jira"
+        );
+        assert_eq!(
+            detect_format("export.xml", "<rss><channel><item></item></channel></rss>").unwrap(),
+            "jira"
+        );
+    }
 }
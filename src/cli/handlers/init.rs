@@ -6,10 +6,11 @@
 use crate::cli::output::OutputFormatter;
 use crate::config::Config;
 use crate::error::{ErrorContext, Result, VibeTicketError};
-use crate::storage::{FileStorage, ProjectState};
+use crate::storage::{FORMAT_VERSION, FileStorage, ProjectState};
 use std::env;
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 /// Handle the init command
 ///
@@ -24,6 +25,9 @@ use std::path::Path;
 /// * `name` - Optional project name (defaults to current directory name)
 /// * `description` - Optional project description
 /// * `force` - Force initialization even if already initialized
+/// * `claude_md` - Generate CLAUDE.md for AI assistance
+/// * `install_merge_driver` - Register `vibe-ticket merge-driver` for ticket
+///   files with git; in an initialized project this is all that is done
 /// * `formatter` - Output formatter for displaying results
 ///
 /// # Errors
@@ -32,6 +36,7 @@ use std::path::Path;
 /// - The project is already initialized (unless `force` is true)
 /// - File system operations fail
 /// - Configuration cannot be saved
+/// - The merge driver is requested outside a Git repository
 ///
 /// # Example
 ///
@@ -40,20 +45,36 @@ use std::path::Path;
 /// use vibe_ticket::cli::output::OutputFormatter;
 ///
 /// let formatter = OutputFormatter::new(false, false);
-/// handle_init(Some("my-project"), None, false, false, &formatter)?;
+/// handle_init(Some("my-project"), None, false, false, false, &formatter)?;
 /// ```
 pub fn handle_init(
     name: Option<&str>,
     description: Option<&str>,
     force: bool,
     claude_md: bool,
+    install_merge_driver: bool,
     formatter: &OutputFormatter,
 ) -> Result<()> {
     let current_dir = env::current_dir().context("Failed to get current directory")?;
     let project_dir = current_dir.join(".vibe-ticket");
+    let initialized = project_dir.exists() && !force;
+
+    if install_merge_driver {
+        install_git_merge_driver(&current_dir)?;
+        if initialized {
+            formatter.success("Installed the vibe-ticket merge driver for ticket files");
+            if formatter.is_json() {
+                formatter.json(&serde_json::json!({
+                    "status": "success",
+                    "merge_driver": true,
+                }))?;
+            }
+            return Ok(());
+        }
+    }
 
     // Check if already initialized
-    if project_dir.exists() && !force {
+    if initialized {
         return Err(VibeTicketError::ProjectAlreadyInitialized { path: project_dir });
     }
 
@@ -100,6 +121,7 @@ pub fn handle_init(
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         ticket_count: 0,
+        format_version: FORMAT_VERSION,
     };
     storage.save_state(&project_state)?;
 
@@ -132,6 +154,7 @@ pub fn handle_init(
             "config_path": config_path,
             "description": description,
             "claude_md": claude_md,
+            "merge_driver": install_merge_driver,
         }))?;
     } else {
         formatter.info(&format!("Project directory: {}", current_dir.display()));
@@ -141,6 +164,9 @@ pub fn handle_init(
         if claude_md {
             formatter.info("Generated CLAUDE.md for AI assistance");
         }
+        if install_merge_driver {
+            formatter.info("Installed the vibe-ticket merge driver for ticket files");
+        }
         formatter.info("\nNext steps:");
         formatter.info("  1. Create your first ticket: vibe-ticket new <slug>");
         formatter.info("  2. List tickets: vibe-ticket list");
@@ -270,6 +296,60 @@ fn create_gitignore(project_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Register `vibe-ticket merge-driver` for ticket files
+///
+/// Ticket and project state files are routed to the driver in
+/// `.gitattributes`, which is committed, and the driver command is set in the
+/// repository's git config, which every clone has to set for itself.
+fn install_git_merge_driver(project_dir: &Path) -> Result<()> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(project_dir)
+            .output()
+            .map_err(|e| VibeTicketError::custom(format!("Failed to run git command: {e}")))
+    };
+
+    if !git(&["rev-parse", "--git-dir"])?.status.success() {
+        return Err(VibeTicketError::custom("Not in a Git repository"));
+    }
+    for (key, value) in [
+        ("merge.vibe-ticket.name", "vibe-ticket ticket merge"),
+        (
+            "merge.vibe-ticket.driver",
+            "vibe-ticket merge-driver %O %A %B %P",
+        ),
+    ] {
+        let output = git(&["config", key, value])?;
+        if !output.status.success() {
+            return Err(VibeTicketError::custom(format!(
+                "Failed to set {key}: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+    }
+
+    let attributes_path = project_dir.join(".gitattributes");
+    let mut attributes = if attributes_path.exists() {
+        fs::read_to_string(&attributes_path).context("Failed to read .gitattributes")?
+    } else {
+        String::new()
+    };
+    if !attributes.contains("merge=vibe-ticket") {
+        if !attributes.is_empty() && !attributes.ends_with('\n') {
+            attributes.push('\n');
+        }
+        attributes.push_str(
+            "# vibe-ticket merge driver\n\
+             .vibe-ticket/tickets/*.yaml merge=vibe-ticket\n\
+             .vibe-ticket/state.yaml merge=vibe-ticket\n",
+        );
+        fs::write(&attributes_path, attributes).context("Failed to update .gitattributes")?;
+    }
+
+    Ok(())
+}
+
 /// Generate CLAUDE.md for a newly initialized project
 fn generate_claude_md_for_init(
     project_dir: &Path,
@@ -467,6 +467,7 @@ fn close_ticket(
         None,  // message
         false, // archive
         false, // create_pr
+        false, // draft
         project_dir,
         output,
     )
@@ -607,6 +608,7 @@ mod tests {
             created_at: Utc::now(),
             started_at: None,
             closed_at: None,
+            updated_at: None,
             assignee: None,
             tasks: vec![],
             metadata: HashMap::new(),
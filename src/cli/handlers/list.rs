@@ -1,8 +1,9 @@
 use crate::cli::{OutputFormatter, find_project_root};
-use crate::core::{Priority, Status, Ticket};
+use crate::core::{Priority, Status};
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{FileStorage, TicketRepository};
+use crate::storage::{ArchiveStore, FileStorage, TicketRepository, TicketSummary};
 use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use std::collections::HashMap;
 
 /// Handler for the `list` command
 #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
@@ -28,8 +29,16 @@ pub fn handle_list_command(
     // Initialize storage
     let storage = FileStorage::new(&vibe_ticket_dir);
 
-    // Load all tickets
-    let mut tickets = storage.load_all()?;
+    // Filter and sort summaries from the index, parsing no ticket files
+    // that did not change since the last run
+    let mut tickets = storage.load_summaries()?;
+    let mut archived_tickets = HashMap::new();
+    if archived {
+        for ticket in ArchiveStore::new(&vibe_ticket_dir).load_all_tickets()? {
+            tickets.push(TicketSummary::from(&ticket));
+            archived_tickets.insert(ticket.id.clone(), ticket);
+        }
+    }
 
     // Parse date filters
     let since_date = since.map(|s| parse_date_filter(&s)).transpose()?;
@@ -58,6 +67,14 @@ pub fn handle_list_command(
 
     // Output results
     if output.is_json() {
+        // JSON carries whole tickets, so only the listed ones are loaded
+        let tickets = tickets
+            .iter()
+            .map(|summary| match archived_tickets.remove(&summary.id) {
+                Some(ticket) => Ok(ticket),
+                None => storage.load(&summary.id),
+            })
+            .collect::<Result<Vec<_>>>()?;
         output.print_json(&serde_json::json!({
             "tickets": tickets,
             "count": tickets.len(),
@@ -65,7 +82,7 @@ pub fn handle_list_command(
     } else if tickets.is_empty() {
         output.info("No tickets found matching the criteria.");
     } else {
-        output.print_tickets(&tickets)?;
+        output.print_summaries(&tickets)?;
     }
 
     Ok(())
@@ -163,7 +180,7 @@ fn parse_date_filter(date_str: &str) -> Result<DateTime<Utc>> {
 /// Filter tickets based on criteria
 #[allow(clippy::too_many_arguments)]
 fn filter_tickets(
-    tickets: Vec<Ticket>,
+    tickets: Vec<TicketSummary>,
     status: Option<String>,
     priority: Option<String>,
     assignee: Option<String>,
@@ -172,7 +189,7 @@ fn filter_tickets(
     since: Option<DateTime<Utc>>,
     until: Option<DateTime<Utc>>,
     include_done: bool,
-) -> Result<Vec<Ticket>> {
+) -> Result<Vec<TicketSummary>> {
     let mut filtered = tickets;
 
     // Filter by status
@@ -203,12 +220,7 @@ fn filter_tickets(
     // Filter by archived status
     if !archived {
         // Filter out archived tickets
-        filtered.retain(|t| {
-            !t.metadata
-                .get("archived")
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or(false)
-        });
+        filtered.retain(|t| !t.archived);
     }
 
     // Filter by open status (todo, doing)
@@ -229,7 +241,7 @@ fn filter_tickets(
 }
 
 /// Sort tickets based on the specified field
-fn sort_tickets(tickets: &mut [Ticket], sort_by: &str, reverse: bool) {
+fn sort_tickets(tickets: &mut [TicketSummary], sort_by: &str, reverse: bool) {
     match sort_by {
         "created" => {
             tickets.sort_by_key(|t| t.created_at);
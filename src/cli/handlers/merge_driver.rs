@@ -0,0 +1,74 @@
+//! Handler for the `merge-driver` command
+//!
+//! Git runs `vibe-ticket merge-driver %O %A %B %P` for files registered with
+//! `vibe-ticket init --install-merge-driver`. The three versions are merged
+//! field by field and the result is written over our version, as git
+//! expects; exiting with an error tells git the file still has conflicts.
+
+use crate::cli::OutputFormatter;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::merge_yaml;
+use std::fs;
+use std::process::Command;
+
+/// Handler for the `merge-driver` command
+///
+/// # Arguments
+///
+/// * `base` - File holding the common ancestor version
+/// * `ours` - File holding our version, overwritten with the result
+/// * `theirs` - File holding their version
+/// * `path` - Path of the merged file in the repository, for messages
+/// * `output` - Output formatter for warnings
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A version cannot be read or the result cannot be written
+/// - Fields changed on both sides could not be merged
+pub fn handle_merge_driver_command(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    path: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let path = path.unwrap_or(ours);
+    let read =
+        |file: &str| fs::read_to_string(file).with_context(|| format!("Failed to read {file}"));
+    let merge = match merge_yaml(&read(base)?, &read(ours)?, &read(theirs)?) {
+        Ok(merge) => merge,
+        Err(error) => {
+            output.warning(&format!(
+                "{path} is not a ticket file ({error}); merging it line by line"
+            ));
+            return merge_lines(base, ours, theirs, path);
+        },
+    };
+
+    fs::write(ours, &merge.content).with_context(|| format!("Failed to write {ours}"))?;
+    if merge.is_clean() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::custom(format!(
+            "Conflicting changes to {} in {path}",
+            merge.conflicts.join(", ")
+        )))
+    }
+}
+
+/// Merge with `git merge-file`, as git would without the driver
+fn merge_lines(base: &str, ours: &str, theirs: &str, path: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(["merge-file", "-L", "ours", "-L", "base", "-L", "theirs"])
+        .args([ours, base, theirs])
+        .status()
+        .map_err(|e| VibeTicketError::custom(format!("Failed to run git merge-file: {e}")))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::custom(format!(
+            "Conflicting changes in {path}"
+        )))
+    }
+}
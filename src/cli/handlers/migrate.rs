@@ -0,0 +1,75 @@
+//! Handler for the `migrate` command
+//!
+//! Upgrades the files in `.vibe-ticket` to the format this version writes,
+//! one migration step at a time. The project is backed up to
+//! `.vibe-ticket/local/backups/` first; `--dry-run` lists the files each
+//! step would change without touching anything.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::Result;
+use crate::storage::FileStorage;
+
+/// Handler for the `migrate` command
+///
+/// # Arguments
+///
+/// * `dry_run` - Report the changes without making them
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for the report
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project is not initialized
+/// - The project was written by a newer version of vibe-ticket
+/// - The backup or a migration step fails
+pub fn handle_migrate_command(
+    dry_run: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let report = storage.migrate(dry_run)?;
+
+    if output.is_json() {
+        return output.json(&report);
+    }
+
+    if report.steps.is_empty() {
+        output.success(&format!(
+            "Project is up to date (format version {})",
+            report.from
+        ));
+        return Ok(());
+    }
+
+    for step in &report.steps {
+        output.info(&format!(
+            "Version {}: {} ({} file(s))",
+            step.version,
+            step.description,
+            step.changed.len()
+        ));
+        for path in &step.changed {
+            output.info(&format!("  {}", path.display()));
+        }
+    }
+    if let Some(backup) = &report.backup {
+        let backup = backup.strip_prefix(&project_root).unwrap_or(backup);
+        output.info(&format!("Backup saved to {}", backup.display()));
+    }
+    if dry_run {
+        output.info(&format!(
+            "Dry run: format version {} would be upgraded to {}",
+            report.from,
+            report.steps.last().map_or(report.from, |step| step.version)
+        ));
+    } else {
+        output.success(&format!(
+            "Migrated from format version {} to {}",
+            report.from, report.to
+        ));
+    }
+    Ok(())
+}
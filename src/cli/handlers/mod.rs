@@ -25,21 +25,31 @@ mod close;
 mod common;
 mod config;
 mod create;
+mod doctor;
 mod edit;
 mod export;
 mod finish;
+mod graph;
 mod import;
 mod init;
 mod list;
 #[cfg(feature = "mcp")]
 mod mcp;
+mod merge_driver;
+mod migrate;
 mod new;
 mod search;
 mod show;
 mod spec;
 pub mod spec_base;
 mod spec_common;
+mod spec_history;
+mod spec_lint;
+mod spec_review;
+mod spec_sync;
+mod spec_trace;
 mod start;
+mod sync;
 mod task;
 mod work_on;
 mod workflow;
@@ -52,32 +62,45 @@ mod filter;
 mod hooks;
 mod interactive;
 mod time;
+#[cfg(feature = "tui")]
+mod tui;
+mod watch;
 
 // Re-export handlers
-pub use archive::handle_archive_command;
+pub use archive::{ArchiveParams, handle_archive_command};
 pub use board::handle_board_command;
 pub use check::handle_check_command;
 pub use close::handle_close_command;
 pub use config::handle_config_command;
 pub use create::{CreateParams, handle_create_command};
+pub use doctor::{Problem, ProblemKind, handle_doctor_command};
 pub use edit::handle_edit_command;
 pub use export::handle_export_command;
 pub use finish::handle_finish_command;
+pub use graph::handle_graph_command;
 pub use import::handle_import_command;
 pub use init::handle_init;
 pub use list::handle_list_command;
 #[cfg(feature = "mcp")]
 pub use mcp::handle_mcp_serve;
+pub use merge_driver::handle_merge_driver_command;
+pub use migrate::handle_migrate_command;
 pub use new::handle_new_command;
 pub use search::handle_search_command;
 pub use show::handle_show_command;
 pub use spec::{
-    handle_spec_activate, handle_spec_approve, handle_spec_delete, handle_spec_design,
-    handle_spec_init, handle_spec_list, handle_spec_plan, handle_spec_requirements,
-    handle_spec_show, handle_spec_specify, handle_spec_status, handle_spec_tasks,
-    handle_spec_template, handle_spec_validate,
+    handle_spec_activate, handle_spec_delete, handle_spec_design, handle_spec_init,
+    handle_spec_list, handle_spec_plan, handle_spec_requirements, handle_spec_show,
+    handle_spec_specify, handle_spec_status, handle_spec_tasks, handle_spec_template,
+    handle_spec_validate,
 };
+pub use spec_history::{handle_spec_diff, handle_spec_history, handle_spec_restore};
+pub use spec_lint::handle_spec_lint;
+pub use spec_review::{handle_spec_approve, handle_spec_request_changes, handle_spec_reviewers};
+pub use spec_sync::handle_spec_sync;
+pub use spec_trace::handle_spec_trace;
 pub use start::handle_start_command;
+pub use sync::{handle_sync_pull, handle_sync_push};
 pub use task::{
     handle_task_add, handle_task_complete, handle_task_list, handle_task_remove,
     handle_task_uncomplete,
@@ -87,17 +110,22 @@ pub use workflow::{
     handle_approve_command, handle_handoff_command, handle_request_changes_command,
     handle_review_command,
 };
-pub use worktree::{handle_worktree_list, handle_worktree_prune, handle_worktree_remove};
+pub use worktree::{
+    handle_worktree_create, handle_worktree_list, handle_worktree_prune, handle_worktree_remove,
+};
 
 // New feature re-exports
-pub use alias::{handle_alias_create, handle_alias_delete, handle_alias_list, handle_alias_run};
+pub use alias::{
+    Aliases, expand_command_line, handle_alias_create, handle_alias_delete, handle_alias_list,
+    handle_alias_run,
+};
 pub use bulk::{handle_bulk_archive, handle_bulk_close, handle_bulk_tag, handle_bulk_update};
 pub use filter::{
-    handle_filter_apply, handle_filter_create, handle_filter_delete, handle_filter_list,
-    handle_filter_show,
+    SavedFilter, SavedFilters, handle_filter_apply, handle_filter_create, handle_filter_delete,
+    handle_filter_list, handle_filter_show,
 };
 pub use hooks::{
-    HookContext, HookEvent, execute_hooks, handle_hook_create, handle_hook_delete,
+    HookContext, HookEvent, Hooks, execute_hooks, handle_hook_create, handle_hook_delete,
     handle_hook_disable, handle_hook_enable, handle_hook_list, handle_hook_test,
 };
 pub use interactive::{
@@ -105,8 +133,12 @@ pub use interactive::{
     handle_interactive_status,
 };
 pub use time::{
-    handle_time_log, handle_time_report, handle_time_start, handle_time_status, handle_time_stop,
+    TimeTracking, handle_time_log, handle_time_report, handle_time_start, handle_time_status,
+    handle_time_stop,
 };
+#[cfg(feature = "tui")]
+pub use tui::handle_tui_command;
+pub use watch::handle_watch_command;
 
 use crate::cli::output::OutputFormatter;
 use crate::error::Result;
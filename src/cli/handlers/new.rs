@@ -148,6 +148,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             ticket_count: 0,
+            format_version: crate::storage::FORMAT_VERSION,
         };
 
         let storage = FileStorage::new(&vibe_ticket_dir);
@@ -6,7 +6,8 @@
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::core::Ticket;
 use crate::error::Result;
-use crate::storage::{FileStorage, TicketRepository};
+use crate::storage::archive::is_archived;
+use crate::storage::{ArchiveStore, FileStorage, TicketRepository};
 use regex::Regex;
 
 /// Handler for the `search` command
@@ -25,15 +26,17 @@ use regex::Regex;
 /// * `description_only` - Search only in descriptions
 /// * `tags_only` - Search only in tags
 /// * `use_regex` - Treat query as a regex pattern
+/// * `include_archived` - Also search the archive store
 /// * `project_dir` - Optional project directory path
 /// * `output` - Output formatter for displaying results
-#[allow(clippy::fn_params_excessive_bools)]
+#[allow(clippy::fn_params_excessive_bools, clippy::too_many_arguments)]
 pub fn handle_search_command(
     query: &str,
     title_only: bool,
     description_only: bool,
     tags_only: bool,
     use_regex: bool,
+    include_archived: bool,
     project_dir: Option<&str>,
     output: &OutputFormatter,
 ) -> Result<()> {
@@ -45,7 +48,10 @@ pub fn handle_search_command(
     let storage = FileStorage::new(&vibe_ticket_dir);
 
     // Load all tickets
-    let tickets = storage.load_all()?;
+    let mut tickets = storage.load_all()?;
+    if include_archived {
+        tickets.extend(ArchiveStore::new(&vibe_ticket_dir).load_all_tickets()?);
+    }
 
     // Compile regex if needed
     let regex = if use_regex {
@@ -83,6 +89,7 @@ pub fn handle_search_command(
                 "title": ticket.title,
                 "status": ticket.status.to_string(),
                 "priority": ticket.priority.to_string(),
+                "archived": is_archived(ticket),
                 "matched_in": locations,
             })).collect::<Vec<_>>(),
             "total": matches.len(),
@@ -108,8 +115,15 @@ pub fn handle_search_command(
             };
 
             output.info(&format!(
-                "{} {} - {}",
-                status_emoji, ticket.slug, ticket.title
+                "{} {} - {}{}",
+                status_emoji,
+                ticket.slug,
+                ticket.title,
+                if is_archived(ticket) {
+                    " (archived)"
+                } else {
+                    ""
+                }
             ));
             output.info(&format!(
                 "   Priority: {} | Status: {} | Matched in: {}",
@@ -175,7 +189,7 @@ fn search_tickets(
     }
 
     // Sort matches by creation date (newest first)
-    matches.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
+    matches.sort_by_key(|m| std::cmp::Reverse(m.0.created_at));
     matches
 }
 
@@ -3,15 +3,20 @@
 //! This module implements all handlers for specification management commands,
 //! supporting the three-phase spec-driven development workflow.
 
+use super::spec_review;
+use crate::cli::find_project_root;
 use crate::cli::output::OutputFormatter;
 use crate::error::{ErrorContext, Result, VibeTicketError};
 use crate::specs::{
-    SpecDocumentType, SpecManager, SpecPhase, SpecTemplate, Specification, TemplateEngine,
+    GeneratorInput, Linter, ReviewConfig, Severity, SpecDocument, SpecDocumentType, SpecManager,
+    SpecPhase, SpecRollup, SpecTemplate, Specification, TaskSync, TemplateEngine, generator, lint,
+    trace,
 };
+use crate::storage::{ArchiveStore, write_project_file};
 use chrono::Utc;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Handle spec init command
 pub fn handle_spec_init(
@@ -22,18 +27,8 @@ pub fn handle_spec_init(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -115,18 +110,8 @@ pub fn handle_spec_design(
     }
 
     // Keep existing complex logic for backward compatibility
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -138,6 +123,8 @@ pub fn handle_spec_design(
 
     // Load specification
     let mut specification = spec_manager.load(&spec_id)?;
+    ReviewConfig::for_project(&project_dir)
+        .ensure_can_start(&specification.metadata, SpecDocumentType::Design)?;
 
     // Check if requirements are complete
     if !specification.metadata.progress.requirements_completed {
@@ -181,14 +168,16 @@ pub fn handle_spec_design(
         );
 
         let content = engine.generate(&template);
-        fs::write(&doc_path, content).context("Failed to create design document")?;
+        write_project_file(&doc_path, content)?;
 
         formatter.info(&format!("Created design document: {}", doc_path.display()));
     }
 
     if editor {
-        // Open in editor
+        // Open in editor and record the edit as a new revision
+        let previous = fs::read_to_string(&doc_path).ok();
         open_in_editor(&doc_path)?;
+        spec_manager.record_edit(&spec_id, SpecDocumentType::Design, previous.as_deref())?;
         formatter.success("Design document saved");
     } else {
         // Display content
@@ -212,18 +201,8 @@ pub fn handle_spec_tasks(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -235,6 +214,8 @@ pub fn handle_spec_tasks(
 
     // Load specification
     let mut specification = spec_manager.load(&spec_id)?;
+    ReviewConfig::for_project(&project_dir)
+        .ensure_can_start(&specification.metadata, SpecDocumentType::Tasks)?;
 
     // Check if design is complete
     if !specification.metadata.progress.design_completed {
@@ -255,10 +236,7 @@ pub fn handle_spec_tasks(
     }
 
     // Get spec directory
-    let spec_dir = project_dir
-        .join(".vibe-ticket")
-        .join("specs")
-        .join(&spec_id);
+    let spec_dir = spec_manager.get_spec_dir(&spec_id);
 
     // Get or create tasks document
     let doc_path = spec_dir.join("tasks.md");
@@ -278,26 +256,27 @@ pub fn handle_spec_tasks(
         };
 
         // Generate tasks based on plan and granularity
-        let tasks_content = generate_tasks_document(
-            &specification.metadata.title,
-            &plan_content,
-            &granularity,
-            parallel,
-        );
+        let input = GeneratorInput::new(&specification, &spec_dir)
+            .with_plan(Some(plan_content))
+            .with_tasks(&granularity, parallel);
+        let tasks_content =
+            generator::for_project(&project_dir)?.generate(SpecDocument::Tasks, &input)?;
 
-        fs::write(&doc_path, tasks_content).context("Failed to create tasks document")?;
+        write_project_file(&doc_path, tasks_content)?;
 
         formatter.info(&format!("Created tasks document: {}", doc_path.display()));
     }
 
     if export_tickets {
         // Export tasks to tickets
-        export_tasks_to_tickets(&doc_path, &specification, &project_dir, formatter)?;
+        export_tasks_to_tickets(&specification, &project_dir, formatter)?;
     }
 
     if editor {
-        // Open in editor
+        // Open in editor and record the edit as a new revision
+        let previous = fs::read_to_string(&doc_path).ok();
         open_in_editor(&doc_path)?;
+        spec_manager.record_edit(&spec_id, SpecDocumentType::Tasks, previous.as_deref())?;
         formatter.success("Tasks document saved");
     } else {
         // Display content
@@ -318,18 +297,8 @@ pub fn handle_spec_specify(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -346,38 +315,26 @@ pub fn handle_spec_specify(
     spec_manager.save(&spec)?;
 
     // Determine output directory
-    let output_dir = if let Some(out) = output {
-        Path::new(out).to_path_buf()
-    } else {
-        project_dir
-            .join(".vibe-ticket")
-            .join("specs")
-            .join(&spec.metadata.id)
-    };
+    let output_dir = output.map_or_else(
+        || spec_manager.get_spec_dir(&spec.metadata.id),
+        |out| Path::new(out).to_path_buf(),
+    );
 
     // Create output directory if it doesn't exist
     fs::create_dir_all(&output_dir)?;
 
-    // Generate specification document from template
-    let mut engine = TemplateEngine::new();
-    engine.set_variable("title", &title);
-    engine.set_variable("requirements", requirements);
-    engine.set_variable("spec_id", &spec.metadata.id);
-    engine.set_variable("created_date", &Utc::now().format("%Y-%m-%d").to_string());
-
-    // Create template and generate content
-    let spec_template = SpecTemplate::Requirements {
-        title: title.clone(),
-        description: requirements.to_string(),
-    };
-    let spec_content = engine.generate(&spec_template);
+    // Generate specification document
+    let input = GeneratorInput::new(&spec, &output_dir);
+    let spec_content =
+        generator::for_project(&project_dir)?.generate(SpecDocument::Spec, &input)?;
 
     // Mark requirements with [NEEDS CLARIFICATION] where ambiguous
-    let analyzed_content = analyze_and_mark_ambiguities(&spec_content);
+    let findings = Linter::for_project(&project_dir).lint("spec.md", &spec_content);
+    let analyzed_content = lint::mark_ambiguities(&spec_content, &findings);
 
     // Save specification document
     let spec_file = output_dir.join("spec.md");
-    fs::write(&spec_file, &analyzed_content)?;
+    write_project_file(&spec_file, &analyzed_content)?;
 
     formatter.success(&format!(
         "Created specification '{}' with ID: {}",
@@ -431,18 +388,8 @@ pub fn handle_spec_plan(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -454,6 +401,8 @@ pub fn handle_spec_plan(
 
     // Load specification
     let mut specification = spec_manager.load(&spec_id)?;
+    ReviewConfig::for_project(&project_dir)
+        .ensure_can_start(&specification.metadata, SpecDocumentType::Design)?;
 
     // Check if requirements are complete
     if !specification.metadata.progress.requirements_completed {
@@ -461,51 +410,37 @@ pub fn handle_spec_plan(
     }
 
     // Determine output directory
-    let output_dir = if let Some(ref out) = output {
-        Path::new(out).to_path_buf()
-    } else {
-        project_dir
-            .join(".vibe-ticket")
-            .join("specs")
-            .join(&spec_id)
-    };
-
-    // Read specification document
-    let spec_file = output_dir.join("spec.md");
-    let spec_content = if spec_file.exists() {
-        fs::read_to_string(&spec_file)?
-    } else {
-        specification.metadata.description.clone()
-    };
+    let output_dir = output.map_or_else(
+        || spec_manager.get_spec_dir(&spec_id),
+        |out| Path::new(&out).to_path_buf(),
+    );
+    fs::create_dir_all(&output_dir)?;
 
     // Parse tech stack
     let tech_list: Vec<String> = tech_stack
         .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
         .unwrap_or_default();
 
-    // Generate implementation plan
-    let mut engine = TemplateEngine::new();
-    engine.set_variable("spec_id", &spec_id);
-    engine.set_variable("title", &specification.metadata.title);
-    engine.set_variable("tech_stack", &tech_list.join(", "));
-    engine.set_variable("architecture", architecture.as_deref().unwrap_or("layered"));
-
-    // Create research document
-    let research_content =
-        generate_research_document(&spec_content, &tech_list, architecture.as_deref());
-    let research_file = output_dir.join("research.md");
-    fs::write(&research_file, research_content)?;
-
-    // Create data model
-    let data_model_content = generate_data_model(&spec_content, &tech_list);
-    let data_model_file = output_dir.join("data-model.md");
-    fs::write(&data_model_file, data_model_content)?;
-
-    // Create implementation plan
-    let plan_content =
-        generate_implementation_plan(&spec_content, &tech_list, architecture.as_deref());
-    let plan_file = output_dir.join("plan.md");
-    fs::write(&plan_file, plan_content)?;
+    let generator = generator::for_project(&project_dir)?;
+    let input = GeneratorInput::new(&specification, &output_dir)
+        .with_tech_stack(tech_list)
+        .with_architecture(architecture);
+
+    // Create research document, data model and implementation plan
+    let research_file = output_dir.join(SpecDocument::Research.file_name());
+    write_project_file(
+        &research_file,
+        generator.generate(SpecDocument::Research, &input)?,
+    )?;
+
+    let data_model_file = output_dir.join(SpecDocument::DataModel.file_name());
+    write_project_file(
+        &data_model_file,
+        generator.generate(SpecDocument::DataModel, &input)?,
+    )?;
+
+    let plan_file = output_dir.join(SpecDocument::Plan.file_name());
+    write_project_file(&plan_file, generator.generate(SpecDocument::Plan, &input)?)?;
 
     // Update specification progress
     specification.metadata.progress.design_completed = true;
@@ -543,18 +478,8 @@ pub fn handle_spec_validate(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -566,13 +491,11 @@ pub fn handle_spec_validate(
 
     // Load specification
     let specification = spec_manager.load(&spec_id)?;
-    let spec_dir = project_dir
-        .join(".vibe-ticket")
-        .join("specs")
-        .join(&spec_id);
+    let spec_dir = spec_manager.get_spec_dir(&spec_id);
 
     let mut validation_results: Vec<String> = Vec::new();
     let mut has_errors = false;
+    let mut trace_gaps = 0;
 
     // Check completeness
     if complete || !ambiguities && !report {
@@ -605,20 +528,64 @@ pub fn handle_spec_validate(
         }
     }
 
-    // Check for ambiguities
+    // Check for ambiguities without touching the documents
     if ambiguities || !report {
-        let spec_file = spec_dir.join("spec.md");
-        if spec_file.exists() {
-            let content = fs::read_to_string(&spec_file)?;
-            let clarification_count = content.matches("[NEEDS CLARIFICATION]").count();
+        let findings = Linter::for_project(&project_dir).lint_dir(&spec_dir)?;
+        for finding in &findings {
+            let icon = match finding.severity {
+                Severity::Error => "❌",
+                Severity::Warning => "⚠️ ",
+                Severity::Info => "ℹ️ ",
+            };
+            validation_results.push(format!(
+                "{icon} {}:{}:{} {} [{}]",
+                finding.file,
+                finding.line,
+                finding.column,
+                finding.message,
+                finding.rule.id()
+            ));
+        }
+        if findings.is_empty() {
+            validation_results.push("✅ No ambiguities found".to_string());
+        }
+        // Warnings and infos are advice; only errors fail validation
+        if findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+        {
+            has_errors = true;
+        }
+    }
 
-            if clarification_count > 0 {
+    // Check requirement traceability
+    if complete || !ambiguities && !report {
+        let tickets = crate::storage::FileStorage::new(&project_dir).load_all_tickets()?;
+        let matrix = trace::TraceMatrix::build(&specification, &tickets);
+        if matrix.rows.is_empty() {
+            validation_results
+                .push("⚠️  No requirement IDs (e.g. REQ-001) found in requirements".to_string());
+        } else {
+            for requirement in matrix.unimplemented() {
                 validation_results.push(format!(
-                    "⚠️  Found {clarification_count} items marked as [NEEDS CLARIFICATION]"
+                    "❌ Requirement {} has no implementing ticket",
+                    requirement.id
+                ));
+            }
+            for ticket in &matrix.untraced_tickets {
+                validation_results.push(format!(
+                    "❌ Ticket {} is not traced to any requirement",
+                    ticket.slug
+                ));
+            }
+            if matrix.gap_count() == 0 {
+                validation_results.push(format!(
+                    "✅ All {} requirements are traced to tickets",
+                    matrix.rows.len()
                 ));
-                has_errors = true;
             } else {
-                validation_results.push("✅ No ambiguities found".to_string());
+                has_errors = true;
+                trace_gaps = matrix.gap_count();
             }
         }
     }
@@ -628,7 +595,7 @@ pub fn handle_spec_validate(
         let validation_refs: Vec<&str> = validation_results.iter().map(|s| s.as_str()).collect();
         let report_content = generate_validation_report(&specification, &validation_refs);
         let report_file = spec_dir.join("validation-report.md");
-        fs::write(&report_file, &report_content)?;
+        write_project_file(&report_file, &report_content)?;
         formatter.info(&format!(
             "Validation report saved to: {}",
             report_file.display()
@@ -646,6 +613,12 @@ pub fn handle_spec_validate(
         formatter.info(result);
     }
 
+    if trace_gaps > 0 {
+        return Err(VibeTicketError::custom(format!(
+            "Specification has {trace_gaps} traceability gap(s); see 'vibe-ticket spec trace'"
+        )));
+    }
+
     if has_errors {
         formatter.warning("\n⚠️  Specification has validation issues that should be addressed");
     } else {
@@ -663,13 +636,9 @@ pub fn handle_spec_template(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let output_dir = Path::new(output);
+    // A relative output directory is relative to the project, if one is given
+    let output_dir = project.map_or_else(|| PathBuf::from(output), |p| Path::new(p).join(output));
+    let output_dir = output_dir.as_path();
 
     // Create output directory if it doesn't exist
     if !output_dir.exists() {
@@ -738,201 +707,6 @@ fn load_specification_template(template_name: &str) -> Result<String> {
     Ok(template.to_string())
 }
 
-#[allow(dead_code)]
-fn analyze_and_mark_ambiguities(content: &str) -> String {
-    // Simple heuristic: mark vague terms and missing details
-    let mut result = content.to_string();
-
-    let vague_terms = [
-        "various",
-        "multiple",
-        "several",
-        "many",
-        "some",
-        "appropriate",
-        "suitable",
-        "proper",
-        "adequate",
-        "fast",
-        "slow",
-        "quick",
-        "efficient",
-        "user-friendly",
-        "intuitive",
-        "easy",
-    ];
-
-    for term in &vague_terms {
-        result = result.replace(
-            term,
-            &format!("{term} [NEEDS CLARIFICATION: Be more specific]"),
-        );
-    }
-
-    result
-}
-
-#[allow(dead_code)]
-fn generate_research_document(
-    spec_content: &str,
-    tech_stack: &[String],
-    architecture: Option<&str>,
-) -> String {
-    let tech_stack_str = if tech_stack.is_empty() {
-        "- No specific technology stack defined".to_string()
-    } else {
-        tech_stack
-            .iter()
-            .map(|t| format!("- {t}"))
-            .collect::<Vec<_>>()
-            .join("\n")
-    };
-
-    format!(
-        r"# Research and Technical Analysis
-
-## Specification Overview
-{}
-
-## Technology Stack Analysis
-{}
-
-## Architecture Pattern
-{}
-
-## Technical Considerations
-- Performance requirements
-- Scalability needs
-- Security requirements
-- Integration points
-
-## Dependencies
-{}
-
-## Risk Assessment
-- Technical risks
-- Implementation challenges
-- Mitigation strategies
-
----
-Generated on: {}
-",
-        spec_content.lines().take(5).collect::<Vec<_>>().join("\n"),
-        tech_stack_str,
-        architecture.unwrap_or("Layered Architecture"),
-        if tech_stack.is_empty() {
-            "To be determined"
-        } else {
-            "Based on selected technology stack"
-        },
-        Utc::now().format("%Y-%m-%d")
-    )
-}
-
-#[allow(dead_code)]
-fn generate_data_model(_spec_content: &str, tech_stack: &[String]) -> String {
-    let is_rust = tech_stack.iter().any(|t| t.to_lowercase().contains("rust"));
-
-    format!(
-        r"# Data Model
-
-## Core Entities
-
-{}
-
-## Relationships
-
-- One-to-many relationships
-- Many-to-many relationships
-- Aggregations
-
-## Validation Rules
-
-- Required fields
-- Format validations
-- Business rules
-
-## Data Types
-
-{}
-
----
-Generated on: {}
-",
-        "Extract entities from specification...",
-        if is_rust {
-            "Using Rust type system with strong typing"
-        } else {
-            "Define appropriate data types for chosen technology"
-        },
-        Utc::now().format("%Y-%m-%d")
-    )
-}
-
-#[allow(dead_code)]
-fn generate_implementation_plan(
-    _spec_content: &str,
-    tech_stack: &[String],
-    architecture: Option<&str>,
-) -> String {
-    let tech_stack_str = if tech_stack.is_empty() {
-        "To be determined".to_string()
-    } else {
-        tech_stack.join(", ")
-    };
-
-    format!(
-        r"# Implementation Plan
-
-## Overview
-Implementation plan based on specification and selected technology stack.
-
-## Technology Stack
-{}
-
-## Architecture
-{}
-
-## Implementation Phases
-
-### Phase 1: Setup and Infrastructure
-- Project initialization
-- Development environment setup
-- Core dependencies installation
-- Basic project structure
-
-### Phase 2: Core Implementation
-- Data models
-- Business logic
-- Core functionality
-
-### Phase 3: Integration
-- External services
-- APIs
-- Database connections
-
-### Phase 4: Testing and Validation
-- Unit tests
-- Integration tests
-- Validation against requirements
-
-### Phase 5: Documentation and Deployment
-- User documentation
-- Deployment preparation
-- Final review
-
-## Timeline
-- Estimated completion: TBD
-
----
-Generated on: {}
-",
-        tech_stack_str,
-        architecture.unwrap_or("Layered Architecture"),
-        Utc::now().format("%Y-%m-%d")
-    )
-}
-
 #[allow(dead_code)]
 fn generate_validation_report(spec: &Specification, results: &[&str]) -> String {
     format!(
@@ -988,172 +762,25 @@ Generated on: {}
     )
 }
 
-fn generate_tasks_document(
-    title: &str,
-    plan_content: &str,
-    granularity: &str,
-    parallel: bool,
-) -> String {
-    let task_prefix = if parallel { "[P] " } else { "" };
-
-    // Determine task detail level based on granularity
-    let (task_count, _task_detail) = match granularity {
-        "fine" => (20, "Detailed implementation steps"),
-        "coarse" => (5, "High-level milestones"),
-        _ => (10, "Standard implementation tasks"),
-    };
-
-    format!(
-        r"# Tasks: {}
-
-## Overview
-Executable tasks generated from implementation plan.
-
-## Task Granularity: {}
-- Estimated task count: ~{}
-- Parallel execution markers: {}
-
-## Phase 1: Setup and Initialization
-- [ ] {}T001: Initialize project structure
-- [ ] {}T002: Set up development environment
-- [ ] {}T003: Install core dependencies
-- [ ] {}T004: Configure build system
-- [ ] {}T005: Set up version control
-
-## Phase 2: Core Implementation
-- [ ] {}T006: Implement data models
-- [ ] {}T007: Create business logic layer
-- [ ] {}T008: Develop core functionality
-- [ ] {}T009: Implement error handling
-- [ ] {}T010: Add logging and monitoring
-
-## Phase 3: Integration and Testing
-- [ ] {}T011: Create unit tests
-- [ ] {}T012: Implement integration tests
-- [ ] {}T013: Set up CI/CD pipeline
-- [ ] {}T014: Perform code review
-- [ ] {}T015: Fix identified issues
-
-## Phase 4: Documentation and Deployment
-- [ ] {}T016: Write user documentation
-- [ ] {}T017: Create API documentation
-- [ ] {}T018: Prepare deployment scripts
-- [ ] {}T019: Perform final testing
-- [ ] {}T020: Deploy to production
-
-## Prerequisites
-{}
-
-## Notes
-- Tasks marked with [P] can be executed in parallel
-- Update task status as work progresses
-- Export to tickets for team collaboration
-
----
-Generated on: {}
-",
-        title,
-        granularity,
-        task_count,
-        if parallel { "Enabled" } else { "Disabled" },
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        extract_prerequisites_from_plan(plan_content),
-        Utc::now().format("%Y-%m-%d")
-    )
-}
-
-fn extract_prerequisites_from_plan(plan_content: &str) -> String {
-    // Simple extraction of technology stack from plan
-    if plan_content.contains("Technology Stack") {
-        for line in plan_content.lines() {
-            if line.contains("Technology Stack") {
-                return plan_content
-                    .lines()
-                    .skip_while(|l| !l.contains("Technology Stack"))
-                    .skip(1)
-                    .take_while(|l| !l.starts_with('#'))
-                    .collect::<Vec<_>>()
-                    .join("\n");
-            }
-        }
-    }
-    "- Plan document available\n- Requirements completed".to_string()
-}
-
 fn export_tasks_to_tickets(
-    tasks_path: &Path,
     spec: &Specification,
     project_dir: &Path,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use crate::core::Priority;
-    use crate::storage::{FileStorage, TicketRepository};
-
-    let content = fs::read_to_string(tasks_path)?;
-    let storage = FileStorage::new(project_dir.join(".vibe-ticket"));
-
-    let mut created_count = 0;
-
-    // Parse tasks from markdown
-    for line in content.lines() {
-        if line.contains("- [ ]") && line.contains("T0") {
-            // Extract task ID and description
-            let task_text = line.trim_start_matches("- [ ]").trim();
-            let parts: Vec<&str> = task_text.splitn(2, ':').collect();
-
-            if parts.len() == 2 {
-                let task_id_str = parts[0].replace("[P]", "");
-                let task_id = task_id_str.trim();
-                let description = parts[1].trim();
-
-                // Create ticket slug from task ID
-                let slug = format!("{}-{}", spec.metadata.id, task_id.to_lowercase());
-
-                // Create new ticket using builder
-                use crate::core::TicketBuilder;
-                let ticket = TicketBuilder::new()
-                    .slug(slug.clone())
-                    .title(format!("[{task_id}] {description}"))
-                    .description(format!("Task from specification: {}", spec.metadata.title))
-                    .priority(Priority::Medium)
-                    .tags(vec![
-                        "spec-driven".to_string(),
-                        "auto-generated".to_string(),
-                        spec.metadata.id.clone(),
-                    ])
-                    .build();
-
-                // Save ticket
-                if storage.save(&ticket).is_ok() {
-                    created_count += 1;
-                }
-            }
-        }
-    }
+    let report = TaskSync::new(project_dir).export(spec)?;
 
     formatter.success(&format!(
-        "Exported {} tasks as tickets from specification '{}'",
-        created_count, spec.metadata.title
+        "Exported tasks from specification '{}': {} created, {} updated",
+        spec.metadata.title,
+        report.created.len(),
+        report.updated.len()
     ));
+    if !report.toggled.is_empty() {
+        formatter.info(&format!(
+            "Updated checkboxes in tasks.md: {}",
+            report.toggled.join(", ")
+        ));
+    }
 
     Ok(())
 }
@@ -1165,18 +792,8 @@ pub fn handle_spec_status(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -1187,7 +804,19 @@ pub fn handle_spec_status(
     };
 
     // Load specification
-    let specification = spec_manager.load(&spec_id)?;
+    let mut specification = spec_manager.load(&spec_id)?;
+
+    // Roll up the work on the tickets generated from the spec's tasks
+    let tickets = crate::storage::FileStorage::new(&project_dir).load_all_tickets()?;
+    let time_tracking = super::TimeTracking::load(project_root.to_str())?;
+    let mut rollup = SpecRollup::build(&specification, &tickets, |id| {
+        time_tracking.total_time_for_ticket(id)
+    });
+    let completed = rollup.is_complete() && spec_manager.mark_completed(&spec_id)?;
+    if completed {
+        specification.metadata.progress.current_phase = SpecPhase::Completed;
+        rollup.phase = SpecPhase::Completed;
+    }
 
     if formatter.is_json() {
         formatter.json(&serde_json::json!({
@@ -1199,7 +828,11 @@ pub fn handle_spec_status(
                 "design": specification.metadata.progress.design_completed,
                 "tasks": specification.metadata.progress.tasks_completed,
             },
-            "approval": specification.metadata.progress.approval_status,
+            "review": spec_review::review_statuses(
+                &specification.metadata,
+                &ReviewConfig::for_project(&project_dir)
+            ),
+            "rollup": rollup,
         }))?;
     } else {
         formatter.info(&format!(
@@ -1237,6 +870,11 @@ pub fn handle_spec_status(
             }
         ));
 
+        print_rollup(&rollup, formatter);
+        if completed {
+            formatter.success("All tickets are done; specification moved to Completed");
+        }
+
         if detailed {
             formatter.info(&format!("\nCreated: {}", specification.metadata.created_at));
             formatter.info(&format!("Updated: {}", specification.metadata.updated_at));
@@ -1252,35 +890,85 @@ pub fn handle_spec_status(
     Ok(())
 }
 
+/// Print the ticket rollup section of `spec status`
+fn print_rollup(rollup: &SpecRollup, formatter: &OutputFormatter) {
+    let counts = &rollup.tickets;
+    if counts.total() == 0 {
+        formatter
+            .info("\nNo tickets generated yet. Export tasks with 'spec tasks --export-tickets'");
+        return;
+    }
+
+    formatter.info(&format!("\nWork ({} tickets):", counts.total()));
+    formatter.info(&format!(
+        "  {}% complete: {} done, {} doing, {} review, {} todo, {} blocked",
+        rollup.percent_complete,
+        counts.done,
+        counts.doing,
+        counts.review,
+        counts.todo,
+        counts.blocked
+    ));
+    formatter.info(&format!(
+        "  Time logged: {}",
+        crate::cli::format_duration(chrono::Duration::minutes(rollup.time_logged_minutes))
+    ));
+    for phase in &rollup.phases {
+        formatter.info(&format!(
+            "  {}: {}/{} done ({}%)",
+            phase.phase,
+            phase.tickets.done,
+            phase.tickets.total(),
+            phase.percent_complete
+        ));
+    }
+    match (rollup.throughput_per_day, rollup.eta) {
+        _ if rollup.is_complete() => {},
+        (Some(throughput), Some(eta)) => formatter.info(&format!(
+            "  ETA: {} ({throughput:.1} tickets/day)",
+            eta.format("%Y-%m-%d")
+        )),
+        _ => formatter.info("  ETA: unknown until the first ticket is done"),
+    }
+
+    if !rollup.blockers.is_empty() {
+        formatter.info("\nBlockers:");
+        for blocker in &rollup.blockers {
+            formatter.warning(&format!("  {} ({})", blocker.slug, blocker.reason));
+        }
+    }
+}
+
 /// Handle spec list command
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_spec_list(
     status: Option<String>,
     phase: Option<String>,
-    _archived: bool,
+    archived: bool,
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
-    let specs = spec_manager.list()?;
+    let archived_specs = if archived {
+        ArchiveStore::new(&project_dir).load_all_specs()?
+    } else {
+        Vec::new()
+    };
 
     // Filter specs
-    let filtered_specs: Vec<_> = specs
+    let filtered_specs: Vec<_> = spec_manager
+        .list()?
         .into_iter()
-        .filter(|spec| {
+        .map(|spec| (spec, false))
+        .chain(
+            archived_specs
+                .into_iter()
+                .map(|bundle| (bundle.metadata, true)),
+        )
+        .filter(|(spec, _)| {
             // Filter by status if provided
             if let Some(ref status_filter) = status {
                 let current_status = format!("{:?}", spec.progress.current_phase()).to_lowercase();
@@ -1291,23 +979,16 @@ pub fn handle_spec_list(
 
             // Filter by phase if provided
             if let Some(ref phase_filter) = phase {
-                match phase_filter.to_lowercase().as_str() {
-                    "requirements" => {
-                        if spec.progress.requirements_completed {
-                            return false;
-                        }
-                    },
+                let in_phase = match phase_filter.to_lowercase().as_str() {
+                    "requirements" => !spec.progress.requirements_completed,
                     "design" => {
-                        if !spec.progress.requirements_completed || spec.progress.design_completed {
-                            return false;
-                        }
-                    },
-                    "tasks" => {
-                        if !spec.progress.design_completed || spec.progress.tasks_completed {
-                            return false;
-                        }
+                        spec.progress.requirements_completed && !spec.progress.design_completed
                     },
-                    _ => {},
+                    "tasks" => spec.progress.design_completed && !spec.progress.tasks_completed,
+                    _ => true,
+                };
+                if !in_phase {
+                    return false;
                 }
             }
 
@@ -1318,7 +999,7 @@ pub fn handle_spec_list(
     if formatter.is_json() {
         let specs_json: Vec<_> = filtered_specs
             .iter()
-            .map(|spec| {
+            .map(|(spec, archived)| {
                 serde_json::json!({
                     "id": spec.id,
                     "title": spec.title,
@@ -1326,6 +1007,7 @@ pub fn handle_spec_list(
                     "phase": format!("{:?}", spec.progress.current_phase()),
                     "created_at": spec.created_at,
                     "updated_at": spec.updated_at,
+                    "archived": archived,
                 })
             })
             .collect();
@@ -1338,12 +1020,13 @@ pub fn handle_spec_list(
             filtered_specs.len()
         ));
 
-        for spec in &filtered_specs {
+        for (spec, archived) in &filtered_specs {
             formatter.info(&format!(
-                "{} - {} ({:?})",
+                "{} - {} ({:?}){}",
                 spec.id,
                 spec.title,
-                spec.progress.current_phase()
+                spec.progress.current_phase(),
+                if *archived { " (archived)" } else { "" }
             ));
         }
     }
@@ -1360,24 +1043,20 @@ pub fn handle_spec_show(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
     let specification = spec_manager.load(&spec)?;
+    let review_config = ReviewConfig::for_project(&project_dir);
 
     if formatter.is_json() {
-        formatter.json(&serde_json::json!(specification))?;
+        let mut value = serde_json::json!(specification);
+        value["review"] = serde_json::json!(spec_review::review_statuses(
+            &specification.metadata,
+            &review_config
+        ));
+        formatter.json(&value)?;
     } else {
         formatter.info(&format!(
             "# Specification: {}",
@@ -1392,6 +1071,8 @@ pub fn handle_spec_show(
             "Phase: {:?}",
             specification.metadata.progress.current_phase()
         ));
+        formatter.info("\n## Review\n");
+        spec_review::print_reviews(&specification.metadata, &review_config, formatter);
 
         if all || markdown {
             // Show all documents
@@ -1424,18 +1105,8 @@ pub fn handle_spec_delete(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -1455,70 +1126,6 @@ pub fn handle_spec_delete(
     Ok(())
 }
 
-/// Handle spec approve command
-#[allow(clippy::needless_pass_by_value)]
-pub fn handle_spec_approve(
-    spec: String,
-    phase: String,
-    message: Option<String>,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
-
-    let spec_manager = SpecManager::new(project_dir.join("specs"));
-    let mut specification = spec_manager.load(&spec)?;
-
-    // Parse phase
-    let phase_enum = match phase.to_lowercase().as_str() {
-        "requirements" => SpecPhase::Requirements,
-        "design" => SpecPhase::Design,
-        "tasks" => SpecPhase::Tasks,
-        _ => {
-            return Err(VibeTicketError::InvalidInput(
-                "Invalid phase. Must be one of: requirements, design, tasks".to_string(),
-            ));
-        },
-    };
-
-    // Update approval status
-    if specification.metadata.progress.approval_status.is_none() {
-        specification.metadata.progress.approval_status = Some(std::collections::HashMap::new());
-    }
-
-    if let Some(ref mut approvals) = specification.metadata.progress.approval_status {
-        approvals.insert(
-            format!("{phase_enum:?}"),
-            serde_json::json!({
-                "approved": true,
-                "approved_at": Utc::now(),
-                "message": message,
-            }),
-        );
-    }
-
-    specification.metadata.updated_at = Utc::now();
-    spec_manager.save(&specification)?;
-
-    formatter.success(&format!(
-        "Approved {} phase for specification '{}'",
-        phase, specification.metadata.title
-    ));
-
-    Ok(())
-}
-
 /// Handle spec activate command
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_spec_activate(
@@ -1526,18 +1133,8 @@ pub fn handle_spec_activate(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    // Change to project directory if specified
-    if let Some(project_path) = project {
-        std::env::set_current_dir(project_path)
-            .with_context(|| format!("Failed to change to project directory: {project_path}"))?;
-    }
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let project_dir = current_dir.join(".vibe-ticket");
-
-    if !project_dir.exists() {
-        return Err(VibeTicketError::ProjectNotInitialized);
-    }
+    let project_root = find_project_root(project)?;
+    let project_dir = project_root.join(".vibe-ticket");
 
     // Verify spec exists
     let spec_manager = SpecManager::new(project_dir.join("specs"));
@@ -1545,7 +1142,7 @@ pub fn handle_spec_activate(
 
     // Save active spec
     let active_spec_path = project_dir.join(".active_spec");
-    fs::write(&active_spec_path, &spec).context("Failed to set active specification")?;
+    write_project_file(&active_spec_path, &spec)?;
 
     formatter.success(&format!(
         "Set active specification to '{}' ({})",
@@ -1699,29 +1296,4 @@ mod tests {
         let result = handle_spec_delete("test-spec".to_string(), false, None, &formatter);
         assert!(result.is_ok()); // Doesn't actually delete without force
     }
-
-    #[test]
-    fn test_spec_approve_invalid_phase() {
-        let temp_dir = TempDir::new().unwrap();
-        let project_dir = temp_dir.path().join(".vibe-ticket");
-        std::fs::create_dir_all(&project_dir).unwrap();
-
-        std::env::set_current_dir(temp_dir.path()).unwrap();
-
-        let formatter = create_test_formatter();
-
-        // Create a spec first
-        handle_spec_init("Approve Test", None, None, None, None, &formatter).unwrap();
-
-        // Try to approve with invalid phase
-        let result = handle_spec_approve(
-            "test-spec".to_string(),
-            "invalid-phase".to_string(),
-            None,
-            None,
-            &formatter,
-        );
-
-        assert!(result.is_err());
-    }
 }
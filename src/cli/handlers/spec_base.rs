@@ -10,7 +10,6 @@ use crate::specs::{
     SpecManager, SpecMetadata, SpecPhase, SpecProgress, SpecVersion, Specification,
 };
 use chrono::Utc;
-use std::env;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -28,13 +27,7 @@ impl SpecContext {
     ///
     /// Handles project directory resolution and spec manager initialization
     pub fn new(project: Option<&str>, formatter: OutputFormatter) -> Result<Self> {
-        // Change to project directory if specified
-        if let Some(project_path) = project {
-            env::set_current_dir(project_path).context("Failed to change to project directory")?;
-        }
-
-        let current_dir = env::current_dir().context("Failed to get current directory")?;
-        let project_root = find_project_root(current_dir.to_str())?;
+        let project_root = find_project_root(project)?;
         let spec_dir = project_root.join(".vibe-ticket").join("specs");
 
         // Ensure spec directory exists
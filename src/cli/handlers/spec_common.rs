@@ -1,3 +1,4 @@
+use crate::cli::find_project_root;
 use crate::cli::output::OutputFormatter;
 use crate::error::{ErrorContext, Result, VibeTicketError};
 use crate::specs::{SpecManager, SpecPhase, Specification};
@@ -11,19 +12,7 @@ pub struct SpecContext {
 impl SpecContext {
     /// Create a new spec context
     pub fn new(project: Option<&str>, formatter: OutputFormatter) -> Result<Self> {
-        // Change to project directory if specified
-        if let Some(project_path) = project {
-            std::env::set_current_dir(project_path).with_context(|| {
-                format!("Failed to change to project directory: {project_path}")
-            })?;
-        }
-
-        let current_dir = env::current_dir().context("Failed to get current directory")?;
-        let project_dir = current_dir.join(".vibe-ticket");
-
-        if !project_dir.exists() {
-            return Err(VibeTicketError::ProjectNotInitialized);
-        }
+        let project_dir = find_project_root(project)?.join(".vibe-ticket");
 
         let spec_manager = SpecManager::new(project_dir.join("specs"));
 
@@ -88,11 +77,6 @@ pub trait SpecPhaseHandler {
             return Err(VibeTicketError::SpecNotFound { id: spec_id });
         };
 
-        // Update phase
-        spec.metadata.progress.current_phase = self.get_phase();
-        ctx.spec_manager.save(&spec)?;
-
-        // Save phase document - needs spec_id and doc_type
         let doc_type = match self.get_phase() {
             SpecPhase::Design => crate::specs::SpecDocumentType::Design,
             SpecPhase::Tasks | SpecPhase::Implementation => crate::specs::SpecDocumentType::Tasks,
@@ -100,6 +84,17 @@ pub trait SpecPhaseHandler {
                 crate::specs::SpecDocumentType::Requirements
             },
         };
+        let vibe_ticket_dir = env::current_dir()
+            .context("Failed to get current directory")?
+            .join(".vibe-ticket");
+        crate::specs::ReviewConfig::for_project(&vibe_ticket_dir)
+            .ensure_can_start(&spec.metadata, doc_type)?;
+
+        // Update phase
+        spec.metadata.progress.current_phase = self.get_phase();
+        ctx.spec_manager.save(&spec)?;
+
+        // Save phase document
         ctx.spec_manager.save_document(&spec_id, doc_type, "")?;
 
         // Open in editor if requested
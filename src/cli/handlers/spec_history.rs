@@ -0,0 +1,196 @@
+//! Handlers for spec revision history commands
+//!
+//! `spec history`, `spec diff` and `spec restore` work on the revisions that
+//! [`SpecManager::save_document`] records whenever a spec document changes.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::{RevisionRef, SpecDocumentType, SpecManager};
+use colored::Colorize;
+
+/// Handler for `spec history`
+///
+/// Lists the recorded revisions of a specification, oldest first.
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_history(
+    spec: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (manager, spec_id) = open(spec, project_dir)?;
+    let spec = manager.load_spec(&spec_id)?;
+    let revisions = manager.revisions(&spec_id)?;
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "spec_id": spec_id,
+            "title": spec.metadata.title,
+            "version": spec.metadata.version.to_string(),
+            "revisions": revisions,
+        }));
+    }
+
+    output.info(&format!(
+        "History of '{}' ({})",
+        spec.metadata.title, spec_id
+    ));
+    if revisions.is_empty() {
+        output.info("No revisions recorded yet");
+    }
+    for revision in &revisions {
+        let changed = revision
+            .changed
+            .iter()
+            .map(|doc_type| doc_type.file_name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        // What replaced this revision
+        let next = revision
+            .note
+            .clone()
+            .unwrap_or_else(|| format!("edited {changed}"));
+        println!(
+            "  {:<8} v{:<8} {}  → {next}",
+            format!("r{}", revision.number),
+            revision.version.to_string(),
+            revision.updated_at.format("%Y-%m-%d %H:%M"),
+        );
+    }
+    println!(
+        "  {:<8} v{:<8} {}",
+        "current",
+        spec.metadata.version.to_string(),
+        spec.metadata.updated_at.format("%Y-%m-%d %H:%M"),
+    );
+    Ok(())
+}
+
+/// Handler for `spec diff`
+///
+/// Prints unified diffs of the documents that differ between two revisions.
+///
+/// # Arguments
+///
+/// * `from` - Older revision number, or `current`
+/// * `to` - Newer revision number, or `current`
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `document` - Only diff this document (requirements, design, tasks)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_diff(
+    from: &str,
+    to: &str,
+    spec: Option<&str>,
+    document: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (manager, spec_id) = open(spec, project_dir)?;
+    let from: RevisionRef = from.parse()?;
+    let to: RevisionRef = to.parse()?;
+    let document = document.map(str::parse::<SpecDocumentType>).transpose()?;
+
+    let diffs: Vec<_> = manager
+        .diff(&spec_id, from, to)?
+        .into_iter()
+        .filter(|diff| document.is_none_or(|document| diff.document == document))
+        .collect();
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "spec_id": spec_id,
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "documents": diffs,
+        }));
+    }
+
+    if diffs.is_empty() {
+        output.info(&format!("No differences between {from} and {to}"));
+    }
+    for diff in &diffs {
+        for line in diff.diff.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                println!("{}", line.bold());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
+            } else {
+                println!("{line}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handler for `spec restore`
+///
+/// Restores the documents of a specification to a recorded revision. The
+/// current state is recorded as a new revision first, and approvals of the
+/// restored documents are withdrawn like for any other change.
+///
+/// # Arguments
+///
+/// * `revision` - Revision number to restore
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_restore(
+    revision: &str,
+    spec: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (manager, spec_id) = open(spec, project_dir)?;
+    let RevisionRef::Number(number) = revision.parse()? else {
+        return Err(VibeTicketError::custom(
+            "Cannot restore the current revision; give a revision number",
+        ));
+    };
+
+    let (metadata, changed) = manager.restore_revision(&spec_id, number)?;
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "status": "success",
+            "spec_id": spec_id,
+            "restored": number,
+            "version": metadata.version.to_string(),
+            "changed": changed,
+        }));
+    }
+
+    if changed.is_empty() {
+        output.info(&format!("'{}' already matches r{number}", metadata.title));
+    } else {
+        output.success(&format!(
+            "Restored '{}' to r{number} (now v{})",
+            metadata.title, metadata.version
+        ));
+        for doc_type in &changed {
+            output.info(&format!("  {}", doc_type.file_name()));
+        }
+    }
+    Ok(())
+}
+
+/// Open the spec manager and resolve the spec ID
+fn open(spec: Option<&str>, project_dir: Option<&str>) -> Result<(SpecManager, String)> {
+    let project_root = find_project_root(project_dir)?;
+    let manager = SpecManager::new(project_root.join(".vibe-ticket").join("specs"));
+    let spec_id = match spec {
+        Some(spec) => spec.to_string(),
+        None => manager
+            .get_active_spec()?
+            .ok_or(VibeTicketError::NoActiveSpec)?,
+    };
+    Ok((manager, spec_id))
+}
@@ -0,0 +1,92 @@
+//! Handler for the `spec lint` command
+//!
+//! Runs the ambiguity linter over the documents of a spec and prints the
+//! findings as text, JSON or SARIF.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecManager;
+use crate::specs::lint::{self, Linter, Severity};
+use colored::Colorize;
+
+/// Handler for `spec lint`
+///
+/// Fails when any finding has error severity.
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `format` - Output format: `text`, `json` or `sarif`
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_lint(
+    spec: Option<&str>,
+    format: &str,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = match spec {
+        Some(spec) => spec.to_string(),
+        None => manager
+            .get_active_spec()?
+            .ok_or(VibeTicketError::NoActiveSpec)?,
+    };
+    let spec_dir = manager.get_spec_dir(&spec_id);
+    if !spec_dir.exists() {
+        return Err(VibeTicketError::SpecNotFound { id: spec_id });
+    }
+    let findings = Linter::for_project(&vibe_ticket_dir).lint_dir(&spec_dir)?;
+
+    let format = if output.is_json() { "json" } else { format };
+    match format {
+        "json" => output.print_json(&serde_json::json!({
+            "spec_id": spec_id,
+            "findings": findings,
+        }))?,
+        "sarif" => {
+            let base = spec_dir
+                .strip_prefix(&project_root)
+                .unwrap_or(&spec_dir)
+                .to_string_lossy()
+                .replace('\\', "/");
+            output.print_json(&lint::to_sarif(&findings, &base))?;
+        },
+        "text" => {
+            for finding in &findings {
+                let severity = match finding.severity {
+                    Severity::Error => finding.severity.as_str().red(),
+                    Severity::Warning => finding.severity.as_str().yellow(),
+                    Severity::Info => finding.severity.as_str().blue(),
+                };
+                println!(
+                    "{}:{}:{}: {severity}[{}] {}",
+                    finding.file,
+                    finding.line,
+                    finding.column,
+                    finding.rule.id(),
+                    finding.message
+                );
+                println!("  {} {}", "help:".bright_black(), finding.suggestion);
+            }
+            if findings.is_empty() {
+                output.success("No ambiguities found");
+            }
+        },
+        other => {
+            return Err(VibeTicketError::custom(format!(
+                "Unknown lint output format: {other} (expected text, json or sarif)"
+            )));
+        },
+    }
+
+    let errors = lint::at_least(&findings, Severity::Error).count();
+    if errors > 0 {
+        return Err(VibeTicketError::custom(format!(
+            "Specification has {errors} lint error(s)"
+        )));
+    }
+    Ok(())
+}
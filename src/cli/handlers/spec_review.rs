@@ -0,0 +1,332 @@
+//! Handlers for spec review commands
+//!
+//! `spec approve` and `spec request-changes` record reviewer sign-offs on a
+//! phase; `spec reviewers add/remove` manage who must approve it.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::review::{self, ReviewConfig, ReviewStatus, SignOff, Verdict};
+use crate::specs::{SpecDocumentType, SpecManager, SpecMetadata};
+use colored::Colorize;
+
+/// Handler for `spec approve`
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID
+/// * `phase` - Phase to approve (requirements, design, tasks)
+/// * `message` - Optional approval comment
+/// * `reviewer` - Reviewer name (defaults to git `user.name`, then `$USER`)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_approve(
+    spec: &str,
+    phase: &str,
+    message: Option<String>,
+    reviewer: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    sign_off(
+        spec,
+        phase,
+        Verdict::Approved,
+        message,
+        reviewer,
+        project_dir,
+        output,
+    )
+}
+
+/// Handler for `spec request-changes`
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID
+/// * `phase` - Phase to request changes on (requirements, design, tasks)
+/// * `message` - Description of the requested changes
+/// * `reviewer` - Reviewer name (defaults to git `user.name`, then `$USER`)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_request_changes(
+    spec: &str,
+    phase: &str,
+    message: &str,
+    reviewer: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if message.trim().is_empty() {
+        return Err(VibeTicketError::InvalidInput(
+            "Describe the requested changes with --message".to_string(),
+        ));
+    }
+    sign_off(
+        spec,
+        phase,
+        Verdict::ChangesRequested,
+        Some(message.to_string()),
+        reviewer,
+        project_dir,
+        output,
+    )
+}
+
+/// Handler for `spec reviewers add` and `spec reviewers remove`
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `phase` - Phase the reviewers are required for
+/// * `add` - Reviewers to require
+/// * `remove` - Reviewers to no longer require
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_reviewers(
+    spec: Option<&str>,
+    phase: &str,
+    add: &[String],
+    remove: &[String],
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let vibe_ticket_dir = find_project_root(project_dir)?.join(".vibe-ticket");
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = match spec {
+        Some(spec) => spec.to_string(),
+        None => manager
+            .get_active_spec()?
+            .ok_or(VibeTicketError::NoActiveSpec)?,
+    };
+    let phase = parse_phase(phase)?;
+    ensure_spec_exists(&manager, &spec_id)?;
+
+    let config = ReviewConfig::for_project(&vibe_ticket_dir);
+    let status = manager.update_reviewers(&spec_id, phase, add, remove, &config)?;
+
+    if output.is_json() {
+        let metadata = manager.load_spec(&spec_id)?.metadata;
+        return output.print_json(&serde_json::json!({
+            "spec_id": spec_id,
+            "reviewers": metadata.progress.reviews.get(&phase).map(|r| &r.reviewers),
+            "status": status,
+        }));
+    }
+    if !add.is_empty() {
+        output.success(&format!(
+            "{} now required for {}: {}",
+            if add.len() == 1 {
+                "Reviewer"
+            } else {
+                "Reviewers"
+            },
+            phase.as_str(),
+            add.join(", ")
+        ));
+    }
+    if !remove.is_empty() {
+        output.success(&format!(
+            "{} no longer required for {}: {}",
+            if remove.len() == 1 {
+                "Reviewer"
+            } else {
+                "Reviewers"
+            },
+            phase.as_str(),
+            remove.join(", ")
+        ));
+    }
+    output.info(&format!("Review: {}", status.summary()));
+    Ok(())
+}
+
+/// Print the sign-offs and status of every phase of a spec
+pub fn print_reviews(metadata: &SpecMetadata, config: &ReviewConfig, output: &OutputFormatter) {
+    for phase in SpecDocumentType::ALL {
+        let status = config.status(metadata, phase);
+        let state = if status.approved {
+            "approved".green()
+        } else {
+            "pending".yellow()
+        };
+        output.info(&format!(
+            "{}: {state} ({})",
+            phase.display_name(),
+            status.summary()
+        ));
+        let Some(review) = metadata.progress.reviews.get(&phase) else {
+            continue;
+        };
+        for sign_off in &review.sign_offs {
+            let verdict = match sign_off.verdict {
+                Verdict::Approved => "approved",
+                Verdict::ChangesRequested => "requested changes",
+            };
+            let mut line = format!(
+                "  {} {verdict} v{} on {}",
+                sign_off.reviewer,
+                sign_off.version,
+                sign_off.at.format("%Y-%m-%d %H:%M")
+            );
+            if let Some(comment) = &sign_off.comment {
+                line.push_str(": ");
+                line.push_str(comment);
+            }
+            if sign_off.is_active() {
+                output.info(&line);
+            } else {
+                output.info(&format!(
+                    "{} {}",
+                    line.bright_black(),
+                    "(withdrawn)".bright_black()
+                ));
+            }
+        }
+    }
+}
+
+/// Review status of every phase of a spec, for JSON output
+#[must_use]
+pub fn review_statuses(metadata: &SpecMetadata, config: &ReviewConfig) -> Vec<ReviewStatus> {
+    SpecDocumentType::ALL
+        .into_iter()
+        .map(|phase| config.status(metadata, phase))
+        .collect()
+}
+
+fn sign_off(
+    spec: &str,
+    phase: &str,
+    verdict: Verdict,
+    comment: Option<String>,
+    reviewer: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let vibe_ticket_dir = find_project_root(project_dir)?.join(".vibe-ticket");
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let phase = parse_phase(phase)?;
+    let reviewer = reviewer
+        .map(str::to_string)
+        .or_else(review::default_reviewer)
+        .ok_or_else(|| {
+            VibeTicketError::InvalidInput(
+                "Could not determine the reviewer; pass --reviewer".to_string(),
+            )
+        })?;
+    ensure_spec_exists(&manager, spec)?;
+
+    let metadata = manager.load_spec(spec)?.metadata;
+    let config = ReviewConfig::for_project(&vibe_ticket_dir);
+    let record = SignOff::new(reviewer, verdict, &metadata, comment);
+    let status = manager.sign_off(spec, phase, record.clone(), &config)?;
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "spec_id": spec,
+            "sign_off": record,
+            "status": status,
+        }));
+    }
+    match verdict {
+        Verdict::Approved => output.success(&format!(
+            "{} approved {} of '{}' (v{})",
+            record.reviewer,
+            phase.as_str(),
+            metadata.title,
+            record.version
+        )),
+        Verdict::ChangesRequested => output.warning(&format!(
+            "{} requested changes to {} of '{}' (v{})",
+            record.reviewer,
+            phase.as_str(),
+            metadata.title,
+            record.version
+        )),
+    }
+    if status.approved {
+        output.info(&format!(
+            "{} is approved ({})",
+            phase.as_str(),
+            status.summary()
+        ));
+    } else {
+        output.info(&format!(
+            "{} is not approved yet ({})",
+            phase.as_str(),
+            status.summary()
+        ));
+    }
+    Ok(())
+}
+
+fn parse_phase(phase: &str) -> Result<SpecDocumentType> {
+    phase.parse().map_err(|_| {
+        VibeTicketError::InvalidInput(
+            "Invalid phase. Must be one of: requirements, design, tasks".to_string(),
+        )
+    })
+}
+
+fn ensure_spec_exists(manager: &SpecManager, spec_id: &str) -> Result<()> {
+    if manager.get_spec_dir(spec_id).join("spec.json").exists() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::SpecNotFound {
+            id: spec_id.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_spec_approve_invalid_phase() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join(".vibe-ticket/specs")).unwrap();
+        let manager = SpecManager::new(temp_dir.path().join(".vibe-ticket/specs"));
+        let metadata = manager
+            .create_spec("Approve Test".to_string(), String::new())
+            .unwrap();
+        let project = temp_dir.path().to_str();
+        let output = OutputFormatter::new(false, true);
+
+        let result = handle_spec_approve(
+            &metadata.id,
+            "invalid-phase",
+            None,
+            Some("alice"),
+            project,
+            &output,
+        );
+        assert!(result.is_err());
+
+        // Approving needs the document
+        let result = handle_spec_approve(
+            &metadata.id,
+            "requirements",
+            None,
+            Some("alice"),
+            project,
+            &output,
+        );
+        assert!(result.is_err());
+
+        handle_spec_request_changes(
+            &metadata.id,
+            "requirements",
+            "Name the users",
+            Some("bob"),
+            project,
+            &output,
+        )
+        .unwrap();
+        let metadata = manager.load_spec(&metadata.id).unwrap().metadata;
+        let review = &metadata.progress.reviews[&SpecDocumentType::Requirements];
+        assert_eq!(review.sign_offs[0].reviewer, "bob");
+        assert_eq!(review.sign_offs[0].verdict, Verdict::ChangesRequested);
+    }
+}
@@ -0,0 +1,55 @@
+//! Handler for the `spec sync` command
+//!
+//! Reconciles the checkboxes of a spec's `tasks.md` with the status of the
+//! tickets exported from it (see [`TaskSync`]).
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::{SpecManager, TaskSync};
+
+/// Handler for `spec sync`
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_sync(
+    spec: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let vibe_ticket_dir = find_project_root(project_dir)?.join(".vibe-ticket");
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = match spec {
+        Some(spec) => spec.to_string(),
+        None => manager
+            .get_active_spec()?
+            .ok_or(VibeTicketError::NoActiveSpec)?,
+    };
+    let specification = manager.load_spec(&spec_id)?;
+    let report = TaskSync::new(&vibe_ticket_dir).sync(&specification)?;
+
+    if output.is_json() {
+        return output.print_json(&serde_json::json!({
+            "spec_id": spec_id,
+            "updated_tickets": report.updated,
+            "toggled_tasks": report.toggled,
+        }));
+    }
+
+    if report.updated.is_empty() && report.toggled.is_empty() {
+        output.info(&format!(
+            "Tasks of '{}' are in sync with their tickets",
+            specification.metadata.title
+        ));
+        return Ok(());
+    }
+    for slug in &report.updated {
+        output.success(&format!("Updated ticket {slug}"));
+    }
+    for task in &report.toggled {
+        output.success(&format!("Updated checkbox of {task} in tasks.md"));
+    }
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Handler for the `spec trace` command
+//!
+//! Renders the requirement → design → task → ticket matrix of a spec and
+//! flags requirements nobody implements and tickets that trace to nothing.
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::{SpecManager, TraceMatrix};
+use crate::storage::FileStorage;
+use colored::Colorize;
+
+/// Handler for `spec trace`
+///
+/// # Arguments
+///
+/// * `spec` - Specification ID (defaults to the active spec)
+/// * `markdown` - Print a Markdown table instead of plain text
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_spec_trace(
+    spec: Option<&str>,
+    markdown: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let vibe_ticket_dir = find_project_root(project_dir)?.join(".vibe-ticket");
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = match spec {
+        Some(spec) => spec.to_string(),
+        None => manager
+            .get_active_spec()?
+            .ok_or(VibeTicketError::NoActiveSpec)?,
+    };
+    let specification = manager.load_spec(&spec_id)?;
+    let tickets = FileStorage::new(&vibe_ticket_dir).load_all_tickets()?;
+    let matrix = TraceMatrix::build(&specification, &tickets);
+
+    if output.is_json() {
+        let unimplemented: Vec<_> = matrix.unimplemented().map(|r| &r.id).collect();
+        return output.print_json(&serde_json::json!({
+            "spec_id": matrix.spec_id,
+            "title": specification.metadata.title,
+            "requirements": matrix.rows,
+            "tasks": matrix.tasks,
+            "unimplemented_requirements": unimplemented,
+            "untraced_tickets": matrix.untraced_tickets,
+        }));
+    }
+
+    if markdown {
+        print_markdown(&matrix);
+    } else {
+        output.info(&format!(
+            "Traceability of '{}' ({})",
+            specification.metadata.title, spec_id
+        ));
+        print_table(&matrix);
+    }
+
+    if matrix.rows.is_empty() {
+        output.warning("No requirement IDs (e.g. REQ-001) found in requirements.md");
+    }
+    for requirement in matrix.unimplemented() {
+        output.warning(&format!(
+            "Requirement {} has no implementing ticket",
+            requirement.id
+        ));
+    }
+    for ticket in &matrix.untraced_tickets {
+        output.warning(&format!(
+            "Ticket {} is not traced to any requirement",
+            ticket.slug
+        ));
+    }
+    Ok(())
+}
+
+/// Cells of one matrix row: requirement, design, tasks, tickets
+fn cells(matrix: &TraceMatrix) -> Vec<[String; 4]> {
+    matrix
+        .rows
+        .iter()
+        .map(|row| {
+            let tickets = row
+                .tickets
+                .iter()
+                .map(|ticket| format!("{} ({})", ticket.slug, ticket.status))
+                .collect::<Vec<_>>();
+            [
+                row.requirement.id.clone(),
+                or_dash(&row.design),
+                or_dash(&row.tasks),
+                or_dash(&tickets),
+            ]
+        })
+        .collect()
+}
+
+fn or_dash(items: &[String]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn print_table(matrix: &TraceMatrix) {
+    let rows = cells(matrix);
+    let headers = ["Requirement", "Design", "Tasks", "Tickets"];
+    let widths: Vec<usize> = (0..4)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let header = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| format!("{header:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("{}", header.bold());
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        if row[3] == "-" {
+            println!("{}", line.red());
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+fn print_markdown(matrix: &TraceMatrix) {
+    println!("| Requirement | Design | Tasks | Tickets |");
+    println!("|---|---|---|---|");
+    for row in cells(matrix) {
+        let row = row.map(|cell| cell.replace('|', "\\|"));
+        println!("| {} | {} | {} | {} |", row[0], row[1], row[2], row[3]);
+    }
+}
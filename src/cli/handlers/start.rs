@@ -8,6 +8,7 @@ use crate::cli::{OutputFormatter, find_project_root};
 use crate::config::Config;
 use crate::core::Status;
 use crate::error::{Result, VibeTicketError};
+use crate::specs::TaskSync;
 use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 use chrono::Utc;
 
@@ -65,11 +66,12 @@ pub fn handle_start_command(
         )));
     }
 
-    // Update ticket status and start time
     #[cfg(feature = "mcp")]
     let old_status = ticket.status;
 
-    ticket.status = Status::Doing;
+    // Update ticket status and start time, keeping the spec task this
+    // ticket was exported from in sync
+    TaskSync::new(&vibe_ticket_dir).set_status(&mut ticket, Status::Doing)?;
     ticket.started_at = Some(Utc::now());
 
     // Save the updated ticket
@@ -202,14 +204,14 @@ fn create_git_branch(
     Ok(())
 }
 
-/// Create a Git worktree for the ticket
-fn create_git_worktree(
+/// Create a Git worktree for the ticket and return its path
+pub fn create_git_worktree(
     project_root: &std::path::Path,
     branch_name: &str,
     ticket_slug: &str,
     config: &Config,
     output: &OutputFormatter,
-) -> Result<()> {
+) -> Result<std::path::PathBuf> {
     use std::process::Command;
 
     // Check if we're in a git repository
@@ -312,7 +314,7 @@ fn create_git_worktree(
     };
     output.info(&format!("You can now cd to '{cd_path}'"));
 
-    Ok(())
+    Ok(worktree_path)
 }
 
 #[cfg(test)]
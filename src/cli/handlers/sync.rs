@@ -0,0 +1,212 @@
+//! Handlers for the `sync` command
+//!
+//! Mirrors tickets to GitHub issues and back so external contributors who
+//! only use GitHub see the same work. See [`crate::remote`] for how tickets
+//! map to issues and how conflicts are detected.
+
+use super::common::resolve_ticket_ref;
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::config::Config;
+use crate::core::{Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::remote::{GitHubRemote, SyncAction, SyncEngine, SyncEntry};
+use crate::specs::TaskSync;
+use crate::storage::{FileStorage, TicketRepository};
+
+/// Handler for `sync push`
+///
+/// Creates or updates a GitHub issue for each ticket (or only the given one).
+///
+/// # Arguments
+///
+/// * `ticket_ref` - Optional ticket ID or slug to push instead of all tickets
+/// * `force` - Overwrite issues that changed remotely since the last sync
+/// * `dry_run` - Report what would be pushed without changing anything
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_sync_push(
+    ticket_ref: Option<&str>,
+    force: bool,
+    dry_run: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, remote) = open(project_dir)?;
+    let mut tickets = match ticket_ref {
+        Some(ticket_ref) => vec![storage.load(&resolve_ticket_ref(&storage, ticket_ref)?)?],
+        None => storage.load_all()?,
+    };
+    tickets.sort_by_key(|t| t.created_at);
+
+    let engine = SyncEngine::new(&remote)
+        .with_force(force)
+        .with_dry_run(dry_run);
+    let entries = engine.push(&mut tickets);
+    save_changed(&storage, &mut tickets, &entries, &[], dry_run)?;
+    report("push", &entries, dry_run, output)
+}
+
+/// Handler for `sync pull`
+///
+/// Updates linked tickets from their GitHub issues and creates tickets for
+/// issues that are not linked yet.
+///
+/// # Arguments
+///
+/// * `force` - Take the remote version even if the ticket has unpushed changes
+/// * `dry_run` - Report what would be pulled without changing anything
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for displaying results
+pub fn handle_sync_pull(
+    force: bool,
+    dry_run: bool,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let (storage, remote) = open(project_dir)?;
+    let mut tickets = storage.load_all()?;
+    let statuses: Vec<_> = tickets.iter().map(|t| (t.id.clone(), t.status)).collect();
+
+    let engine = SyncEngine::new(&remote)
+        .with_force(force)
+        .with_dry_run(dry_run);
+    let entries = engine.pull(&mut tickets)?;
+    let moved: Vec<_> = tickets
+        .iter()
+        .filter(|t| !statuses.contains(&(t.id.clone(), t.status)))
+        .map(|t| t.id.clone())
+        .collect();
+    save_changed(&storage, &mut tickets, &entries, &moved, dry_run)?;
+    report("pull", &entries, dry_run, output)
+}
+
+/// Open the project storage and the configured GitHub remote
+fn open(project_dir: Option<&str>) -> Result<(FileStorage, GitHubRemote)> {
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let config = Config::load_from_path(vibe_ticket_dir.join("config.yaml")).unwrap_or_default();
+    let github = config.remote.github;
+
+    let repository = github.repository.ok_or_else(|| {
+        VibeTicketError::custom(
+            "GitHub repository is not configured. \
+             Run: vibe-ticket config set remote.github.repository <owner/repo>",
+        )
+    })?;
+    let token = std::env::var(&github.token_env)
+        .ok()
+        .filter(|token| !token.is_empty());
+    let remote = GitHubRemote::new(github.api_url, repository, token)?;
+
+    Ok((FileStorage::new(vibe_ticket_dir), remote))
+}
+
+/// Save the tickets that changed in one transaction
+///
+/// The spec tasks of the `moved` tickets, whose status changed, are updated
+/// with them.
+fn save_changed(
+    storage: &FileStorage,
+    tickets: &mut [Ticket],
+    entries: &[SyncEntry],
+    moved: &[TicketId],
+    dry_run: bool,
+) -> Result<()> {
+    if dry_run {
+        return Ok(());
+    }
+    let mut transaction = storage.begin("sync")?;
+    let sync = TaskSync::new(storage.base_dir());
+    let specs = sync.stage_tickets(
+        tickets.iter_mut().filter(|t| moved.contains(&t.id)),
+        &mut transaction,
+    )?;
+    for entry in entries.iter().filter(|entry| entry.action.is_change()) {
+        if let Some(ticket) = tickets.iter().find(|t| t.id == entry.ticket_id) {
+            transaction.save(ticket)?;
+        }
+    }
+    transaction.commit()?;
+    sync.complete_specs(&specs)
+}
+
+fn report(
+    direction: &str,
+    entries: &[SyncEntry],
+    dry_run: bool,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let count = |f: fn(&SyncAction) -> bool| entries.iter().filter(|e| f(&e.action)).count();
+    let created = count(|a| matches!(a, SyncAction::Created | SyncAction::Imported));
+    let updated = count(|a| matches!(a, SyncAction::Updated));
+    let unchanged = count(|a| matches!(a, SyncAction::Unchanged));
+    let conflicts = count(|a| matches!(a, SyncAction::Conflict(_)));
+    let failed = count(|a| matches!(a, SyncAction::Failed(_)));
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "direction": direction,
+            "dry_run": dry_run,
+            "created": created,
+            "updated": updated,
+            "unchanged": unchanged,
+            "conflicts": conflicts,
+            "failed": failed,
+            "tickets": entries.iter().map(|e| serde_json::json!({
+                "ticket_id": e.ticket_id.to_string(),
+                "slug": e.slug,
+                "issue": e.issue,
+                "action": action_name(&e.action),
+                "message": match &e.action {
+                    SyncAction::Conflict(message) | SyncAction::Failed(message) => Some(message),
+                    _ => None,
+                },
+            })).collect::<Vec<_>>(),
+        }))?;
+    } else {
+        print_entries(entries, dry_run, output);
+        output.success(&format!(
+            "Sync {direction} completed: {created} created, {updated} updated, \
+             {unchanged} unchanged, {conflicts} conflicts, {failed} failed"
+        ));
+    }
+
+    if failed > 0 {
+        return Err(VibeTicketError::custom(format!(
+            "{failed} ticket(s) failed to sync"
+        )));
+    }
+    Ok(())
+}
+
+fn print_entries(entries: &[SyncEntry], dry_run: bool, output: &OutputFormatter) {
+    if dry_run {
+        output.warning("DRY RUN MODE - No changes will be made");
+    }
+    for entry in entries.iter().filter(|e| e.action != SyncAction::Unchanged) {
+        let issue = entry
+            .issue
+            .as_deref()
+            .map_or_else(String::new, |issue| format!(" (#{issue})"));
+        match &entry.action {
+            SyncAction::Conflict(message) => {
+                output.warning(&format!("Conflict: {}{issue}: {message}", entry.slug));
+            },
+            SyncAction::Failed(message) => {
+                output.error(&format!("Failed: {}{issue}: {message}", entry.slug));
+            },
+            action => output.info(&format!("  {} {}{issue}", action_name(action), entry.slug)),
+        }
+    }
+}
+
+const fn action_name(action: &SyncAction) -> &'static str {
+    match action {
+        SyncAction::Created => "created",
+        SyncAction::Updated => "updated",
+        SyncAction::Imported => "imported",
+        SyncAction::Unchanged => "unchanged",
+        SyncAction::Conflict(_) => "conflict",
+        SyncAction::Failed(_) => "failed",
+    }
+}
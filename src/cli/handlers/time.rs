@@ -3,7 +3,7 @@
 use crate::cli::output::OutputFormatter;
 use crate::cli::utils::find_project_root;
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository, write_project_file};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -72,9 +72,7 @@ impl TimeTracking {
         let content = serde_yaml::to_string(self).map_err(|e| {
             VibeTicketError::custom(format!("Failed to serialize time tracking: {e}"))
         })?;
-        fs::write(&path, content).map_err(|e| {
-            VibeTicketError::custom(format!("Failed to write time tracking file: {e}"))
-        })?;
+        write_project_file(&path, content)?;
         Ok(())
     }
 
@@ -93,6 +91,7 @@ impl TimeTracking {
     }
 
     /// Get total time for a ticket in minutes
+    #[must_use]
     pub fn total_time_for_ticket(&self, ticket_id: &str) -> i64 {
         self.entries
             .get(ticket_id)
@@ -411,7 +410,7 @@ pub fn handle_time_report(
             ticket_totals.push((ticket_id.clone(), ticket_total));
         }
 
-        ticket_totals.sort_by(|a, b| b.1.cmp(&a.1)); // Sort by time, descending
+        ticket_totals.sort_by_key(|t| std::cmp::Reverse(t.1)); // Sort by time, descending
 
         if output.is_json() {
             let report: Vec<_> = ticket_totals
@@ -0,0 +1,34 @@
+//! Handler for the `tui` command
+//!
+//! Opens the full-screen kanban board implemented in [`crate::tui`].
+
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+
+/// Handler for the `tui` command
+///
+/// # Arguments
+///
+/// * `filter` - Optional name of a saved filter to apply on start
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter; JSON output is not supported
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project is not initialized
+/// - The saved filter does not exist
+/// - The terminal cannot be put into full-screen mode
+pub fn handle_tui_command(
+    filter: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    if output.is_json() {
+        return Err(VibeTicketError::InvalidInput(
+            "The tui command does not support JSON output".to_string(),
+        ));
+    }
+    let project_root = find_project_root(project_dir)?;
+    crate::tui::run(&project_root, filter)
+}
@@ -0,0 +1,133 @@
+//! Handler for the `watch` command
+//!
+//! Streams changes to ticket files, whichever process makes them, as one
+//! JSON object per line until interrupted.
+
+use crate::cli::handlers::{SavedFilter, SavedFilters};
+use crate::cli::{OutputFormatter, find_project_root};
+use crate::error::{Result, VibeTicketError};
+use crate::events::{TicketChange, TicketEvent, TicketWatcher};
+use crate::storage::FileStorage;
+use serde_json::json;
+use std::process::Command;
+use std::time::Duration;
+
+/// Handler for the `watch` command
+///
+/// # Arguments
+///
+/// * `filter` - Filter expression, or `@name` of a saved filter; only
+///   changes to matching tickets are reported
+/// * `exec` - Shell command run for each change
+/// * `project_dir` - Optional project directory path
+/// * `output` - Output formatter for warnings
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project is not initialized
+/// - The saved filter does not exist
+/// - The tickets directory cannot be watched
+pub fn handle_watch_command(
+    filter: Option<&str>,
+    exec: Option<&str>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let filter = filter
+        .map(|filter| resolve_filter(filter, project_dir))
+        .transpose()?;
+    let storage = FileStorage::new(project_root.join(".vibe-ticket"));
+    let mut watcher = TicketWatcher::new(&storage)?;
+
+    loop {
+        for change in watcher.wait(Duration::from_secs(1))? {
+            if filter
+                .as_ref()
+                .is_some_and(|filter| !filter.matches(&change.ticket))
+            {
+                continue;
+            }
+            let line = change_json(&change).to_string();
+            println!("{line}");
+            if let Some(command) = exec {
+                if let Err(error) = run_exec(command, &change, &line) {
+                    output.warning(&error.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// A saved filter for `@name`, or a filter for an inline expression
+fn resolve_filter(filter: &str, project_dir: Option<&str>) -> Result<SavedFilter> {
+    if let Some(name) = filter.strip_prefix('@') {
+        return SavedFilters::load(project_dir)?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| VibeTicketError::custom(format!("Filter '{name}' not found")));
+    }
+    Ok(SavedFilter {
+        name: String::new(),
+        expression: filter.to_string(),
+        description: None,
+        created_at: chrono::Utc::now(),
+    })
+}
+
+/// The JSON line reported for a change
+fn change_json(change: &TicketChange) -> serde_json::Value {
+    let ticket = &change.ticket;
+    let mut line = json!({
+        "event": change.event.name(),
+        "ticket_id": ticket.id.to_string(),
+        "slug": ticket.slug,
+        "title": ticket.title,
+        "status": ticket.status.to_string(),
+        "priority": ticket.priority.to_string(),
+        "timestamp": chrono::Utc::now(),
+    });
+    match &change.event {
+        TicketEvent::StatusChanged(_, old_status, new_status) => {
+            line["old_status"] = json!(old_status.to_string());
+            line["new_status"] = json!(new_status.to_string());
+        },
+        TicketEvent::Closed(_, message) => line["message"] = json!(message),
+        TicketEvent::TaskAdded(_, task) => {
+            line["task_id"] = json!(task.id.to_string());
+            line["task"] = json!(task.title);
+        },
+        TicketEvent::TaskCompleted(_, task_id) | TicketEvent::TaskRemoved(_, task_id) => {
+            line["task_id"] = json!(task_id);
+        },
+        TicketEvent::Created(_) | TicketEvent::Updated(_) | TicketEvent::Removed(_) => {},
+    }
+    line
+}
+
+/// Run `command` for a change, as hooks are run
+fn run_exec(command: &str, change: &TicketChange, line: &str) -> Result<()> {
+    let (shell, shell_arg) = if cfg!(target_os = "windows") {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let status = Command::new(shell)
+        .arg(shell_arg)
+        .arg(command)
+        .env("VIBE_TICKET_CONTEXT", line)
+        .env("VIBE_TICKET_EVENT", change.event.name())
+        .env("VIBE_TICKET_SLUG", &change.ticket.slug)
+        .status()
+        .map_err(|e| VibeTicketError::custom(format!("Failed to run --exec command: {e}")))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(VibeTicketError::custom(format!(
+            "--exec command failed for {} of {}: {status}",
+            change.event.name(),
+            change.ticket.slug
+        )))
+    }
+}
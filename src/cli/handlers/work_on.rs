@@ -10,7 +10,6 @@ use crate::error::{Result, VibeTicketError};
 use crate::storage::{FileStorage, TicketRepository};
 use dialoguer::{Select, theme::ColorfulTheme};
 use std::env;
-use std::fs;
 use std::path::Path;
 
 /// Handle the intent-focused work-on command
@@ -81,8 +80,7 @@ pub fn handle_work_on_command(
     }
 
     // Set as active ticket
-    let active_ticket_path = tickets_dir.join("active_ticket");
-    fs::write(&active_ticket_path, ticket.id.to_string())?;
+    storage.set_active_ticket(&ticket.id)?;
 
     // Create worktree if needed
     if !no_worktree && should_create_worktree(&project_root)? {
@@ -3,14 +3,57 @@
 //! This module provides functionality to manage Git worktrees associated with tickets,
 //! enabling parallel development workflows.
 
+use crate::cli::handlers::common::resolve_ticket_ref;
+use crate::cli::handlers::start::create_git_worktree;
 use crate::cli::{OutputFormatter, find_project_root};
 use crate::config::Config;
 use crate::error::{Result, VibeTicketError};
-use crate::storage::{FileStorage, TicketRepository};
+use crate::storage::{ActiveTicketRepository, FileStorage, TicketRepository};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Handle the worktree create command
+///
+/// Creates a worktree and branch for a ticket without changing its status,
+/// e.g. for a ticket that was started without one.
+pub fn handle_worktree_create(
+    ticket_ref: Option<String>,
+    branch_name: Option<String>,
+    project_dir: Option<&str>,
+    output: &OutputFormatter,
+) -> Result<()> {
+    let project_root = find_project_root(project_dir)?;
+    let vibe_ticket_dir = project_root.join(".vibe-ticket");
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    let ticket_id = match ticket_ref {
+        Some(ticket_ref) => resolve_ticket_ref(&storage, &ticket_ref)?,
+        None => storage
+            .get_active()?
+            .ok_or(VibeTicketError::NoActiveTicket)?,
+    };
+    let ticket = storage.load(&ticket_id)?;
+
+    let config = Config::load_from_path(vibe_ticket_dir.join("config.yaml")).unwrap_or_default();
+    let branch_name =
+        branch_name.unwrap_or_else(|| format!("{}{}", config.git.branch_prefix, ticket.slug));
+    let worktree_path =
+        create_git_worktree(&project_root, &branch_name, &ticket.slug, &config, output)?;
+
+    if output.is_json() {
+        output.print_json(&serde_json::json!({
+            "status": "success",
+            "ticket": {
+                "id": ticket.id.to_string(),
+                "slug": ticket.slug,
+            },
+            "branch_name": branch_name,
+            "worktree_path": worktree_path,
+        }))?;
+    }
+    Ok(())
+}
+
 /// Handle the worktree list command
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_worktree_list(
@@ -491,6 +534,9 @@ mod tests {
                 enabled: vec![],
                 directory: ".vibe-ticket/plugins".to_string(),
             },
+            remote: crate::config::RemoteConfig::default(),
+            forge: crate::config::ForgeConfig::default(),
+            spec: crate::config::SpecConfig::default(),
         }
     }
 
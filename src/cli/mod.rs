@@ -26,6 +26,7 @@
 //! }
 //! ```
 
+pub mod args;
 mod commands;
 pub mod handlers;
 mod output;
@@ -35,7 +36,8 @@ mod utils;
 pub use commands::McpCommands;
 pub use commands::{
     AliasCommands, BulkCommands, Cli, Commands, ConfigCommands, FilterCommands, HookCommands,
-    InteractiveCommands, SpecCommands, TaskCommands, TimeCommands, WorktreeCommands,
+    InteractiveCommands, SpecCommands, SpecReviewersCommands, SyncCommands, TaskCommands,
+    TimeCommands, WorktreeCommands,
 };
-pub use output::{OutputFormatter, ProgressBar};
+pub use output::{CapturedOutput, OutputFormatter, ProgressBar};
 pub use utils::*;
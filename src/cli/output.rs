@@ -1,9 +1,11 @@
 use colored::{ColoredString, Colorize};
 use serde::Serialize;
 use std::io::Write as IoWrite;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use crate::core::{Priority, Status, Ticket};
 use crate::error::Result;
+use crate::storage::TicketSummary;
 
 /// Message level for output formatting
 #[derive(Debug, Clone, Copy)]
@@ -18,6 +20,17 @@ pub enum MessageLevel {
 #[derive(Clone)]
 pub struct OutputFormatter {
     json: bool,
+    /// Collects output instead of printing it, see [`Self::capture`]
+    captured: Option<Arc<Mutex<CapturedOutput>>>,
+}
+
+/// Output collected by a capturing formatter
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CapturedOutput {
+    /// JSON documents the command printed
+    pub json: Vec<serde_json::Value>,
+    /// Messages the command printed
+    pub messages: Vec<String>,
 }
 
 impl OutputFormatter {
@@ -27,7 +40,33 @@ impl OutputFormatter {
         if no_color {
             colored::control::set_override(false);
         }
-        Self { json }
+        Self {
+            json,
+            captured: None,
+        }
+    }
+
+    /// Creates a JSON formatter that collects output instead of printing it
+    ///
+    /// Used to run CLI handlers where stdout is not the user's terminal,
+    /// like the MCP server, whose stdio transport owns stdout.
+    #[must_use]
+    pub fn capture() -> Self {
+        Self {
+            json: true,
+            captured: Some(Arc::default()),
+        }
+    }
+
+    /// Take the output collected so far by a capturing formatter
+    #[must_use]
+    pub fn take_captured(&self) -> CapturedOutput {
+        self.captured
+            .as_ref()
+            .map(|captured| {
+                std::mem::take(&mut *captured.lock().unwrap_or_else(PoisonError::into_inner))
+            })
+            .unwrap_or_default()
     }
 
     /// Check if JSON output is enabled
@@ -92,6 +131,16 @@ impl OutputFormatter {
 
     /// Unified message printing method
     fn print_message(&self, message: &str, level: MessageLevel) {
+        if let Some(captured) = &self.captured {
+            if !message.trim().is_empty() {
+                captured
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .messages
+                    .push(message.trim().to_string());
+            }
+            return;
+        }
         if self.json {
             return;
         }
@@ -119,13 +168,33 @@ impl OutputFormatter {
         if self.json {
             self.print_json(tickets)?;
         } else {
-            Self::print_tickets_table(tickets);
+            let summaries: Vec<_> = tickets.iter().map(TicketSummary::from).collect();
+            Self::print_tickets_table(&summaries);
+        }
+        Ok(())
+    }
+
+    /// Prints ticket summaries as a table, or as JSON in JSON mode
+    pub fn print_summaries(&self, summaries: &[TicketSummary]) -> Result<()> {
+        if self.json {
+            self.print_json(summaries)?;
+        } else {
+            Self::print_tickets_table(summaries);
         }
         Ok(())
     }
 
     /// Prints data as JSON
     pub fn print_json<T: Serialize + ?Sized>(&self, data: &T) -> Result<()> {
+        if let Some(captured) = &self.captured {
+            let value = serde_json::to_value(data)?;
+            captured
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .json
+                .push(value);
+            return Ok(());
+        }
         let json = serde_json::to_string_pretty(data)?;
         println!("{json}");
         Ok(())
@@ -213,7 +282,7 @@ impl OutputFormatter {
     }
 
     /// Prints tickets in a table format
-    fn print_tickets_table(tickets: &[Ticket]) {
+    fn print_tickets_table(tickets: &[TicketSummary]) {
         if tickets.is_empty() {
             println!("No tickets found.");
             return;
@@ -232,11 +301,7 @@ impl OutputFormatter {
 
         // Rows
         for ticket in tickets {
-            let tasks = format!(
-                "{}/{}",
-                ticket.completed_tasks_count(),
-                ticket.total_tasks_count()
-            );
+            let tasks = format!("{}/{}", ticket.tasks_completed, ticket.tasks_total);
 
             println!(
                 "{:<8} {:<10} {:<10} {:<40} {}",
@@ -389,4 +454,20 @@ mod tests {
         assert_eq!(truncate("abc", 3), "abc");
         assert_eq!(truncate("abcd", 3), "...");
     }
+
+    #[test]
+    fn test_capture() {
+        let output = OutputFormatter::capture();
+        assert!(output.is_json());
+        output.success("Done");
+        output.info("");
+        output
+            .print_json(&serde_json::json!({ "count": 1 }))
+            .unwrap();
+
+        let captured = output.clone().take_captured();
+        assert_eq!(captured.messages, vec!["Done"]);
+        assert_eq!(captured.json, vec![serde_json::json!({ "count": 1 })]);
+        assert!(output.take_captured().json.is_empty());
+    }
 }
@@ -38,6 +38,19 @@ pub fn get_vibe_ticket_dir(project_root: &Path) -> PathBuf {
     project_root.join(".vibe-ticket")
 }
 
+/// Gets the user-level configuration directory
+///
+/// This is `$XDG_CONFIG_HOME/vibe-ticket`, or `~/.config/vibe-ticket` when
+/// `XDG_CONFIG_HOME` is not set. Returns `None` if no home directory is known.
+#[must_use]
+pub fn user_config_dir() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".config")))?;
+    Some(config_home.join("vibe-ticket"))
+}
+
 /// Generate a slug from a title
 ///
 /// Creates a URL-safe slug from the given title string
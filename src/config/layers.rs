@@ -0,0 +1,421 @@
+//! Layered configuration sources
+//!
+//! The effective configuration is built from these sources, each overriding
+//! the ones before it:
+//! 1. Default values
+//! 2. User configuration file (`~/.config/vibe-ticket/config.yaml`)
+//! 3. Project configuration file (`.vibe-ticket/config.yaml`)
+//! 4. Environment variables, e.g. `VIBE_TICKET_GIT__WORKTREE_PREFIX`
+//! 5. `--set key=value` command-line overrides
+//!
+//! Files may set any subset of keys. Values from environment variables and
+//! `--set` are parsed to the type of the field they set, and the origin of
+//! every value is recorded for `config show --origin`.
+
+use super::Config;
+use crate::cli::user_config_dir;
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::storage::write_project_file;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Prefix of environment variables that set configuration values
+pub const ENV_PREFIX: &str = "VIBE_TICKET";
+
+/// Overrides from the `--set` flag, applied on every load
+static OVERRIDES: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+
+/// Where a configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// Built-in default
+    Default,
+    /// User configuration file
+    UserFile(PathBuf),
+    /// Project configuration file
+    ProjectFile(PathBuf),
+    /// Environment variable
+    Env(String),
+    /// `--set` command-line flag
+    Flag,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::UserFile(path) => write!(f, "user file {}", path.display()),
+            Self::ProjectFile(path) => write!(f, "project file {}", path.display()),
+            Self::Env(var) => write!(f, "environment variable {var}"),
+            Self::Flag => write!(f, "--set flag"),
+        }
+    }
+}
+
+/// The effective configuration with the origin of each value
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    /// Effective configuration
+    pub config: Config,
+    /// Origin of each value, by dotted key
+    pub origins: BTreeMap<String, Origin>,
+}
+
+/// Set the `--set key=value` overrides applied when loading configuration
+///
+/// # Errors
+///
+/// Returns an error if an override is not of the form `key=value`.
+pub fn set_overrides(overrides: &[String]) -> Result<()> {
+    let parsed = overrides
+        .iter()
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+                .ok_or_else(|| {
+                    VibeTicketError::InvalidInput(format!(
+                        "Invalid --set '{entry}'. Use key=value, like ui.emoji=false"
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    *OVERRIDES
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = parsed;
+    Ok(())
+}
+
+/// Path of the user configuration file
+#[must_use]
+pub fn user_config_path() -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join("config.yaml"))
+}
+
+/// Load the effective configuration
+///
+/// `project_file` is the project configuration file; it must exist when given.
+pub(super) fn load(project_file: Option<&Path>) -> Result<LayeredConfig> {
+    load_with_env(project_file, None)
+}
+
+/// Load the effective configuration, reading environment variables from
+/// `env` instead of the process environment when given
+pub(super) fn load_with_env(
+    project_file: Option<&Path>,
+    env: Option<config::Map<String, String>>,
+) -> Result<LayeredConfig> {
+    let mut tree = serde_json::to_value(Config::default())?;
+    let mut origins = BTreeMap::new();
+    for_each_leaf(&tree, "", &mut |key, _| {
+        origins.insert(key.to_string(), Origin::Default);
+    });
+
+    if let Some(path) = user_config_path().filter(|path| path.exists()) {
+        let layer = read_file(&path)?;
+        merge(&mut tree, layer, &Origin::UserFile(path), "", &mut origins);
+    }
+    if let Some(path) = project_file {
+        let layer = read_file(path)?;
+        let origin = Origin::ProjectFile(path.to_path_buf());
+        merge(&mut tree, layer, &origin, "", &mut origins);
+    }
+
+    let env = config::Environment::with_prefix(ENV_PREFIX)
+        .prefix_separator("_")
+        .separator("__")
+        .source(env);
+    let vars = config::Source::collect(&env)
+        .map_err(|e| VibeTicketError::custom(format!("Failed to read environment: {e}")))?;
+    let mut vars: Vec<_> = vars.into_iter().collect();
+    vars.sort_by(|a, b| a.0.cmp(&b.0));
+    for (key, value) in vars {
+        // Other VIBE_TICKET_* variables, e.g. for hooks, are not settings
+        if lookup(&tree, &key).is_none_or(Value::is_object) {
+            continue;
+        }
+        let var = format!("{ENV_PREFIX}_{}", key.to_uppercase().replace('.', "__"));
+        let value = value
+            .into_string()
+            .map_err(|e| VibeTicketError::InvalidInput(format!("{var}: {e}")))?;
+        set_value(&mut tree, &key, &value)
+            .map_err(|e| VibeTicketError::InvalidInput(format!("{var}: {e}")))?;
+        origins.insert(key, Origin::Env(var));
+    }
+
+    let overrides = OVERRIDES
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    for (key, value) in overrides {
+        set_value(&mut tree, &key, &value)
+            .map_err(|e| VibeTicketError::InvalidInput(format!("--set {key}: {e}")))?;
+        origins.insert(key, Origin::Flag);
+    }
+
+    let config = serde_json::from_value(tree)
+        .map_err(|e| VibeTicketError::InvalidInput(format!("Invalid configuration: {e}")))?;
+    Ok(LayeredConfig { config, origins })
+}
+
+/// Set a value in a configuration file, creating the file if needed
+///
+/// Only the file is changed; values from other sources are not written to
+/// it. Returns the value as stored.
+///
+/// # Errors
+///
+/// Returns an error if the key does not exist or the value does not fit
+/// its type.
+pub(super) fn set_in_file(path: &Path, key: &str, raw: &str) -> Result<Value> {
+    let mut file: serde_yaml::Value = if path.exists() {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config from {}", path.display()))?;
+        serde_yaml::from_str(&content).context("Failed to parse configuration")?
+    } else {
+        serde_yaml::Value::Mapping(serde_yaml::Mapping::new())
+    };
+
+    // Validate against the defaults overridden by this file
+    let mut tree = serde_json::to_value(Config::default())?;
+    merge(
+        &mut tree,
+        serde_json::to_value(&file)?,
+        &Origin::Default,
+        "",
+        &mut BTreeMap::new(),
+    );
+    set_value(&mut tree, key, raw)?;
+    let value = lookup(&tree, key).cloned().unwrap_or(Value::Null);
+
+    let mut current = &mut file;
+    for part in key.split('.') {
+        if !current.is_mapping() {
+            *current = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+        }
+        let serde_yaml::Value::Mapping(map) = current else {
+            unreachable!("replaced by a mapping above");
+        };
+        current = map
+            .entry(serde_yaml::Value::String(part.to_string()))
+            .or_insert(serde_yaml::Value::Null);
+    }
+    *current = serde_yaml::to_value(&value)?;
+
+    let yaml = serde_yaml::to_string(&file).context("Failed to serialize configuration")?;
+    write_project_file(path, yaml)?;
+    Ok(value)
+}
+
+/// Set the value at `key` in a full configuration tree
+///
+/// `raw` is parsed to the type of the field: as YAML first (so `true`, `20`
+/// and `[a, b]` work), then as a plain string, then for lists as
+/// comma-separated strings. The first candidate the configuration accepts
+/// wins.
+pub(super) fn set_value(tree: &mut Value, key: &str, raw: &str) -> Result<()> {
+    let current = lookup(tree, key).ok_or_else(|| {
+        VibeTicketError::InvalidInput(format!("Configuration key '{key}' doesn't exist"))
+    })?;
+    if current.is_object() {
+        return Err(VibeTicketError::InvalidInput(format!(
+            "'{key}' is a section; set one of its keys instead"
+        )));
+    }
+
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = serde_yaml::from_str::<Value>(raw) {
+        if parsed.as_str() != Some(raw) {
+            candidates.push(parsed);
+        }
+    }
+    candidates.push(Value::String(raw.to_string()));
+    if current.is_array() {
+        candidates.push(Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        ));
+    }
+
+    let mut first_error = None;
+    for candidate in candidates {
+        let mut updated = tree.clone();
+        if let Some(slot) = lookup_mut(&mut updated, key) {
+            *slot = candidate;
+        }
+        match serde_json::from_value::<Config>(updated.clone()) {
+            Ok(config) => {
+                config.validate_key(key)?;
+                *tree = updated;
+                return Ok(());
+            },
+            Err(e) => {
+                first_error.get_or_insert(e);
+            },
+        }
+    }
+    Err(VibeTicketError::InvalidInput(format!(
+        "Invalid value '{raw}' for {key}: {}",
+        first_error.map_or_else(String::new, |e| e.to_string())
+    )))
+}
+
+/// Look up a dotted key in a configuration tree
+pub(super) fn lookup<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(tree, |value, part| value.get(part))
+}
+
+fn lookup_mut<'a>(tree: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    key.split('.')
+        .try_fold(tree, |value, part| value.get_mut(part))
+}
+
+fn read_file(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config from {}", path.display()))?;
+    let layer: Value = serde_yaml::from_str(&content).context("Failed to parse configuration")?;
+    Ok(layer)
+}
+
+/// Merge `layer` into `tree`, recording `origin` for every value it sets
+fn merge(
+    tree: &mut Value,
+    layer: Value,
+    origin: &Origin,
+    prefix: &str,
+    origins: &mut BTreeMap<String, Origin>,
+) {
+    match (tree, layer) {
+        (Value::Object(base), Value::Object(layer)) => {
+            for (name, value) in layer {
+                let key = join_key(prefix, &name);
+                let slot = base.entry(name).or_insert(Value::Null);
+                if slot.is_object() && value.is_object() {
+                    merge(slot, value, origin, &key, origins);
+                } else {
+                    for_each_leaf(&value, &key, &mut |leaf, _| {
+                        origins.insert(leaf.to_string(), origin.clone());
+                    });
+                    *slot = value;
+                }
+            }
+        },
+        // An empty file parses as null
+        (_, Value::Null) if prefix.is_empty() => {},
+        (tree, layer) => *tree = layer,
+    }
+}
+
+/// Call `f` with the dotted key and value of every leaf below `value`
+///
+/// Lists count as leaves.
+pub fn for_each_leaf(value: &Value, prefix: &str, f: &mut impl FnMut(&str, &Value)) {
+    match value {
+        Value::Object(map) if !map.is_empty() || prefix.is_empty() => {
+            for (name, value) in map {
+                for_each_leaf(value, &join_key(prefix, name), f);
+            }
+        },
+        _ => f(prefix, value),
+    }
+}
+
+fn join_key(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn env(vars: &[(&str, &str)]) -> Option<config::Map<String, String>> {
+        Some(
+            vars.iter()
+                .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_set_value_parses_to_field_type() {
+        let mut tree = serde_json::to_value(Config::default()).unwrap();
+
+        set_value(&mut tree, "ui.page_size", "50").unwrap();
+        set_value(&mut tree, "git.worktree_enabled", "false").unwrap();
+        set_value(&mut tree, "project.name", "123").unwrap();
+        set_value(&mut tree, "forge.reviewers", "alice, bob").unwrap();
+        set_value(&mut tree, "project.description", "null").unwrap();
+        let config: Config = serde_json::from_value(tree.clone()).unwrap();
+        assert_eq!(config.ui.page_size, 50);
+        assert!(!config.git.worktree_enabled);
+        assert_eq!(config.project.name, "123");
+        assert_eq!(config.forge.reviewers, vec!["alice", "bob"]);
+        assert_eq!(config.project.description, None);
+
+        assert!(set_value(&mut tree, "ui.page_size", "many").is_err());
+        assert!(set_value(&mut tree, "ui.emoji", "sometimes").is_err());
+        assert!(set_value(&mut tree, "ui.theme", "neon").is_err());
+        assert!(set_value(&mut tree, "ui", "x").is_err());
+        assert!(set_value(&mut tree, "ui.colour", "x").is_err());
+    }
+
+    #[test]
+    fn test_layers_and_origins() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        std::fs::write(&path, "git:\n  branch_prefix: feature/\n").unwrap();
+
+        let layered = load_with_env(
+            Some(&path),
+            env(&[
+                ("VIBE_TICKET_UI__PAGE_SIZE", "5"),
+                ("VIBE_TICKET_DOCUMENT", "not a setting"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(layered.config.git.branch_prefix, "feature/");
+        assert_eq!(layered.config.ui.page_size, 5);
+        assert!(layered.config.git.enabled);
+        assert_eq!(
+            layered.origins["git.branch_prefix"],
+            Origin::ProjectFile(path.clone())
+        );
+        assert_eq!(
+            layered.origins["ui.page_size"],
+            Origin::Env("VIBE_TICKET_UI__PAGE_SIZE".to_string())
+        );
+        assert_eq!(layered.origins["git.enabled"], Origin::Default);
+
+        let error = load_with_env(Some(&path), env(&[("VIBE_TICKET_UI__EMOJI", "maybe")]))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("VIBE_TICKET_UI__EMOJI"), "{error}");
+    }
+
+    #[test]
+    fn test_set_in_file_keeps_other_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.yaml");
+        std::fs::write(&path, "project:\n  name: Demo\n").unwrap();
+
+        let value = set_in_file(&path, "git.worktree_prefix", "../wt-").unwrap();
+        assert_eq!(value, "../wt-");
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "project:\n  name: Demo\ngit:\n  worktree_prefix: ../wt-\n"
+        );
+        assert!(set_in_file(&path, "git.worktree_enabled", "yes please").is_err());
+    }
+}
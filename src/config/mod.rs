@@ -6,20 +6,22 @@
 //! # Configuration Sources
 //!
 //! Configuration is loaded in the following priority order (highest to lowest):
-//! 1. Command-line arguments
-//! 2. Environment variables (prefixed with `VIDE_TICKET_`)
-//! 3. User configuration file (`~/.config/vibe-ticket/config.toml`)
-//! 4. Project configuration file (`.vibe-ticket.toml` in project root)
+//! 1. Command-line overrides (`--set key=value`)
+//! 2. Environment variables (prefixed with `VIBE_TICKET_`, with `__` between
+//!    path segments, e.g. `VIBE_TICKET_UI__PAGE_SIZE`)
+//! 3. Project configuration file (`.vibe-ticket/config.yaml`)
+//! 4. User configuration file (`~/.config/vibe-ticket/config.yaml`)
 //! 5. Default values
 //!
+//! See [`layers`] for how the sources are combined.
+//!
 //! # Configuration Structure
 //!
 //! The configuration includes:
-//! - Database connection settings
-//! - API server settings (when API feature is enabled)
-//! - Plugin configuration
+//! - Project defaults for new tickets
 //! - UI preferences
-//! - Default ticket fields and workflows
+//! - Git and worktree integration
+//! - Plugins, remote trackers, forges and spec generation
 //!
 //! # Example
 //!
@@ -40,20 +42,20 @@
 //!
 //! # File Format
 //!
-//! Configuration files use TOML format:
-//! ```toml
-//! [database]
-//! url = "sqlite://tickets.db"
-//!
-//! [api]
-//! port = 8080
-//! host = "127.0.0.1"
-//!
-//! [ui]
-//! theme = "dark"
+//! Configuration files use YAML format and may set any subset of keys:
+//! ```yaml
+//! ui:
+//!   theme: dark
+//! git:
+//!   worktree_prefix: ../{project}-
 //! ```
 
+pub mod layers;
+
+pub use layers::{LayeredConfig, Origin, set_overrides, user_config_path};
+
 use crate::error::{ErrorContext, Result};
+use crate::storage::write_project_file;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -71,6 +73,18 @@ pub struct Config {
 
     /// Plugin configuration
     pub plugins: PluginsConfig,
+
+    /// Remote issue tracker settings
+    #[serde(default)]
+    pub remote: RemoteConfig,
+
+    /// Pull request creation settings
+    #[serde(default)]
+    pub forge: ForgeConfig,
+
+    /// Spec document generation settings
+    #[serde(default)]
+    pub spec: SpecConfig,
 }
 
 /// Project-specific configuration
@@ -144,6 +158,94 @@ pub struct PluginsConfig {
     pub directory: String,
 }
 
+/// Remote issue tracker configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteConfig {
+    /// GitHub issues sync settings
+    #[serde(default)]
+    pub github: GitHubRemoteConfig,
+}
+
+/// GitHub issues sync configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRemoteConfig {
+    /// Repository in `owner/repo` form
+    pub repository: Option<String>,
+
+    /// REST API base URL (change for GitHub Enterprise Server)
+    pub api_url: String,
+
+    /// Environment variable holding the API token
+    pub token_env: String,
+}
+
+impl Default for GitHubRemoteConfig {
+    fn default() -> Self {
+        Self {
+            repository: None,
+            api_url: crate::remote::github::DEFAULT_API_URL.to_string(),
+            token_env: "GITHUB_TOKEN".to_string(),
+        }
+    }
+}
+
+/// Forge configuration used when opening pull requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForgeConfig {
+    /// Forge flavour: `github`, `gitlab` or `gitea`
+    pub kind: crate::forge::ForgeKind,
+
+    /// REST API base URL (defaults to the public instance of the forge)
+    pub api_url: Option<String>,
+
+    /// Repository path (defaults to the path of the `origin` remote)
+    pub repository: Option<String>,
+
+    /// Environment variable holding the API token (defaults per forge)
+    pub token_env: Option<String>,
+
+    /// Branch pull requests target
+    pub base_branch: String,
+
+    /// Open pull requests as drafts by default
+    pub draft: bool,
+
+    /// Usernames to request reviews from
+    pub reviewers: Vec<String>,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            kind: crate::forge::ForgeKind::default(),
+            api_url: None,
+            repository: None,
+            token_env: None,
+            base_branch: "main".to_string(),
+            draft: false,
+            reviewers: vec![],
+        }
+    }
+}
+
+/// Spec document generation configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpecConfig {
+    /// Generator writing spec documents: `builtin`, `template` or `command`
+    pub generator: crate::specs::generator::GeneratorKind,
+
+    /// Command run by the `command` generator
+    pub command: Option<String>,
+
+    /// Ambiguity linter rules
+    pub lint: crate::specs::LintConfig,
+
+    /// Approval quorum, required reviewers and phase ordering
+    pub review: crate::specs::ReviewConfig,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -173,6 +275,9 @@ impl Default for Config {
                 enabled: vec![],
                 directory: ".vibe-ticket/plugins".to_string(),
             },
+            remote: RemoteConfig::default(),
+            forge: ForgeConfig::default(),
+            spec: SpecConfig::default(),
         }
     }
 }
@@ -186,15 +291,57 @@ impl Config {
     }
 
     /// Load configuration from a specific path
+    ///
+    /// `path` is the project configuration file. It must exist, and is
+    /// layered with the other configuration sources.
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config from {}", path.display()))?;
+        Ok(Self::load_layered(path)?.config)
+    }
+
+    /// Load configuration from a project configuration file and all other
+    /// sources, with the origin of each value
+    pub fn load_layered<P: AsRef<Path>>(path: P) -> Result<LayeredConfig> {
+        layers::load(Some(path.as_ref()))
+    }
+
+    /// Set a value in a configuration file by dotted key
+    ///
+    /// The value is parsed to the type of the field. Returns the value as
+    /// stored.
+    pub fn set_in_file<P: AsRef<Path>>(
+        path: P,
+        key: &str,
+        value: &str,
+    ) -> Result<serde_json::Value> {
+        layers::set_in_file(path.as_ref(), key, value)
+    }
 
-        let config: Self =
-            serde_yaml::from_str(&content).context("Failed to parse configuration")?;
+    /// Get a configuration value by dotted key
+    pub fn get(&self, key: &str) -> Result<serde_json::Value> {
+        let tree = serde_json::to_value(self)?;
+        layers::lookup(&tree, key).cloned().ok_or_else(|| {
+            crate::error::VibeTicketError::InvalidInput(format!(
+                "Configuration key '{key}' not found"
+            ))
+        })
+    }
 
-        Ok(config)
+    /// Check constraints on the value of `key` that its type doesn't express
+    fn validate_key(&self, key: &str) -> Result<()> {
+        let allowed: &[&str] = match key {
+            "project.default_priority" => &["low", "medium", "high", "critical"],
+            "ui.theme" => &["light", "dark", "auto"],
+            _ => return Ok(()),
+        };
+        let value = self.get(key)?;
+        if value.as_str().is_some_and(|value| allowed.contains(&value)) {
+            Ok(())
+        } else {
+            Err(crate::error::VibeTicketError::InvalidInput(format!(
+                "Invalid value for {key}. Must be one of: {}",
+                allowed.join(", ")
+            )))
+        }
     }
 
     /// Load configuration or return default if not found
@@ -220,8 +367,7 @@ impl Config {
         let path = path.as_ref();
         let yaml = serde_yaml::to_string(self).context("Failed to serialize configuration")?;
 
-        std::fs::write(path, yaml)
-            .with_context(|| format!("Failed to write config to {}", path.display()))?;
+        write_project_file(path, yaml)?;
 
         Ok(())
     }
@@ -146,6 +146,7 @@ impl TicketBuilder {
             created_at: self.created_at.unwrap_or_else(Utc::now),
             started_at: self.started_at,
             closed_at: self.closed_at,
+            updated_at: None,
             assignee: self.assignee,
             tasks: self.tasks,
             metadata: self.metadata,
@@ -41,6 +41,10 @@ pub struct Ticket {
     /// Timestamp when the ticket was closed
     pub closed_at: Option<DateTime<Utc>>,
 
+    /// Timestamp when the ticket was last saved, used to settle merge conflicts
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+
     /// Username of the person assigned to the ticket
     pub assignee: Option<String>,
 
@@ -78,6 +82,25 @@ impl Ticket {
         self.closed_at = Some(Utc::now());
     }
 
+    /// Changes the status of the ticket, updating lifecycle timestamps
+    ///
+    /// Moving into doing or review records the start time unless the ticket
+    /// was started before, moving into done records the close time, and
+    /// reopening a done ticket clears it.
+    pub fn set_status(&mut self, status: Status) {
+        match (self.status, status) {
+            (_, Status::Doing | Status::Review) => {
+                self.started_at.get_or_insert_with(Utc::now);
+            },
+            (old, Status::Done) if old != Status::Done => self.closed_at = Some(Utc::now()),
+            _ => {},
+        }
+        if status != Status::Done {
+            self.closed_at = None;
+        }
+        self.status = status;
+    }
+
     /// Adds a task to the ticket
     pub fn add_task(&mut self, title: impl Into<String>) -> TaskId {
         let task = Task::new(title);
@@ -167,6 +190,27 @@ mod tests {
         assert!(ticket.closed_at.is_some());
     }
 
+    #[test]
+    fn test_set_status() {
+        let mut ticket = Ticket::new("test", "Test");
+        ticket.set_status(Status::Blocked);
+        assert!(ticket.started_at.is_none());
+        ticket.set_status(Status::Todo);
+        ticket.set_status(Status::Doing);
+        assert!(ticket.started_at.is_some());
+        assert!(ticket.closed_at.is_none());
+        ticket.set_status(Status::Done);
+        assert_eq!(ticket.status, Status::Done);
+        assert!(ticket.closed_at.is_some());
+
+        // Reopening keeps the original start time
+        let started_at = ticket.started_at;
+        ticket.set_status(Status::Todo);
+        assert!(ticket.closed_at.is_none());
+        ticket.set_status(Status::Doing);
+        assert_eq!(ticket.started_at, started_at);
+    }
+
     #[test]
     fn test_task_management() {
         let mut ticket = Ticket::new("test", "Test");
@@ -48,6 +48,12 @@ pub enum VibeTicketError {
     #[error("Project not initialized. Run 'vibe-ticket init' first")]
     ProjectNotInitialized,
 
+    /// Project written by a newer version of vibe-ticket
+    #[error(
+        "This project uses format version {found}, but this version of vibe-ticket only supports up to {supported}"
+    )]
+    UnsupportedFormat { found: u32, supported: u32 },
+
     /// Template not found
     #[error("Template not found: {0}")]
     TemplateNotFound(String),
@@ -150,7 +156,10 @@ impl VibeTicketError {
     pub const fn is_config_error(&self) -> bool {
         matches!(
             self,
-            Self::Config(_) | Self::ProjectNotInitialized | Self::ProjectAlreadyInitialized { .. }
+            Self::Config(_)
+                | Self::ProjectNotInitialized
+                | Self::ProjectAlreadyInitialized { .. }
+                | Self::UnsupportedFormat { .. }
         )
     }
 
@@ -206,6 +215,10 @@ impl VibeTicketError {
                 "Run 'vibe-ticket init' to initialize the project".to_string(),
                 "Make sure you're in the correct directory".to_string(),
             ],
+            Self::UnsupportedFormat { .. } => vec![
+                "Upgrade vibe-ticket to the version the rest of the team uses".to_string(),
+                "Run 'vibe-ticket --version' to see the installed version".to_string(),
+            ],
             Self::NoActiveTicket => vec![
                 "Run 'vibe-ticket list' to see available tickets".to_string(),
                 "Run 'vibe-ticket start <id>' to start working on a ticket".to_string(),
@@ -1,4 +1,11 @@
 //! Event system for CLI-MCP synchronization
+//!
+//! Ticket events come from CLI operations and from [`watcher::TicketWatcher`],
+//! which notices ticket files changed by other processes.
+
+pub mod watcher;
+
+pub use watcher::{TicketChange, TicketWatcher};
 
 use crate::core::{Status, Task, Ticket, TicketId};
 use crate::error::Result;
@@ -24,6 +31,25 @@ pub enum TicketEvent {
     TaskRemoved(TicketId, String), // ticket id, task id
     /// Ticket status changed
     StatusChanged(TicketId, Status, Status), // id, old status, new status
+    /// A ticket file was removed (deleted or archived)
+    Removed(TicketId),
+}
+
+impl TicketEvent {
+    /// Name of the event, e.g. `status_changed`
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Created(_) => "created",
+            Self::Updated(_) => "updated",
+            Self::Closed(..) => "closed",
+            Self::TaskAdded(..) => "task_added",
+            Self::TaskCompleted(..) => "task_completed",
+            Self::TaskRemoved(..) => "task_removed",
+            Self::StatusChanged(..) => "status_changed",
+            Self::Removed(_) => "removed",
+        }
+    }
 }
 
 /// Trait for handling ticket events
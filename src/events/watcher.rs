@@ -0,0 +1,294 @@
+//! Filesystem watcher turning ticket file changes into events
+//!
+//! [`TicketWatcher`] watches the tickets directory of a [`FileStorage`] with
+//! the platform's change notifications (inotify on Linux). When any process
+//! writes, renames or removes a ticket file, the watcher drops that ticket
+//! from the storage's cache and compares the file with its last known
+//! contents to produce [`TicketEvent`]s.
+
+use super::TicketEvent;
+use crate::cache::TicketCache;
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::FileStorage;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// How long to wait for more notifications after one arrives, so a write
+/// reported as several notifications is read once
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// A change to a ticket file
+#[derive(Debug, Clone)]
+pub struct TicketChange {
+    /// What changed
+    pub event: TicketEvent,
+    /// The ticket after the change, or its last known state if it was removed
+    pub ticket: Ticket,
+}
+
+/// Watches ticket files for changes made by any process
+pub struct TicketWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: mpsc::Receiver<notify::Result<notify::Event>>,
+    tickets_dir: PathBuf,
+    cache: Arc<TicketCache>,
+    known: HashMap<PathBuf, Ticket>,
+}
+
+impl TicketWatcher {
+    /// Start watching the tickets of `storage`
+    ///
+    /// Changes invalidate the matching entries of the storage's cache.
+    pub fn new(storage: &FileStorage) -> Result<Self> {
+        let tickets_dir = storage.tickets_dir();
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+        watcher
+            .watch(&tickets_dir, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        let known = std::fs::read_dir(&tickets_dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter_map(|path| read_ticket(&path).map(|ticket| (path, ticket)))
+            .collect();
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            tickets_dir,
+            cache: Arc::clone(&storage.cache),
+            known,
+        })
+    }
+
+    /// Wait up to `timeout` for ticket files to change
+    ///
+    /// Returns no changes if nothing changed in time.
+    pub fn wait(&mut self, timeout: Duration) -> Result<Vec<TicketChange>> {
+        let mut paths = BTreeSet::new();
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => self.collect(event, &mut paths)?,
+            Err(RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(VibeTicketError::custom("File watcher stopped"));
+            },
+        }
+        while let Ok(event) = self.receiver.recv_timeout(DEBOUNCE) {
+            self.collect(event, &mut paths)?;
+        }
+        Ok(paths.iter().flat_map(|path| self.reload(path)).collect())
+    }
+
+    /// Changes since the last call, without waiting
+    pub fn poll(&mut self) -> Result<Vec<TicketChange>> {
+        self.wait(Duration::ZERO)
+    }
+
+    /// Emit changes into the global event bus until the bus is dropped
+    ///
+    /// The watcher runs on its own thread; events are emitted from a task on
+    /// the current Tokio runtime.
+    #[cfg(feature = "mcp")]
+    #[must_use]
+    pub fn forward_to_event_bus(mut self) -> tokio::task::JoinHandle<()> {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        std::thread::spawn(move || {
+            while !sender.is_closed() {
+                match self.wait(Duration::from_secs(1)) {
+                    Ok(changes) => {
+                        for change in changes {
+                            let _ = sender.send(change.event);
+                        }
+                    },
+                    Err(error) => {
+                        tracing::warn!("Stopped watching ticket files: {error}");
+                        return;
+                    },
+                }
+            }
+        });
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if let Err(error) = super::emit_event(event).await {
+                    tracing::warn!("Failed to emit ticket event: {error}");
+                }
+            }
+        })
+    }
+
+    /// Add the ticket files named by a notification to `paths`
+    fn collect(
+        &self,
+        event: notify::Result<notify::Event>,
+        paths: &mut BTreeSet<PathBuf>,
+    ) -> Result<()> {
+        let event = event.map_err(watch_error)?;
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(());
+        }
+        paths.extend(event.paths.into_iter().filter(|path| {
+            path.parent() == Some(self.tickets_dir.as_path())
+                && path.extension().is_some_and(|ext| ext == "yaml")
+        }));
+        Ok(())
+    }
+
+    /// Read a changed ticket file and compare it with its last known state
+    fn reload(&mut self, path: &Path) -> Vec<TicketChange> {
+        let previous = self.known.get(path);
+        let current = read_ticket(path);
+        let id = path
+            .file_stem()
+            .and_then(|stem| TicketId::parse_str(&stem.to_string_lossy()).ok())
+            .or_else(|| {
+                previous
+                    .or(current.as_ref())
+                    .map(|ticket| ticket.id.clone())
+            });
+        if let Some(id) = id {
+            self.cache.invalidate_ticket(&id);
+        }
+
+        match (previous, current) {
+            // A partial write; the rest of it is notified separately
+            (_, None) if path.exists() => Vec::new(),
+            (None, None) => Vec::new(),
+            (Some(_), None) => self
+                .known
+                .remove(path)
+                .map(|ticket| TicketChange {
+                    event: TicketEvent::Removed(ticket.id.clone()),
+                    ticket,
+                })
+                .into_iter()
+                .collect(),
+            (None, Some(ticket)) => {
+                self.known.insert(path.to_path_buf(), ticket.clone());
+                vec![TicketChange {
+                    event: TicketEvent::Created(ticket.clone()),
+                    ticket,
+                }]
+            },
+            (Some(previous), Some(ticket)) if *previous == ticket => Vec::new(),
+            (Some(previous), Some(ticket)) => {
+                let events = diff(previous, &ticket);
+                self.known.insert(path.to_path_buf(), ticket.clone());
+                events
+                    .into_iter()
+                    .map(|event| TicketChange {
+                        event,
+                        ticket: ticket.clone(),
+                    })
+                    .collect()
+            },
+        }
+    }
+}
+
+/// Events describing how `previous` became `ticket`
+fn diff(previous: &Ticket, ticket: &Ticket) -> Vec<TicketEvent> {
+    let id = &ticket.id;
+    let mut events = vec![TicketEvent::Updated(ticket.clone())];
+    if previous.status != ticket.status {
+        events.push(TicketEvent::StatusChanged(
+            id.clone(),
+            previous.status,
+            ticket.status,
+        ));
+        if ticket.status == Status::Done {
+            let message = ticket
+                .metadata
+                .get("close_message")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            events.push(TicketEvent::Closed(id.clone(), message.to_string()));
+        }
+    }
+    for task in &ticket.tasks {
+        match previous.tasks.iter().find(|old| old.id == task.id) {
+            None => events.push(TicketEvent::TaskAdded(id.clone(), task.clone())),
+            Some(old) if task.completed && !old.completed => {
+                events.push(TicketEvent::TaskCompleted(id.clone(), task.id.to_string()));
+            },
+            Some(_) => {},
+        }
+    }
+    for task in &previous.tasks {
+        if !ticket.tasks.iter().any(|new| new.id == task.id) {
+            events.push(TicketEvent::TaskRemoved(id.clone(), task.id.to_string()));
+        }
+    }
+    events
+}
+
+fn read_ticket(path: &Path) -> Option<Ticket> {
+    if path.extension().is_none_or(|ext| ext != "yaml") {
+        return None;
+    }
+    let yaml = std::fs::read_to_string(path).ok()?;
+    serde_yaml::from_str(&yaml).ok()
+}
+
+fn watch_error(error: notify::Error) -> VibeTicketError {
+    VibeTicketError::custom(format!("Failed to watch ticket files: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    /// Wait until the watcher reports changes
+    fn next_changes(watcher: &mut TicketWatcher) -> Vec<TicketChange> {
+        watcher.wait(Duration::from_secs(5)).unwrap()
+    }
+
+    fn names(changes: &[TicketChange]) -> Vec<&'static str> {
+        changes.iter().map(|change| change.event.name()).collect()
+    }
+
+    #[test]
+    fn test_watcher_reports_changes_and_invalidates_cache() {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+        storage.ensure_directories().unwrap();
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.add_task("Reproduce");
+        storage.save(&ticket).unwrap();
+
+        let mut watcher = TicketWatcher::new(&storage).unwrap();
+        assert!(watcher.poll().unwrap().is_empty());
+        assert_eq!(storage.load_all().unwrap()[0].status, Status::Todo);
+
+        // Another process closes the ticket and completes its task
+        let other = FileStorage::new(dir.path());
+        ticket.close();
+        let task_id = ticket.tasks[0].id.clone();
+        ticket.complete_task(&task_id).unwrap();
+        other.save(&ticket).unwrap();
+
+        let changes = next_changes(&mut watcher);
+        assert_eq!(
+            names(&changes),
+            ["updated", "status_changed", "closed", "task_completed"]
+        );
+        assert_eq!(changes[0].ticket.slug, "fix-login");
+        // The cached list was dropped, so the change is visible
+        assert_eq!(storage.load_all().unwrap()[0].status, Status::Done);
+
+        let created = Ticket::new("write-docs", "Write docs");
+        other.save(&created).unwrap();
+        assert_eq!(names(&next_changes(&mut watcher)), ["created"]);
+
+        other.delete(&created.id).unwrap();
+        let changes = next_changes(&mut watcher);
+        assert_eq!(names(&changes), ["removed"]);
+        assert_eq!(changes[0].ticket.slug, "write-docs");
+    }
+}
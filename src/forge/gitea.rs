@@ -0,0 +1,77 @@
+//! Gitea and Forgejo pull requests
+
+use super::github::ApiPullRequest;
+use super::{Forge, Http, PullRequest, PullRequestDraft};
+use crate::error::Result;
+
+/// Title prefix Gitea uses to mark work-in-progress pull requests
+const DRAFT_PREFIX: &str = "WIP: ";
+
+/// Pull requests of a Gitea repository
+pub struct GiteaForge {
+    http: Http,
+    repository: String,
+}
+
+impl GiteaForge {
+    /// Create a forge for `owner/repo` at the given API base URL (ending in `/api/v1`)
+    #[must_use]
+    pub fn new(api_url: &str, repository: &str, token: Option<String>) -> Self {
+        Self {
+            http: Http::new(
+                "Gitea",
+                api_url,
+                token.map(|token| ("Authorization", format!("token {token}"))),
+            ),
+            repository: repository.trim_matches('/').to_string(),
+        }
+    }
+}
+
+impl Forge for GiteaForge {
+    fn name(&self) -> &'static str {
+        "gitea"
+    }
+
+    fn create_pull_request(&self, draft: &PullRequestDraft) -> Result<PullRequest> {
+        // Gitea has no draft flag; drafts are pull requests with a WIP title
+        let title = if draft.draft {
+            format!("{DRAFT_PREFIX}{}", draft.title)
+        } else {
+            draft.title.clone()
+        };
+        let created: ApiPullRequest = self.http.send(
+            self.http
+                .request("POST", &format!("/repos/{}/pulls", self.repository)),
+            Some(serde_json::json!({
+                "title": title,
+                "body": draft.body,
+                "head": draft.head,
+                "base": draft.base,
+            })),
+        )?;
+
+        let mut warnings = Vec::new();
+        if !draft.reviewers.is_empty() {
+            let requested: Result<serde_json::Value> = self.http.send(
+                self.http.request(
+                    "POST",
+                    &format!(
+                        "/repos/{}/pulls/{}/requested_reviewers",
+                        self.repository, created.number
+                    ),
+                ),
+                Some(serde_json::json!({ "reviewers": draft.reviewers })),
+            );
+            if let Err(e) = requested {
+                warnings.push(format!("Could not request reviews: {e}"));
+            }
+        }
+
+        Ok(PullRequest {
+            number: created.number,
+            url: created.html_url,
+            warnings,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! GitHub pull requests
+
+use super::{Forge, Http, PullRequest, PullRequestDraft};
+use crate::error::Result;
+use serde::Deserialize;
+
+/// Pull requests of a GitHub repository
+pub struct GitHubForge {
+    http: Http,
+    repository: String,
+}
+
+impl GitHubForge {
+    /// Create a forge for `owner/repo` at the given API base URL
+    #[must_use]
+    pub fn new(api_url: &str, repository: &str, token: Option<String>) -> Self {
+        Self {
+            http: Http::new(
+                "GitHub",
+                api_url,
+                token.map(|token| ("Authorization", format!("Bearer {token}"))),
+            ),
+            repository: repository.trim_matches('/').to_string(),
+        }
+    }
+}
+
+impl Forge for GitHubForge {
+    fn name(&self) -> &'static str {
+        "github"
+    }
+
+    fn create_pull_request(&self, draft: &PullRequestDraft) -> Result<PullRequest> {
+        let created: ApiPullRequest = self.http.send(
+            self.http
+                .request("POST", &format!("/repos/{}/pulls", self.repository)),
+            Some(serde_json::json!({
+                "title": draft.title,
+                "body": draft.body,
+                "head": draft.head,
+                "base": draft.base,
+                "draft": draft.draft,
+            })),
+        )?;
+
+        let mut warnings = Vec::new();
+        if !draft.reviewers.is_empty() {
+            let requested: Result<serde_json::Value> = self.http.send(
+                self.http.request(
+                    "POST",
+                    &format!(
+                        "/repos/{}/pulls/{}/requested_reviewers",
+                        self.repository, created.number
+                    ),
+                ),
+                Some(serde_json::json!({ "reviewers": draft.reviewers })),
+            );
+            if let Err(e) = requested {
+                warnings.push(format!("Could not request reviews: {e}"));
+            }
+        }
+
+        Ok(PullRequest {
+            number: created.number,
+            url: created.html_url,
+            warnings,
+        })
+    }
+}
+
+/// Pull request as returned by the REST API
+#[derive(Deserialize)]
+pub(super) struct ApiPullRequest {
+    pub(super) number: u64,
+    pub(super) html_url: String,
+}
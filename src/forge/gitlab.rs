@@ -0,0 +1,99 @@
+//! GitLab merge requests
+
+use super::{Forge, Http, PullRequest, PullRequestDraft};
+use crate::error::{Result, VibeTicketError};
+use serde::Deserialize;
+
+/// Title prefix GitLab uses to mark draft merge requests
+const DRAFT_PREFIX: &str = "Draft: ";
+
+/// Merge requests of a GitLab project
+pub struct GitLabForge {
+    http: Http,
+    project: String,
+}
+
+impl GitLabForge {
+    /// Create a forge for the project at `group/project` (subgroups allowed)
+    /// at the given API base URL (ending in `/api/v4`)
+    #[must_use]
+    pub fn new(api_url: &str, repository: &str, token: Option<String>) -> Self {
+        Self {
+            http: Http::new(
+                "GitLab",
+                api_url,
+                token.map(|token| ("PRIVATE-TOKEN", token)),
+            ),
+            project: repository.trim_matches('/').replace('/', "%2F"),
+        }
+    }
+
+    /// Look up the numeric user ID GitLab expects for reviewers
+    fn user_id(&self, username: &str) -> Result<u64> {
+        let users: Vec<ApiUser> = self.http.send(
+            self.http
+                .request("GET", "/users")
+                .query("username", username),
+            None,
+        )?;
+        users
+            .first()
+            .map(|user| user.id)
+            .ok_or_else(|| VibeTicketError::custom(format!("GitLab user '{username}' not found")))
+    }
+}
+
+impl Forge for GitLabForge {
+    fn name(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn create_pull_request(&self, draft: &PullRequestDraft) -> Result<PullRequest> {
+        // An unknown reviewer should not keep the merge request from opening
+        let mut warnings = Vec::new();
+        let mut reviewer_ids = Vec::new();
+        for username in &draft.reviewers {
+            match self.user_id(username) {
+                Ok(id) => reviewer_ids.push(id),
+                Err(e) => warnings.push(format!("Could not request a review from {username}: {e}")),
+            }
+        }
+        let title = if draft.draft {
+            format!("{DRAFT_PREFIX}{}", draft.title)
+        } else {
+            draft.title.clone()
+        };
+
+        let created: ApiMergeRequest = self.http.send(
+            self.http.request(
+                "POST",
+                &format!("/projects/{}/merge_requests", self.project),
+            ),
+            Some(serde_json::json!({
+                "title": title,
+                "description": draft.body,
+                "source_branch": draft.head,
+                "target_branch": draft.base,
+                "reviewer_ids": reviewer_ids,
+            })),
+        )?;
+
+        Ok(PullRequest {
+            number: created.iid,
+            url: created.web_url,
+            warnings,
+        })
+    }
+}
+
+/// Merge request as returned by the REST API
+#[derive(Deserialize)]
+struct ApiMergeRequest {
+    iid: u64,
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    id: u64,
+}
@@ -0,0 +1,277 @@
+//! Code forges for opening pull requests
+//!
+//! A [`Forge`] is a code hosting service that pull requests (merge requests
+//! on GitLab) can be opened on. All forges talk to their REST API over HTTP
+//! with a configurable base URL, so self-hosted instances and local mock
+//! servers work the same way as the public services.
+//!
+//! The pull request title and body are rendered from a [`PullRequestTemplate`].
+
+mod gitea;
+mod github;
+mod gitlab;
+mod template;
+
+pub use gitea::GiteaForge;
+pub use github::GitHubForge;
+pub use gitlab::GitLabForge;
+pub use template::{PullRequestContext, PullRequestTemplate, TEMPLATE_PATH};
+
+use crate::error::{Result, VibeTicketError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A pull request that is about to be opened
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestDraft {
+    /// Pull request title
+    pub title: String,
+    /// Pull request description
+    pub body: String,
+    /// Branch containing the changes
+    pub head: String,
+    /// Branch the changes should be merged into
+    pub base: String,
+    /// Open the pull request as a draft
+    pub draft: bool,
+    /// Usernames to request a review from
+    pub reviewers: Vec<String>,
+}
+
+/// A pull request as created on the forge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PullRequest {
+    /// Forge-specific number (`!iid` on GitLab)
+    pub number: u64,
+    /// Link to the pull request
+    pub url: String,
+    /// Problems that did not stop the pull request from being opened, such
+    /// as reviewers that could not be requested
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A code hosting service that accepts pull requests
+pub trait Forge {
+    /// Short name used in messages, e.g. `github`
+    fn name(&self) -> &'static str;
+
+    /// Open a pull request and request reviews from its reviewers
+    ///
+    /// Once the pull request exists it is returned; reviewers that cannot be
+    /// requested are reported in [`PullRequest::warnings`].
+    fn create_pull_request(&self, draft: &PullRequestDraft) -> Result<PullRequest>;
+}
+
+/// Supported forge flavours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    /// GitHub or GitHub Enterprise Server
+    #[default]
+    GitHub,
+    /// GitLab.com or a self-managed GitLab
+    GitLab,
+    /// Gitea or Forgejo
+    Gitea,
+}
+
+impl ForgeKind {
+    /// Default API base URL, if the forge has a public instance
+    #[must_use]
+    pub const fn default_api_url(self) -> Option<&'static str> {
+        match self {
+            Self::GitHub => Some(crate::remote::github::DEFAULT_API_URL),
+            Self::GitLab => Some("https://gitlab.com/api/v4"),
+            Self::Gitea => None,
+        }
+    }
+
+    /// Environment variable the API token is read from by default
+    #[must_use]
+    pub const fn default_token_env(self) -> &'static str {
+        match self {
+            Self::GitHub => "GITHUB_TOKEN",
+            Self::GitLab => "GITLAB_TOKEN",
+            Self::Gitea => "GITEA_TOKEN",
+        }
+    }
+
+    /// Connect to a forge of this kind
+    ///
+    /// `repository` is the `owner/repo` path of the project (`group/subgroup/project`
+    /// on GitLab).
+    pub fn connect(
+        self,
+        api_url: &str,
+        repository: &str,
+        token: Option<String>,
+    ) -> Result<Box<dyn Forge>> {
+        let segments = repository.split('/').filter(|s| !s.is_empty()).count();
+        let valid = match self {
+            Self::GitLab => segments >= 2,
+            Self::GitHub | Self::Gitea => segments == 2,
+        };
+        if !valid {
+            return Err(VibeTicketError::custom(format!(
+                "Invalid {self} repository '{repository}': expected owner/repo"
+            )));
+        }
+        Ok(match self {
+            Self::GitHub => Box::new(GitHubForge::new(api_url, repository, token)),
+            Self::GitLab => Box::new(GitLabForge::new(api_url, repository, token)),
+            Self::Gitea => Box::new(GiteaForge::new(api_url, repository, token)),
+        })
+    }
+}
+
+impl fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+            Self::Gitea => "gitea",
+        })
+    }
+}
+
+impl FromStr for ForgeKind {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            "gitea" | "forgejo" => Ok(Self::Gitea),
+            _ => Err(VibeTicketError::custom(format!(
+                "Unknown forge '{s}'. Valid forges: github, gitlab, gitea"
+            ))),
+        }
+    }
+}
+
+/// Extract the `owner/repo` path from a Git remote URL
+///
+/// Handles `https://host/owner/repo.git`, `ssh://git@host/owner/repo.git` and
+/// scp-like `git@host:owner/repo.git` remotes.
+#[must_use]
+pub fn repository_from_remote_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        rest.split_once('/')?.1
+    } else {
+        url.split_once(':')?.1
+    };
+    let path = path.trim_matches('/').trim_end_matches(".git");
+    (path.split('/').filter(|s| !s.is_empty()).count() >= 2).then(|| path.to_string())
+}
+
+/// Minimal JSON-over-HTTP client shared by the forge implementations
+struct Http {
+    agent: ureq::Agent,
+    api_url: String,
+    forge: &'static str,
+    auth: Option<(&'static str, String)>,
+}
+
+impl Http {
+    fn new(forge: &'static str, api_url: &str, auth: Option<(&'static str, String)>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("vibe-ticket/", env!("CARGO_PKG_VERSION")))
+                .build(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            forge,
+            auth,
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let request = self
+            .agent
+            .request(method, &format!("{}{path}", self.api_url))
+            .set("Accept", "application/json");
+        match &self.auth {
+            Some((header, value)) => request.set(header, value),
+            None => request,
+        }
+    }
+
+    fn send<T: serde::de::DeserializeOwned>(
+        &self,
+        request: ureq::Request,
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let result = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        let response = result.map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                let message = response
+                    .into_json::<serde_json::Value>()
+                    .ok()
+                    .and_then(|json| error_message(&json))
+                    .unwrap_or_default();
+                VibeTicketError::custom(format!("{} API returned {code}: {message}", self.forge))
+            },
+            ureq::Error::Transport(transport) => {
+                VibeTicketError::custom(format!("{} API request failed: {transport}", self.forge))
+            },
+        })?;
+        response.into_json().map_err(|e| {
+            VibeTicketError::custom(format!("Invalid {} API response: {e}", self.forge))
+        })
+    }
+}
+
+/// Pull the human-readable error out of an API error body
+fn error_message(json: &serde_json::Value) -> Option<String> {
+    match json.get("message")? {
+        serde_json::Value::String(message) => Some(message.clone()),
+        // GitLab returns validation errors as a list or an object of lists
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_from_remote_url() {
+        for url in [
+            "https://github.com/owner/repo.git",
+            "https://github.com/owner/repo",
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+        ] {
+            assert_eq!(
+                repository_from_remote_url(url).as_deref(),
+                Some("owner/repo"),
+                "{url}"
+            );
+        }
+        assert_eq!(
+            repository_from_remote_url("git@gitlab.com:group/sub/project.git").as_deref(),
+            Some("group/sub/project")
+        );
+        assert_eq!(repository_from_remote_url("/srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_forge_kind_parsing() {
+        assert_eq!("GitLab".parse::<ForgeKind>().unwrap(), ForgeKind::GitLab);
+        assert_eq!("forgejo".parse::<ForgeKind>().unwrap(), ForgeKind::Gitea);
+        assert!("bitbucket".parse::<ForgeKind>().is_err());
+        assert!(
+            ForgeKind::GitHub
+                .connect("http://x", "owner", None)
+                .is_err()
+        );
+        assert!(ForgeKind::GitLab.connect("http://x", "a/b/c", None).is_ok());
+    }
+}
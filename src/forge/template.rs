@@ -0,0 +1,199 @@
+//! Pull request title and body templates
+//!
+//! Templates use Tera syntax. The title is taken from an optional front
+//! matter block at the top of the rendered template:
+//!
+//! ```text
+//! ---
+//! title: [{{ ticket.slug }}] {{ ticket.title }}
+//! ---
+//! {{ ticket.description }}
+//! ```
+//!
+//! Everything after the front matter becomes the body. Templates can use
+//! `ticket`, `tasks`, `tasks_completed`, `tasks_total`, `time_spent`,
+//! `time_spent_minutes`, `spec`, `branch` and `base`.
+
+use crate::core::{Task, Ticket};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+use serde::Serialize;
+use std::path::Path;
+use tera::{Context, Tera};
+
+/// Location of the project template, relative to `.vibe-ticket`
+pub const TEMPLATE_PATH: &str = "templates/pr.md";
+
+/// Template used when the project does not provide one
+const BUILTIN_TEMPLATE: &str = include_str!("../../templates/pr.md");
+
+/// Values available to pull request templates
+#[derive(Debug, Serialize)]
+pub struct PullRequestContext<'a> {
+    /// The ticket being closed
+    pub ticket: &'a Ticket,
+    /// The ticket's tasks
+    pub tasks: &'a [Task],
+    /// Number of completed tasks
+    pub tasks_completed: usize,
+    /// Total number of tasks
+    pub tasks_total: usize,
+    /// Tracked time in minutes
+    pub time_spent_minutes: i64,
+    /// Tracked time as a human-readable string, if any was logged
+    pub time_spent: Option<String>,
+    /// Specification the ticket belongs to
+    pub spec: Option<&'a SpecMetadata>,
+    /// Branch containing the changes
+    pub branch: &'a str,
+    /// Branch the pull request targets
+    pub base: &'a str,
+}
+
+impl<'a> PullRequestContext<'a> {
+    /// Create a context for merging `branch` into `base`
+    #[must_use]
+    pub fn new(ticket: &'a Ticket, branch: &'a str, base: &'a str) -> Self {
+        Self {
+            ticket,
+            tasks: &ticket.tasks,
+            tasks_completed: ticket.tasks.iter().filter(|t| t.completed).count(),
+            tasks_total: ticket.tasks.len(),
+            time_spent_minutes: 0,
+            time_spent: None,
+            spec: None,
+            branch,
+            base,
+        }
+    }
+
+    /// Include the tracked time
+    #[must_use]
+    pub fn with_time_spent(mut self, minutes: i64) -> Self {
+        self.time_spent_minutes = minutes;
+        self.time_spent =
+            (minutes > 0).then(|| crate::cli::format_duration(chrono::Duration::minutes(minutes)));
+        self
+    }
+
+    /// Include the linked specification
+    #[must_use]
+    pub const fn with_spec(mut self, spec: Option<&'a SpecMetadata>) -> Self {
+        self.spec = spec;
+        self
+    }
+}
+
+/// A pull request template
+#[derive(Debug, Clone)]
+pub struct PullRequestTemplate {
+    source: String,
+}
+
+impl PullRequestTemplate {
+    /// Create a template from Tera source
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// The built-in template
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::new(BUILTIN_TEMPLATE)
+    }
+
+    /// Load the project template from `.vibe-ticket/templates/pr.md`,
+    /// falling back to the built-in one
+    pub fn load(vibe_ticket_dir: &Path) -> Result<Self> {
+        let path = vibe_ticket_dir.join(TEMPLATE_PATH);
+        if !path.exists() {
+            return Ok(Self::builtin());
+        }
+        std::fs::read_to_string(&path)
+            .map(Self::new)
+            .map_err(|e| VibeTicketError::io_error("read", &path, e))
+    }
+
+    /// Render the title and body
+    pub fn render(&self, context: &PullRequestContext<'_>) -> Result<(String, String)> {
+        let values = Context::from_serialize(context).map_err(|e| {
+            VibeTicketError::custom(format!("Failed to build template context: {e}"))
+        })?;
+        let rendered = Tera::one_off(&self.source, &values, false).map_err(|e| {
+            VibeTicketError::custom(format!("Failed to render pull request template: {e:?}"))
+        })?;
+
+        let (title, body) = split_front_matter(&rendered);
+        let title =
+            title.unwrap_or_else(|| format!("[{}] {}", context.ticket.slug, context.ticket.title));
+        Ok((title, body.trim().to_string() + "\n"))
+    }
+}
+
+/// Split a `title:` front matter block off the rendered template
+fn split_front_matter(rendered: &str) -> (Option<String>, &str) {
+    let Some(rest) = rendered.trim_start().strip_prefix("---\n") else {
+        return (None, rendered);
+    };
+    let Some((front, body)) = rest.split_once("\n---") else {
+        return (None, rendered);
+    };
+    let title = front
+        .lines()
+        .find_map(|line| line.strip_prefix("title:"))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    (title, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_template() {
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.description = "Users cannot log in".to_string();
+        ticket.tasks = vec![Task::new("Reproduce"), Task::new("Write fix")];
+        ticket.tasks[0].completed = true;
+        let spec = SpecMetadata::new("Auth".to_string(), String::new());
+
+        let context = PullRequestContext::new(&ticket, "ticket/fix-login", "main")
+            .with_time_spent(90)
+            .with_spec(Some(&spec));
+        let (title, body) = PullRequestTemplate::builtin().render(&context).unwrap();
+
+        assert_eq!(title, "[fix-login] Fix login");
+        assert!(body.contains("Users cannot log in"));
+        assert!(body.contains("- [x] Reproduce"));
+        assert!(body.contains("- [ ] Write fix"));
+        assert!(body.contains("1h 30m"));
+        assert!(body.contains("Auth"));
+    }
+
+    #[test]
+    fn test_custom_template_without_front_matter() {
+        let ticket = Ticket::new("docs", "Write docs");
+        let template =
+            PullRequestTemplate::new("Closes {{ ticket.slug }} ({{ tasks_total }} tasks)");
+        let (title, body) = template
+            .render(&PullRequestContext::new(&ticket, "docs", "main"))
+            .unwrap();
+        assert_eq!(title, "[docs] Write docs");
+        assert_eq!(body, "Closes docs (0 tasks)\n");
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let ticket = Ticket::new("docs", "Docs");
+        let template = PullRequestTemplate::new("{% if %}");
+        assert!(
+            template
+                .render(&PullRequestContext::new(&ticket, "docs", "main"))
+                .is_err()
+        );
+    }
+}
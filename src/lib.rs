@@ -57,9 +57,11 @@ pub mod config;
 pub mod core;
 pub mod error;
 pub mod events;
+pub mod forge;
 pub mod integration;
 pub mod interactive;
 pub mod plugins;
+pub mod remote;
 pub mod specs;
 pub mod storage;
 pub mod templates;
@@ -70,6 +72,9 @@ pub mod api;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 
+#[cfg(feature = "tui")]
+pub mod tui;
+
 #[cfg(test)]
 pub mod test_utils;
 
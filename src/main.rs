@@ -8,18 +8,27 @@ use clap::Parser;
 use std::process;
 use vibe_ticket::cli::{
     AliasCommands, BulkCommands, Cli, Commands, ConfigCommands, FilterCommands, HookCommands,
-    InteractiveCommands, OutputFormatter, SpecCommands, TaskCommands, TimeCommands,
-    WorktreeCommands, handlers::handle_init,
+    InteractiveCommands, OutputFormatter, SpecCommands, SpecReviewersCommands, SyncCommands,
+    TaskCommands, TimeCommands, WorktreeCommands, find_project_root,
+    handlers::{expand_command_line, handle_doctor_command, handle_init, handle_migrate_command},
 };
 use vibe_ticket::error::Result;
+use vibe_ticket::storage::{FORMAT_VERSION, FileStorage, Recovery};
 
 /// Main entry point for the vibe-ticket CLI
 ///
 /// Parses command-line arguments and executes the requested command.
 /// Handles errors gracefully and provides helpful error messages to users.
 fn main() {
-    // Parse command-line arguments
-    let cli = Cli::parse();
+    // Expand aliases, then parse command-line arguments
+    let args = match expand_command_line(std::env::args_os().collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            handle_error(&e, &OutputFormatter::new(false, false));
+            process::exit(1);
+        },
+    };
+    let cli = Cli::parse_from(args);
 
     // Configure output formatter based on flags
     let formatter = OutputFormatter::new(cli.json, cli.no_color);
@@ -50,15 +59,69 @@ fn run(cli: Cli, formatter: &OutputFormatter) -> Result<()> {
         tracing_subscriber::fmt().with_env_filter("debug").init();
     }
 
+    // Apply configuration overrides for this run
+    vibe_ticket::config::set_overrides(&cli.set)?;
+
     // Change to project directory if specified
     if let Some(project_path) = &cli.project {
         std::env::set_current_dir(project_path).map_err(vibe_ticket::error::VibeTicketError::Io)?;
     }
 
+    // Finish whatever an interrupted earlier run left half done
+    recover_interrupted_transaction(formatter);
+    check_project_format(&cli.command, formatter);
+
     // Dispatch to command handler
     dispatch_command(cli.command, cli.project, formatter)
 }
 
+/// Complete or discard a transaction that an earlier run was interrupted in
+fn recover_interrupted_transaction(formatter: &OutputFormatter) {
+    let Ok(project_root) = find_project_root(None) else {
+        return;
+    };
+    match FileStorage::new(project_root.join(".vibe-ticket")).recover() {
+        Ok(Some(Recovery {
+            operation: Some(operation),
+            applied,
+        })) => formatter.warning(&format!(
+            "Completed the interrupted {operation} ({applied} file(s) changed)"
+        )),
+        Ok(Some(_)) => formatter.warning("Discarded an interrupted change that had not started"),
+        Ok(None) => {},
+        Err(e) => formatter.warning(&format!(
+            "Could not complete an interrupted change, it will be retried: {e}"
+        )),
+    }
+}
+
+/// Point out a project whose format differs from the one this version writes
+fn check_project_format(command: &Commands, formatter: &OutputFormatter) {
+    if matches!(
+        command,
+        Commands::Init { .. } | Commands::Migrate { .. } | Commands::MergeDriver { .. }
+    ) {
+        return;
+    }
+    let Ok(project_root) = find_project_root(None) else {
+        return;
+    };
+    let Ok(version) = FileStorage::new(project_root.join(".vibe-ticket")).format_version() else {
+        return;
+    };
+    if version < FORMAT_VERSION {
+        formatter.warning(&format!(
+            "This project uses format version {version}; run `vibe-ticket migrate` \
+             to upgrade it to version {FORMAT_VERSION}"
+        ));
+    } else if version > FORMAT_VERSION {
+        formatter.warning(&format!(
+            "This project uses format version {version}, newer than this version of \
+             vibe-ticket supports ({FORMAT_VERSION}); changes will be refused"
+        ));
+    }
+}
+
 /// Arguments for the new command dispatcher
 struct NewCommandArgs<'a> {
     slug: String,
@@ -117,6 +180,7 @@ struct SearchOptions {
     description: bool,
     tags: bool,
     regex: bool,
+    archived: bool,
 }
 
 fn dispatch_command(
@@ -130,11 +194,13 @@ fn dispatch_command(
             description,
             force,
             claude_md,
+            install_merge_driver,
         } => handle_init(
             name.as_deref(),
             description.as_deref(),
             force,
             claude_md,
+            install_merge_driver,
             formatter,
         ),
         Commands::New {
@@ -183,14 +249,16 @@ fn dispatch_command(
             project,
             formatter,
         }),
-        Commands::Board {
-            assignee,
-            active_only,
-            compact,
-        } => dispatch_board_command(
-            assignee.as_deref(),
-            active_only,
-            compact,
+        Commands::Board(args) => args.run(project.as_deref(), formatter),
+        #[cfg(feature = "tui")]
+        Commands::Tui { filter } => vibe_ticket::cli::handlers::handle_tui_command(
+            filter.as_deref(),
+            project.as_deref(),
+            formatter,
+        ),
+        Commands::Watch { filter, exec } => vibe_ticket::cli::handlers::handle_watch_command(
+            filter.as_deref(),
+            exec.as_deref(),
             project.as_deref(),
             formatter,
         ),
@@ -229,7 +297,8 @@ fn dispatch_main_commands(
             message,
             archive,
             pr,
-        } => dispatch_close_command(ticket, message, archive, pr, project, formatter),
+            draft,
+        } => dispatch_close_command(ticket, message, archive, pr, draft, project, formatter),
         Commands::Check { detailed, stats } => {
             dispatch_check_command(detailed, stats, project, formatter)
         },
@@ -264,20 +333,10 @@ fn dispatch_remaining_commands(
     formatter: &OutputFormatter,
 ) -> Result<()> {
     match command {
-        Commands::Review { ticket, notes } => {
-            dispatch_review_command(ticket, notes.as_deref(), project, formatter)
-        },
-        Commands::Approve { ticket, message } => {
-            dispatch_approve_command(ticket, message.as_deref(), project, formatter)
-        },
-        Commands::RequestChanges { ticket, changes } => {
-            dispatch_request_changes_command(ticket, &changes, project, formatter)
-        },
-        Commands::Handoff {
-            ticket,
-            assignee,
-            notes,
-        } => dispatch_handoff_command(ticket, &assignee, notes.as_deref(), project, formatter),
+        Commands::Review(args) => args.run(project, formatter),
+        Commands::Approve(args) => args.run(project, formatter),
+        Commands::RequestChanges(args) => args.run(project, formatter),
+        Commands::Handoff(args) => args.run(project, formatter),
         Commands::Show {
             ticket,
             tasks,
@@ -285,15 +344,14 @@ fn dispatch_remaining_commands(
             markdown,
         } => dispatch_show_command(&ticket, tasks, history, markdown, project, formatter),
         Commands::Task { command } => handle_task_command(command, project, formatter),
-        Commands::Archive { ticket, unarchive } => {
-            dispatch_archive_command(&ticket, unarchive, project, formatter)
-        },
+        Commands::Archive(args) => args.run(project, formatter),
         Commands::Search {
             query,
             title,
             description,
             tags,
             regex,
+            archived,
         } => dispatch_search_command(
             &query,
             SearchOptions {
@@ -301,6 +359,7 @@ fn dispatch_remaining_commands(
                 description,
                 tags,
                 regex,
+                archived,
             },
             project,
             formatter,
@@ -310,14 +369,32 @@ fn dispatch_remaining_commands(
             output,
             include_archived,
         } => dispatch_export_command(&format, output, include_archived, project, formatter),
+        Commands::Graph {
+            format,
+            group_by,
+            output,
+            include_archived,
+        } => {
+            use vibe_ticket::cli::handlers::handle_graph_command;
+            handle_graph_command(
+                &format,
+                &group_by,
+                output,
+                include_archived,
+                project,
+                formatter,
+            )
+        },
         Commands::Import {
             file,
             format,
+            mapping,
             skip_validation,
             dry_run,
         } => dispatch_import_command(
             &file,
             format.as_deref(),
+            mapping.as_deref(),
             skip_validation,
             dry_run,
             project,
@@ -325,7 +402,22 @@ fn dispatch_remaining_commands(
         ),
         Commands::Config { command } => dispatch_config_command(command, project, formatter),
         Commands::Spec { command } => dispatch_spec_command(command, project, formatter),
-        Commands::Worktree { command } => dispatch_worktree_command(command, formatter),
+        Commands::Worktree { command } => dispatch_worktree_command(command, project, formatter),
+        Commands::Sync { command } => dispatch_sync_command(command, project, formatter),
+        Commands::Doctor { fix } => handle_doctor_command(fix, project, formatter),
+        Commands::Migrate { dry_run } => handle_migrate_command(dry_run, project, formatter),
+        Commands::MergeDriver {
+            base,
+            ours,
+            theirs,
+            path,
+        } => vibe_ticket::cli::handlers::handle_merge_driver_command(
+            &base,
+            &ours,
+            &theirs,
+            path.as_deref(),
+            formatter,
+        ),
         #[cfg(feature = "mcp")]
         Commands::Mcp { command } => dispatch_mcp_command(command, project, formatter),
         Commands::Bulk { command } => dispatch_bulk_command(command, project, formatter),
@@ -389,58 +481,6 @@ fn dispatch_open_command(
     )
 }
 
-fn dispatch_board_command(
-    assignee: Option<&str>,
-    active_only: bool,
-    compact: bool,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_board_command;
-    handle_board_command(assignee, active_only, compact, project, formatter)
-}
-
-fn dispatch_review_command(
-    ticket: Option<String>,
-    notes: Option<&str>,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_review_command;
-    handle_review_command(ticket, notes, project, formatter)
-}
-
-fn dispatch_approve_command(
-    ticket: Option<String>,
-    message: Option<&str>,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_approve_command;
-    handle_approve_command(ticket, message, project, formatter)
-}
-
-fn dispatch_request_changes_command(
-    ticket: Option<String>,
-    changes: &str,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_request_changes_command;
-    handle_request_changes_command(ticket, changes, project, formatter)
-}
-
-fn dispatch_handoff_command(
-    ticket: Option<String>,
-    assignee: &str,
-    notes: Option<&str>,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_handoff_command;
-    handle_handoff_command(ticket, assignee, notes, project, formatter)
-}
-
 fn dispatch_start_command(
     ticket: String,
     branch: bool,
@@ -467,11 +507,12 @@ fn dispatch_close_command(
     message: Option<String>,
     archive: bool,
     pr: bool,
+    draft: bool,
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
     use vibe_ticket::cli::handlers::handle_close_command;
-    handle_close_command(ticket, message, archive, pr, project, formatter)
+    handle_close_command(ticket, message, archive, pr, draft, project, formatter)
 }
 
 fn dispatch_check_command(
@@ -518,16 +559,6 @@ fn dispatch_show_command(
     handle_show_command(ticket, tasks, history, markdown, project, formatter)
 }
 
-fn dispatch_archive_command(
-    ticket: &str,
-    unarchive: bool,
-    project: Option<&str>,
-    formatter: &OutputFormatter,
-) -> Result<()> {
-    use vibe_ticket::cli::handlers::handle_archive_command;
-    handle_archive_command(ticket, unarchive, project, formatter)
-}
-
 fn dispatch_search_command(
     query: &str,
     options: SearchOptions,
@@ -541,6 +572,7 @@ fn dispatch_search_command(
         options.description,
         options.tags,
         options.regex,
+        options.archived,
         project,
         formatter,
     )
@@ -560,13 +592,22 @@ fn dispatch_export_command(
 fn dispatch_import_command(
     file: &str,
     format: Option<&str>,
+    mapping: Option<&str>,
     skip_validation: bool,
     dry_run: bool,
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
     use vibe_ticket::cli::handlers::handle_import_command;
-    handle_import_command(file, format, skip_validation, dry_run, project, formatter)
+    handle_import_command(
+        file,
+        format,
+        mapping,
+        skip_validation,
+        dry_run,
+        project,
+        formatter,
+    )
 }
 
 fn dispatch_config_command(
@@ -686,46 +727,52 @@ fn dispatch_spec_command(
             use vibe_ticket::cli::handlers::handle_spec_design;
             handle_spec_design(spec, editor, complete, project, formatter)
         },
-        SpecCommands::Status {
-            spec,
-            detailed,
-            validation,
-        } => {
-            use vibe_ticket::cli::handlers::handle_spec_status;
-            // Note: validation flag is handled within the status handler if needed
-            handle_spec_status(spec, detailed || validation, project, formatter)
+        SpecCommands::Status(args) => args.run(project, formatter),
+        SpecCommands::List(args) => args.run(project, formatter),
+        SpecCommands::Show(args) => args.run(project, formatter),
+        SpecCommands::Delete(args) => args.run(project, formatter),
+        SpecCommands::Approve(args) => args.run(project, formatter),
+        SpecCommands::RequestChanges(args) => args.run(project, formatter),
+        SpecCommands::Reviewers { command } => match command {
+            SpecReviewersCommands::Add(args) => args.add(project, formatter),
+            SpecReviewersCommands::Remove(args) => args.remove(project, formatter),
         },
-        SpecCommands::List {
-            status,
-            phase,
-            archived,
-        } => {
-            use vibe_ticket::cli::handlers::handle_spec_list;
-            handle_spec_list(status, phase, archived, project, formatter)
+        SpecCommands::Activate(args) => args.run(project, formatter),
+        SpecCommands::History { spec } => {
+            use vibe_ticket::cli::handlers::handle_spec_history;
+            handle_spec_history(spec.as_deref(), project, formatter)
         },
-        SpecCommands::Show {
+        SpecCommands::Diff {
+            from,
+            to,
             spec,
-            all,
-            markdown,
+            document,
         } => {
-            use vibe_ticket::cli::handlers::handle_spec_show;
-            handle_spec_show(spec, all, markdown, project, formatter)
+            use vibe_ticket::cli::handlers::handle_spec_diff;
+            handle_spec_diff(
+                &from,
+                &to,
+                spec.as_deref(),
+                document.as_deref(),
+                project,
+                formatter,
+            )
         },
-        SpecCommands::Delete { spec, force } => {
-            use vibe_ticket::cli::handlers::handle_spec_delete;
-            handle_spec_delete(spec, force, project, formatter)
+        SpecCommands::Restore { revision, spec } => {
+            use vibe_ticket::cli::handlers::handle_spec_restore;
+            handle_spec_restore(&revision, spec.as_deref(), project, formatter)
         },
-        SpecCommands::Approve {
-            spec,
-            phase,
-            message,
-        } => {
-            use vibe_ticket::cli::handlers::handle_spec_approve;
-            handle_spec_approve(spec, phase, message, project, formatter)
+        SpecCommands::Sync { spec } => {
+            use vibe_ticket::cli::handlers::handle_spec_sync;
+            handle_spec_sync(spec.as_deref(), project, formatter)
         },
-        SpecCommands::Activate { spec } => {
-            use vibe_ticket::cli::handlers::handle_spec_activate;
-            handle_spec_activate(spec, project, formatter)
+        SpecCommands::Lint { spec, format } => {
+            use vibe_ticket::cli::handlers::handle_spec_lint;
+            handle_spec_lint(spec.as_deref(), &format, project, formatter)
+        },
+        SpecCommands::Trace { spec, markdown } => {
+            use vibe_ticket::cli::handlers::handle_spec_trace;
+            handle_spec_trace(spec.as_deref(), markdown, project, formatter)
         },
         SpecCommands::Template {
             template_type,
@@ -738,8 +785,31 @@ fn dispatch_spec_command(
     }
 }
 
-fn dispatch_worktree_command(command: WorktreeCommands, formatter: &OutputFormatter) -> Result<()> {
+fn dispatch_sync_command(
+    command: SyncCommands,
+    project: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    use vibe_ticket::cli::handlers::{handle_sync_pull, handle_sync_push};
     match command {
+        SyncCommands::Push {
+            ticket,
+            force,
+            dry_run,
+        } => handle_sync_push(ticket.as_deref(), force, dry_run, project, formatter),
+        SyncCommands::Pull { force, dry_run } => {
+            handle_sync_pull(force, dry_run, project, formatter)
+        },
+    }
+}
+
+fn dispatch_worktree_command(
+    command: WorktreeCommands,
+    project: Option<&str>,
+    formatter: &OutputFormatter,
+) -> Result<()> {
+    match command {
+        WorktreeCommands::Create(args) => args.run(project, formatter),
         WorktreeCommands::List {
             all,
             status,
@@ -840,10 +910,7 @@ fn handle_task_command(
             use vibe_ticket::cli::handlers::handle_task_complete;
             handle_task_complete(task, ticket, project.map(str::to_string), formatter)
         },
-        TaskCommands::Uncomplete { task, ticket } => {
-            use vibe_ticket::cli::handlers::handle_task_uncomplete;
-            handle_task_uncomplete(task, ticket, project.map(str::to_string), formatter)
-        },
+        TaskCommands::Uncomplete(args) => args.run(project, formatter),
         TaskCommands::List {
             ticket,
             completed,
@@ -874,34 +941,11 @@ fn dispatch_bulk_command(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use vibe_ticket::cli::handlers::{
-        handle_bulk_archive, handle_bulk_close, handle_bulk_tag, handle_bulk_update,
-    };
     match command {
-        BulkCommands::Update {
-            filter,
-            status,
-            priority,
-            assignee,
-            dry_run,
-        } => handle_bulk_update(
-            filter, status, priority, assignee, dry_run, project, formatter,
-        ),
-        BulkCommands::Tag {
-            filter,
-            add,
-            remove,
-            dry_run,
-        } => handle_bulk_tag(filter, add, remove, dry_run, project, formatter),
-        BulkCommands::Close {
-            filter,
-            message,
-            archive,
-            dry_run,
-        } => handle_bulk_close(filter, message, archive, dry_run, project, formatter),
-        BulkCommands::Archive { filter, dry_run } => {
-            handle_bulk_archive(filter, dry_run, project, formatter)
-        },
+        BulkCommands::Update(args) => args.run(project, formatter),
+        BulkCommands::Tag(args) => args.run(project, formatter),
+        BulkCommands::Close(args) => args.run(project, formatter),
+        BulkCommands::Archive(args) => args.run(project, formatter),
     }
 }
 
@@ -910,24 +954,13 @@ fn dispatch_filter_command(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use vibe_ticket::cli::handlers::{
-        handle_filter_apply, handle_filter_create, handle_filter_delete, handle_filter_list,
-        handle_filter_show,
-    };
+    use vibe_ticket::cli::handlers::handle_filter_list;
     match command {
-        FilterCommands::Create {
-            name,
-            expression,
-            description,
-        } => handle_filter_create(name, expression, description, project, formatter),
+        FilterCommands::Create(args) => args.run(project, formatter),
         FilterCommands::List => handle_filter_list(project, formatter),
-        FilterCommands::Show { name } => handle_filter_show(name, project, formatter),
-        FilterCommands::Delete { name, force } => {
-            handle_filter_delete(name, force, project, formatter)
-        },
-        FilterCommands::Apply { name, additional } => {
-            handle_filter_apply(name, additional, project, formatter)
-        },
+        FilterCommands::Show(args) => args.run(project, formatter),
+        FilterCommands::Delete(args) => args.run(project, formatter),
+        FilterCommands::Apply(args) => args.run(project, formatter),
     }
 }
 
@@ -936,18 +969,21 @@ fn dispatch_alias_command(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use vibe_ticket::cli::handlers::{
-        handle_alias_create, handle_alias_delete, handle_alias_list, handle_alias_run,
-    };
+    use vibe_ticket::cli::handlers::{handle_alias_list, handle_alias_run};
     match command {
-        AliasCommands::Create {
-            name,
-            command,
-            description,
-        } => handle_alias_create(name, command, description, project, formatter),
+        AliasCommands::Create(args) => args.run(project, formatter),
         AliasCommands::List => handle_alias_list(project, formatter),
-        AliasCommands::Delete { name } => handle_alias_delete(name, project, formatter),
-        AliasCommands::Run { name, args } => handle_alias_run(name, args, project, formatter),
+        AliasCommands::Delete(args) => args.run(project, formatter),
+        AliasCommands::Run { name, args } => {
+            let words = handle_alias_run(&name, &args, project)?;
+            let cli = Cli::try_parse_from(std::iter::once("vibe-ticket".to_string()).chain(words))
+                .map_err(|e| vibe_ticket::error::VibeTicketError::InvalidInput(e.to_string()))?;
+            dispatch_command(
+                cli.command,
+                cli.project.or_else(|| project.map(str::to_string)),
+                formatter,
+            )
+        },
     }
 }
 
@@ -956,28 +992,13 @@ fn dispatch_time_command(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use vibe_ticket::cli::handlers::{
-        handle_time_log, handle_time_report, handle_time_start, handle_time_status,
-        handle_time_stop,
-    };
+    use vibe_ticket::cli::handlers::handle_time_status;
     match command {
-        TimeCommands::Log {
-            time,
-            ticket,
-            notes,
-            date,
-        } => handle_time_log(time, ticket, notes, date, project, formatter),
-        TimeCommands::Start { ticket, notes } => {
-            handle_time_start(ticket, notes, project, formatter)
-        },
-        TimeCommands::Stop { notes } => handle_time_stop(notes, project, formatter),
+        TimeCommands::Log(args) => args.run(project, formatter),
+        TimeCommands::Start(args) => args.run(project, formatter),
+        TimeCommands::Stop(args) => args.run(project, formatter),
         TimeCommands::Status => handle_time_status(project, formatter),
-        TimeCommands::Report {
-            ticket,
-            all,
-            since,
-            until,
-        } => handle_time_report(ticket, all, since, until, project, formatter),
+        TimeCommands::Report(args) => args.run(project, formatter),
     }
 }
 
@@ -986,31 +1007,14 @@ fn dispatch_hook_command(
     project: Option<&str>,
     formatter: &OutputFormatter,
 ) -> Result<()> {
-    use vibe_ticket::cli::handlers::{
-        handle_hook_create, handle_hook_delete, handle_hook_disable, handle_hook_enable,
-        handle_hook_list, handle_hook_test,
-    };
+    use vibe_ticket::cli::handlers::handle_hook_list;
     match command {
-        HookCommands::Create {
-            name,
-            event,
-            command,
-            description,
-            abort_on_failure,
-        } => handle_hook_create(
-            name,
-            event,
-            command,
-            description,
-            abort_on_failure,
-            project,
-            formatter,
-        ),
+        HookCommands::Create(args) => args.run(project, formatter),
         HookCommands::List => handle_hook_list(project, formatter),
-        HookCommands::Delete { name } => handle_hook_delete(name, project, formatter),
-        HookCommands::Enable { name } => handle_hook_enable(name, project, formatter),
-        HookCommands::Disable { name } => handle_hook_disable(name, project, formatter),
-        HookCommands::Test { name } => handle_hook_test(name, project, formatter),
+        HookCommands::Delete(args) => args.delete(project, formatter),
+        HookCommands::Enable(args) => args.enable(project, formatter),
+        HookCommands::Disable(args) => args.disable(project, formatter),
+        HookCommands::Test(args) => args.test(project, formatter),
     }
 }
 
@@ -1,9 +1,14 @@
 //! Event bridge for MCP-CLI synchronization
 
 use crate::mcp::handlers::events::McpEventHandler;
+use std::sync::Arc;
 
 /// Start the event bridge to handle CLI events
-pub const fn start_event_bridge(_handler: McpEventHandler) {
-    // Event bridge functionality would go here
-    // For now, this is a placeholder to satisfy compilation
+///
+/// Registers the handler with the global event bus, which also receives
+/// events for ticket files changed by other processes.
+pub async fn start_event_bridge(handler: McpEventHandler) {
+    crate::events::event_bus()
+        .register_handler(Arc::new(handler))
+        .await;
 }
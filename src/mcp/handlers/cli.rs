@@ -0,0 +1,266 @@
+//! MCP tools that run CLI commands
+//!
+//! Each tool runs one CLI command in-process through the same handler the
+//! CLI uses, with a capturing [`OutputFormatter`] so nothing reaches the
+//! stdio transport. Input schemas are generated from the argument structs in
+//! [`crate::cli::args`] and descriptions are the commands' help text, so the
+//! tools follow the CLI as it changes.
+
+use crate::cli::args::{
+    AliasCreateArgs, AliasDeleteArgs, ApproveArgs, ArchiveArgs, BoardArgs, BulkArchiveArgs,
+    BulkCloseArgs, BulkTagArgs, BulkUpdateArgs, FilterApplyArgs, FilterCreateArgs,
+    FilterDeleteArgs, FilterShowArgs, HandoffArgs, HookCreateArgs, HookNameArgs,
+    RequestChangesArgs, ReviewArgs, SpecActivateArgs, SpecApproveArgs, SpecDeleteArgs,
+    SpecListArgs, SpecRequestChangesArgs, SpecReviewersArgs, SpecShowArgs, SpecStatusArgs,
+    TaskUncompleteArgs, TimeLogArgs, TimeReportArgs, TimeStartArgs, TimeStopArgs,
+    WorktreeCreateArgs,
+};
+use crate::cli::handlers::{
+    handle_alias_list, handle_filter_list, handle_hook_list, handle_time_status,
+};
+use crate::cli::{Cli, OutputFormatter};
+use crate::mcp::service::VibeTicketService;
+use clap::CommandFactory;
+use rmcp::handler::server::common::schema_for_type;
+use rmcp::model::{JsonObject, Tool};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// CLI commands deliberately not exposed as MCP tools, with the reason
+///
+/// An entry covers the command and all of its subcommands.
+pub const EXCLUDED_COMMANDS: &[(&str, &str)] = &[
+    ("init", "the MCP server runs inside an initialized project"),
+    (
+        "doctor",
+        "reports problems through its exit status; run it from a terminal",
+    ),
+    ("merge-driver", "run by git while merging ticket files"),
+    (
+        "migrate",
+        "rewrites project files after a backup; run it from a terminal",
+    ),
+    ("open", "covered by vibe-ticket_list with `open`"),
+    (
+        "graph",
+        "writes the graph to stdout, which the stdio transport owns",
+    ),
+    (
+        "sync",
+        "pushes and pulls with the user's GitHub credentials",
+    ),
+    ("config get", "covered by vibe-ticket_config_show"),
+    (
+        "config reset",
+        "resets the whole configuration; run it from a terminal",
+    ),
+    (
+        "config claude",
+        "writes CLAUDE.md for editor setup; run it from a terminal",
+    ),
+    (
+        "spec requirements",
+        "opens an editor; use vibe-ticket_spec_update",
+    ),
+    (
+        "spec design",
+        "opens an editor; use vibe-ticket_spec_update",
+    ),
+    ("spec history", "prints revisions straight to stdout"),
+    ("spec diff", "prints diffs straight to stdout"),
+    (
+        "spec restore",
+        "rewrites documents from history; review the diff in a terminal first",
+    ),
+    ("spec sync", "runs as part of ticket status changes"),
+    ("spec lint", "prints reports straight to stdout"),
+    ("spec trace", "prints the matrix straight to stdout"),
+    (
+        "spec template",
+        "writes template files for people to fill in",
+    ),
+    ("mcp", "starts the MCP server itself"),
+    (
+        "alias run",
+        "expands to another command; call that command's tool",
+    ),
+    ("interactive", "prompts on a terminal"),
+    ("tui", "takes over the terminal"),
+    (
+        "watch",
+        "streams until interrupted; the MCP server watches ticket files itself",
+    ),
+];
+
+/// CLI commands whose MCP tool predates the naming scheme of [`tool_name`]
+pub const RENAMED_COMMANDS: &[(&str, &str)] = &[
+    ("spec init", "vibe-ticket_spec_add"),
+    ("spec tasks", "vibe-ticket_spec_generate_tasks"),
+];
+
+/// Arguments of commands that take none
+#[derive(Deserialize, JsonSchema)]
+struct NoArgs {}
+
+type Runner =
+    Box<dyn Fn(Value, Option<&str>, &OutputFormatter) -> Result<(), String> + Send + Sync>;
+
+/// A CLI command exposed as an MCP tool
+pub struct CliTool {
+    /// Command path below `vibe-ticket`, e.g. `["bulk", "update"]`
+    pub command: &'static [&'static str],
+    schema: fn() -> JsonObject,
+    run: Runner,
+}
+
+impl CliTool {
+    fn new<A: DeserializeOwned + JsonSchema + 'static>(
+        command: &'static [&'static str],
+        run: fn(A, Option<&str>, &OutputFormatter) -> crate::error::Result<()>,
+    ) -> Self {
+        Self {
+            command,
+            schema: schema_for_type::<A>,
+            run: Box::new(move |arguments, project_dir, output| {
+                let args: A = serde_json::from_value(arguments)
+                    .map_err(|e| format!("Invalid arguments: {e}"))?;
+                run(args, project_dir, output).map_err(|e| e.to_string())
+            }),
+        }
+    }
+
+    /// Name of the tool
+    #[must_use]
+    pub fn name(&self) -> String {
+        tool_name(self.command)
+    }
+
+    /// Input schema of the tool, generated from the command's arguments
+    #[must_use]
+    pub fn input_schema(&self) -> JsonObject {
+        let mut schema = (self.schema)();
+        schema.remove("$schema");
+        schema.remove("title");
+        schema.entry("properties").or_insert_with(|| json!({}));
+        schema
+    }
+}
+
+/// Name of the MCP tool of a CLI command, e.g. `vibe-ticket_bulk_update`
+#[must_use]
+pub fn tool_name(command: &[&str]) -> String {
+    format!("vibe-ticket_{}", command.join("_").replace('-', "_"))
+}
+
+/// Every CLI command exposed through this module
+#[must_use]
+pub fn cli_tools() -> Vec<CliTool> {
+    vec![
+        CliTool::new(&["board"], BoardArgs::run),
+        CliTool::new(&["review"], ReviewArgs::run),
+        CliTool::new(&["approve"], ApproveArgs::run),
+        CliTool::new(&["request-changes"], RequestChangesArgs::run),
+        CliTool::new(&["handoff"], HandoffArgs::run),
+        CliTool::new(&["archive"], ArchiveArgs::run),
+        CliTool::new(&["task", "uncomplete"], TaskUncompleteArgs::run),
+        CliTool::new(&["worktree", "create"], WorktreeCreateArgs::run),
+        CliTool::new(&["bulk", "update"], BulkUpdateArgs::run),
+        CliTool::new(&["bulk", "tag"], BulkTagArgs::run),
+        CliTool::new(&["bulk", "close"], BulkCloseArgs::run),
+        CliTool::new(&["bulk", "archive"], BulkArchiveArgs::run),
+        CliTool::new(&["filter", "create"], FilterCreateArgs::run),
+        CliTool::new(&["filter", "list"], |NoArgs {}, project_dir, output| {
+            handle_filter_list(project_dir, output)
+        }),
+        CliTool::new(&["filter", "show"], FilterShowArgs::run),
+        CliTool::new(&["filter", "delete"], FilterDeleteArgs::run),
+        CliTool::new(&["filter", "apply"], FilterApplyArgs::run),
+        CliTool::new(&["alias", "create"], AliasCreateArgs::run),
+        CliTool::new(&["alias", "list"], |NoArgs {}, project_dir, output| {
+            handle_alias_list(project_dir, output)
+        }),
+        CliTool::new(&["alias", "delete"], AliasDeleteArgs::run),
+        CliTool::new(&["time", "log"], TimeLogArgs::run),
+        CliTool::new(&["time", "start"], TimeStartArgs::run),
+        CliTool::new(&["time", "stop"], TimeStopArgs::run),
+        CliTool::new(&["time", "status"], |NoArgs {}, project_dir, output| {
+            handle_time_status(project_dir, output)
+        }),
+        CliTool::new(&["time", "report"], TimeReportArgs::run),
+        CliTool::new(&["hook", "create"], HookCreateArgs::run),
+        CliTool::new(&["hook", "list"], |NoArgs {}, project_dir, output| {
+            handle_hook_list(project_dir, output)
+        }),
+        CliTool::new(&["hook", "delete"], HookNameArgs::delete),
+        CliTool::new(&["hook", "enable"], HookNameArgs::enable),
+        CliTool::new(&["hook", "disable"], HookNameArgs::disable),
+        CliTool::new(&["hook", "test"], HookNameArgs::test),
+        CliTool::new(&["spec", "status"], SpecStatusArgs::run),
+        CliTool::new(&["spec", "list"], SpecListArgs::run),
+        CliTool::new(&["spec", "show"], SpecShowArgs::run),
+        CliTool::new(&["spec", "delete"], SpecDeleteArgs::run),
+        CliTool::new(&["spec", "activate"], SpecActivateArgs::run),
+        CliTool::new(&["spec", "approve"], SpecApproveArgs::run),
+        CliTool::new(&["spec", "request-changes"], SpecRequestChangesArgs::run),
+        CliTool::new(&["spec", "reviewers", "add"], SpecReviewersArgs::add),
+        CliTool::new(&["spec", "reviewers", "remove"], SpecReviewersArgs::remove),
+    ]
+}
+
+/// Register a tool for every command in [`cli_tools`]
+#[must_use]
+pub fn register_tools() -> Vec<Tool> {
+    let cli = Cli::command();
+    cli_tools()
+        .iter()
+        .map(|tool| {
+            let description = tool
+                .command
+                .iter()
+                .try_fold(&cli, |command, name| command.find_subcommand(name))
+                .and_then(clap::Command::get_about)
+                .map(|about| Cow::Owned(about.to_string()));
+            Tool {
+                name: Cow::Owned(tool.name()),
+                description,
+                input_schema: Arc::new(tool.input_schema()),
+                title: None,
+                output_schema: None,
+                icons: None,
+                annotations: None,
+                meta: None,
+            }
+        })
+        .collect()
+}
+
+/// Run the CLI command behind a tool
+///
+/// Returns the JSON the command printed: one document as is, several as an
+/// array. Commands that print no JSON return their messages instead.
+pub fn handle(service: &VibeTicketService, name: &str, arguments: Value) -> Result<Value, String> {
+    let tool = cli_tools()
+        .into_iter()
+        .find(|tool| tool.name() == name)
+        .ok_or_else(|| format!("Unknown tool: {name}"))?;
+
+    let output = OutputFormatter::capture();
+    let result = (tool.run)(arguments, service.project_root.to_str(), &output);
+    // The command wrote through its own storage
+    service.storage.cache.invalidate_all();
+    result?;
+
+    let mut captured = output.take_captured();
+    Ok(match captured.json.len() {
+        0 => json!({
+            "status": "success",
+            "messages": captured.messages,
+        }),
+        1 => captured.json.remove(0),
+        _ => Value::Array(captured.json),
+    })
+}
@@ -21,12 +21,6 @@ impl ConfigManager {
     fn load_from_path(path: &std::path::Path) -> Result<Config, String> {
         Config::load_from_path(path).map_err(|e| format!("Failed to load config: {e}"))
     }
-
-    fn save_to_path(config: &Config, path: &std::path::Path) -> Result<(), String> {
-        config
-            .save_to_path(path)
-            .map_err(|e| format!("Failed to save config: {e}"))
-    }
 }
 
 /// Register all configuration management tools
@@ -97,24 +91,7 @@ pub fn handle_show(service: &VibeTicketService, arguments: Value) -> Result<Valu
 
     if let Some(key) = args.key {
         // Show specific key
-        let value = match key.as_str() {
-            "project.name" => json!(config.project.name),
-            "project.description" => json!(config.project.description),
-            "project.default_priority" => json!(config.project.default_priority),
-            "project.default_assignee" => json!(config.project.default_assignee),
-
-            "git.auto_branch" => json!(config.git.auto_branch),
-            "git.branch_prefix" => json!(config.git.branch_prefix),
-            "git.commit_template" => json!(config.git.commit_template),
-            "git.worktree_enabled" => json!(config.git.worktree_enabled),
-            "git.worktree_default" => json!(config.git.worktree_default),
-            "git.worktree_prefix" => json!(config.git.worktree_prefix),
-            "git.worktree_cleanup_on_close" => json!(config.git.worktree_cleanup_on_close),
-
-            "ui.date_format" => json!(config.ui.date_format),
-
-            _ => return Err(format!("Unknown configuration key: {key}")),
-        };
+        let value = config.get(&key).map_err(|e| e.to_string())?;
 
         Ok(json!({
             "key": key,
@@ -122,26 +99,7 @@ pub fn handle_show(service: &VibeTicketService, arguments: Value) -> Result<Valu
         }))
     } else {
         // Show all configuration
-        Ok(json!({
-            "project": {
-                "name": config.project.name,
-                "description": config.project.description,
-                "default_priority": config.project.default_priority,
-                "default_assignee": config.project.default_assignee,
-            },
-            "git": {
-                "auto_branch": config.git.auto_branch,
-                "branch_prefix": config.git.branch_prefix,
-                "commit_template": config.git.commit_template,
-                "worktree_enabled": config.git.worktree_enabled,
-                "worktree_default": config.git.worktree_default,
-                "worktree_prefix": config.git.worktree_prefix,
-                "worktree_cleanup_on_close": config.git.worktree_cleanup_on_close,
-            },
-            "ui": {
-                "date_format": config.ui.date_format,
-            }
-        }))
+        serde_json::to_value(&config).map_err(|e| e.to_string())
     }
 }
 
@@ -160,82 +118,17 @@ pub fn handle_set(service: &VibeTicketService, arguments: Value) -> Result<Value
         .project_root
         .join(".vibe-ticket")
         .join("config.yaml");
-    let mut config = ConfigManager::load_from_path(&config_path)
-        .map_err(|e| format!("Failed to load configuration: {e}"))?;
-
-    // Parse and set the value
-    match args.key.as_str() {
-        "project.name" => {
-            config.project.name = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
-        },
-        "project.description" => {
-            config.project.description = args.value.as_str().map(std::string::ToString::to_string);
-        },
-        "project.default_priority" => {
-            config.project.default_priority = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
-        },
-        "project.default_assignee" => {
-            config.project.default_assignee =
-                args.value.as_str().map(std::string::ToString::to_string);
-        },
-
-        "git.auto_branch" => {
-            config.git.auto_branch = args.value.as_bool().ok_or("Value must be a boolean")?;
-        },
-        "git.branch_prefix" => {
-            config.git.branch_prefix = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
-        },
-        "git.commit_template" => {
-            config.git.commit_template = args.value.as_str().map(std::string::ToString::to_string);
-        },
-        "git.worktree_enabled" => {
-            config.git.worktree_enabled = args.value.as_bool().ok_or("Value must be a boolean")?;
-        },
-        "git.worktree_default" => {
-            config.git.worktree_default = args.value.as_bool().ok_or("Value must be a boolean")?;
-        },
-        "git.worktree_prefix" => {
-            config.git.worktree_prefix = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
-        },
-        "git.worktree_cleanup_on_close" => {
-            config.git.worktree_cleanup_on_close =
-                args.value.as_bool().ok_or("Value must be a boolean")?;
-        },
-
-        "ui.date_format" => {
-            config.ui.date_format = args
-                .value
-                .as_str()
-                .ok_or("Value must be a string")?
-                .to_string();
-        },
-
-        _ => return Err(format!("Unknown configuration key: {}", args.key)),
-    }
-
-    // Save the updated configuration
-    ConfigManager::save_to_path(&config, &config_path)
-        .map_err(|e| format!("Failed to save configuration: {e}"))?;
+    // Strings are parsed to the type of the field, like on the command line
+    let raw = match &args.value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    };
+    let value = Config::set_in_file(&config_path, &args.key, &raw)
+        .map_err(|e| format!("Failed to set configuration: {e}"))?;
 
     Ok(json!({
         "status": "updated",
         "key": args.key,
-        "value": args.value
+        "value": value
     }))
 }
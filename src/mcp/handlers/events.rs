@@ -61,6 +61,9 @@ impl EventHandler for McpEventHandler {
                     new_status
                 );
             },
+            TicketEvent::Removed(ticket_id) => {
+                tracing::info!("MCP: Ticket removed: {}", ticket_id);
+            },
         }
 
         Ok(())
@@ -2,7 +2,7 @@
 
 use crate::mcp::handlers::schema_helper::json_to_schema;
 use crate::mcp::service::VibeTicketService;
-use crate::storage::TicketRepository;
+use crate::storage::{TicketRepository, write_project_file};
 use rmcp::model::Tool;
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -224,6 +224,28 @@ pub fn register_tools() -> Vec<Tool> {
             annotations: None,
             meta: None,
         },
+        // Progress tool
+        Tool {
+            name: Cow::Borrowed("vibe-ticket_spec_progress"),
+            description: Some(Cow::Borrowed(
+                "Report how far along a specification is from the tickets generated from its tasks: counts by status per phase, percent complete, time logged, blockers and ETA",
+            )),
+            input_schema: Arc::new(json_to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "spec": {
+                        "type": "string",
+                        "description": "Specification ID (uses active spec if not provided)"
+                    }
+                },
+                "required": []
+            }))),
+            title: None,
+            output_schema: None,
+            icons: None,
+            annotations: None,
+            meta: None,
+        },
     ]
 }
 
@@ -321,9 +343,8 @@ pub fn handle_check(service: &VibeTicketService, arguments: Value) -> Result<Val
 }
 
 /// Handle creating specification from natural language
-pub fn handle_specify(_service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
-    use crate::specs::{SpecManager, Specification};
-    use std::path::PathBuf;
+pub fn handle_specify(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    use crate::specs::{GeneratorInput, SpecDocument, SpecManager, Specification, generator};
 
     #[derive(Deserialize)]
     struct Args {
@@ -337,7 +358,7 @@ pub fn handle_specify(_service: &VibeTicketService, arguments: Value) -> Result<
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
 
     // Get project directory
-    let project_dir = PathBuf::from(".vibe-ticket");
+    let project_dir = service.project_root.join(".vibe-ticket");
     if !project_dir.exists() {
         return Err("Project not initialized. Run 'vibe-ticket init' first.".to_string());
     }
@@ -369,6 +390,15 @@ pub fn handle_specify(_service: &VibeTicketService, arguments: Value) -> Result<
         .save(&spec)
         .map_err(|e| format!("Failed to save specification: {e}"))?;
 
+    // Write the specification document
+    let spec_dir = spec_manager.get_spec_dir(&spec.metadata.id);
+    let input = GeneratorInput::new(&spec, &spec_dir);
+    let spec_content = generator::for_project(&project_dir)
+        .and_then(|generator| generator.generate(SpecDocument::Spec, &input))
+        .map_err(|e| format!("Failed to generate specification: {e}"))?;
+    write_project_file(spec_dir.join(SpecDocument::Spec.file_name()), spec_content)
+        .map_err(|e| format!("Failed to save specification document: {e}"))?;
+
     Ok(json!({
         "status": "created",
         "spec_id": spec.metadata.id,
@@ -384,9 +414,10 @@ pub fn handle_specify(_service: &VibeTicketService, arguments: Value) -> Result<
 }
 
 /// Handle generating implementation plan
-pub fn handle_plan(_service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
-    use crate::specs::SpecManager;
-    use std::path::PathBuf;
+pub fn handle_plan(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    use crate::specs::{
+        GeneratorInput, ReviewConfig, SpecDocument, SpecDocumentType, SpecManager, generator,
+    };
 
     #[derive(Deserialize)]
     struct Args {
@@ -399,7 +430,7 @@ pub fn handle_plan(_service: &VibeTicketService, arguments: Value) -> Result<Val
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
 
     // Get project directory
-    let project_dir = PathBuf::from(".vibe-ticket");
+    let project_dir = service.project_root.join(".vibe-ticket");
     if !project_dir.exists() {
         return Err("Project not initialized. Run 'vibe-ticket init' first.".to_string());
     }
@@ -419,59 +450,28 @@ pub fn handle_plan(_service: &VibeTicketService, arguments: Value) -> Result<Val
     let spec = spec_manager
         .load(&spec_id)
         .map_err(|e| format!("Failed to load specification: {e}"))?;
+    ReviewConfig::for_project(&project_dir)
+        .ensure_can_start(&spec.metadata, SpecDocumentType::Design)
+        .map_err(|e| e.to_string())?;
 
-    // Generate plan document
-    let tech_stack_str = args
-        .tech_stack
-        .as_ref()
-        .map_or_else(|| "To be determined".to_string(), |ts| ts.join(", "));
-
-    let plan_content = format!(
-        r"# Implementation Plan: {}
-
-## Technology Stack
-{}
-
-## Architecture Pattern
-{}
-
-## Implementation Phases
-
-### Phase 1: Setup and Infrastructure
-- Project initialization
-- Development environment setup
-- Core dependencies installation
-
-### Phase 2: Core Implementation
-- Data models
-- Business logic
-- Core functionality
-
-### Phase 3: Integration and Testing
-- Unit tests
-- Integration tests
-- Validation against requirements
-
-### Phase 4: Documentation and Deployment
-- User documentation
-- Deployment preparation
-- Final review
-
----
-Generated on: {}
-",
-        spec.metadata.title,
-        tech_stack_str,
-        args.architecture
-            .as_deref()
-            .unwrap_or("Layered Architecture"),
-        chrono::Utc::now().format("%Y-%m-%d")
-    );
-
-    // Save plan document
+    // Generate research, data model and plan documents
     let spec_dir = spec_manager.get_spec_dir(&spec_id);
-    std::fs::write(spec_dir.join("plan.md"), &plan_content)
-        .map_err(|e| format!("Failed to save plan: {e}"))?;
+    let generator = generator::for_project(&project_dir)
+        .map_err(|e| format!("Failed to load spec generator: {e}"))?;
+    let input = GeneratorInput::new(&spec, &spec_dir)
+        .with_tech_stack(args.tech_stack.clone().unwrap_or_default())
+        .with_architecture(args.architecture.clone());
+    for document in [
+        SpecDocument::Research,
+        SpecDocument::DataModel,
+        SpecDocument::Plan,
+    ] {
+        let content = generator
+            .generate(document, &input)
+            .map_err(|e| format!("Failed to generate {}: {e}", document.as_str()))?;
+        write_project_file(spec_dir.join(document.file_name()), content)
+            .map_err(|e| format!("Failed to save {}: {e}", document.as_str()))?;
+    }
 
     Ok(json!({
         "status": "created",
@@ -485,11 +485,13 @@ Generated on: {}
 
 /// Handle generating tasks
 pub fn handle_generate_tasks(
-    _service: &VibeTicketService,
+    service: &VibeTicketService,
     arguments: Value,
 ) -> Result<Value, String> {
-    use crate::specs::SpecManager;
-    use std::path::PathBuf;
+    use crate::specs::tasks::parse_tasks;
+    use crate::specs::{
+        GeneratorInput, ReviewConfig, SpecDocument, SpecDocumentType, SpecManager, generator,
+    };
 
     #[derive(Deserialize)]
     struct Args {
@@ -503,7 +505,7 @@ pub fn handle_generate_tasks(
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
 
     // Get project directory
-    let project_dir = PathBuf::from(".vibe-ticket");
+    let project_dir = service.project_root.join(".vibe-ticket");
     if !project_dir.exists() {
         return Err("Project not initialized. Run 'vibe-ticket init' first.".to_string());
     }
@@ -523,63 +525,24 @@ pub fn handle_generate_tasks(
     let spec = spec_manager
         .load(&spec_id)
         .map_err(|e| format!("Failed to load specification: {e}"))?;
+    ReviewConfig::for_project(&project_dir)
+        .ensure_can_start(&spec.metadata, SpecDocumentType::Tasks)
+        .map_err(|e| e.to_string())?;
 
     let granularity = args.granularity.as_deref().unwrap_or("medium");
     let parallel = args.parallel.unwrap_or(false);
-    let task_prefix = if parallel { "[P] " } else { "" };
 
     // Generate tasks document
-    let tasks_content = format!(
-        r"# Tasks: {}
-
-## Task Granularity: {}
-- Parallel execution: {}
-
-## Phase 1: Setup
-- [ ] {}T001: Initialize project structure
-- [ ] {}T002: Set up development environment
-- [ ] {}T003: Install dependencies
-
-## Phase 2: Implementation
-- [ ] {}T004: Implement data models
-- [ ] {}T005: Create business logic
-- [ ] {}T006: Develop core functionality
-
-## Phase 3: Testing
-- [ ] {}T007: Write unit tests
-- [ ] {}T008: Create integration tests
-- [ ] {}T009: Perform validation
-
-## Phase 4: Deployment
-- [ ] {}T010: Prepare documentation
-- [ ] {}T011: Configure deployment
-- [ ] {}T012: Deploy to production
-
----
-Generated on: {}
-",
-        spec.metadata.title,
-        granularity,
-        if parallel { "Enabled" } else { "Disabled" },
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        task_prefix,
-        chrono::Utc::now().format("%Y-%m-%d")
-    );
-
-    // Save tasks document
     let spec_dir = spec_manager.get_spec_dir(&spec_id);
-    std::fs::write(spec_dir.join("tasks.md"), &tasks_content)
-        .map_err(|e| format!("Failed to save tasks: {e}"))?;
+    let input = GeneratorInput::new(&spec, &spec_dir).with_tasks(granularity, parallel);
+    let tasks_content = generator::for_project(&project_dir)
+        .and_then(|generator| generator.generate(SpecDocument::Tasks, &input))
+        .map_err(|e| format!("Failed to generate tasks: {e}"))?;
+    write_project_file(
+        spec_dir.join(SpecDocument::Tasks.file_name()),
+        &tasks_content,
+    )
+    .map_err(|e| format!("Failed to save tasks: {e}"))?;
 
     let mut message = "Task list generated successfully.".to_string();
 
@@ -595,16 +558,15 @@ Generated on: {}
         "title": spec.metadata.title,
         "granularity": granularity,
         "parallel": parallel,
-        "task_count": 12,
+        "task_count": parse_tasks(&tasks_content).len(),
         "message": message
     }))
 }
 
 /// Handle validating specification
 #[allow(clippy::too_many_lines)]
-pub fn handle_validate(_service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+pub fn handle_validate(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
     use crate::specs::SpecManager;
-    use std::path::PathBuf;
 
     #[derive(Deserialize)]
     struct Args {
@@ -617,7 +579,7 @@ pub fn handle_validate(_service: &VibeTicketService, arguments: Value) -> Result
         serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
 
     // Get project directory
-    let project_dir = PathBuf::from(".vibe-ticket");
+    let project_dir = service.project_root.join(".vibe-ticket");
     if !project_dir.exists() {
         return Err("Project not initialized. Run 'vibe-ticket init' first.".to_string());
     }
@@ -729,7 +691,7 @@ Generated on: {}
         );
 
         let spec_dir = spec_manager.get_spec_dir(&spec_id);
-        std::fs::write(spec_dir.join("validation-report.md"), report_content)
+        write_project_file(spec_dir.join("validation-report.md"), report_content)
             .map_err(|e| format!("Failed to save report: {e}"))?;
     }
 
@@ -739,6 +701,7 @@ Generated on: {}
         "title": spec.metadata.title,
         "validation_results": validation_results,
         "has_issues": has_issues,
+        "review": review_json(service, &spec.metadata),
         "message": if has_issues {
             "Specification has validation issues that should be addressed"
         } else {
@@ -746,3 +709,80 @@ Generated on: {}
         }
     }))
 }
+
+/// Handle reporting the progress of a specification
+pub fn handle_progress(service: &VibeTicketService, arguments: Value) -> Result<Value, String> {
+    use crate::cli::handlers::TimeTracking;
+    use crate::specs::{SpecManager, SpecPhase, SpecRollup};
+
+    #[derive(Deserialize)]
+    struct Args {
+        spec: Option<String>,
+    }
+
+    let args: Args =
+        serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {e}"))?;
+
+    let spec_manager = SpecManager::new(service.project_root.join(".vibe-ticket").join("specs"));
+    let spec_id = match args.spec {
+        Some(id) => id,
+        None => spec_manager
+            .get_active_spec()
+            .map_err(|e| format!("Failed to get active spec: {e}"))?
+            .ok_or("No active specification. Use 'spec activate' to set one.")?,
+    };
+    let spec = spec_manager
+        .load(&spec_id)
+        .map_err(|e| format!("Failed to load specification: {e}"))?;
+
+    let tickets = service
+        .storage
+        .load_all_tickets()
+        .map_err(|e| format!("Failed to load tickets: {e}"))?;
+    let time_tracking = TimeTracking::load(service.project_root.to_str())
+        .map_err(|e| format!("Failed to load time tracking: {e}"))?;
+    let mut rollup = SpecRollup::build(&spec, &tickets, |id| {
+        time_tracking.total_time_for_ticket(id)
+    });
+
+    // Move the spec to Completed once all of its tickets are done
+    if rollup.is_complete()
+        && spec_manager
+            .mark_completed(&spec_id)
+            .map_err(|e| format!("Failed to update specification: {e}"))?
+    {
+        rollup.phase = SpecPhase::Completed;
+    }
+
+    let mut value =
+        serde_json::to_value(&rollup).map_err(|e| format!("Failed to serialize progress: {e}"))?;
+    value["review"] = review_json(service, &spec.metadata);
+    Ok(value)
+}
+
+/// Sign-offs and approval status of every phase of a spec
+fn review_json(service: &VibeTicketService, metadata: &crate::specs::SpecMetadata) -> Value {
+    use crate::specs::{ReviewConfig, SpecDocumentType};
+
+    let config = ReviewConfig::for_project(&service.project_root.join(".vibe-ticket"));
+    SpecDocumentType::ALL
+        .into_iter()
+        .map(|phase| {
+            let review = metadata
+                .progress
+                .reviews
+                .get(&phase)
+                .cloned()
+                .unwrap_or_default();
+            (
+                phase.as_str().to_string(),
+                json!({
+                    "status": config.status(metadata, phase),
+                    "reviewers": review.reviewers,
+                    "sign_offs": review.sign_offs,
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
@@ -232,7 +232,7 @@ pub fn handle_complete(service: &VibeTicketService, arguments: Value) -> Result<
         "progress": {
             "completed": completed_count,
             "total": total_count,
-            "percentage": if total_count > 0 { (completed_count * 100) / total_count } else { 0 }
+            "percentage": (completed_count * 100).checked_div(total_count).unwrap_or(0)
         }
     }))
 }
@@ -273,7 +273,7 @@ pub fn handle_list(service: &VibeTicketService, arguments: Value) -> Result<Valu
         "progress": {
             "completed": completed_count,
             "total": total_count,
-            "percentage": if total_count > 0 { (completed_count * 100) / total_count } else { 0 }
+            "percentage": (completed_count * 100).checked_div(total_count).unwrap_or(0)
         },
         "tasks": tasks.iter().map(|t| json!({
             "id": t.id.to_string(),
@@ -5,6 +5,7 @@ use crate::mcp::handlers::schema_helper::{
     create_tool, filter_properties_schema, json_to_schema, ticket_properties_schema,
 };
 use crate::mcp::service::VibeTicketService;
+use crate::specs::TaskSync;
 use crate::storage::{ActiveTicketRepository, TicketRepository};
 use rmcp::model::Tool;
 use serde::Deserialize;
@@ -195,18 +196,11 @@ pub fn resolve_ticket_ref(
     }
 
     // Otherwise, search by slug
-    let tickets = service
+    service
         .storage
-        .load_all()
-        .map_err(|e| format!("Failed to list tickets: {e}"))?;
-
-    for ticket in tickets {
-        if ticket.slug == ticket_ref {
-            return Ok(ticket.id);
-        }
-    }
-
-    Err(format!("Ticket not found: {ticket_ref}"))
+        .find_ticket_id_by_slug(ticket_ref)
+        .map_err(|e| format!("Failed to list tickets: {e}"))?
+        .ok_or_else(|| format!("Ticket not found: {ticket_ref}"))
 }
 
 /// Handle creating a new ticket
@@ -431,12 +425,7 @@ pub fn handle_edit(service: &VibeTicketService, arguments: Value) -> Result<Valu
             _ => return Err(format!("Invalid status: {status_str}")),
         };
 
-        // Handle status transitions
-        if ticket.status == Status::Todo && status == Status::Doing && ticket.started_at.is_none() {
-            ticket.started_at = Some(chrono::Utc::now());
-        }
-
-        ticket.status = status;
+        set_status(service, &mut ticket, status)?;
         changes.push("status");
     }
 
@@ -481,7 +470,18 @@ pub fn handle_edit(service: &VibeTicketService, arguments: Value) -> Result<Valu
     }))
 }
 
-/// Handle closing a ticket
+/// Change the status of a ticket, keeping the spec task it was exported from in sync
+fn set_status(
+    service: &VibeTicketService,
+    ticket: &mut Ticket,
+    status: Status,
+) -> Result<(), String> {
+    TaskSync::new(service.storage.base_dir())
+        .set_status(ticket, status)
+        .map(drop)
+        .map_err(|e| format!("Failed to sync spec task: {e}"))
+}
+
 /// Handle closing a ticket
 ///
 /// # Panics
@@ -507,8 +507,7 @@ pub fn handle_close(service: &VibeTicketService, arguments: Value) -> Result<Val
         return Err("Ticket is already closed".to_string());
     }
 
-    ticket.status = Status::Done;
-    ticket.closed_at = Some(chrono::Utc::now());
+    set_status(service, &mut ticket, Status::Done)?;
 
     if let Some(message) = args.message {
         ticket
@@ -555,10 +554,7 @@ pub fn handle_start(service: &VibeTicketService, arguments: Value) -> Result<Val
 
     // Update ticket status if needed
     if ticket.status == Status::Todo {
-        ticket.status = Status::Doing;
-        if ticket.started_at.is_none() {
-            ticket.started_at = Some(chrono::Utc::now());
-        }
+        set_status(service, &mut ticket, Status::Doing)?;
         service
             .storage
             .save(&ticket)
@@ -580,15 +576,28 @@ pub fn handle_start(service: &VibeTicketService, arguments: Value) -> Result<Val
     // Handle worktree creation if not disabled
     if !args.no_worktree.unwrap_or(false) {
         // Load config to check worktree settings
-        let config = crate::config::Config::load_or_default()
-            .map_err(|e| format!("Failed to load config: {e}"))?;
+        let config_path = service
+            .project_root
+            .join(".vibe-ticket")
+            .join("config.yaml");
+        let config = if config_path.exists() {
+            crate::config::Config::load_from_path(&config_path)
+                .map_err(|e| format!("Failed to load config: {e}"))?
+        } else {
+            crate::config::Config::default()
+        };
 
         if config.git.enabled && config.git.worktree_enabled && config.git.worktree_default {
             // Create branch name
             let branch_name = format!("{}{}", config.git.branch_prefix, ticket.slug);
 
             // Try to create worktree
-            match create_git_worktree_mcp(&branch_name, &ticket.slug, &config) {
+            match create_git_worktree_mcp(
+                &service.project_root,
+                &branch_name,
+                &ticket.slug,
+                &config,
+            ) {
                 Ok(worktree_path) => {
                     response["worktree_created"] = json!(true);
                     response["worktree_path"] = json!(worktree_path);
@@ -608,19 +617,17 @@ pub fn handle_start(service: &VibeTicketService, arguments: Value) -> Result<Val
 
 /// Create a Git worktree for the ticket (MCP version)
 fn create_git_worktree_mcp(
+    project_root: &std::path::Path,
     branch_name: &str,
     ticket_slug: &str,
     config: &crate::config::Config,
 ) -> Result<String, String> {
     use std::process::Command;
 
-    // Get project root
-    let project_root =
-        std::env::current_dir().map_err(|e| format!("Failed to get current directory: {e}"))?;
-
     // Check if we're in a git repository
     let status = Command::new("git")
         .args(["status"])
+        .current_dir(project_root)
         .output()
         .map_err(|e| format!("Failed to run git status: {e}"))?;
 
@@ -630,6 +637,7 @@ fn create_git_worktree_mcp(
 
     // Check if branch already exists
     let branch_exists = Command::new("git")
+        .current_dir(project_root)
         .args([
             "show-ref",
             "--verify",
@@ -649,7 +657,7 @@ fn create_git_worktree_mcp(
     let worktree_dir = format!("{worktree_prefix}{ticket_slug}");
     let worktree_path = project_root
         .parent()
-        .unwrap_or(&project_root)
+        .unwrap_or(project_root)
         .join(&worktree_dir);
 
     // Check if worktree already exists
@@ -662,7 +670,7 @@ fn create_git_worktree_mcp(
 
     // Create worktree with new or existing branch
     let mut cmd = Command::new("git");
-    cmd.arg("worktree").arg("add");
+    cmd.current_dir(project_root).arg("worktree").arg("add");
 
     if !branch_exists {
         cmd.arg("-b");
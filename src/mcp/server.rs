@@ -61,9 +61,19 @@ impl McpServer {
             use crate::mcp::handlers::events::McpEventHandler;
             use std::sync::Arc;
             let mcp_handler = McpEventHandler::new(Arc::new(service.clone()));
-            crate::mcp::event_bridge::start_event_bridge(mcp_handler);
+            crate::mcp::event_bridge::start_event_bridge(mcp_handler).await;
         }
 
+        // Notice tickets changed by other processes, so the cache never
+        // serves stale tickets
+        let _watch = match crate::events::TicketWatcher::new(&service.storage) {
+            Ok(watcher) => Some(watcher.forward_to_event_bus()),
+            Err(error) => {
+                tracing::warn!("Not watching ticket files: {error}");
+                None
+            },
+        };
+
         // Create stdio transport
         let transport = (tokio::io::stdin(), tokio::io::stdout());
 
@@ -43,11 +43,114 @@ impl VibeTicketService {
         tools.extend(handlers::search::register_tools());
         tools.extend(handlers::config::register_tools());
         tools.extend(handlers::spec::register_tools());
+        tools.extend(handlers::cli::register_tools());
+
+        // Every tool can target another project than the server's own
+        for tool in &mut tools {
+            let schema = Arc::make_mut(&mut tool.input_schema);
+            if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+                properties.insert(
+                    PROJECT_ARG.to_string(),
+                    serde_json::json!({
+                        "type": "string",
+                        "description": "Project directory to operate on (defaults to the server's project; relative paths are resolved against it)"
+                    }),
+                );
+            }
+        }
 
         tools
     }
+
+    /// Scope the service to another project
+    ///
+    /// `project` is a directory containing `.vibe-ticket`, or a subdirectory
+    /// of one; relative paths are resolved against the server's project root.
+    /// Without a project, the service itself is returned.
+    pub fn for_project(&self, project: Option<&str>) -> Result<Self, String> {
+        let Some(project) = project else {
+            return Ok(self.clone());
+        };
+        let path = self.project_root.join(project);
+        let project_root = crate::cli::find_project_root(path.to_str())
+            .map_err(|_| format!("No vibe-ticket project found at {}", path.display()))?;
+        if project_root == self.project_root {
+            return Ok(self.clone());
+        }
+        Ok(Self::new(
+            FileStorage::new(project_root.join(".vibe-ticket")),
+            project_root,
+        ))
+    }
+
+    /// Run a tool and return its result
+    ///
+    /// The optional `project` argument selects the project the tool runs on
+    /// (see [`Self::for_project`]); it is removed before the arguments reach
+    /// the tool handler.
+    pub fn call(&self, name: &str, mut arguments: Value) -> Result<Value, String> {
+        use crate::mcp::handlers;
+
+        let project = arguments
+            .as_object_mut()
+            .and_then(|arguments| arguments.remove(PROJECT_ARG));
+        let project = match &project {
+            Some(Value::String(project)) => Some(project.as_str()),
+            Some(Value::Null) | None => None,
+            Some(_) => return Err(format!("'{PROJECT_ARG}' must be a string")),
+        };
+        let service = self.for_project(project)?;
+
+        match name {
+            // Ticket operations
+            "vibe-ticket_new" => handlers::tickets::handle_new(&service, arguments),
+            "vibe-ticket_list" => handlers::tickets::handle_list(&service, arguments),
+            "vibe-ticket_show" => handlers::tickets::handle_show(&service, arguments),
+            "vibe-ticket_edit" => handlers::tickets::handle_edit(&service, arguments),
+            "vibe-ticket_close" => handlers::tickets::handle_close(&service, arguments),
+            "vibe-ticket_start" => handlers::tickets::handle_start(&service, arguments),
+            "vibe-ticket_check" => handlers::tickets::handle_check(&service, arguments),
+
+            // Task operations
+            "vibe-ticket_task_add" => handlers::tasks::handle_add(&service, arguments),
+            "vibe-ticket_task_complete" => handlers::tasks::handle_complete(&service, arguments),
+            "vibe-ticket_task_list" => handlers::tasks::handle_list(&service, arguments),
+            "vibe-ticket_task_remove" => handlers::tasks::handle_remove(&service, arguments),
+
+            // Worktree operations
+            "vibe-ticket_worktree_list" => handlers::worktree::handle_list(&service, arguments),
+            "vibe-ticket_worktree_remove" => handlers::worktree::handle_remove(&service, arguments),
+            "vibe-ticket_worktree_prune" => handlers::worktree::handle_prune(&service, arguments),
+
+            // Search and export
+            "vibe-ticket_search" => handlers::search::handle_search(&service, arguments),
+            "vibe-ticket_export" => handlers::search::handle_export(&service, arguments),
+            "vibe-ticket_import" => handlers::search::handle_import(&service, arguments),
+
+            // Config operations
+            "vibe-ticket_config_show" => handlers::config::handle_show(&service, arguments),
+            "vibe-ticket_config_set" => handlers::config::handle_set(&service, arguments),
+
+            // Spec operations
+            "vibe-ticket_spec_add" => handlers::spec::handle_add(&service, arguments),
+            "vibe-ticket_spec_update" => handlers::spec::handle_update(&service, arguments),
+            "vibe-ticket_spec_check" => handlers::spec::handle_check(&service, arguments),
+            "vibe-ticket_spec_specify" => handlers::spec::handle_specify(&service, arguments),
+            "vibe-ticket_spec_plan" => handlers::spec::handle_plan(&service, arguments),
+            "vibe-ticket_spec_generate_tasks" => {
+                handlers::spec::handle_generate_tasks(&service, arguments)
+            },
+            "vibe-ticket_spec_validate" => handlers::spec::handle_validate(&service, arguments),
+            "vibe-ticket_spec_progress" => handlers::spec::handle_progress(&service, arguments),
+
+            _ => handlers::cli::handle(&service, name, arguments),
+        }
+    }
 }
 
+/// Tool argument selecting the project a tool runs on
+pub const PROJECT_ARG: &str = "project";
+
 // Implement ServerHandler trait for MCP protocol
 #[allow(refining_impl_trait_reachable)]
 impl ServerHandler for VibeTicketService {
@@ -90,97 +193,7 @@ impl ServerHandler for VibeTicketService {
         let arguments = Value::Object(request.arguments.unwrap_or_default());
 
         Box::pin(async move {
-            let result = match name.as_ref() {
-                // Ticket operations
-                "vibe-ticket_new" => crate::mcp::handlers::tickets::handle_new(&service, arguments),
-                "vibe-ticket_list" => {
-                    crate::mcp::handlers::tickets::handle_list(&service, arguments)
-                },
-                "vibe-ticket_show" => {
-                    crate::mcp::handlers::tickets::handle_show(&service, arguments)
-                },
-                "vibe-ticket_edit" => {
-                    crate::mcp::handlers::tickets::handle_edit(&service, arguments)
-                },
-                "vibe-ticket_close" => {
-                    crate::mcp::handlers::tickets::handle_close(&service, arguments)
-                },
-                "vibe-ticket_start" => {
-                    crate::mcp::handlers::tickets::handle_start(&service, arguments)
-                },
-                "vibe-ticket_check" => {
-                    crate::mcp::handlers::tickets::handle_check(&service, arguments)
-                },
-
-                // Task operations
-                "vibe-ticket_task_add" => {
-                    crate::mcp::handlers::tasks::handle_add(&service, arguments)
-                },
-                "vibe-ticket_task_complete" => {
-                    crate::mcp::handlers::tasks::handle_complete(&service, arguments)
-                },
-                "vibe-ticket_task_list" => {
-                    crate::mcp::handlers::tasks::handle_list(&service, arguments)
-                },
-                "vibe-ticket_task_remove" => {
-                    crate::mcp::handlers::tasks::handle_remove(&service, arguments)
-                },
-
-                // Worktree operations
-                "vibe-ticket_worktree_list" => {
-                    crate::mcp::handlers::worktree::handle_list(&service, arguments)
-                },
-                "vibe-ticket_worktree_remove" => {
-                    crate::mcp::handlers::worktree::handle_remove(&service, arguments)
-                },
-                "vibe-ticket_worktree_prune" => {
-                    crate::mcp::handlers::worktree::handle_prune(&service, arguments)
-                },
-
-                // Search and export
-                "vibe-ticket_search" => {
-                    crate::mcp::handlers::search::handle_search(&service, arguments)
-                },
-                "vibe-ticket_export" => {
-                    crate::mcp::handlers::search::handle_export(&service, arguments)
-                },
-                "vibe-ticket_import" => {
-                    crate::mcp::handlers::search::handle_import(&service, arguments)
-                },
-
-                // Config operations
-                "vibe-ticket_config_show" => {
-                    crate::mcp::handlers::config::handle_show(&service, arguments)
-                },
-                "vibe-ticket_config_set" => {
-                    crate::mcp::handlers::config::handle_set(&service, arguments)
-                },
-
-                // Spec operations
-                "vibe-ticket_spec_add" => {
-                    crate::mcp::handlers::spec::handle_add(&service, arguments)
-                },
-                "vibe-ticket_spec_update" => {
-                    crate::mcp::handlers::spec::handle_update(&service, arguments)
-                },
-                "vibe-ticket_spec_check" => {
-                    crate::mcp::handlers::spec::handle_check(&service, arguments)
-                },
-                "vibe-ticket_spec_specify" => {
-                    crate::mcp::handlers::spec::handle_specify(&service, arguments)
-                },
-                "vibe-ticket_spec_plan" => {
-                    crate::mcp::handlers::spec::handle_plan(&service, arguments)
-                },
-                "vibe-ticket_spec_generate_tasks" => {
-                    crate::mcp::handlers::spec::handle_generate_tasks(&service, arguments)
-                },
-                "vibe-ticket_spec_validate" => {
-                    crate::mcp::handlers::spec::handle_validate(&service, arguments)
-                },
-
-                _ => Err(format!("Unknown tool: {name}")),
-            };
+            let result = service.call(&name, arguments);
 
             match result {
                 Ok(content) => Ok(rmcp::model::CallToolResult {
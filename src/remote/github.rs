@@ -0,0 +1,237 @@
+//! GitHub issues remote
+//!
+//! Talks to the GitHub REST API directly. The base URL is configurable so
+//! GitHub Enterprise Server and local mock servers can be used.
+
+use super::{IssueFields, Remote, RemoteIssue};
+use crate::error::{Result, VibeTicketError};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Default GitHub API endpoint
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Issues requested per page when listing
+const PAGE_SIZE: usize = 100;
+
+/// GitHub issues of a single repository
+pub struct GitHubRemote {
+    agent: ureq::Agent,
+    api_url: String,
+    repository: String,
+    token: Option<String>,
+}
+
+impl GitHubRemote {
+    /// Create a remote for `owner/repo` at the given API base URL
+    pub fn new(
+        api_url: impl Into<String>,
+        repository: impl Into<String>,
+        token: Option<String>,
+    ) -> Result<Self> {
+        let repository = repository.into();
+        if repository
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .count()
+            != 2
+        {
+            return Err(VibeTicketError::custom(format!(
+                "Invalid GitHub repository '{repository}': expected owner/repo"
+            )));
+        }
+        Ok(Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(concat!("vibe-ticket/", env!("CARGO_PKG_VERSION")))
+                .build(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            repository,
+            token,
+        })
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let url = format!("{}/repos/{}{path}", self.api_url, self.repository);
+        let request = self
+            .agent
+            .request(method, &url)
+            .set("Accept", "application/vnd.github+json")
+            .set("X-GitHub-Api-Version", "2022-11-28");
+        match &self.token {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    fn send(request: ureq::Request, body: Option<serde_json::Value>) -> Result<ureq::Response> {
+        let result = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        result.map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                let message = response
+                    .into_json::<serde_json::Value>()
+                    .ok()
+                    .and_then(|json| json.get("message")?.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                VibeTicketError::custom(format!("GitHub API returned {code}: {message}"))
+            },
+            ureq::Error::Transport(transport) => {
+                VibeTicketError::custom(format!("GitHub API request failed: {transport}"))
+            },
+        })
+    }
+
+    fn parse(response: ureq::Response) -> Result<ApiIssue> {
+        response
+            .into_json()
+            .map_err(|e| VibeTicketError::custom(format!("Invalid GitHub API response: {e}")))
+    }
+
+    fn payload(fields: &IssueFields) -> serde_json::Value {
+        serde_json::json!({
+            "title": fields.title,
+            "body": fields.body,
+            "state": if fields.open { "open" } else { "closed" },
+            "labels": fields.labels,
+            "assignees": fields.assignee.iter().collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Remote for GitHubRemote {
+    fn name(&self) -> &'static str {
+        "github"
+    }
+
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>> {
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request("GET", "/issues")
+                .query("state", "all")
+                .query("per_page", &PAGE_SIZE.to_string())
+                .query("page", &page.to_string());
+            let batch: Vec<ApiIssue> = Self::send(request, None)?.into_json().map_err(|e| {
+                VibeTicketError::custom(format!("Invalid GitHub API response: {e}"))
+            })?;
+            let done = batch.len() < PAGE_SIZE;
+            issues.extend(
+                batch
+                    .into_iter()
+                    .filter(|issue| issue.pull_request.is_none())
+                    .map(RemoteIssue::from),
+            );
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    fn get_issue(&self, id: &str) -> Result<RemoteIssue> {
+        let response = Self::send(self.request("GET", &format!("/issues/{id}")), None)?;
+        Self::parse(response).map(RemoteIssue::from)
+    }
+
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue> {
+        let created = Self::parse(Self::send(
+            self.request("POST", "/issues"),
+            Some(Self::payload(fields)),
+        )?)?;
+        // New issues are always open; close it in a second request if needed
+        if fields.open {
+            Ok(created.into())
+        } else {
+            self.update_issue(&created.number.to_string(), fields)
+        }
+    }
+
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue> {
+        let response = Self::send(
+            self.request("PATCH", &format!("/issues/{id}")),
+            Some(Self::payload(fields)),
+        )?;
+        Self::parse(response).map(RemoteIssue::from)
+    }
+}
+
+/// Issue as returned by the REST API
+#[derive(Deserialize)]
+struct ApiIssue {
+    number: u64,
+    html_url: Option<String>,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    labels: Vec<ApiLabel>,
+    #[serde(default)]
+    assignees: Vec<ApiUser>,
+    updated_at: DateTime<Utc>,
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ApiLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+impl From<ApiIssue> for RemoteIssue {
+    fn from(issue: ApiIssue) -> Self {
+        let mut labels: Vec<String> = issue.labels.into_iter().map(|l| l.name).collect();
+        labels.sort();
+        Self {
+            id: issue.number.to_string(),
+            url: issue.html_url,
+            fields: IssueFields {
+                title: issue.title,
+                body: issue.body.unwrap_or_default(),
+                open: issue.state == "open",
+                labels,
+                assignee: issue.assignees.into_iter().next().map(|u| u.login),
+            },
+            updated_at: issue.updated_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repository_validation() {
+        assert!(GitHubRemote::new(DEFAULT_API_URL, "owner/repo", None).is_ok());
+        assert!(GitHubRemote::new(DEFAULT_API_URL, "owner", None).is_err());
+        assert!(GitHubRemote::new(DEFAULT_API_URL, "a/b/c", None).is_err());
+    }
+
+    #[test]
+    fn test_api_issue_conversion() {
+        let issue: ApiIssue = serde_json::from_value(serde_json::json!({
+            "number": 4,
+            "html_url": "https://github.com/o/r/issues/4",
+            "title": "Title",
+            "body": null,
+            "state": "closed",
+            "labels": [{"name": "status:doing"}, {"name": "bug"}],
+            "assignees": [{"login": "alice"}],
+            "updated_at": "2025-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let remote = RemoteIssue::from(issue);
+        assert_eq!(remote.id, "4");
+        assert!(!remote.fields.open);
+        assert_eq!(remote.fields.labels, vec!["bug", "status:doing"]);
+        assert_eq!(remote.fields.assignee.as_deref(), Some("alice"));
+    }
+}
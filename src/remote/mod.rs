@@ -0,0 +1,72 @@
+//! Remote issue trackers for two-way ticket synchronization
+//!
+//! A [`Remote`] is an issue tracker that tickets can be mirrored to. The
+//! [`SyncEngine`] maps tickets to issues and back, and records the remote
+//! issue ID together with the remote `updated_at` of the last sync in the
+//! ticket metadata. Comparing those values on the next sync tells whether the
+//! remote issue, the local ticket, or both have changed since.
+//!
+//! # Ticket ↔ issue mapping
+//!
+//! - Title and description map to the issue title and body
+//! - Tasks are appended to the body as a Markdown checklist
+//! - `done` maps to a closed issue; other statuses stay open and carry a
+//!   `status:<status>` label
+//! - Priority is a `priority:<priority>` label and tags are plain labels
+//! - The assignee maps to the first issue assignee
+
+pub mod github;
+mod sync;
+
+pub use github::GitHubRemote;
+pub use sync::{SyncAction, SyncEngine, SyncEntry};
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// An issue as stored on a remote tracker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteIssue {
+    /// Remote identifier, e.g. the issue number
+    pub id: String,
+    /// Link to the issue
+    pub url: Option<String>,
+    /// Issue contents
+    pub fields: IssueFields,
+    /// Last modification time reported by the remote
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The synchronized fields of an issue
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IssueFields {
+    /// Issue title
+    pub title: String,
+    /// Issue body (Markdown)
+    pub body: String,
+    /// Whether the issue is open
+    pub open: bool,
+    /// Labels, sorted
+    pub labels: Vec<String>,
+    /// Assignee login
+    pub assignee: Option<String>,
+}
+
+/// An issue tracker that tickets can be synchronized with
+pub trait Remote {
+    /// Short name used as metadata prefix, e.g. `github`
+    fn name(&self) -> &'static str;
+
+    /// List all issues, open and closed
+    fn list_issues(&self) -> Result<Vec<RemoteIssue>>;
+
+    /// Fetch a single issue
+    fn get_issue(&self, id: &str) -> Result<RemoteIssue>;
+
+    /// Create a new issue
+    fn create_issue(&self, fields: &IssueFields) -> Result<RemoteIssue>;
+
+    /// Overwrite an existing issue
+    fn update_issue(&self, id: &str, fields: &IssueFields) -> Result<RemoteIssue>;
+}
@@ -0,0 +1,444 @@
+//! Push and pull of tickets against a [`Remote`]
+
+use super::{IssueFields, Remote, RemoteIssue};
+use crate::cli::slugify;
+use crate::core::{Priority, Status, Task, Ticket, TicketId};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Marker separating the description from the task checklist in issue bodies
+const TASKS_MARKER: &str = "<!-- vibe-ticket:tasks -->";
+
+/// Label prefix carrying the ticket status
+const STATUS_LABEL: &str = "status:";
+
+/// Label prefix carrying the ticket priority
+const PRIORITY_LABEL: &str = "priority:";
+
+/// What happened to a ticket during a sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncAction {
+    /// A remote issue was created for the ticket
+    Created,
+    /// The remote issue or the ticket was updated
+    Updated,
+    /// A new ticket was created from a remote issue
+    Imported,
+    /// Nothing changed since the last sync
+    Unchanged,
+    /// Both sides changed since the last sync
+    Conflict(String),
+    /// The remote rejected the change
+    Failed(String),
+}
+
+impl SyncAction {
+    /// Whether the local ticket was modified and needs saving
+    #[must_use]
+    pub const fn is_change(&self) -> bool {
+        matches!(self, Self::Created | Self::Updated | Self::Imported)
+    }
+}
+
+/// Result of syncing one ticket
+#[derive(Debug, Clone)]
+pub struct SyncEntry {
+    /// Ticket ID
+    pub ticket_id: TicketId,
+    /// Ticket slug
+    pub slug: String,
+    /// Remote issue ID, if linked
+    pub issue: Option<String>,
+    /// Outcome
+    pub action: SyncAction,
+}
+
+/// Synchronizes tickets with a remote issue tracker
+pub struct SyncEngine<'a> {
+    remote: &'a dyn Remote,
+    force: bool,
+    dry_run: bool,
+}
+
+impl<'a> SyncEngine<'a> {
+    /// Create a sync engine for a remote
+    #[must_use]
+    pub fn new(remote: &'a dyn Remote) -> Self {
+        Self {
+            remote,
+            force: false,
+            dry_run: false,
+        }
+    }
+
+    /// Overwrite the other side even when both sides changed
+    #[must_use]
+    pub const fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Report what would happen without changing anything
+    #[must_use]
+    pub const fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Push tickets to the remote
+    ///
+    /// Unlinked tickets get a new issue. Linked tickets are only pushed when
+    /// they changed since the last sync; if the issue changed remotely too,
+    /// the ticket is reported as a conflict unless forced.
+    pub fn push(&self, tickets: &mut [Ticket]) -> Vec<SyncEntry> {
+        tickets
+            .iter_mut()
+            .map(|ticket| {
+                let action = self
+                    .push_ticket(ticket)
+                    .unwrap_or_else(|e| SyncAction::Failed(e.to_string()));
+                self.entry(ticket, action)
+            })
+            .collect()
+    }
+
+    /// Pull remote issues into tickets
+    ///
+    /// Linked tickets are updated when their issue changed since the last
+    /// sync; if the ticket also has unpushed changes, it is reported as a
+    /// conflict unless forced. Unlinked issues become new tickets.
+    pub fn pull(&self, tickets: &mut Vec<Ticket>) -> Result<Vec<SyncEntry>> {
+        let issues = self.remote.list_issues()?;
+        let mut linked: HashMap<String, usize> = tickets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, ticket)| Some((self.link(ticket)?.to_string(), index)))
+            .collect();
+
+        let mut entries = Vec::new();
+        for issue in issues {
+            if let Some(index) = linked.remove(&issue.id) {
+                let ticket = &mut tickets[index];
+                let action = self.pull_issue(ticket, &issue);
+                entries.push(self.entry(ticket, action));
+            } else {
+                let ticket = self.ticket_from_issue(&issue);
+                entries.push(self.entry(&ticket, SyncAction::Imported));
+                if !self.dry_run {
+                    tickets.push(ticket);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    fn push_ticket(&self, ticket: &mut Ticket) -> Result<SyncAction> {
+        let fields = issue_fields(ticket);
+        let fingerprint = fingerprint(&fields);
+
+        let Some(id) = self.link(ticket).map(str::to_string) else {
+            if !self.dry_run {
+                let issue = self.remote.create_issue(&fields)?;
+                self.record(ticket, &issue, &fields);
+            }
+            return Ok(SyncAction::Created);
+        };
+
+        if self.meta(ticket, "fingerprint") == Some(fingerprint.as_str()) {
+            return Ok(SyncAction::Unchanged);
+        }
+        let remote = self.remote.get_issue(&id)?;
+        if self.remote_changed(ticket, &remote) && !self.force {
+            return Ok(SyncAction::Conflict(
+                "issue changed remotely since the last sync; pull first or use --force".into(),
+            ));
+        }
+        if !self.dry_run {
+            let issue = self.remote.update_issue(&id, &fields)?;
+            self.record(ticket, &issue, &fields);
+        }
+        Ok(SyncAction::Updated)
+    }
+
+    fn pull_issue(&self, ticket: &mut Ticket, issue: &RemoteIssue) -> SyncAction {
+        if !self.remote_changed(ticket, issue) {
+            return SyncAction::Unchanged;
+        }
+        let local_changed =
+            self.meta(ticket, "fingerprint") != Some(fingerprint(&issue_fields(ticket)).as_str());
+        if local_changed && !self.force {
+            return SyncAction::Conflict(
+                "ticket has unpushed changes; use --force to take the remote version".into(),
+            );
+        }
+        if !self.dry_run {
+            apply_issue(ticket, &issue.fields);
+            let fields = issue_fields(ticket);
+            self.record(ticket, issue, &fields);
+        }
+        SyncAction::Updated
+    }
+
+    fn ticket_from_issue(&self, issue: &RemoteIssue) -> Ticket {
+        let mut ticket = Ticket::new(
+            format!(
+                "{}-{}-{}",
+                self.remote.name(),
+                issue.id,
+                slugify(&issue.fields.title)
+            )
+            .trim_end_matches('-')
+            .to_string(),
+            issue.fields.title.clone(),
+        );
+        apply_issue(&mut ticket, &issue.fields);
+        let fields = issue_fields(&ticket);
+        self.record(&mut ticket, issue, &fields);
+        ticket
+    }
+
+    fn entry(&self, ticket: &Ticket, action: SyncAction) -> SyncEntry {
+        SyncEntry {
+            ticket_id: ticket.id.clone(),
+            slug: ticket.slug.clone(),
+            issue: self.link(ticket).map(str::to_string),
+            action,
+        }
+    }
+
+    fn key(&self, field: &str) -> String {
+        format!("{}_{field}", self.remote.name())
+    }
+
+    fn meta<'t>(&self, ticket: &'t Ticket, field: &str) -> Option<&'t str> {
+        ticket
+            .metadata
+            .get(&self.key(field))
+            .and_then(serde_json::Value::as_str)
+    }
+
+    /// Remote issue ID the ticket is linked to
+    fn link<'t>(&self, ticket: &'t Ticket) -> Option<&'t str> {
+        self.meta(ticket, "issue")
+    }
+
+    fn remote_changed(&self, ticket: &Ticket, issue: &RemoteIssue) -> bool {
+        let synced = self
+            .meta(ticket, "updated_at")
+            .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        synced != Some(issue.updated_at)
+    }
+
+    /// Remember the state both sides agreed on after a sync
+    fn record(&self, ticket: &mut Ticket, issue: &RemoteIssue, fields: &IssueFields) {
+        let mut set = |field: &str, value: String| {
+            ticket.metadata.insert(self.key(field), value.into());
+        };
+        set("issue", issue.id.clone());
+        set("updated_at", issue.updated_at.to_rfc3339());
+        set("fingerprint", fingerprint(fields));
+        if let Some(url) = &issue.url {
+            set("url", url.clone());
+        }
+    }
+}
+
+/// Map a ticket to issue fields
+#[must_use]
+pub fn issue_fields(ticket: &Ticket) -> IssueFields {
+    let mut body = ticket.description.trim_end().to_string();
+    if !ticket.tasks.is_empty() {
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(TASKS_MARKER);
+        body.push_str("\n## Tasks\n\n");
+        for task in &ticket.tasks {
+            let check = if task.completed { 'x' } else { ' ' };
+            let _ = writeln!(body, "- [{check}] {}", task.title);
+        }
+    }
+
+    let mut labels = ticket.tags.clone();
+    if !matches!(ticket.status, Status::Todo | Status::Done) {
+        labels.push(format!(
+            "{STATUS_LABEL}{}",
+            ticket.status.to_string().to_lowercase()
+        ));
+    }
+    labels.push(format!(
+        "{PRIORITY_LABEL}{}",
+        ticket.priority.to_string().to_lowercase()
+    ));
+    labels.sort();
+    labels.dedup();
+
+    IssueFields {
+        title: ticket.title.clone(),
+        body,
+        open: ticket.status != Status::Done,
+        labels,
+        assignee: ticket.assignee.clone(),
+    }
+}
+
+/// Update a ticket from issue fields
+pub fn apply_issue(ticket: &mut Ticket, fields: &IssueFields) {
+    let (description, tasks) = fields
+        .body
+        .split_once(TASKS_MARKER)
+        .map_or((fields.body.as_str(), ""), |(description, tasks)| {
+            (description, tasks)
+        });
+    ticket.title.clone_from(&fields.title);
+    ticket.description = description.trim_end().to_string();
+
+    // Keep task identity for tasks whose title did not change
+    let mut existing: Vec<Task> = std::mem::take(&mut ticket.tasks);
+    for (title, completed) in parse_checklist(tasks) {
+        let mut task = existing
+            .iter()
+            .position(|task| task.title == title)
+            .map_or_else(|| Task::new(title), |index| existing.remove(index));
+        if completed {
+            task.complete();
+        } else {
+            task.uncomplete();
+        }
+        ticket.tasks.push(task);
+    }
+
+    let mut status = if fields.open {
+        Status::Todo
+    } else {
+        Status::Done
+    };
+    ticket.tags.clear();
+    for label in &fields.labels {
+        // Labels that only look like ours, e.g. `priority:p0`, stay tags
+        if let Some(parsed) = label
+            .strip_prefix(STATUS_LABEL)
+            .and_then(|value| Status::try_from(value).ok())
+        {
+            if fields.open {
+                status = parsed;
+            }
+        } else if let Some(parsed) = label
+            .strip_prefix(PRIORITY_LABEL)
+            .and_then(|value| Priority::try_from(value).ok())
+        {
+            ticket.priority = parsed;
+        } else {
+            ticket.tags.push(label.clone());
+        }
+    }
+
+    if status != ticket.status {
+        ticket.set_status(status);
+    }
+    ticket.assignee.clone_from(&fields.assignee);
+}
+
+fn parse_checklist(section: &str) -> Vec<(String, bool)> {
+    section
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = line
+                .strip_prefix("- [")
+                .or_else(|| line.strip_prefix("* ["))?;
+            let (check, title) = rest.split_once("] ")?;
+            let completed = match check {
+                "x" | "X" => true,
+                " " => false,
+                _ => return None,
+            };
+            Some((title.trim().to_string(), completed))
+        })
+        .collect()
+}
+
+/// Stable fingerprint of issue fields (64-bit FNV-1a of their JSON form)
+fn fingerprint(fields: &IssueFields) -> String {
+    let json = serde_json::to_string(fields).unwrap_or_default();
+    let hash = json.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_ticket() -> Ticket {
+        let mut ticket = Ticket::new("fix-login", "Fix login");
+        ticket.description = "Users cannot log in".to_string();
+        ticket.status = Status::Doing;
+        ticket.priority = Priority::High;
+        ticket.tags = vec!["bug".to_string()];
+        ticket.tasks = vec![Task::new("Reproduce"), Task::new("Write fix")];
+        ticket.tasks[0].complete();
+        ticket
+    }
+
+    #[test]
+    fn test_issue_fields_mapping() {
+        let fields = issue_fields(&sample_ticket());
+        assert!(fields.open);
+        assert_eq!(fields.labels, vec!["bug", "priority:high", "status:doing"]);
+        assert!(fields.body.starts_with("Users cannot log in\n\n"));
+        assert!(fields.body.contains("- [x] Reproduce\n- [ ] Write fix\n"));
+    }
+
+    #[test]
+    fn test_apply_issue_round_trip() {
+        let ticket = sample_ticket();
+        let mut fields = issue_fields(&ticket);
+        fields.body = fields.body.replace("- [ ] Write fix", "- [x] Write fix");
+        fields.open = false;
+        fields.labels.retain(|label| label.starts_with("priority:"));
+
+        let mut pulled = ticket.clone();
+        apply_issue(&mut pulled, &fields);
+        assert_eq!(pulled.description, ticket.description);
+        assert_eq!(pulled.status, Status::Done);
+        assert!(pulled.closed_at.is_some());
+        assert!(pulled.tags.is_empty());
+        assert_eq!(pulled.tasks[1].id, ticket.tasks[1].id);
+        assert!(pulled.tasks.iter().all(|task| task.completed));
+        assert_eq!(issue_fields(&pulled), fields);
+    }
+
+    #[test]
+    fn test_apply_issue_keeps_unknown_prefixed_labels() {
+        let ticket = sample_ticket();
+        let mut fields = issue_fields(&ticket);
+        fields.labels = vec![
+            "priority:p0".to_string(),
+            "status:legacy".to_string(),
+            "status:doing".to_string(),
+        ];
+
+        let mut pulled = ticket.clone();
+        apply_issue(&mut pulled, &fields);
+        assert_eq!(pulled.tags, vec!["priority:p0", "status:legacy"]);
+        assert_eq!(pulled.priority, ticket.priority);
+        assert_eq!(pulled.status, Status::Doing);
+
+        // Pushing the ticket back keeps the labels as they were
+        let mut again = pulled.clone();
+        apply_issue(&mut again, &issue_fields(&pulled));
+        assert_eq!(again.tags, pulled.tags);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let fields = issue_fields(&sample_ticket());
+        assert_eq!(fingerprint(&fields), fingerprint(&fields.clone()));
+        assert_ne!(fingerprint(&fields), fingerprint(&IssueFields::default()));
+    }
+}
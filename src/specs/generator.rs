@@ -0,0 +1,883 @@
+//! Generators for the documents of a spec
+//!
+//! `spec specify`, `spec plan` and `spec tasks` (and their MCP tools) write
+//! their documents through a [`SpecGenerator`]. The project picks one in the
+//! `spec` section of `.vibe-ticket/config.yaml`:
+//!
+//! ```yaml
+//! spec:
+//!   generator: template   # builtin, template or command
+//!   command: ./scripts/generate-spec-doc
+//! ```
+//!
+//! - [`BuiltinGenerator`] writes the fixed boilerplate documents.
+//! - [`TemplateGenerator`] renders Tera templates from `.vibe-ticket/templates/`
+//!   (`spec-template.md`, `plan-template.md`, `task-template.md`,
+//!   `research-template.md`, `data-model-template.md`), falling back to the
+//!   built-in document when a template is missing.
+//! - [`CommandGenerator`] pipes the [`GeneratorInput`] as JSON into a command
+//!   and reads the document from its standard output.
+
+use super::{SpecTemplate, Specification, TemplateEngine};
+use crate::error::{Result, VibeTicketError};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+use std::thread;
+use tera::{Context, Tera};
+
+/// Templates directory, relative to `.vibe-ticket`
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// Architecture used when none is given
+const DEFAULT_ARCHITECTURE: &str = "Layered Architecture";
+
+/// Plain `{{ variable }}` references in a template
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").expect("valid regex"));
+
+/// A document generated for a spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpecDocument {
+    /// Specification written from the user's requirements (`spec.md`)
+    Spec,
+    /// Research and technical analysis (`research.md`)
+    Research,
+    /// Data model (`data-model.md`)
+    DataModel,
+    /// Implementation plan (`plan.md`)
+    Plan,
+    /// Task list (`tasks.md`)
+    Tasks,
+}
+
+impl SpecDocument {
+    /// Name of the document as passed to generator commands
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Spec => "spec",
+            Self::Research => "research",
+            Self::DataModel => "data-model",
+            Self::Plan => "plan",
+            Self::Tasks => "tasks",
+        }
+    }
+
+    /// File name of the document in the spec directory
+    #[must_use]
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::Spec => "spec.md",
+            Self::Research => "research.md",
+            Self::DataModel => "data-model.md",
+            Self::Plan => "plan.md",
+            Self::Tasks => "tasks.md",
+        }
+    }
+
+    /// File name of the document's template in the templates directory
+    #[must_use]
+    pub const fn template_name(self) -> &'static str {
+        match self {
+            Self::Spec => "spec-template.md",
+            Self::Research => "research-template.md",
+            Self::DataModel => "data-model-template.md",
+            Self::Plan => "plan-template.md",
+            Self::Tasks => "task-template.md",
+        }
+    }
+}
+
+/// Which generator a project uses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GeneratorKind {
+    /// Built-in boilerplate documents
+    #[default]
+    Builtin,
+    /// Tera templates from `.vibe-ticket/templates/`
+    Template,
+    /// An external command
+    Command,
+}
+
+/// Everything a generator knows about the spec being written
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratorInput {
+    /// Spec ID
+    pub spec_id: String,
+    /// Spec title
+    pub title: String,
+    /// Requirements the spec was created from
+    pub requirements: String,
+    /// Content of `spec.md`, or the requirements if it does not exist yet
+    pub spec_content: String,
+    /// Sections of `spec.md` by snake-cased heading, e.g. `functional_requirements`
+    pub sections: BTreeMap<String, String>,
+    /// Content of `plan.md`, if any
+    pub plan_content: Option<String>,
+    /// Technology stack
+    pub tech_stack: Vec<String>,
+    /// Architecture pattern
+    pub architecture: Option<String>,
+    /// Task granularity: `fine`, `medium` or `coarse`
+    pub granularity: String,
+    /// Whether tasks are marked for parallel execution
+    pub parallel: bool,
+    /// Directory the documents are written to
+    pub spec_dir: PathBuf,
+}
+
+impl GeneratorInput {
+    /// Collect the input for a spec whose documents live in `spec_dir`
+    #[must_use]
+    pub fn new(spec: &Specification, spec_dir: &Path) -> Self {
+        let spec_content = std::fs::read_to_string(spec_dir.join(SpecDocument::Spec.file_name()))
+            .unwrap_or_else(|_| spec.metadata.description.clone());
+        let plan_content =
+            std::fs::read_to_string(spec_dir.join(SpecDocument::Plan.file_name())).ok();
+
+        Self {
+            spec_id: spec.metadata.id.clone(),
+            title: spec.metadata.title.clone(),
+            requirements: spec.metadata.description.clone(),
+            sections: extract_sections(&spec_content),
+            spec_content,
+            plan_content,
+            tech_stack: vec![],
+            architecture: None,
+            granularity: "medium".to_string(),
+            parallel: false,
+            spec_dir: spec_dir.to_path_buf(),
+        }
+    }
+
+    /// Set the technology stack
+    #[must_use]
+    pub fn with_tech_stack(mut self, tech_stack: Vec<String>) -> Self {
+        self.tech_stack = tech_stack;
+        self
+    }
+
+    /// Set the architecture pattern
+    #[must_use]
+    pub fn with_architecture(mut self, architecture: Option<String>) -> Self {
+        self.architecture = architecture;
+        self
+    }
+
+    /// Set the plan the tasks are generated from
+    #[must_use]
+    pub fn with_plan(mut self, plan_content: Option<String>) -> Self {
+        self.plan_content = plan_content;
+        self
+    }
+
+    /// Set the task granularity and parallel markers
+    #[must_use]
+    pub fn with_tasks(mut self, granularity: &str, parallel: bool) -> Self {
+        self.granularity = granularity.to_string();
+        self.parallel = parallel;
+        self
+    }
+
+    fn architecture(&self) -> &str {
+        self.architecture.as_deref().unwrap_or(DEFAULT_ARCHITECTURE)
+    }
+}
+
+/// Writes the documents of a spec
+pub trait SpecGenerator {
+    /// Generate the content of `document`
+    fn generate(&self, document: SpecDocument, input: &GeneratorInput) -> Result<String>;
+}
+
+/// Create the generator configured for a project
+#[must_use]
+pub fn from_config(
+    config: &crate::config::SpecConfig,
+    vibe_ticket_dir: &Path,
+) -> Box<dyn SpecGenerator> {
+    match (config.generator, config.command.as_deref()) {
+        (GeneratorKind::Template, _) => {
+            Box::new(TemplateGenerator::new(vibe_ticket_dir.join(TEMPLATES_DIR)))
+        },
+        (GeneratorKind::Command, Some(command)) => Box::new(CommandGenerator::new(command)),
+        (GeneratorKind::Command, None) => Box::new(MissingCommand),
+        (GeneratorKind::Builtin, _) => Box::new(BuiltinGenerator),
+    }
+}
+
+/// Create the generator configured in `.vibe-ticket/config.yaml`
+///
+/// # Errors
+///
+/// Returns an error if the configuration exists but cannot be loaded, rather
+/// than silently falling back to the builtin generator.
+pub fn for_project(vibe_ticket_dir: &Path) -> Result<Box<dyn SpecGenerator>> {
+    let path = vibe_ticket_dir.join("config.yaml");
+    let config = if path.exists() {
+        crate::config::Config::load_from_path(path)?
+    } else {
+        crate::config::Config::default()
+    };
+    Ok(from_config(&config.spec, vibe_ticket_dir))
+}
+
+/// Generator selected as `command` without a command to run
+struct MissingCommand;
+
+impl SpecGenerator for MissingCommand {
+    fn generate(&self, _document: SpecDocument, _input: &GeneratorInput) -> Result<String> {
+        Err(VibeTicketError::custom(
+            "spec.generator is 'command' but spec.command is not set",
+        ))
+    }
+}
+
+/// Built-in boilerplate documents
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinGenerator;
+
+impl SpecGenerator for BuiltinGenerator {
+    fn generate(&self, document: SpecDocument, input: &GeneratorInput) -> Result<String> {
+        Ok(match document {
+            SpecDocument::Spec => builtin_spec(input),
+            SpecDocument::Research => builtin_research(input),
+            SpecDocument::DataModel => builtin_data_model(input),
+            SpecDocument::Plan => builtin_plan(input),
+            SpecDocument::Tasks => builtin_tasks(input),
+        })
+    }
+}
+
+/// Renders Tera templates, falling back to the built-in documents
+#[derive(Debug, Clone)]
+pub struct TemplateGenerator {
+    dir: PathBuf,
+}
+
+impl TemplateGenerator {
+    /// Create a generator rendering the templates in `dir`
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Render template source with the input as context
+    ///
+    /// Besides the [`GeneratorInput`] fields, templates can use every section
+    /// of `spec.md` directly (`{{ functional_requirements }}`), `tech_stack`
+    /// joined with commas (`tech_stack_list` is the list), and `created_date`,
+    /// `generated_date` and `start_date`. Plain variables the context does not
+    /// provide render as `[NEEDS CLARIFICATION: name]`.
+    pub fn render(source: &str, input: &GeneratorInput) -> Result<String> {
+        let mut context = Context::new();
+        for (key, value) in &input.sections {
+            context.insert(key.as_str(), value);
+        }
+        let values = Context::from_serialize(input).map_err(|e| {
+            VibeTicketError::custom(format!("Failed to build template context: {e}"))
+        })?;
+        context.extend(values);
+
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let tech_stack = if input.tech_stack.is_empty() {
+            "To be determined".to_string()
+        } else {
+            input.tech_stack.join(", ")
+        };
+        context.insert("tech_stack", &tech_stack);
+        context.insert("tech_stack_list", &input.tech_stack);
+        context.insert("architecture", input.architecture());
+        context.insert("spec_dir", &input.spec_dir.display().to_string());
+        context.insert("created_date", &today);
+        context.insert("generated_date", &today);
+        context.insert("start_date", &today);
+
+        for variable in VARIABLE.captures_iter(source) {
+            let name = &variable[1];
+            if !context.contains_key(name) {
+                context.insert(name, &format!("[NEEDS CLARIFICATION: {name}]"));
+            }
+        }
+
+        Tera::one_off(source, &context, false)
+            .map_err(|e| VibeTicketError::custom(format!("Failed to render spec template: {e:?}")))
+    }
+}
+
+impl SpecGenerator for TemplateGenerator {
+    fn generate(&self, document: SpecDocument, input: &GeneratorInput) -> Result<String> {
+        let path = self.dir.join(document.template_name());
+        if !path.exists() {
+            return BuiltinGenerator.generate(document, input);
+        }
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+        Self::render(&source, input)
+    }
+}
+
+/// Runs a command that reads the input as JSON and prints the document
+///
+/// The command runs through the shell with the document name in
+/// `VIBE_TICKET_DOCUMENT`. Its standard input is a JSON
+/// object with a `document` field and the [`GeneratorInput`] fields.
+#[derive(Debug, Clone)]
+pub struct CommandGenerator {
+    command: String,
+}
+
+/// JSON written to a generator command
+#[derive(Serialize)]
+struct CommandRequest<'a> {
+    document: SpecDocument,
+    #[serde(flatten)]
+    input: &'a GeneratorInput,
+}
+
+impl CommandGenerator {
+    /// Create a generator running `command`
+    #[must_use]
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+}
+
+impl SpecGenerator for CommandGenerator {
+    fn generate(&self, document: SpecDocument, input: &GeneratorInput) -> Result<String> {
+        let (shell, shell_arg) = if cfg!(target_os = "windows") {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        let request = serde_json::to_vec(&CommandRequest { document, input })?;
+
+        let mut child = Command::new(shell)
+            .arg(shell_arg)
+            .arg(&self.command)
+            .env("VIBE_TICKET_DOCUMENT", document.as_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| VibeTicketError::custom(format!("Failed to run spec generator: {e}")))?;
+        // Write the request while output is read, so neither side blocks on
+        // a full pipe
+        let writer = child.stdin.take().map(|mut stdin| {
+            thread::spawn(move || {
+                // A command that ignores its input may exit before reading it all
+                let _ = stdin.write_all(&request);
+            })
+        });
+        let output = child
+            .wait_with_output()
+            .map_err(|e| VibeTicketError::custom(format!("Failed to run spec generator: {e}")))?;
+        if let Some(writer) = writer {
+            let _ = writer.join();
+        }
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(VibeTicketError::custom(format!(
+                "Spec generator failed for {}: {}",
+                document.as_str(),
+                stderr.trim()
+            )));
+        }
+        let content = String::from_utf8(output.stdout).map_err(|_| {
+            VibeTicketError::custom("Spec generator printed invalid UTF-8".to_string())
+        })?;
+        if content.trim().is_empty() {
+            return Err(VibeTicketError::custom(format!(
+                "Spec generator printed no {} document",
+                document.as_str()
+            )));
+        }
+        Ok(content)
+    }
+}
+
+/// Sections of a Markdown document by snake-cased heading
+///
+/// Each section holds the text up to the next heading. Headings inside fenced
+/// code blocks are ignored and a trailing `*(mandatory)*` style note is
+/// dropped from the key.
+#[must_use]
+pub fn extract_sections(markdown: &str) -> BTreeMap<String, String> {
+    let mut sections = BTreeMap::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let heading = (!in_fence)
+            .then(|| trimmed.strip_prefix('#'))
+            .flatten()
+            .map(|rest| rest.trim_start_matches('#'))
+            .filter(|rest| rest.starts_with(' '));
+        if let Some(heading) = heading {
+            if let Some((key, body)) = current.take() {
+                sections.insert(key, body.join("\n").trim().to_string());
+            }
+            let key = section_key(heading);
+            current = (!key.is_empty()).then(|| (key, Vec::new()));
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((key, body)) = current {
+        sections.insert(key, body.join("\n").trim().to_string());
+    }
+    sections
+}
+
+fn section_key(heading: &str) -> String {
+    let heading = heading.split(" *(").next().unwrap_or(heading);
+    let mut key = String::new();
+    for c in heading.chars() {
+        if c.is_ascii_alphanumeric() {
+            key.push(c.to_ascii_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_end_matches('_').to_string()
+}
+
+fn builtin_spec(input: &GeneratorInput) -> String {
+    let mut engine = TemplateEngine::new();
+    engine.set_variable("title", &input.title);
+    engine.set_variable("requirements", &input.requirements);
+    engine.set_variable("spec_id", &input.spec_id);
+    engine.set_variable("created_date", &Utc::now().format("%Y-%m-%d").to_string());
+    engine.generate(&SpecTemplate::Requirements {
+        title: input.title.clone(),
+        description: input.requirements.clone(),
+    })
+}
+
+fn builtin_research(input: &GeneratorInput) -> String {
+    let tech_stack = if input.tech_stack.is_empty() {
+        "- No specific technology stack defined".to_string()
+    } else {
+        input
+            .tech_stack
+            .iter()
+            .map(|t| format!("- {t}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(
+        r"# Research and Technical Analysis
+
+## Specification Overview
+{}
+
+## Technology Stack Analysis
+{}
+
+## Architecture Pattern
+{}
+
+## Technical Considerations
+- Performance requirements
+- Scalability needs
+- Security requirements
+- Integration points
+
+## Dependencies
+{}
+
+## Risk Assessment
+- Technical risks
+- Implementation challenges
+- Mitigation strategies
+
+---
+Generated on: {}
+",
+        input
+            .spec_content
+            .lines()
+            .take(5)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        tech_stack,
+        input.architecture(),
+        if input.tech_stack.is_empty() {
+            "To be determined"
+        } else {
+            "Based on selected technology stack"
+        },
+        Utc::now().format("%Y-%m-%d")
+    )
+}
+
+fn builtin_data_model(input: &GeneratorInput) -> String {
+    let is_rust = input
+        .tech_stack
+        .iter()
+        .any(|t| t.to_lowercase().contains("rust"));
+
+    format!(
+        r"# Data Model
+
+## Core Entities
+
+{}
+
+## Relationships
+
+- One-to-many relationships
+- Many-to-many relationships
+- Aggregations
+
+## Validation Rules
+
+- Required fields
+- Format validations
+- Business rules
+
+## Data Types
+
+{}
+
+---
+Generated on: {}
+",
+        "Extract entities from specification...",
+        if is_rust {
+            "Using Rust type system with strong typing"
+        } else {
+            "Define appropriate data types for chosen technology"
+        },
+        Utc::now().format("%Y-%m-%d")
+    )
+}
+
+fn builtin_plan(input: &GeneratorInput) -> String {
+    let tech_stack = if input.tech_stack.is_empty() {
+        "To be determined".to_string()
+    } else {
+        input.tech_stack.join(", ")
+    };
+
+    format!(
+        r"# Implementation Plan: {}
+
+## Overview
+Implementation plan based on specification and selected technology stack.
+
+## Technology Stack
+{}
+
+## Architecture
+{}
+
+## Implementation Phases
+
+### Phase 1: Setup and Infrastructure
+- Project initialization
+- Development environment setup
+- Core dependencies installation
+- Basic project structure
+
+### Phase 2: Core Implementation
+- Data models
+- Business logic
+- Core functionality
+
+### Phase 3: Integration
+- External services
+- APIs
+- Database connections
+
+### Phase 4: Testing and Validation
+- Unit tests
+- Integration tests
+- Validation against requirements
+
+### Phase 5: Documentation and Deployment
+- User documentation
+- Deployment preparation
+- Final review
+
+## Timeline
+- Estimated completion: TBD
+
+---
+Generated on: {}
+",
+        input.title,
+        tech_stack,
+        input.architecture(),
+        Utc::now().format("%Y-%m-%d")
+    )
+}
+
+/// Boilerplate tasks by phase
+const BUILTIN_TASKS: [(&str, [&str; 5]); 4] = [
+    (
+        "Phase 1: Setup and Initialization",
+        [
+            "Initialize project structure",
+            "Set up development environment",
+            "Install core dependencies",
+            "Configure build system",
+            "Set up version control",
+        ],
+    ),
+    (
+        "Phase 2: Core Implementation",
+        [
+            "Implement data models",
+            "Create business logic layer",
+            "Develop core functionality",
+            "Implement error handling",
+            "Add logging and monitoring",
+        ],
+    ),
+    (
+        "Phase 3: Integration and Testing",
+        [
+            "Create unit tests",
+            "Implement integration tests",
+            "Set up CI/CD pipeline",
+            "Perform code review",
+            "Fix identified issues",
+        ],
+    ),
+    (
+        "Phase 4: Documentation and Deployment",
+        [
+            "Write user documentation",
+            "Create API documentation",
+            "Prepare deployment scripts",
+            "Perform final testing",
+            "Deploy to production",
+        ],
+    ),
+];
+
+fn builtin_tasks(input: &GeneratorInput) -> String {
+    let task_prefix = if input.parallel { "[P] " } else { "" };
+    let task_count = match input.granularity.as_str() {
+        "fine" => 20,
+        "coarse" => 5,
+        _ => 10,
+    };
+
+    let mut number = 0;
+    let phases = BUILTIN_TASKS
+        .iter()
+        .map(|(phase, tasks)| {
+            let tasks = tasks
+                .iter()
+                .map(|task| {
+                    number += 1;
+                    format!("- [ ] {task_prefix}T{number:03}: {task}")
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("## {phase}\n{tasks}\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r"# Tasks: {}
+
+## Overview
+Executable tasks generated from implementation plan.
+
+## Task Granularity: {}
+- Estimated task count: ~{}
+- Parallel execution markers: {}
+
+{}
+## Prerequisites
+{}
+
+## Notes
+- Tasks marked with [P] can be executed in parallel
+- Update task status as work progresses
+- Export to tickets for team collaboration
+
+---
+Generated on: {}
+",
+        input.title,
+        input.granularity,
+        task_count,
+        if input.parallel {
+            "Enabled"
+        } else {
+            "Disabled"
+        },
+        phases,
+        prerequisites(input.plan_content.as_deref()),
+        Utc::now().format("%Y-%m-%d")
+    )
+}
+
+/// Technology stack section of the plan, if there is one
+fn prerequisites(plan_content: Option<&str>) -> String {
+    match plan_content {
+        Some(plan) if plan.contains("Technology Stack") => plan
+            .lines()
+            .skip_while(|l| !l.contains("Technology Stack"))
+            .skip(1)
+            .take_while(|l| !l.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => "- Plan document available\n- Requirements completed".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::tasks::parse_tasks;
+    use tempfile::TempDir;
+
+    const SPEC: &str = "\
+# Feature Specification: Export
+
+## Requirements *(mandatory)*
+
+### Functional Requirements
+- REQ-001: Export tickets as CSV
+
+```markdown
+# Not a heading
+```
+
+### Key Entities
+- Ticket
+";
+
+    fn input(dir: &Path) -> GeneratorInput {
+        let spec = Specification::new("Export".to_string(), "Export".to_string(), None, vec![]);
+        std::fs::write(dir.join("spec.md"), SPEC).unwrap();
+        GeneratorInput::new(&spec, dir)
+            .with_tech_stack(vec!["Rust".to_string()])
+            .with_tasks("fine", true)
+    }
+
+    #[test]
+    fn test_extract_sections() {
+        let sections = extract_sections(SPEC);
+        assert_eq!(
+            sections["functional_requirements"],
+            "- REQ-001: Export tickets as CSV\n\n```markdown\n# Not a heading\n```"
+        );
+        assert_eq!(sections["key_entities"], "- Ticket");
+        assert_eq!(sections["requirements"], "");
+        assert!(sections.contains_key("feature_specification_export"));
+    }
+
+    #[test]
+    fn test_builtin_tasks_parse() {
+        let temp_dir = TempDir::new().unwrap();
+        let tasks = BuiltinGenerator
+            .generate(SpecDocument::Tasks, &input(temp_dir.path()))
+            .unwrap();
+        let parsed = parse_tasks(&tasks);
+        assert_eq!(parsed.len(), 20);
+        assert!(parsed.iter().all(|task| task.parallel));
+        assert_eq!(parsed[19].id, "T020");
+    }
+
+    #[test]
+    fn test_template_generator() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("plan-template.md"),
+            "# {{ title }} ({{tech_stack}})\n{{ functional_requirements }}\n{{ phase1_duration }}\n\
+             {% for tech in tech_stack_list %}- {{ tech }}\n{% endfor %}",
+        )
+        .unwrap();
+        let generator = TemplateGenerator::new(temp_dir.path());
+        let input = input(temp_dir.path());
+
+        let plan = generator.generate(SpecDocument::Plan, &input).unwrap();
+        assert!(plan.starts_with("# Export (Rust)\n- REQ-001: Export tickets as CSV"));
+        assert!(plan.contains("[NEEDS CLARIFICATION: phase1_duration]"));
+        assert!(plan.ends_with("- Rust\n"));
+
+        // No template for research, so the built-in document is used
+        let research = generator.generate(SpecDocument::Research, &input).unwrap();
+        assert!(research.starts_with("# Research and Technical Analysis"));
+    }
+
+    #[test]
+    fn test_shipped_templates_render() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = input(temp_dir.path());
+        for source in [
+            include_str!("../../templates/spec-template.md"),
+            include_str!("../../templates/plan-template.md"),
+            include_str!("../../templates/task-template.md"),
+        ] {
+            let rendered = TemplateGenerator::render(source, &input).unwrap();
+            assert!(!rendered.contains("{{"));
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_generator() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = input(temp_dir.path());
+
+        let generator = CommandGenerator::new(
+            r#"printf '# %s\n' "$VIBE_TICKET_DOCUMENT"; grep -o '"title":"[^"]*"'"#,
+        );
+        let plan = generator.generate(SpecDocument::Plan, &input).unwrap();
+        assert_eq!(plan, "# plan\n\"title\":\"Export\"\n");
+
+        let failing = CommandGenerator::new("echo broken >&2; exit 1");
+        let error = failing.generate(SpecDocument::Tasks, &input).unwrap_err();
+        assert!(error.to_string().contains("broken"));
+        assert!(
+            CommandGenerator::new("true")
+                .generate(SpecDocument::Tasks, &input)
+                .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_generator_with_large_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut input = input(temp_dir.path());
+        input.requirements = "x".repeat(1 << 20);
+
+        // Prints more than a pipe holds before reading its input
+        let generator = CommandGenerator::new("yes | head -c 1048576; cat >/dev/null");
+        let content = generator.generate(SpecDocument::Spec, &input).unwrap();
+        assert_eq!(content.len(), 1 << 20);
+    }
+
+    #[test]
+    fn test_for_project_reports_broken_config() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("config.yaml"), "spec: [unclosed").unwrap();
+        assert!(for_project(temp_dir.path()).is_err());
+    }
+}
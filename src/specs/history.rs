@@ -0,0 +1,338 @@
+//! Revision history for specification documents
+//!
+//! Every time a spec document is saved, the previous state of all three
+//! documents is snapshotted into `specs/<id>/history/<n>/` together with a
+//! `revision.json` describing it. Revisions are numbered from 1 in the order
+//! they were recorded; the live documents are the `current` revision.
+
+use super::SpecDocumentType;
+use super::SpecVersion;
+use crate::error::{Result, VibeTicketError};
+use crate::storage::write_project_file;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Directory holding the revisions of a spec, relative to the spec directory
+pub const HISTORY_DIR: &str = "history";
+
+/// File describing a revision inside its directory
+const REVISION_FILE: &str = "revision.json";
+
+/// A recorded revision of a specification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecRevision {
+    /// Revision number, starting at 1
+    pub number: u32,
+
+    /// Spec version at this revision
+    pub version: SpecVersion,
+
+    /// When the spec was last updated before this revision was superseded
+    pub updated_at: DateTime<Utc>,
+
+    /// When the revision was superseded and recorded
+    pub recorded_at: DateTime<Utc>,
+
+    /// Documents that were changed by the save that superseded this revision
+    pub changed: Vec<SpecDocumentType>,
+
+    /// Optional note, e.g. when the change was a restore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Reference to a revision on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionRef {
+    /// A recorded revision
+    Number(u32),
+    /// The live documents
+    Current,
+}
+
+impl FromStr for RevisionRef {
+    type Err = VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "current" | "head" => Ok(Self::Current),
+            other => other
+                .trim_start_matches(['r', '#'])
+                .parse()
+                .ok()
+                .filter(|number| *number > 0)
+                .map(Self::Number)
+                .ok_or_else(|| {
+                    VibeTicketError::custom(format!(
+                        "Invalid revision '{s}'. Use a revision number or 'current'"
+                    ))
+                }),
+        }
+    }
+}
+
+impl std::fmt::Display for RevisionRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "r{number}"),
+            Self::Current => write!(f, "current"),
+        }
+    }
+}
+
+/// Contents of the three spec documents at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpecSnapshot {
+    /// Requirements definition
+    pub requirements: Option<String>,
+    /// Technical design
+    pub design: Option<String>,
+    /// Implementation plan
+    pub tasks: Option<String>,
+}
+
+impl SpecSnapshot {
+    /// Content of one document
+    #[must_use]
+    pub fn document(&self, doc_type: SpecDocumentType) -> Option<&str> {
+        match doc_type {
+            SpecDocumentType::Requirements => self.requirements.as_deref(),
+            SpecDocumentType::Design => self.design.as_deref(),
+            SpecDocumentType::Tasks => self.tasks.as_deref(),
+        }
+    }
+
+    /// Replace the content of one document
+    pub fn set_document(&mut self, doc_type: SpecDocumentType, content: Option<String>) {
+        match doc_type {
+            SpecDocumentType::Requirements => self.requirements = content,
+            SpecDocumentType::Design => self.design = content,
+            SpecDocumentType::Tasks => self.tasks = content,
+        }
+    }
+
+    /// Whether no document exists
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        SpecDocumentType::ALL
+            .iter()
+            .all(|doc_type| self.document(*doc_type).is_none())
+    }
+}
+
+/// Unified diff of one document between two revisions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DocumentDiff {
+    /// The document that differs
+    pub document: SpecDocumentType,
+    /// Unified diff text
+    pub diff: String,
+}
+
+/// Compute the unified diffs of all documents that differ between two snapshots
+#[must_use]
+pub fn diff_snapshots(
+    from: &SpecSnapshot,
+    to: &SpecSnapshot,
+    from_label: &str,
+    to_label: &str,
+) -> Vec<DocumentDiff> {
+    SpecDocumentType::ALL
+        .iter()
+        .filter(|doc_type| from.document(**doc_type) != to.document(**doc_type))
+        .map(|doc_type| {
+            let name = doc_type.file_name();
+            let old = from.document(*doc_type).unwrap_or_default();
+            let new = to.document(*doc_type).unwrap_or_default();
+            DocumentDiff {
+                document: *doc_type,
+                diff: TextDiff::from_lines(old, new)
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(
+                        &format!("{from_label}/{name}"),
+                        &format!("{to_label}/{name}"),
+                    )
+                    .to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Revision store of a single spec
+pub struct SpecHistory {
+    dir: PathBuf,
+}
+
+impl SpecHistory {
+    /// Open the history of the spec stored in `spec_dir`
+    #[must_use]
+    pub fn new(spec_dir: &Path) -> Self {
+        Self {
+            dir: spec_dir.join(HISTORY_DIR),
+        }
+    }
+
+    /// All recorded revisions, oldest first
+    pub fn list(&self) -> Result<Vec<SpecRevision>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let entries =
+            fs::read_dir(&self.dir).map_err(|e| VibeTicketError::io_error("read", &self.dir, e))?;
+        let mut revisions = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| VibeTicketError::io_error("read", &self.dir, e))?
+                .path()
+                .join(REVISION_FILE);
+            if path.exists() {
+                revisions.push(read_json(&path)?);
+            }
+        }
+        revisions.sort_by_key(|revision: &SpecRevision| revision.number);
+        Ok(revisions)
+    }
+
+    /// Load the documents of a recorded revision
+    pub fn load(&self, number: u32) -> Result<(SpecRevision, SpecSnapshot)> {
+        let dir = self.dir.join(number.to_string());
+        let path = dir.join(REVISION_FILE);
+        if !path.exists() {
+            return Err(VibeTicketError::custom(format!(
+                "Revision {number} does not exist"
+            )));
+        }
+        let revision = read_json(&path)?;
+        let mut snapshot = SpecSnapshot::default();
+        for doc_type in SpecDocumentType::ALL {
+            let doc_path = dir.join(doc_type.file_name());
+            if doc_path.exists() {
+                let content = fs::read_to_string(&doc_path)
+                    .map_err(|e| VibeTicketError::io_error("read", &doc_path, e))?;
+                snapshot.set_document(doc_type, Some(content));
+            }
+        }
+        Ok((revision, snapshot))
+    }
+
+    /// Record a snapshot as the next revision
+    pub fn record(
+        &self,
+        version: &SpecVersion,
+        updated_at: DateTime<Utc>,
+        snapshot: &SpecSnapshot,
+        changed: Vec<SpecDocumentType>,
+        note: Option<String>,
+    ) -> Result<SpecRevision> {
+        let number = self.list()?.last().map_or(1, |last| last.number + 1);
+        let dir = self.dir.join(number.to_string());
+        fs::create_dir_all(&dir).map_err(|e| VibeTicketError::io_error("create", &dir, e))?;
+
+        for doc_type in SpecDocumentType::ALL {
+            if let Some(content) = snapshot.document(doc_type) {
+                let path = dir.join(doc_type.file_name());
+                write_project_file(&path, content)?;
+            }
+        }
+
+        let revision = SpecRevision {
+            number,
+            version: version.clone(),
+            updated_at,
+            recorded_at: Utc::now(),
+            changed,
+            note,
+        };
+        let path = dir.join(REVISION_FILE);
+        let json = serde_json::to_string_pretty(&revision)
+            .map_err(|e| VibeTicketError::custom(format!("Failed to serialize revision: {e}")))?;
+        write_project_file(&path, json)?;
+        Ok(revision)
+    }
+}
+
+fn read_json(path: &Path) -> Result<SpecRevision> {
+    let content =
+        fs::read_to_string(path).map_err(|e| VibeTicketError::io_error("read", path, e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| VibeTicketError::custom(format!("Invalid revision {}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_revision_ref_parsing() {
+        assert_eq!(
+            "current".parse::<RevisionRef>().unwrap(),
+            RevisionRef::Current
+        );
+        assert_eq!("3".parse::<RevisionRef>().unwrap(), RevisionRef::Number(3));
+        assert_eq!(
+            "r12".parse::<RevisionRef>().unwrap(),
+            RevisionRef::Number(12)
+        );
+        assert!("0".parse::<RevisionRef>().is_err());
+        assert!("latest".parse::<RevisionRef>().is_err());
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let history = SpecHistory::new(temp_dir.path());
+        assert!(history.list().unwrap().is_empty());
+
+        let snapshot = SpecSnapshot {
+            requirements: Some("v1".to_string()),
+            ..SpecSnapshot::default()
+        };
+        let version = SpecVersion::default();
+        history
+            .record(&version, Utc::now(), &snapshot, vec![], None)
+            .unwrap();
+        let second = history
+            .record(
+                &version,
+                Utc::now(),
+                &SpecSnapshot::default(),
+                vec![SpecDocumentType::Design],
+                None,
+            )
+            .unwrap();
+        assert_eq!(second.number, 2);
+
+        let (revision, loaded) = history.load(1).unwrap();
+        assert_eq!(revision.number, 1);
+        assert_eq!(loaded, snapshot);
+        assert_eq!(history.list().unwrap().len(), 2);
+        assert!(history.load(3).is_err());
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let from = SpecSnapshot {
+            requirements: Some("- REQ-1\n- REQ-2\n".to_string()),
+            design: Some("same\n".to_string()),
+            tasks: None,
+        };
+        let to = SpecSnapshot {
+            requirements: Some("- REQ-1\n- REQ-3\n".to_string()),
+            design: Some("same\n".to_string()),
+            tasks: Some("- [ ] Task\n".to_string()),
+        };
+        let diffs = diff_snapshots(&from, &to, "r1", "current");
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].document, SpecDocumentType::Requirements);
+        assert!(diffs[0].diff.contains("--- r1/requirements.md"));
+        assert!(diffs[0].diff.contains("-- REQ-2"));
+        assert!(diffs[0].diff.contains("+- REQ-3"));
+        assert_eq!(diffs[1].document, SpecDocumentType::Tasks);
+    }
+}
@@ -0,0 +1,751 @@
+//! Ambiguity linter for spec documents
+//!
+//! Flags wording that makes requirements hard to implement or test. Terms are
+//! matched on word boundaries outside fenced code blocks and inline code, and
+//! every finding carries its line and column:
+//!
+//! - `vague-quantifier`: "some", "several", "many", ...
+//! - `untestable-adjective`: "fast", "intuitive", "user-friendly", ...
+//! - `tbd-marker`: `TBD`, `TODO`, `[NEEDS CLARIFICATION]`, ...
+//! - `missing-acceptance-criteria`: a requirements document without
+//!   acceptance criteria or Given/When/Then scenarios
+//! - `undefined-actor`: "As a reviewer" or "the admin can" when no actors,
+//!   roles or entities section defines the actor
+//!
+//! Rules can be disabled, have their severity changed and get extra terms in
+//! the `spec.lint` section of `.vibe-ticket/config.yaml`.
+
+use crate::error::{Result, VibeTicketError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Documents of a spec directory that are linted, in order
+pub const LINTED_DOCUMENTS: [&str; 4] = ["spec.md", "requirements.md", "design.md", "tasks.md"];
+
+/// Document-level findings are reported at this position
+const DOCUMENT_START: (usize, usize) = (1, 1);
+
+static ACCEPTANCE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)acceptance (?:criteria|scenarios?|tests?)|^\W*(?:\*\*)?given\b.*\bthen\b")
+        .expect("valid regex")
+});
+
+static ACTOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\bas an? ([a-z][a-z-]*)\b|\bthe ([a-z][a-z-]*) (?:can|shall|must|should|will|may)\b",
+    )
+    .expect("valid regex")
+});
+
+/// Headings of sections that define actors
+const ACTOR_SECTIONS: [&str; 6] = [
+    "actor",
+    "role",
+    "persona",
+    "stakeholder",
+    "entit",
+    "glossary",
+];
+
+/// Words after "the" that are never actors
+const NOT_ACTORS: [&str; 9] = [
+    "system",
+    "application",
+    "app",
+    "service",
+    "api",
+    "server",
+    "client",
+    "feature",
+    "tool",
+];
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth a look
+    Info,
+    /// Should be fixed before planning
+    Warning,
+    /// Must be fixed; fails `spec lint` and `spec validate`
+    Error,
+}
+
+impl Severity {
+    /// Lowercase name
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    /// SARIF result level
+    #[must_use]
+    pub const fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Info => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A lint rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Quantities without a number
+    VagueQuantifier,
+    /// Qualities without a measurable criterion
+    UntestableAdjective,
+    /// Placeholders left in the document
+    TbdMarker,
+    /// Requirements without acceptance criteria
+    MissingAcceptanceCriteria,
+    /// Actors that are not defined anywhere
+    UndefinedActor,
+}
+
+impl Rule {
+    /// All rules
+    pub const ALL: [Self; 5] = [
+        Self::VagueQuantifier,
+        Self::UntestableAdjective,
+        Self::TbdMarker,
+        Self::MissingAcceptanceCriteria,
+        Self::UndefinedActor,
+    ];
+
+    /// Rule ID, e.g. `vague-quantifier`
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::VagueQuantifier => "vague-quantifier",
+            Self::UntestableAdjective => "untestable-adjective",
+            Self::TbdMarker => "tbd-marker",
+            Self::MissingAcceptanceCriteria => "missing-acceptance-criteria",
+            Self::UndefinedActor => "undefined-actor",
+        }
+    }
+
+    /// One-line description
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::VagueQuantifier => "Quantity without a number",
+            Self::UntestableAdjective => "Quality without a measurable criterion",
+            Self::TbdMarker => "Unresolved placeholder",
+            Self::MissingAcceptanceCriteria => "Requirements without acceptance criteria",
+            Self::UndefinedActor => "Actor not defined in the specification",
+        }
+    }
+
+    /// Severity unless configured otherwise
+    #[must_use]
+    pub const fn default_severity(self) -> Severity {
+        match self {
+            Self::TbdMarker => Severity::Error,
+            Self::VagueQuantifier | Self::UntestableAdjective | Self::MissingAcceptanceCriteria => {
+                Severity::Warning
+            },
+            Self::UndefinedActor => Severity::Info,
+        }
+    }
+
+    /// Terms the rule flags, for the rules that match terms
+    #[must_use]
+    pub const fn default_terms(self) -> &'static [&'static str] {
+        match self {
+            Self::VagueQuantifier => &[
+                "some",
+                "several",
+                "many",
+                "various",
+                "multiple",
+                "numerous",
+                "few",
+                "a lot of",
+                "most",
+                "a number of",
+                "etc",
+            ],
+            Self::UntestableAdjective => &[
+                "fast",
+                "slow",
+                "quick",
+                "quickly",
+                "efficient",
+                "user-friendly",
+                "intuitive",
+                "easy",
+                "simple",
+                "appropriate",
+                "suitable",
+                "proper",
+                "adequate",
+                "robust",
+                "scalable",
+                "seamless",
+                "reasonable",
+                "flexible",
+            ],
+            Self::TbdMarker => &[
+                "TBD",
+                "TODO",
+                "FIXME",
+                "TBC",
+                "to be determined",
+                "To be determined",
+                "to be decided",
+                "???",
+                "[NEEDS CLARIFICATION",
+            ],
+            Self::MissingAcceptanceCriteria | Self::UndefinedActor => &[],
+        }
+    }
+
+    fn suggestion(self, term: &str) -> String {
+        match self {
+            Self::VagueQuantifier => format!("Replace '{term}' with an exact number or range"),
+            Self::UntestableAdjective => format!(
+                "Replace '{term}' with a measurable criterion, e.g. 'responds within 200 ms'"
+            ),
+            Self::TbdMarker => "Resolve the open question before planning".to_string(),
+            Self::MissingAcceptanceCriteria => {
+                "Add an 'Acceptance Criteria' section or Given/When/Then scenarios".to_string()
+            },
+            Self::UndefinedActor => {
+                format!("Describe '{term}' in an actors, roles or key entities section")
+            },
+        }
+    }
+}
+
+/// Lint settings of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Rules that do not run
+    pub disable: Vec<Rule>,
+
+    /// Severity overrides by rule
+    pub severity: BTreeMap<Rule, Severity>,
+
+    /// Extra terms flagged by the term-matching rules
+    pub terms: BTreeMap<Rule, Vec<String>>,
+}
+
+/// A problem found in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    /// Rule that found the problem
+    pub rule: Rule,
+    /// How serious it is
+    pub severity: Severity,
+    /// Document file name, e.g. `spec.md`
+    pub file: String,
+    /// One-based line
+    pub line: usize,
+    /// One-based column, in characters
+    pub column: usize,
+    /// One-based column just past the flagged text
+    pub end_column: usize,
+    /// The flagged text
+    pub text: String,
+    /// What is wrong
+    pub message: String,
+    /// How to fix it
+    pub suggestion: String,
+}
+
+/// A rule that matches terms
+struct TermRule {
+    rule: Rule,
+    severity: Severity,
+    pattern: Regex,
+}
+
+/// Lints spec documents with a rule set
+pub struct Linter {
+    term_rules: Vec<TermRule>,
+    document_rules: Vec<(Rule, Severity)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new(&LintConfig::default())
+    }
+}
+
+impl Linter {
+    /// Create a linter with the rules enabled in `config`
+    #[must_use]
+    pub fn new(config: &LintConfig) -> Self {
+        let mut term_rules = Vec::new();
+        let mut document_rules = Vec::new();
+        for rule in Rule::ALL {
+            if config.disable.contains(&rule) {
+                continue;
+            }
+            let severity = config
+                .severity
+                .get(&rule)
+                .copied()
+                .unwrap_or_else(|| rule.default_severity());
+            let extra = config.terms.get(&rule).into_iter().flatten();
+            let terms: Vec<&str> = rule
+                .default_terms()
+                .iter()
+                .copied()
+                .chain(extra.map(String::as_str))
+                .collect();
+            if terms.is_empty() {
+                document_rules.push((rule, severity));
+            } else {
+                term_rules.push(TermRule {
+                    rule,
+                    severity,
+                    pattern: term_pattern(rule, &terms),
+                });
+            }
+        }
+        Self {
+            term_rules,
+            document_rules,
+        }
+    }
+
+    /// Create the linter configured in `.vibe-ticket/config.yaml`
+    #[must_use]
+    pub fn for_project(vibe_ticket_dir: &Path) -> Self {
+        let config = crate::config::Config::load_from_path(vibe_ticket_dir.join("config.yaml"))
+            .unwrap_or_default();
+        Self::new(&config.spec.lint)
+    }
+
+    /// Lint the documents of a spec directory
+    pub fn lint_dir(&self, spec_dir: &Path) -> Result<Vec<Finding>> {
+        let mut findings = Vec::new();
+        for file in LINTED_DOCUMENTS {
+            let path = spec_dir.join(file);
+            if !path.exists() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+            findings.extend(self.lint(file, &content));
+        }
+        Ok(findings)
+    }
+
+    /// Lint one document
+    ///
+    /// Acceptance criteria and actors are only checked in requirement
+    /// documents (`spec.md` and `requirements.md`).
+    #[must_use]
+    pub fn lint(&self, file: &str, content: &str) -> Vec<Finding> {
+        let lines = prose_lines(content);
+        let mut findings = Vec::new();
+
+        for (number, line) in &lines {
+            for term_rule in &self.term_rules {
+                for found in term_rule.pattern.find_iter(line) {
+                    let text = found.as_str().to_string();
+                    let column = line[..found.start()].chars().count() + 1;
+                    findings.push(Finding {
+                        rule: term_rule.rule,
+                        severity: term_rule.severity,
+                        file: file.to_string(),
+                        line: *number,
+                        column,
+                        end_column: column + text.chars().count(),
+                        message: format!("{}: '{text}'", term_rule.rule.description()),
+                        suggestion: term_rule.rule.suggestion(&text),
+                        text,
+                    });
+                }
+            }
+        }
+
+        if matches!(file, "spec.md" | "requirements.md") && !lines.is_empty() {
+            for (rule, severity) in &self.document_rules {
+                match rule {
+                    Rule::MissingAcceptanceCriteria if !ACCEPTANCE.is_match(content) => {
+                        findings.push(Finding {
+                            rule: *rule,
+                            severity: *severity,
+                            file: file.to_string(),
+                            line: DOCUMENT_START.0,
+                            column: DOCUMENT_START.1,
+                            end_column: DOCUMENT_START.1,
+                            text: String::new(),
+                            message: rule.description().to_string(),
+                            suggestion: rule.suggestion(""),
+                        });
+                    },
+                    Rule::UndefinedActor => {
+                        findings.extend(undefined_actors(file, content, &lines, *severity));
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        findings.sort_by_key(|finding| (finding.line, finding.column));
+        findings
+    }
+}
+
+/// Case-insensitive pattern of terms on word boundaries
+fn term_pattern(rule: Rule, terms: &[&str]) -> Regex {
+    let alternatives = terms
+        .iter()
+        .map(|term| {
+            let escaped = regex::escape(term);
+            // Only anchor on the sides that are word characters
+            let start = if term.starts_with(|c: char| c.is_alphanumeric()) {
+                r"\b"
+            } else {
+                ""
+            };
+            let end = if term.ends_with(|c: char| c.is_alphanumeric()) {
+                r"\b"
+            } else {
+                ""
+            };
+            format!("{start}{escaped}{end}")
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+    // Placeholders are conventionally upper case; everything else is not
+    let flags = if rule == Rule::TbdMarker {
+        "(?-i)"
+    } else {
+        "(?i)"
+    };
+    Regex::new(&format!("{flags}(?:{alternatives})")).expect("escaped terms")
+}
+
+/// Lines outside fenced code blocks with inline code blanked out
+///
+/// Line numbers are one-based. Blanking keeps columns intact.
+fn prose_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                continue;
+            },
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                continue;
+            },
+            (Some(_), _) => continue,
+            (None, None) => {},
+        }
+        lines.push((index + 1, blank_inline_code(line)));
+    }
+    lines
+}
+
+fn blank_inline_code(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            result.push(c);
+        } else if in_code {
+            result.push(' ');
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Actors used in the document that no actor section defines
+fn undefined_actors(
+    file: &str,
+    content: &str,
+    lines: &[(usize, String)],
+    severity: Severity,
+) -> Vec<Finding> {
+    let defined = defined_actors(content);
+    let mut reported = BTreeSet::new();
+    let mut findings = Vec::new();
+    for (number, line) in lines {
+        for captures in ACTOR.captures_iter(line) {
+            let Some(actor) = captures.get(1).or_else(|| captures.get(2)) else {
+                continue;
+            };
+            let name = actor.as_str().to_lowercase();
+            if NOT_ACTORS.contains(&name.as_str())
+                || is_defined(&name, &defined)
+                || !reported.insert(name.clone())
+            {
+                continue;
+            }
+            let column = line[..actor.start()].chars().count() + 1;
+            findings.push(Finding {
+                rule: Rule::UndefinedActor,
+                severity,
+                file: file.to_string(),
+                line: *number,
+                column,
+                end_column: column + actor.as_str().chars().count(),
+                text: actor.as_str().to_string(),
+                message: format!("Actor '{}' is not defined", actor.as_str()),
+                suggestion: Rule::UndefinedActor.suggestion(actor.as_str()),
+            });
+        }
+    }
+    findings
+}
+
+/// Lowercased names listed under actor, role or entity headings
+fn defined_actors(content: &str) -> Vec<String> {
+    let mut defined = Vec::new();
+    let mut in_actor_section = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.to_lowercase();
+            in_actor_section = ACTOR_SECTIONS.iter().any(|word| heading.contains(word));
+            continue;
+        }
+        if !in_actor_section {
+            continue;
+        }
+        let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        else {
+            continue;
+        };
+        let name = item
+            .split([':', '(', '—', '–'])
+            .next()
+            .unwrap_or(item)
+            .split(" - ")
+            .next()
+            .unwrap_or(item)
+            .replace(['*', '`', '_'], "");
+        let name = name.trim().to_lowercase();
+        if !name.is_empty() {
+            defined.push(name);
+        }
+    }
+    defined
+}
+
+fn is_defined(actor: &str, defined: &[String]) -> bool {
+    let singular = actor.strip_suffix('s').unwrap_or(actor);
+    defined.iter().any(|name| {
+        let name = name.strip_suffix('s').unwrap_or(name);
+        name == singular || name.ends_with(&format!(" {singular}"))
+    })
+}
+
+/// Findings at or above `severity`
+pub fn at_least(findings: &[Finding], severity: Severity) -> impl Iterator<Item = &Finding> {
+    findings
+        .iter()
+        .filter(move |finding| finding.severity >= severity)
+}
+
+/// SARIF 2.1.0 log of the findings, with paths relative to `base`
+#[must_use]
+pub fn to_sarif(findings: &[Finding], base: &str) -> serde_json::Value {
+    let rules: Vec<_> = Rule::ALL
+        .iter()
+        .map(|rule| {
+            serde_json::json!({
+                "id": rule.id(),
+                "shortDescription": { "text": rule.description() },
+                "defaultConfiguration": { "level": rule.default_severity().sarif_level() },
+            })
+        })
+        .collect();
+    let results: Vec<_> = findings
+        .iter()
+        .map(|finding| {
+            serde_json::json!({
+                "ruleId": finding.rule.id(),
+                "level": finding.severity.sarif_level(),
+                "message": { "text": format!("{}. {}", finding.message, finding.suggestion) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": format!("{base}/{}", finding.file) },
+                        "region": {
+                            "startLine": finding.line,
+                            "startColumn": finding.column,
+                            "endColumn": finding.end_column.max(finding.column + 1),
+                        },
+                    },
+                }],
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "vibe-ticket",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Insert a `[NEEDS CLARIFICATION: ...]` marker after each vague term
+///
+/// Only the term-matching rules that flag wording (quantifiers and
+/// adjectives) add markers; placeholders are left alone.
+#[must_use]
+pub fn mark_ambiguities(content: &str, findings: &[Finding]) -> String {
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    // Insert from the end of each line so earlier columns stay valid
+    for finding in findings.iter().rev().filter(|finding| {
+        matches!(
+            finding.rule,
+            Rule::VagueQuantifier | Rule::UntestableAdjective
+        )
+    }) {
+        let Some(line) = lines.get_mut(finding.line - 1) else {
+            continue;
+        };
+        let offset = line
+            .char_indices()
+            .nth(finding.end_column - 1)
+            .map_or(line.len(), |(offset, _)| offset);
+        line.insert_str(
+            offset,
+            &format!(" [NEEDS CLARIFICATION: {}]", finding.suggestion),
+        );
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "\
+# Feature Specification: Search
+
+## Actors
+- **Reviewer**: approves changes
+
+## Requirements
+- REQ-001: As a reviewer, I want fast search over some tickets
+- REQ-002: The admin can export handsome results (TBD)
+- REQ-003: Results render with `some fast code`
+
+```rust
+let some = fast();
+```
+";
+
+    fn rules(findings: &[Finding]) -> Vec<(Rule, usize, usize)> {
+        findings
+            .iter()
+            .map(|finding| (finding.rule, finding.line, finding.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        let findings = Linter::default().lint("spec.md", SPEC);
+        assert_eq!(
+            rules(&findings),
+            vec![
+                (Rule::MissingAcceptanceCriteria, 1, 1),
+                (Rule::UntestableAdjective, 7, 34),
+                (Rule::VagueQuantifier, 7, 51),
+                (Rule::UndefinedActor, 8, 16),
+                (Rule::TbdMarker, 8, 51),
+            ]
+        );
+        assert_eq!(findings[2].text, "some");
+        assert_eq!(findings[2].end_column, 55);
+        assert_eq!(findings[3].text, "admin");
+        assert_eq!(findings[4].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_config() {
+        let config = LintConfig {
+            disable: vec![Rule::MissingAcceptanceCriteria, Rule::UndefinedActor],
+            severity: BTreeMap::from([(Rule::VagueQuantifier, Severity::Error)]),
+            terms: BTreeMap::from([(Rule::UntestableAdjective, vec!["handsome".to_string()])]),
+        };
+        let findings = Linter::new(&config).lint("spec.md", SPEC);
+        assert_eq!(
+            rules(&findings),
+            vec![
+                (Rule::UntestableAdjective, 7, 34),
+                (Rule::VagueQuantifier, 7, 51),
+                (Rule::UntestableAdjective, 8, 33),
+                (Rule::TbdMarker, 8, 51),
+            ]
+        );
+        assert_eq!(findings[1].severity, Severity::Error);
+        assert_eq!(at_least(&findings, Severity::Error).count(), 2);
+    }
+
+    #[test]
+    fn test_document_rules_only_in_requirements() {
+        let findings = Linter::default().lint("design.md", "The admin can do some things");
+        assert_eq!(rules(&findings), vec![(Rule::VagueQuantifier, 1, 18)]);
+
+        let accepted = "As a user I log in\n\n## Acceptance Criteria\n- Given a user, then ok\n";
+        let findings = Linter::default().lint("requirements.md", accepted);
+        assert_eq!(rules(&findings), vec![(Rule::UndefinedActor, 1, 6)]);
+    }
+
+    #[test]
+    fn test_mark_ambiguities() {
+        let content = "Handsome UI with some fast pages";
+        let findings = Linter::default().lint("design.md", content);
+        let marked = mark_ambiguities(content, &findings);
+        assert_eq!(
+            marked,
+            "Handsome UI with some [NEEDS CLARIFICATION: Replace 'some' with an exact number \
+             or range] fast [NEEDS CLARIFICATION: Replace 'fast' with a measurable criterion, \
+             e.g. 'responds within 200 ms'] pages"
+        );
+    }
+
+    #[test]
+    fn test_sarif() {
+        let findings = Linter::default().lint("design.md", "Decide TBD");
+        let sarif = to_sarif(&findings, "specs/abc");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "tbd-marker");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "specs/abc/design.md");
+        assert_eq!(location["region"]["startColumn"], 8);
+        assert_eq!(location["region"]["endColumn"], 11);
+    }
+}
@@ -3,9 +3,15 @@
 //! This module provides the core functionality for managing specifications,
 //! including creation, loading, saving, and version control.
 
-use super::{SpecDocumentType, SpecMetadata, SpecPhase, Specification};
+use super::history::{SpecHistory, diff_snapshots};
+use super::review::{self, ReviewConfig, ReviewStatus, SignOff, Verdict};
+use super::{
+    DocumentDiff, RevisionRef, SpecDocumentType, SpecMetadata, SpecPhase, SpecRevision,
+    SpecSnapshot, Specification,
+};
 use crate::error::{Result, VibeTicketError};
 use crate::specs::storage::{DocumentOperations, FileSystemStore};
+use crate::storage::write_project_file;
 use std::path::PathBuf;
 
 /// Manages specifications in a project
@@ -58,36 +64,179 @@ impl SpecManager {
     }
 
     /// Save a document for a specification
+    ///
+    /// If the content changed, the previous state of the spec is recorded as
+    /// a revision, the version is bumped and approvals based on the old
+    /// content are withdrawn (see [`SpecManager::restore_revision`]).
     pub fn save_document(
         &self,
         spec_id: &str,
         doc_type: SpecDocumentType,
         content: &str,
     ) -> Result<()> {
+        let previous = self.load_document(spec_id, doc_type)?;
+
         // Save document
         self.ops
             .save_text_in_subdir(spec_id, doc_type.file_name(), content)?;
 
-        // Update metadata
+        self.record_change(spec_id, doc_type, previous.as_deref(), content)
+    }
+
+    /// Record a document that was changed outside of [`SpecManager::save_document`]
+    ///
+    /// Used after a document was edited in place (e.g. in an editor), with
+    /// `previous` being its content before the edit.
+    pub fn record_edit(
+        &self,
+        spec_id: &str,
+        doc_type: SpecDocumentType,
+        previous: Option<&str>,
+    ) -> Result<()> {
+        let content = self.load_document(spec_id, doc_type)?.unwrap_or_default();
+        self.record_change(spec_id, doc_type, previous, &content)
+    }
+
+    fn record_change(
+        &self,
+        spec_id: &str,
+        doc_type: SpecDocumentType,
+        previous: Option<&str>,
+        content: &str,
+    ) -> Result<()> {
         let mut metadata = self.load_metadata(spec_id)?;
         match doc_type {
-            SpecDocumentType::Requirements => {
-                metadata.progress.requirements_completed = true;
-                metadata.version.bump_patch();
-            },
-            SpecDocumentType::Design => {
-                metadata.progress.design_completed = true;
-                metadata.version.bump_patch();
-            },
-            SpecDocumentType::Tasks => {
-                metadata.progress.tasks_completed = true;
+            SpecDocumentType::Requirements => metadata.progress.requirements_completed = true,
+            SpecDocumentType::Design => metadata.progress.design_completed = true,
+            SpecDocumentType::Tasks => metadata.progress.tasks_completed = true,
+        }
+
+        if previous != Some(content) {
+            let mut snapshot = self.current_snapshot(spec_id)?;
+            snapshot.set_document(doc_type, previous.map(str::to_string));
+            if !snapshot.is_empty() {
+                self.history(spec_id).record(
+                    &metadata.version,
+                    metadata.updated_at,
+                    &snapshot,
+                    vec![doc_type],
+                    None,
+                )?;
+            }
+            // Withdrawn approvals are a bigger change than an edit of a draft
+            if metadata.progress.invalidate_approvals(doc_type) {
+                metadata.version.bump_minor();
+            } else {
                 metadata.version.bump_patch();
-            },
+            }
+        }
+
+        metadata.update_phase();
+        self.save_metadata(&metadata)
+    }
+
+    /// List the recorded revisions of a specification, oldest first
+    pub fn revisions(&self, spec_id: &str) -> Result<Vec<SpecRevision>> {
+        self.load_metadata(spec_id)?;
+        self.history(spec_id).list()
+    }
+
+    /// Load the documents of a specification at a revision
+    pub fn snapshot(&self, spec_id: &str, revision: RevisionRef) -> Result<SpecSnapshot> {
+        self.load_metadata(spec_id)?;
+        match revision {
+            RevisionRef::Current => self.current_snapshot(spec_id),
+            RevisionRef::Number(number) => Ok(self.history(spec_id).load(number)?.1),
+        }
+    }
+
+    /// Diff the documents of a specification between two revisions
+    pub fn diff(
+        &self,
+        spec_id: &str,
+        from: RevisionRef,
+        to: RevisionRef,
+    ) -> Result<Vec<DocumentDiff>> {
+        Ok(diff_snapshots(
+            &self.snapshot(spec_id, from)?,
+            &self.snapshot(spec_id, to)?,
+            &from.to_string(),
+            &to.to_string(),
+        ))
+    }
+
+    /// Restore the documents of a specification to a recorded revision
+    ///
+    /// The current state is recorded as a new revision first, so a restore
+    /// can itself be undone. Returns the updated metadata and the documents
+    /// that changed.
+    pub fn restore_revision(
+        &self,
+        spec_id: &str,
+        number: u32,
+    ) -> Result<(SpecMetadata, Vec<SpecDocumentType>)> {
+        let mut metadata = self.load_metadata(spec_id)?;
+        let history = self.history(spec_id);
+        let (_, target) = history.load(number)?;
+        let current = self.current_snapshot(spec_id)?;
+
+        let changed: Vec<_> = SpecDocumentType::ALL
+            .into_iter()
+            .filter(|doc_type| target.document(*doc_type) != current.document(*doc_type))
+            .collect();
+        if changed.is_empty() {
+            return Ok((metadata, changed));
+        }
+
+        history.record(
+            &metadata.version,
+            metadata.updated_at,
+            &current,
+            changed.clone(),
+            Some(format!("Replaced by restore of r{number}")),
+        )?;
+
+        let mut invalidated = false;
+        for doc_type in &changed {
+            let completed = target.document(*doc_type).is_some();
+            if let Some(content) = target.document(*doc_type) {
+                self.ops
+                    .save_text_in_subdir(spec_id, doc_type.file_name(), content)?;
+            } else {
+                let path = self.get_document_path(spec_id, *doc_type);
+                std::fs::remove_file(&path)
+                    .map_err(|e| VibeTicketError::io_error("delete", &path, e))?;
+            }
+            match doc_type {
+                SpecDocumentType::Requirements => {
+                    metadata.progress.requirements_completed = completed;
+                },
+                SpecDocumentType::Design => metadata.progress.design_completed = completed,
+                SpecDocumentType::Tasks => metadata.progress.tasks_completed = completed,
+            }
+            invalidated |= metadata.progress.invalidate_approvals(*doc_type);
+        }
+        if invalidated {
+            metadata.version.bump_minor();
+        } else {
+            metadata.version.bump_patch();
         }
         metadata.update_phase();
         self.save_metadata(&metadata)?;
 
-        Ok(())
+        Ok((metadata, changed))
+    }
+
+    fn history(&self, spec_id: &str) -> SpecHistory {
+        SpecHistory::new(&self.get_spec_dir(spec_id))
+    }
+
+    fn current_snapshot(&self, spec_id: &str) -> Result<SpecSnapshot> {
+        Ok(SpecSnapshot {
+            requirements: self.load_document(spec_id, SpecDocumentType::Requirements)?,
+            design: self.load_document(spec_id, SpecDocumentType::Design)?,
+            tasks: self.load_document(spec_id, SpecDocumentType::Tasks)?,
+        })
     }
 
     /// List all specifications
@@ -111,50 +260,89 @@ impl SpecManager {
         }
 
         // Sort by creation date (newest first)
-        specs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        specs.sort_by_key(|s| std::cmp::Reverse(s.created_at));
 
         Ok(specs)
     }
 
-    /// Approve a document phase
-    pub fn approve_phase(&self, spec_id: &str, phase: SpecPhase) -> Result<()> {
+    /// Record a reviewer's sign-off on a phase
+    ///
+    /// Approvals need the phase's document to exist. Returns the phase's
+    /// review status after the sign-off.
+    pub fn sign_off(
+        &self,
+        spec_id: &str,
+        phase: SpecDocumentType,
+        sign_off: SignOff,
+        config: &ReviewConfig,
+    ) -> Result<ReviewStatus> {
         let mut metadata = self.load_metadata(spec_id)?;
-
-        // Validate phase can be approved
-        match phase {
-            SpecPhase::Requirements => {
-                if !metadata.progress.requirements_completed {
-                    return Err(VibeTicketError::custom(
-                        "Cannot approve requirements: document not completed",
-                    ));
-                }
-                metadata.progress.requirements_approved = true;
-            },
-            SpecPhase::Design => {
-                if !metadata.progress.design_completed {
-                    return Err(VibeTicketError::custom(
-                        "Cannot approve design: document not completed",
-                    ));
-                }
-                metadata.progress.design_approved = true;
-            },
-            SpecPhase::Implementation => {
-                if !metadata.progress.tasks_completed {
-                    return Err(VibeTicketError::custom(
-                        "Cannot approve tasks: document not completed",
-                    ));
-                }
-                metadata.progress.tasks_approved = true;
-            },
-            _ => {
-                return Err(VibeTicketError::custom("Invalid phase for approval"));
-            },
+        let completed = match phase {
+            SpecDocumentType::Requirements => metadata.progress.requirements_completed,
+            SpecDocumentType::Design => metadata.progress.design_completed,
+            SpecDocumentType::Tasks => metadata.progress.tasks_completed,
+        };
+        if sign_off.verdict == Verdict::Approved && !completed {
+            return Err(VibeTicketError::custom(format!(
+                "Cannot approve {}: document not completed",
+                phase.as_str()
+            )));
         }
 
+        metadata
+            .progress
+            .reviews
+            .entry(phase)
+            .or_default()
+            .sign_offs
+            .push(sign_off);
+        let status = config.status(&metadata, phase);
+        *metadata.progress.approved_mut(phase) = status.approved;
         metadata.update_phase();
+        metadata.updated_at = chrono::Utc::now();
         self.save_metadata(&metadata)?;
+        Ok(status)
+    }
 
-        Ok(())
+    /// Add or remove required reviewers of a phase
+    ///
+    /// Returns the phase's review status afterwards.
+    pub fn update_reviewers(
+        &self,
+        spec_id: &str,
+        phase: SpecDocumentType,
+        add: &[String],
+        remove: &[String],
+        config: &ReviewConfig,
+    ) -> Result<ReviewStatus> {
+        let mut metadata = self.load_metadata(spec_id)?;
+        let review = metadata.progress.reviews.entry(phase).or_default();
+        for name in add {
+            if !review.reviewers.contains(name) {
+                review.reviewers.push(name.clone());
+            }
+        }
+        review.reviewers.retain(|name| !remove.contains(name));
+
+        let status = config.status(&metadata, phase);
+        *metadata.progress.approved_mut(phase) = status.approved;
+        metadata.updated_at = chrono::Utc::now();
+        self.save_metadata(&metadata)?;
+        Ok(status)
+    }
+
+    /// Move a spec to the completed phase
+    ///
+    /// Returns whether the phase changed.
+    pub fn mark_completed(&self, spec_id: &str) -> Result<bool> {
+        let mut metadata = self.load_metadata(spec_id)?;
+        if metadata.progress.current_phase == SpecPhase::Completed {
+            return Ok(false);
+        }
+        metadata.progress.current_phase = SpecPhase::Completed;
+        metadata.updated_at = chrono::Utc::now();
+        self.save_metadata(&metadata)?;
+        Ok(true)
     }
 
     /// Get the directory path for a spec
@@ -165,7 +353,9 @@ impl SpecManager {
 
     /// Load metadata for a spec
     fn load_metadata(&self, spec_id: &str) -> Result<SpecMetadata> {
-        self.ops.load_from_subdir(spec_id, "spec.json")
+        let mut metadata: SpecMetadata = self.ops.load_from_subdir(spec_id, "spec.json")?;
+        review::migrate_legacy_approvals(&mut metadata);
+        Ok(metadata)
     }
 
     /// Save metadata for a spec
@@ -217,8 +407,24 @@ impl SpecManager {
             .ok_or_else(|| VibeTicketError::custom("Invalid specs directory structure"))?
             .join(".active_spec");
 
-        std::fs::write(&active_file, spec_id)
-            .map_err(|e| VibeTicketError::io_error("write", &active_file, e))?;
+        write_project_file(&active_file, spec_id)?;
+
+        Ok(())
+    }
+
+    /// Clear the active specification
+    pub fn clear_active_spec(&self) -> Result<()> {
+        let active_file = self
+            .ops
+            .base_dir()
+            .parent()
+            .ok_or_else(|| VibeTicketError::custom("Invalid specs directory structure"))?
+            .join(".active_spec");
+
+        if active_file.exists() {
+            std::fs::remove_file(&active_file)
+                .map_err(|e| VibeTicketError::io_error("delete", &active_file, e))?;
+        }
 
         Ok(())
     }
@@ -394,4 +600,107 @@ mod tests {
         assert!(spec.metadata.progress.requirements_completed);
         assert!(spec.metadata.progress.design_completed);
     }
+
+    #[test]
+    fn test_saving_records_revisions_and_invalidates_approvals() {
+        let (manager, _temp) = create_test_manager();
+        let metadata = manager
+            .create_spec("Test Spec".to_string(), String::new())
+            .unwrap();
+        let id = &metadata.id;
+
+        manager
+            .save_document(id, SpecDocumentType::Requirements, "REQ-1")
+            .unwrap();
+        manager
+            .save_document(id, SpecDocumentType::Design, "Design")
+            .unwrap();
+        let config = ReviewConfig::default();
+        for phase in [SpecDocumentType::Requirements, SpecDocumentType::Design] {
+            let metadata = manager.load_spec(id).unwrap().metadata;
+            let sign_off = SignOff::new("alice", Verdict::Approved, &metadata, None);
+            assert!(
+                manager
+                    .sign_off(id, phase, sign_off, &config)
+                    .unwrap()
+                    .approved
+            );
+        }
+        assert!(
+            manager
+                .sign_off(
+                    id,
+                    SpecDocumentType::Tasks,
+                    SignOff::new("alice", Verdict::Approved, &metadata, None),
+                    &config,
+                )
+                .is_err()
+        );
+        assert_eq!(manager.revisions(id).unwrap().len(), 1);
+
+        // Saving unchanged content records nothing
+        manager
+            .save_document(id, SpecDocumentType::Requirements, "REQ-1")
+            .unwrap();
+        assert_eq!(manager.revisions(id).unwrap().len(), 1);
+
+        manager
+            .save_document(id, SpecDocumentType::Requirements, "REQ-1 changed")
+            .unwrap();
+        let spec = manager.load_spec(id).unwrap();
+        assert!(!spec.metadata.progress.requirements_approved);
+        assert!(!spec.metadata.progress.design_approved);
+        assert_eq!(spec.metadata.version.to_string(), "0.2.0");
+        let design = &spec.metadata.progress.reviews[&SpecDocumentType::Design];
+        assert!(design.sign_offs.iter().all(|s| !s.is_active()));
+
+        let revisions = manager.revisions(id).unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[1].changed, vec![SpecDocumentType::Requirements]);
+        let previous = manager.snapshot(id, RevisionRef::Number(2)).unwrap();
+        assert_eq!(previous.requirements.as_deref(), Some("REQ-1"));
+        assert_eq!(previous.design.as_deref(), Some("Design"));
+    }
+
+    #[test]
+    fn test_restore_revision() {
+        let (manager, _temp) = create_test_manager();
+        let metadata = manager
+            .create_spec("Test Spec".to_string(), String::new())
+            .unwrap();
+        let id = &metadata.id;
+
+        manager
+            .save_document(id, SpecDocumentType::Requirements, "first")
+            .unwrap();
+        manager
+            .save_document(id, SpecDocumentType::Requirements, "second")
+            .unwrap();
+        manager
+            .save_document(id, SpecDocumentType::Tasks, "- [ ] Task")
+            .unwrap();
+
+        let diffs = manager
+            .diff(id, RevisionRef::Number(1), RevisionRef::Current)
+            .unwrap();
+        assert_eq!(diffs.len(), 2);
+
+        let (_, changed) = manager.restore_revision(id, 1).unwrap();
+        assert_eq!(
+            changed,
+            vec![SpecDocumentType::Requirements, SpecDocumentType::Tasks]
+        );
+        let spec = manager.load_spec(id).unwrap();
+        assert_eq!(spec.requirements.as_deref(), Some("first"));
+        assert!(spec.tasks.is_none());
+        assert!(!spec.metadata.progress.tasks_completed);
+
+        // The state before the restore can be restored again
+        let revisions = manager.revisions(id).unwrap();
+        assert_eq!(revisions.len(), 3);
+        manager.restore_revision(id, 3).unwrap();
+        let spec = manager.load_spec(id).unwrap();
+        assert_eq!(spec.requirements.as_deref(), Some("second"));
+        assert_eq!(spec.tasks.as_deref(), Some("- [ ] Task"));
+    }
 }
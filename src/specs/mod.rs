@@ -23,12 +23,28 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod generator;
+pub mod history;
+pub mod lint;
 pub mod manager;
+pub mod review;
+pub mod rollup;
 pub mod storage;
+pub mod task_sync;
+pub mod tasks;
 pub mod templates;
+pub mod trace;
 
+pub use generator::{GeneratorInput, SpecDocument, SpecGenerator};
+pub use history::{DocumentDiff, RevisionRef, SpecRevision, SpecSnapshot};
+pub use lint::{Finding, LintConfig, Linter, Severity};
 pub use manager::{SpecManager, delete, get_document_path, list, load, save};
+pub use review::{PhaseReview, ReviewConfig, ReviewStatus, SignOff, Verdict};
+pub use rollup::SpecRollup;
+pub use task_sync::{TaskSync, TaskSyncReport};
+pub use tasks::SpecTask;
 pub use templates::{SpecTemplate, TemplateEngine};
+pub use trace::TraceMatrix;
 
 /// Specification metadata and progress tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,7 +102,15 @@ pub struct SpecProgress {
     /// Current phase
     pub current_phase: SpecPhase,
 
-    /// Approval status with additional metadata
+    /// Reviewers and sign-offs per phase
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub reviews: std::collections::BTreeMap<SpecDocumentType, review::PhaseReview>,
+
+    /// Untyped approvals written by older versions
+    ///
+    /// Read for compatibility only; loading a spec migrates them into
+    /// `reviews`.
+    #[serde(default, skip_serializing)]
     pub approval_status: Option<std::collections::HashMap<String, serde_json::Value>>,
 }
 
@@ -114,7 +138,7 @@ pub enum SpecPhase {
 }
 
 /// Version information for spec documents
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpecVersion {
     /// Major version (breaking changes)
     pub major: u32,
@@ -127,7 +151,8 @@ pub struct SpecVersion {
 }
 
 /// Specification document type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SpecDocumentType {
     /// Requirements definition document
     Requirements,
@@ -216,6 +241,57 @@ impl SpecProgress {
     pub const fn current_phase(&self) -> SpecPhase {
         self.current_phase
     }
+
+    /// Cached approval flag of a phase
+    pub const fn approved_mut(&mut self, phase: SpecDocumentType) -> &mut bool {
+        match phase {
+            SpecDocumentType::Requirements => &mut self.requirements_approved,
+            SpecDocumentType::Design => &mut self.design_approved,
+            SpecDocumentType::Tasks => &mut self.tasks_approved,
+        }
+    }
+
+    /// Withdraw approvals invalidated by a change to `doc_type`
+    ///
+    /// A changed document invalidates its own approval and the approvals of
+    /// all later phases, since those were based on it. Sign-offs are marked
+    /// withdrawn rather than removed. Returns whether any approval was
+    /// withdrawn.
+    pub fn invalidate_approvals(&mut self, doc_type: SpecDocumentType) -> bool {
+        let now = chrono::Utc::now();
+        let mut invalidated = false;
+        for (phase, approved, keys) in [
+            (
+                SpecDocumentType::Requirements,
+                &mut self.requirements_approved,
+                &["Requirements"][..],
+            ),
+            (
+                SpecDocumentType::Design,
+                &mut self.design_approved,
+                &["Design"][..],
+            ),
+            (
+                SpecDocumentType::Tasks,
+                &mut self.tasks_approved,
+                &["Tasks", "Implementation"][..],
+            ),
+        ] {
+            if phase.position() < doc_type.position() {
+                continue;
+            }
+            invalidated |= std::mem::take(approved);
+            if let Some(review) = self.reviews.get_mut(&phase) {
+                invalidated |= review.withdraw(now);
+            }
+            if let Some(approvals) = &mut self.approval_status {
+                for key in keys {
+                    invalidated |= approvals.remove(*key).is_some();
+                }
+            }
+        }
+        invalidated
+    }
 }
 
 impl Default for SpecProgress {
@@ -228,6 +304,7 @@ impl Default for SpecProgress {
             design_approved: false,
             tasks_approved: false,
             current_phase: SpecPhase::Initial,
+            reviews: std::collections::BTreeMap::new(),
             approval_status: None,
         }
     }
@@ -270,6 +347,28 @@ impl SpecVersion {
 }
 
 impl SpecDocumentType {
+    /// All document types in phase order
+    pub const ALL: [Self; 3] = [Self::Requirements, Self::Design, Self::Tasks];
+
+    /// Position of the document in the phase order
+    const fn position(self) -> usize {
+        match self {
+            Self::Requirements => 0,
+            Self::Design => 1,
+            Self::Tasks => 2,
+        }
+    }
+
+    /// Lowercase name, as used on the command line
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Requirements => "requirements",
+            Self::Design => "design",
+            Self::Tasks => "tasks",
+        }
+    }
+
     /// Get file name for this document type
     #[must_use]
     pub const fn file_name(&self) -> &'static str {
@@ -291,6 +390,21 @@ impl SpecDocumentType {
     }
 }
 
+impl std::str::FromStr for SpecDocumentType {
+    type Err = crate::error::VibeTicketError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "requirements" => Ok(Self::Requirements),
+            "design" => Ok(Self::Design),
+            "tasks" => Ok(Self::Tasks),
+            _ => Err(crate::error::VibeTicketError::custom(format!(
+                "Invalid document '{s}'. Must be one of: requirements, design, tasks"
+            ))),
+        }
+    }
+}
+
 impl std::fmt::Display for SpecPhase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -450,6 +564,44 @@ mod tests {
         assert_eq!(SpecPhase::Completed.to_string(), "Completed");
     }
 
+    #[test]
+    fn test_invalidate_approvals() {
+        let metadata = SpecMetadata::new("Review".to_string(), String::new());
+        let approve = |who: &str| review::PhaseReview {
+            reviewers: Vec::new(),
+            sign_offs: vec![review::SignOff::new(
+                who,
+                review::Verdict::Approved,
+                &metadata,
+                None,
+            )],
+        };
+        let mut progress = SpecProgress {
+            requirements_approved: true,
+            design_approved: true,
+            tasks_approved: true,
+            reviews: std::collections::BTreeMap::from([
+                (SpecDocumentType::Requirements, approve("alice")),
+                (SpecDocumentType::Tasks, approve("bob")),
+            ]),
+            ..SpecProgress::default()
+        };
+
+        assert!(progress.invalidate_approvals(SpecDocumentType::Design));
+        assert!(progress.requirements_approved);
+        assert!(!progress.design_approved);
+        assert!(!progress.tasks_approved);
+        assert!(progress.reviews[&SpecDocumentType::Requirements].sign_offs[0].is_active());
+        let tasks = &progress.reviews[&SpecDocumentType::Tasks];
+        assert_eq!(tasks.sign_offs.len(), 1);
+        assert!(!tasks.sign_offs[0].is_active());
+
+        // Nothing left to invalidate downstream of design
+        assert!(!progress.invalidate_approvals(SpecDocumentType::Tasks));
+        assert!(progress.invalidate_approvals(SpecDocumentType::Requirements));
+        assert!(!progress.requirements_approved);
+    }
+
     #[test]
     fn test_spec_phase_equality() {
         assert_eq!(SpecPhase::Initial, SpecPhase::Initial);
@@ -0,0 +1,413 @@
+//! Review and sign-off records of spec phases
+//!
+//! Each phase (requirements, design, tasks) keeps the sign-offs of its
+//! reviewers: approvals and change requests with who, when, the spec version
+//! and a comment. A phase is approved once
+//!
+//! - at least the configured quorum of reviewers approve it,
+//! - every required reviewer approves it, and
+//! - nobody's latest sign-off requests changes.
+//!
+//! Changing a document withdraws the sign-offs of its phase and of all later
+//! phases; the records stay for the audit trail. Quorum, required reviewers
+//! and whether phases must be approved in order are set in the `spec.review`
+//! section of `.vibe-ticket/config.yaml`:
+//!
+//! ```yaml
+//! spec:
+//!   review:
+//!     quorum:
+//!       requirements: 2
+//!     reviewers:
+//!       design: [alice]
+//!     enforce_order: true
+//! ```
+
+use super::{SpecDocumentType, SpecMetadata, SpecVersion};
+use crate::error::{Result, VibeTicketError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Outcome of a sign-off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The reviewer approves the phase
+    Approved,
+    /// The reviewer wants changes before approving
+    ChangesRequested,
+}
+
+/// A reviewer's sign-off on a phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignOff {
+    /// Who signed off
+    pub reviewer: String,
+
+    /// Approval or change request
+    pub verdict: Verdict,
+
+    /// When the sign-off was given
+    pub at: DateTime<Utc>,
+
+    /// Spec version that was reviewed; every document change bumps it
+    pub version: SpecVersion,
+
+    /// Comment, required for change requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// When a document change withdrew the sign-off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawn_at: Option<DateTime<Utc>>,
+}
+
+impl SignOff {
+    /// Create a sign-off of the current version of a spec
+    #[must_use]
+    pub fn new(
+        reviewer: impl Into<String>,
+        verdict: Verdict,
+        metadata: &SpecMetadata,
+        comment: Option<String>,
+    ) -> Self {
+        Self {
+            reviewer: reviewer.into(),
+            verdict,
+            at: Utc::now(),
+            version: metadata.version.clone(),
+            comment,
+            withdrawn_at: None,
+        }
+    }
+
+    /// Whether the sign-off still counts
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.withdrawn_at.is_none()
+    }
+}
+
+/// Reviewers and sign-offs of one phase
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhaseReview {
+    /// Reviewers who must approve this phase of the spec
+    pub reviewers: Vec<String>,
+
+    /// All sign-offs, oldest first
+    pub sign_offs: Vec<SignOff>,
+}
+
+impl PhaseReview {
+    /// Latest active sign-off of each reviewer
+    #[must_use]
+    pub fn latest(&self) -> BTreeMap<&str, &SignOff> {
+        let mut latest = BTreeMap::new();
+        for sign_off in self.sign_offs.iter().filter(|s| s.is_active()) {
+            latest.insert(sign_off.reviewer.as_str(), sign_off);
+        }
+        latest
+    }
+
+    /// Withdraw the active sign-offs
+    ///
+    /// Returns whether an approval was withdrawn.
+    pub fn withdraw(&mut self, at: DateTime<Utc>) -> bool {
+        let mut withdrew_approval = false;
+        for sign_off in self.sign_offs.iter_mut().filter(|s| s.is_active()) {
+            sign_off.withdrawn_at = Some(at);
+            withdrew_approval |= sign_off.verdict == Verdict::Approved;
+        }
+        withdrew_approval
+    }
+}
+
+/// Review settings of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewConfig {
+    /// Approvals needed per phase (default 1)
+    pub quorum: BTreeMap<SpecDocumentType, usize>,
+
+    /// Reviewers who must approve a phase in every spec
+    pub reviewers: BTreeMap<SpecDocumentType, Vec<String>>,
+
+    /// Refuse to start a phase before the previous one is approved
+    pub enforce_order: bool,
+}
+
+impl ReviewConfig {
+    /// Load the settings from `.vibe-ticket/config.yaml`
+    #[must_use]
+    pub fn for_project(vibe_ticket_dir: &std::path::Path) -> Self {
+        crate::config::Config::load_from_path(vibe_ticket_dir.join("config.yaml"))
+            .unwrap_or_default()
+            .spec
+            .review
+    }
+
+    /// Approvals needed for a phase
+    #[must_use]
+    pub fn quorum(&self, phase: SpecDocumentType) -> usize {
+        self.quorum.get(&phase).copied().unwrap_or(1).max(1)
+    }
+
+    /// Status of a phase of a spec
+    #[must_use]
+    pub fn status(&self, metadata: &SpecMetadata, phase: SpecDocumentType) -> ReviewStatus {
+        let default = PhaseReview::default();
+        let review = metadata.progress.reviews.get(&phase).unwrap_or(&default);
+        let latest = review.latest();
+
+        let mut required: Vec<String> = self.reviewers.get(&phase).cloned().unwrap_or_default();
+        for reviewer in &review.reviewers {
+            if !required.contains(reviewer) {
+                required.push(reviewer.clone());
+            }
+        }
+
+        let approved_by: Vec<String> = latest
+            .values()
+            .filter(|s| s.verdict == Verdict::Approved)
+            .map(|s| s.reviewer.clone())
+            .collect();
+        let changes_requested: Vec<SignOff> = latest
+            .values()
+            .filter(|s| s.verdict == Verdict::ChangesRequested)
+            .map(|s| (*s).clone())
+            .collect();
+        let missing_reviewers: Vec<String> = required
+            .into_iter()
+            .filter(|reviewer| !approved_by.contains(reviewer))
+            .collect();
+        let quorum = self.quorum(phase);
+
+        ReviewStatus {
+            phase,
+            approved: approved_by.len() >= quorum
+                && missing_reviewers.is_empty()
+                && changes_requested.is_empty(),
+            approved_by,
+            quorum,
+            missing_reviewers,
+            changes_requested,
+        }
+    }
+
+    /// Check that work on `phase` may start
+    ///
+    /// Only enforced with `enforce_order`: design needs approved requirements
+    /// and tasks need an approved design.
+    pub fn ensure_can_start(&self, metadata: &SpecMetadata, phase: SpecDocumentType) -> Result<()> {
+        let previous = match phase {
+            SpecDocumentType::Requirements => return Ok(()),
+            SpecDocumentType::Design => SpecDocumentType::Requirements,
+            SpecDocumentType::Tasks => SpecDocumentType::Design,
+        };
+        if !self.enforce_order {
+            return Ok(());
+        }
+        let status = self.status(metadata, previous);
+        if status.approved {
+            return Ok(());
+        }
+        Err(VibeTicketError::custom(format!(
+            "Cannot start {} before {} is approved ({})",
+            phase.as_str(),
+            previous.as_str(),
+            status.summary()
+        )))
+    }
+}
+
+/// Approval state of a phase
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewStatus {
+    /// The phase
+    pub phase: SpecDocumentType,
+    /// Whether the phase is approved
+    pub approved: bool,
+    /// Reviewers whose latest sign-off approves the phase
+    pub approved_by: Vec<String>,
+    /// Approvals needed
+    pub quorum: usize,
+    /// Required reviewers who have not approved
+    pub missing_reviewers: Vec<String>,
+    /// Latest sign-offs that request changes
+    pub changes_requested: Vec<SignOff>,
+}
+
+impl ReviewStatus {
+    /// One-line summary, e.g. `1/2 approvals, waiting on alice`
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut summary = format!("{}/{} approvals", self.approved_by.len(), self.quorum);
+        if !self.missing_reviewers.is_empty() {
+            summary.push_str(", waiting on ");
+            summary.push_str(&self.missing_reviewers.join(", "));
+        }
+        if !self.changes_requested.is_empty() {
+            let reviewers: Vec<&str> = self
+                .changes_requested
+                .iter()
+                .map(|s| s.reviewer.as_str())
+                .collect();
+            summary.push_str(", changes requested by ");
+            summary.push_str(&reviewers.join(", "));
+        }
+        summary
+    }
+}
+
+/// Convert untyped approvals written by older versions into sign-offs
+///
+/// Returns whether anything was migrated.
+pub fn migrate_legacy_approvals(metadata: &mut SpecMetadata) -> bool {
+    let Some(legacy) = metadata.progress.approval_status.take() else {
+        return false;
+    };
+    let mut migrated = false;
+    for (key, value) in legacy {
+        let phase = match key.as_str() {
+            "Requirements" => SpecDocumentType::Requirements,
+            "Design" => SpecDocumentType::Design,
+            "Tasks" | "Implementation" => SpecDocumentType::Tasks,
+            _ => continue,
+        };
+        if value.get("approved").and_then(serde_json::Value::as_bool) != Some(true) {
+            continue;
+        }
+        let at = value
+            .get("approved_at")
+            .and_then(|at| serde_json::from_value(at.clone()).ok())
+            .unwrap_or(metadata.updated_at);
+        let comment = value
+            .get("message")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string);
+        metadata
+            .progress
+            .reviews
+            .entry(phase)
+            .or_default()
+            .sign_offs
+            .push(SignOff {
+                reviewer: "unknown".to_string(),
+                verdict: Verdict::Approved,
+                at,
+                version: metadata.version.clone(),
+                comment,
+                withdrawn_at: None,
+            });
+        migrated = true;
+    }
+    migrated
+}
+
+/// Name to sign off as: `git config user.name`, then `$USER`
+#[must_use]
+pub fn default_reviewer() -> Option<String> {
+    git2::Config::open_default()
+        .and_then(|config| config.get_string("user.name"))
+        .ok()
+        .or_else(|| {
+            std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok()
+        })
+        .filter(|name| !name.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn sign(metadata: &mut SpecMetadata, phase: SpecDocumentType, who: &str, verdict: Verdict) {
+        let sign_off = SignOff::new(who, verdict, metadata, None);
+        metadata
+            .progress
+            .reviews
+            .entry(phase)
+            .or_default()
+            .sign_offs
+            .push(sign_off);
+    }
+
+    #[test]
+    fn test_quorum_and_required_reviewers() {
+        let config = ReviewConfig {
+            quorum: BTreeMap::from([(SpecDocumentType::Requirements, 2)]),
+            reviewers: BTreeMap::from([(SpecDocumentType::Design, vec!["alice".to_string()])]),
+            enforce_order: true,
+        };
+        let mut metadata = SpecMetadata::new("Review".to_string(), String::new());
+        let requirements = SpecDocumentType::Requirements;
+
+        sign(&mut metadata, requirements, "bob", Verdict::Approved);
+        let status = config.status(&metadata, requirements);
+        assert!(!status.approved);
+        assert_eq!(status.summary(), "1/2 approvals");
+        assert!(
+            config
+                .ensure_can_start(&metadata, SpecDocumentType::Design)
+                .is_err()
+        );
+
+        sign(
+            &mut metadata,
+            requirements,
+            "carol",
+            Verdict::ChangesRequested,
+        );
+        assert_eq!(
+            config.status(&metadata, requirements).summary(),
+            "1/2 approvals, changes requested by carol"
+        );
+
+        // A later approval replaces the reviewer's change request
+        sign(&mut metadata, requirements, "carol", Verdict::Approved);
+        assert!(config.status(&metadata, requirements).approved);
+        config
+            .ensure_can_start(&metadata, SpecDocumentType::Design)
+            .unwrap();
+
+        sign(
+            &mut metadata,
+            SpecDocumentType::Design,
+            "bob",
+            Verdict::Approved,
+        );
+        let design = config.status(&metadata, SpecDocumentType::Design);
+        assert!(!design.approved);
+        assert_eq!(design.missing_reviewers, vec!["alice"]);
+    }
+
+    #[test]
+    fn test_withdraw_and_migrate() {
+        let mut metadata = SpecMetadata::new("Legacy".to_string(), String::new());
+        metadata.progress.approval_status = Some(HashMap::from([(
+            "Design".to_string(),
+            serde_json::json!({"approved": true, "message": "LGTM"}),
+        )]));
+        assert!(migrate_legacy_approvals(&mut metadata));
+        assert!(metadata.progress.approval_status.is_none());
+
+        let config = ReviewConfig::default();
+        let status = config.status(&metadata, SpecDocumentType::Design);
+        assert!(status.approved);
+        assert_eq!(status.approved_by, vec!["unknown"]);
+
+        let review = metadata
+            .progress
+            .reviews
+            .get_mut(&SpecDocumentType::Design)
+            .unwrap();
+        assert!(review.withdraw(Utc::now()));
+        assert!(!review.withdraw(Utc::now()));
+        assert_eq!(review.sign_offs.len(), 1);
+        assert!(!config.status(&metadata, SpecDocumentType::Design).approved);
+    }
+}
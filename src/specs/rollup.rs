@@ -0,0 +1,316 @@
+//! Progress rollup of a spec from the tickets generated from its tasks
+//!
+//! The phase flags of [`super::SpecProgress`] only say which documents are
+//! written. Once tasks are exported as tickets, [`SpecRollup`] measures the
+//! actual work: ticket counts by status per task phase, completion, logged
+//! time, blockers and an ETA projected from the throughput so far.
+
+use super::task_sync::{DEPENDS_ON_KEY, PHASE_KEY};
+use super::tasks::parse_tasks;
+use super::trace::{SPEC_TASK_KEY, belongs_to_spec};
+use super::{SpecPhase, Specification};
+use crate::core::{Status, Ticket};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// Phase name of tickets whose task is not under a heading
+const UNPHASED: &str = "Unphased";
+
+/// Number of tickets in each status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StatusCounts {
+    /// Tickets not started
+    pub todo: usize,
+    /// Tickets in progress
+    pub doing: usize,
+    /// Tickets in review
+    pub review: usize,
+    /// Blocked tickets
+    pub blocked: usize,
+    /// Finished tickets
+    pub done: usize,
+}
+
+impl StatusCounts {
+    const fn add(&mut self, status: Status) {
+        match status {
+            Status::Todo => self.todo += 1,
+            Status::Doing => self.doing += 1,
+            Status::Review => self.review += 1,
+            Status::Blocked => self.blocked += 1,
+            Status::Done => self.done += 1,
+        }
+    }
+
+    /// Total number of tickets
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.todo + self.doing + self.review + self.blocked + self.done
+    }
+
+    /// Share of finished tickets, from 0 to 100
+    #[must_use]
+    pub fn percent_complete(&self) -> u8 {
+        match self.total() {
+            0 => 0,
+            total => u8::try_from(self.done * 100 / total).unwrap_or(100),
+        }
+    }
+}
+
+/// Ticket counts of one task phase (a heading of `tasks.md`)
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseRollup {
+    /// Phase heading
+    pub phase: String,
+    /// Tickets by status
+    pub tickets: StatusCounts,
+    /// Share of finished tickets, from 0 to 100
+    pub percent_complete: u8,
+}
+
+/// An unfinished ticket that cannot progress
+#[derive(Debug, Clone, Serialize)]
+pub struct Blocker {
+    /// Ticket slug
+    pub slug: String,
+    /// Ticket title
+    pub title: String,
+    /// Why the ticket is blocked
+    pub reason: String,
+}
+
+/// Progress of a spec measured from its tickets
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecRollup {
+    /// Spec ID
+    pub spec_id: String,
+    /// Spec title
+    pub title: String,
+    /// Current spec phase
+    pub phase: SpecPhase,
+    /// All linked tickets by status
+    pub tickets: StatusCounts,
+    /// Share of finished tickets, from 0 to 100
+    pub percent_complete: u8,
+    /// Time logged on the linked tickets, in minutes
+    pub time_logged_minutes: i64,
+    /// Ticket counts per task phase, in document order
+    pub phases: Vec<PhaseRollup>,
+    /// Unfinished tickets that cannot progress
+    pub blockers: Vec<Blocker>,
+    /// Tickets finished per day since the first linked ticket was created
+    pub throughput_per_day: Option<f64>,
+    /// Projected completion at the current throughput
+    pub eta: Option<DateTime<Utc>>,
+}
+
+impl SpecRollup {
+    /// Build the rollup of a spec
+    ///
+    /// `minutes_logged` returns the time logged on a ticket, by ticket ID.
+    pub fn build(
+        spec: &Specification,
+        tickets: &[Ticket],
+        minutes_logged: impl Fn(&str) -> i64,
+    ) -> Self {
+        let spec_id = spec.metadata.id.as_str();
+        let tasks = parse_tasks(spec.tasks.as_deref().unwrap_or_default());
+        let linked: Vec<&Ticket> = tickets
+            .iter()
+            .filter(|ticket| belongs_to_spec(ticket, spec_id))
+            .collect();
+
+        // Phases in the order their tasks appear, then any unknown ones
+        let mut phases: Vec<PhaseRollup> = Vec::new();
+        for phase in tasks.iter().map(|task| task.phase.as_deref()) {
+            let phase = phase.unwrap_or(UNPHASED);
+            if phases.iter().all(|p| p.phase != phase) {
+                phases.push(PhaseRollup {
+                    phase: phase.to_string(),
+                    tickets: StatusCounts::default(),
+                    percent_complete: 0,
+                });
+            }
+        }
+
+        let mut counts = StatusCounts::default();
+        let mut time_logged_minutes = 0;
+        let mut blockers = Vec::new();
+        for ticket in &linked {
+            counts.add(ticket.status);
+            time_logged_minutes += minutes_logged(&ticket.id.to_string());
+
+            let task_id = metadata_str(ticket, SPEC_TASK_KEY);
+            let phase = task_id
+                .and_then(|id| tasks.iter().find(|task| task.id == id))
+                .and_then(|task| task.phase.as_deref())
+                .or_else(|| metadata_str(ticket, PHASE_KEY))
+                .unwrap_or(UNPHASED);
+            let index = phases
+                .iter()
+                .position(|p| p.phase == phase)
+                .unwrap_or_else(|| {
+                    phases.push(PhaseRollup {
+                        phase: phase.to_string(),
+                        tickets: StatusCounts::default(),
+                        percent_complete: 0,
+                    });
+                    phases.len() - 1
+                });
+            phases[index].tickets.add(ticket.status);
+
+            if let Some(reason) = blocker_reason(ticket, &linked) {
+                blockers.push(Blocker {
+                    slug: ticket.slug.clone(),
+                    title: ticket.title.clone(),
+                    reason,
+                });
+            }
+        }
+        phases.retain(|p| p.tickets.total() > 0);
+        for phase in &mut phases {
+            phase.percent_complete = phase.tickets.percent_complete();
+        }
+
+        let (throughput_per_day, eta) = projection(&linked, &counts, Utc::now());
+
+        Self {
+            spec_id: spec_id.to_string(),
+            title: spec.metadata.title.clone(),
+            phase: spec.metadata.progress.current_phase(),
+            tickets: counts,
+            percent_complete: counts.percent_complete(),
+            time_logged_minutes,
+            phases,
+            blockers,
+            throughput_per_day,
+            eta,
+        }
+    }
+
+    /// Whether the spec has linked tickets and all of them are done
+    #[must_use]
+    pub const fn is_complete(&self) -> bool {
+        self.tickets.total() > 0 && self.tickets.done == self.tickets.total()
+    }
+}
+
+/// Why an unfinished ticket cannot progress
+fn blocker_reason(ticket: &Ticket, linked: &[&Ticket]) -> Option<String> {
+    if ticket.status == Status::Done {
+        return None;
+    }
+    if ticket.status == Status::Blocked {
+        return Some("marked as blocked".to_string());
+    }
+    let waiting_on: Vec<&str> = ticket
+        .metadata
+        .get(DEPENDS_ON_KEY)
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .filter(|task_id| {
+            linked.iter().any(|other| {
+                metadata_str(other, SPEC_TASK_KEY) == Some(task_id) && other.status != Status::Done
+            })
+        })
+        .collect();
+    (!waiting_on.is_empty()).then(|| format!("waiting on {}", waiting_on.join(", ")))
+}
+
+/// Throughput since the first ticket was created and the resulting ETA
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn projection(
+    linked: &[&Ticket],
+    counts: &StatusCounts,
+    now: DateTime<Utc>,
+) -> (Option<f64>, Option<DateTime<Utc>>) {
+    let Some(first) = linked.iter().map(|ticket| ticket.created_at).min() else {
+        return (None, None);
+    };
+    if counts.done == 0 {
+        return (None, None);
+    }
+    // At least a day, so a burst of early closes does not inflate throughput
+    let elapsed_days = ((now - first).num_minutes() as f64 / 1440.0).max(1.0);
+    let throughput = counts.done as f64 / elapsed_days;
+    let remaining = (counts.total() - counts.done) as f64;
+    let eta = now + Duration::minutes((remaining / throughput * 1440.0).round() as i64);
+    (Some(throughput), Some(eta))
+}
+
+fn metadata_str<'a>(ticket: &'a Ticket, key: &str) -> Option<&'a str> {
+    ticket.metadata.get(key).and_then(|v| v.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS: &str = "\
+## Setup
+- [x] T001 Scaffold
+## Core
+- [ ] T002 Parser
+- [ ] T003 CLI (depends on T002)
+";
+
+    fn ticket(spec_id: &str, task: &str, status: Status, days_ago: i64) -> Ticket {
+        let mut ticket = Ticket::new(format!("{spec_id}-{}", task.to_lowercase()), task);
+        ticket.status = status;
+        ticket.created_at = Utc::now() - Duration::days(days_ago);
+        ticket
+            .metadata
+            .insert(SPEC_TASK_KEY.to_string(), task.into());
+        ticket
+            .metadata
+            .insert("spec_id".to_string(), spec_id.into());
+        ticket
+    }
+
+    #[test]
+    fn test_rollup() {
+        let mut spec = Specification::new("Rollup".to_string(), String::new(), None, vec![]);
+        spec.tasks = Some(TASKS.to_string());
+        let spec_id = spec.metadata.id.clone();
+
+        let mut cli = ticket(&spec_id, "T003", Status::Todo, 1);
+        cli.metadata
+            .insert(DEPENDS_ON_KEY.to_string(), serde_json::json!(["T002"]));
+        let tickets = vec![
+            ticket(&spec_id, "T001", Status::Done, 4),
+            ticket(&spec_id, "T002", Status::Doing, 2),
+            cli,
+            Ticket::new("unrelated", "Unrelated"),
+        ];
+
+        let rollup = SpecRollup::build(&spec, &tickets, |_| 30);
+        assert_eq!(rollup.tickets.total(), 3);
+        assert_eq!(rollup.percent_complete, 33);
+        assert_eq!(rollup.time_logged_minutes, 90);
+        assert_eq!(rollup.phases.len(), 2);
+        assert_eq!(rollup.phases[0].phase, "Setup");
+        assert_eq!(rollup.phases[0].percent_complete, 100);
+        assert_eq!(rollup.phases[1].tickets.doing, 1);
+        assert_eq!(rollup.blockers.len(), 1);
+        assert_eq!(rollup.blockers[0].reason, "waiting on T002");
+
+        // One ticket done in four days leaves two, eight days out
+        let throughput = rollup.throughput_per_day.unwrap();
+        assert!((throughput - 0.25).abs() < 0.01);
+        let eta_days = (rollup.eta.unwrap() - Utc::now()).num_days();
+        assert!((7..=8).contains(&eta_days));
+        assert!(!rollup.is_complete());
+    }
+
+    #[test]
+    fn test_empty_rollup() {
+        let spec = Specification::new("Empty".to_string(), String::new(), None, vec![]);
+        let rollup = SpecRollup::build(&spec, &[], |_| 0);
+        assert_eq!(rollup.percent_complete, 0);
+        assert!(rollup.eta.is_none());
+        assert!(!rollup.is_complete());
+    }
+}
@@ -4,6 +4,7 @@
 //! reducing code duplication in file I/O operations.
 
 use crate::error::{ErrorContext, Result};
+use crate::storage::write_project_file;
 use serde::{Serialize, de::DeserializeOwned};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -49,8 +50,7 @@ impl DocumentStore for FileSystemStore {
             self.ensure_dir(parent)?;
         }
 
-        fs::write(path, content)
-            .with_context(|| format!("Failed to write file: {}", path.display()))
+        write_project_file(path, content)
     }
 
     fn load_text(&self, path: &Path) -> Result<String> {
@@ -0,0 +1,550 @@
+//! Two-way sync between `tasks.md` and tickets
+//!
+//! Every task of a spec can be exported as a ticket. Exporting again updates
+//! the existing tickets instead of creating new ones: a ticket is linked to
+//! its task through the `spec_id`/`spec_task` metadata, or the
+//! `<spec-id>-t001` slug used by older exports.
+//!
+//! A ticked checkbox corresponds to a `done` ticket. The state seen at the
+//! last sync is kept in the ticket's `spec_task_checked` metadata, so whichever
+//! side changed since then wins; if both changed, completion wins.
+
+use super::tasks::{SpecTask, parse_tasks, set_checked};
+use super::trace::{REQUIREMENTS_KEY, SPEC_ID_KEY, SPEC_TASK_KEY, belongs_to_spec};
+use super::{SpecDocumentType, SpecManager, Specification};
+use crate::core::{Priority, Status, Ticket, TicketBuilder};
+use crate::error::{Result, VibeTicketError};
+use crate::storage::{FileStorage, Transaction, write_project_file};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Ticket metadata key holding the checkbox state seen at the last sync
+pub const CHECKED_KEY: &str = "spec_task_checked";
+
+/// Ticket metadata key holding the tasks the ticket's task depends on
+pub const DEPENDS_ON_KEY: &str = "spec_depends_on";
+
+/// Ticket metadata key holding the heading the task appears under
+pub const PHASE_KEY: &str = "spec_phase";
+
+/// Changes made by an export or sync
+#[derive(Debug, Default, Serialize)]
+pub struct TaskSyncReport {
+    /// Slugs of the tickets created
+    pub created: Vec<String>,
+    /// Slugs of the existing tickets that changed
+    pub updated: Vec<String>,
+    /// IDs of the tasks whose checkbox was toggled
+    pub toggled: Vec<String>,
+}
+
+/// Which side of a task/ticket pair has to change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    InSync,
+    /// Set the ticket to done (`true`) or reopen it (`false`)
+    UpdateTicket(bool),
+    /// Tick (`true`) or clear (`false`) the checkbox
+    UpdateTask(bool),
+}
+
+/// Decide how to reconcile a checkbox with its ticket
+const fn resolve(checked: bool, done: bool, last_synced: Option<bool>) -> Resolution {
+    if checked == done {
+        return Resolution::InSync;
+    }
+    match last_synced {
+        // The checkbox changed since the last sync
+        Some(last) if last != checked => Resolution::UpdateTicket(checked),
+        // The ticket changed since the last sync
+        Some(_) => Resolution::UpdateTask(done),
+        // Never synced: completion on either side wins
+        None if checked => Resolution::UpdateTicket(true),
+        None => Resolution::UpdateTask(true),
+    }
+}
+
+/// Contents of `tasks.md` documents by path, and whether they changed
+type Documents = BTreeMap<PathBuf, (String, bool)>;
+
+/// Syncs the tasks of specs with tickets
+pub struct TaskSync {
+    manager: SpecManager,
+    storage: FileStorage,
+}
+
+impl TaskSync {
+    /// Create a sync for the project in `vibe_ticket_dir`
+    #[must_use]
+    pub fn new(vibe_ticket_dir: &Path) -> Self {
+        Self {
+            manager: SpecManager::new(vibe_ticket_dir.join("specs")),
+            storage: FileStorage::new(vibe_ticket_dir),
+        }
+    }
+
+    /// Create or update a ticket for every task of the spec, then sync their states
+    pub fn export(&self, spec: &Specification) -> Result<TaskSyncReport> {
+        self.run(spec, true)
+    }
+
+    /// Sync checkbox states with the spec's existing tickets
+    pub fn sync(&self, spec: &Specification) -> Result<TaskSyncReport> {
+        self.run(spec, false)
+    }
+
+    /// Change the status of a ticket and keep its spec task in sync
+    ///
+    /// Every command that changes a single ticket's status goes through here
+    /// before saving the ticket, so the checkbox of the task it was exported
+    /// from follows and the spec completes with its last ticket. Returns
+    /// whether the checkbox was toggled.
+    pub fn set_status(&self, ticket: &mut Ticket, status: Status) -> Result<bool> {
+        ticket.set_status(status);
+        self.sync_ticket(ticket)
+    }
+
+    /// Change the status of tickets saved in `transaction`
+    ///
+    /// Like [`TaskSync::set_status`], but the checkboxes are staged in the
+    /// transaction, so they change together with the tickets or not at all.
+    /// Returns the specs to pass to [`TaskSync::complete_specs`] once the
+    /// transaction is committed.
+    pub fn stage_statuses(
+        &self,
+        tickets: &mut [Ticket],
+        status: Status,
+        transaction: &mut Transaction<'_>,
+    ) -> Result<Vec<String>> {
+        for ticket in tickets.iter_mut() {
+            ticket.set_status(status);
+        }
+        self.stage_tickets(tickets, transaction)
+    }
+
+    /// Stage the checkboxes of tickets whose status was already changed
+    ///
+    /// Used where something other than [`TaskSync`] changes statuses, such
+    /// as `sync pull`. Returns the specs of the tickets that are now done.
+    pub fn stage_tickets<'t>(
+        &self,
+        tickets: impl IntoIterator<Item = &'t mut Ticket>,
+        transaction: &mut Transaction<'_>,
+    ) -> Result<Vec<String>> {
+        let mut documents = Documents::new();
+        let mut specs = Vec::new();
+        for ticket in tickets {
+            let spec_id = self.sync_checkbox(ticket, &mut documents)?;
+            if let Some(spec_id) = spec_id.filter(|_| ticket.status == Status::Done) {
+                if !specs.contains(&spec_id) {
+                    specs.push(spec_id);
+                }
+            }
+        }
+        for (path, (content, changed)) in documents {
+            if changed {
+                transaction.write(&path, content);
+            }
+        }
+        Ok(specs)
+    }
+
+    /// Move each spec whose tickets are all done to the completed phase
+    ///
+    /// Called with the specs returned by [`TaskSync::stage_statuses`] after
+    /// the tickets were saved.
+    pub fn complete_specs(&self, spec_ids: &[String]) -> Result<()> {
+        for spec_id in spec_ids {
+            self.complete_if_done(spec_id, &[])?;
+        }
+        Ok(())
+    }
+
+    /// Update the checkbox of the task a ticket was exported from
+    ///
+    /// Called after a ticket's status changed, before it is saved. Returns
+    /// whether the checkbox was toggled; tickets not linked to a task, or
+    /// whose spec or task no longer exists, are left alone.
+    pub fn sync_ticket(&self, ticket: &mut Ticket) -> Result<bool> {
+        let mut documents = Documents::new();
+        let Some(spec_id) = self.sync_checkbox(ticket, &mut documents)? else {
+            return Ok(false);
+        };
+        let mut toggled = false;
+        for (path, (content, changed)) in documents {
+            if changed {
+                write_project_file(&path, content)?;
+                toggled = true;
+            }
+        }
+        if ticket.status == Status::Done {
+            self.complete_if_done(&spec_id, std::slice::from_ref(ticket))?;
+        }
+        Ok(toggled)
+    }
+
+    /// Update the checkbox of a ticket's task in `documents`
+    ///
+    /// Returns the ID of the spec, or `None` if the ticket is not linked to
+    /// an existing task.
+    fn sync_checkbox(
+        &self,
+        ticket: &mut Ticket,
+        documents: &mut Documents,
+    ) -> Result<Option<String>> {
+        let (Some(spec_id), Some(task_id)) = (
+            metadata_str(ticket, SPEC_ID_KEY),
+            metadata_str(ticket, SPEC_TASK_KEY),
+        ) else {
+            return Ok(None);
+        };
+        let path = self
+            .manager
+            .get_document_path(&spec_id, SpecDocumentType::Tasks);
+        let (content, changed) = match documents.entry(path) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = entry.key();
+                if !path.exists() {
+                    return Ok(None);
+                }
+                let content = fs::read_to_string(path)
+                    .map_err(|e| VibeTicketError::io_error("read", path, e))?;
+                entry.insert((content, false))
+            },
+        };
+        let Some(task) = parse_tasks(content)
+            .into_iter()
+            .find(|task| task.id == task_id)
+        else {
+            return Ok(None);
+        };
+
+        let done = ticket.status == Status::Done;
+        ticket.metadata.insert(CHECKED_KEY.to_string(), done.into());
+        if task.completed != done {
+            if let Some(updated) = set_checked(content, task.line, done) {
+                *content = updated;
+                *changed = true;
+            }
+        }
+        Ok(Some(spec_id))
+    }
+
+    /// Move the spec to the completed phase once all of its tickets are done
+    ///
+    /// `pending` are tickets changed but not saved yet.
+    fn complete_if_done(&self, spec_id: &str, pending: &[Ticket]) -> Result<bool> {
+        let mut tickets = self.storage.load_all_tickets()?;
+        tickets.retain(|ticket| pending.iter().all(|p| p.id != ticket.id));
+        tickets.extend_from_slice(pending);
+        let mut linked = tickets
+            .iter()
+            .filter(|ticket| belongs_to_spec(ticket, spec_id))
+            .peekable();
+        if linked.peek().is_none() || !linked.all(|ticket| ticket.status == Status::Done) {
+            return Ok(false);
+        }
+        self.manager.mark_completed(spec_id)
+    }
+
+    fn run(&self, spec: &Specification, create: bool) -> Result<TaskSyncReport> {
+        let spec_id = &spec.metadata.id;
+        let path = self
+            .manager
+            .get_document_path(spec_id, SpecDocumentType::Tasks);
+        if !path.exists() {
+            return Err(VibeTicketError::custom(format!(
+                "Specification '{}' has no tasks document",
+                spec.metadata.title
+            )));
+        }
+        let mut content =
+            fs::read_to_string(&path).map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+        let tasks = parse_tasks(&content);
+        // Tickets and checkboxes change together or not at all
+        let mut transaction = self.storage.begin(if create {
+            "spec task export"
+        } else {
+            "spec task sync"
+        })?;
+        let mut tickets = self.storage.load_all_tickets()?;
+        let mut report = TaskSyncReport::default();
+
+        for task in &tasks {
+            let existing = tickets
+                .iter_mut()
+                .find(|ticket| is_linked(ticket, spec_id, &task.id));
+            let ticket = match existing {
+                Some(ticket) => ticket,
+                None if create => {
+                    let mut ticket = new_ticket(spec, task);
+                    apply_task(&mut ticket, spec_id, task);
+                    if task.completed {
+                        ticket.close();
+                    }
+                    ticket
+                        .metadata
+                        .insert(CHECKED_KEY.to_string(), task.completed.into());
+                    transaction.save(&ticket)?;
+                    report.created.push(ticket.slug.clone());
+                    continue;
+                },
+                None => continue,
+            };
+
+            let before = ticket.clone();
+            if create {
+                apply_task(ticket, spec_id, task);
+            }
+            let last_synced = ticket.metadata.get(CHECKED_KEY).and_then(|v| v.as_bool());
+            let checked = match resolve(task.completed, ticket.status == Status::Done, last_synced)
+            {
+                Resolution::InSync => task.completed,
+                Resolution::UpdateTicket(true) => {
+                    ticket.set_status(Status::Done);
+                    true
+                },
+                Resolution::UpdateTicket(false) => {
+                    ticket.set_status(Status::Todo);
+                    false
+                },
+                Resolution::UpdateTask(checked) => {
+                    if let Some(updated) = set_checked(&content, task.line, checked) {
+                        content = updated;
+                        report.toggled.push(task.id.clone());
+                    }
+                    checked
+                },
+            };
+            ticket
+                .metadata
+                .insert(CHECKED_KEY.to_string(), checked.into());
+
+            if *ticket != before {
+                transaction.save(ticket)?;
+                report.updated.push(ticket.slug.clone());
+            }
+        }
+
+        if !report.toggled.is_empty() {
+            transaction.write(&path, content);
+        }
+        transaction.commit()?;
+        self.complete_if_done(spec_id, &[])?;
+        Ok(report)
+    }
+}
+
+/// Whether a ticket was exported from the given task
+fn is_linked(ticket: &Ticket, spec_id: &str, task_id: &str) -> bool {
+    if let (Some(spec), Some(task)) = (
+        metadata_str(ticket, SPEC_ID_KEY),
+        metadata_str(ticket, SPEC_TASK_KEY),
+    ) {
+        return spec == spec_id && task == task_id;
+    }
+    ticket.slug == legacy_slug(spec_id, task_id)
+}
+
+fn legacy_slug(spec_id: &str, task_id: &str) -> String {
+    format!("{spec_id}-{}", task_id.to_lowercase())
+}
+
+fn metadata_str(ticket: &Ticket, key: &str) -> Option<String> {
+    ticket
+        .metadata
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn new_ticket(spec: &Specification, task: &SpecTask) -> Ticket {
+    TicketBuilder::new()
+        .slug(legacy_slug(&spec.metadata.id, &task.id))
+        .title(format!("[{}] {}", task.id, task.title))
+        .description(format!("Task from specification: {}", spec.metadata.title))
+        .priority(Priority::Medium)
+        .tags(vec![
+            "spec-driven".to_string(),
+            "auto-generated".to_string(),
+            spec.metadata.id.clone(),
+        ])
+        .build()
+}
+
+/// Copy the task's title and links onto its ticket
+fn apply_task(ticket: &mut Ticket, spec_id: &str, task: &SpecTask) {
+    ticket.title = format!("[{}] {}", task.id, task.title);
+    if !ticket.tags.iter().any(|tag| tag == spec_id) {
+        ticket.tags.push(spec_id.to_string());
+    }
+    let metadata = &mut ticket.metadata;
+    metadata.insert(SPEC_ID_KEY.to_string(), spec_id.into());
+    metadata.insert(SPEC_TASK_KEY.to_string(), task.id.clone().into());
+    metadata.insert(
+        REQUIREMENTS_KEY.to_string(),
+        task.requirements.clone().into(),
+    );
+    metadata.insert(DEPENDS_ON_KEY.to_string(), task.depends_on.clone().into());
+    match &task.phase {
+        Some(phase) => metadata.insert(PHASE_KEY.to_string(), phase.clone().into()),
+        None => metadata.remove(PHASE_KEY),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TASKS: &str = "## Setup\n- [ ] T001 First\n- [x] T002 Second (depends on T001)\n";
+
+    fn setup() -> (TempDir, TaskSync, Specification) {
+        let temp_dir = TempDir::new().unwrap();
+        let sync = TaskSync::new(temp_dir.path());
+        fs::create_dir_all(temp_dir.path().join("tickets")).unwrap();
+        let metadata = sync
+            .manager
+            .create_spec("Sync".to_string(), String::new())
+            .unwrap();
+        sync.manager
+            .save_document(&metadata.id, SpecDocumentType::Tasks, TASKS)
+            .unwrap();
+        let spec = sync.manager.load_spec(&metadata.id).unwrap();
+        (temp_dir, sync, spec)
+    }
+
+    fn tasks_md(sync: &TaskSync, spec: &Specification) -> String {
+        fs::read_to_string(
+            sync.manager
+                .get_document_path(&spec.metadata.id, SpecDocumentType::Tasks),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve(true, true, None), Resolution::InSync);
+        assert_eq!(resolve(true, false, None), Resolution::UpdateTicket(true));
+        assert_eq!(resolve(false, true, None), Resolution::UpdateTask(true));
+        assert_eq!(
+            resolve(false, true, Some(true)),
+            Resolution::UpdateTicket(false)
+        );
+        assert_eq!(
+            resolve(true, false, Some(true)),
+            Resolution::UpdateTask(false)
+        );
+    }
+
+    #[test]
+    fn test_export_is_idempotent() {
+        let (_temp_dir, sync, spec) = setup();
+
+        let report = sync.export(&spec).unwrap();
+        assert_eq!(report.created.len(), 2);
+        let report = sync.export(&spec).unwrap();
+        assert!(report.created.is_empty() && report.updated.is_empty());
+
+        let tickets = sync.storage.load_all_tickets().unwrap();
+        assert_eq!(tickets.len(), 2);
+        let second = tickets.iter().find(|t| t.title == "[T002] Second").unwrap();
+        assert_eq!(second.status, Status::Done);
+        assert_eq!(second.metadata[DEPENDS_ON_KEY], serde_json::json!(["T001"]));
+        assert_eq!(second.metadata[PHASE_KEY], "Setup");
+    }
+
+    #[test]
+    fn test_spec_completes_with_its_tickets() {
+        let (_temp_dir, sync, spec) = setup();
+        sync.export(&spec).unwrap();
+        let phase = |sync: &TaskSync| {
+            sync.manager
+                .load_spec(&spec.metadata.id)
+                .unwrap()
+                .metadata
+                .progress
+                .current_phase()
+        };
+        assert_ne!(phase(&sync), crate::specs::SpecPhase::Completed);
+
+        let mut first = sync
+            .storage
+            .load_all_tickets()
+            .unwrap()
+            .into_iter()
+            .find(|ticket| ticket.status != Status::Done)
+            .unwrap();
+        first.close();
+        sync.sync_ticket(&mut first).unwrap();
+        assert_eq!(phase(&sync), crate::specs::SpecPhase::Completed);
+    }
+
+    #[test]
+    fn test_staged_statuses_apply_on_commit() {
+        let (_temp_dir, sync, spec) = setup();
+        sync.export(&spec).unwrap();
+        let mut tickets = sync.storage.load_all_tickets().unwrap();
+        let before = tasks_md(&sync, &spec);
+
+        // A transaction dropped without committing leaves the spec alone
+        let mut transaction = sync.storage.begin("test").unwrap();
+        sync.stage_statuses(&mut tickets, Status::Done, &mut transaction)
+            .unwrap();
+        drop(transaction);
+        assert_eq!(tasks_md(&sync, &spec), before);
+
+        let mut transaction = sync.storage.begin("test").unwrap();
+        let specs = sync
+            .stage_statuses(&mut tickets, Status::Done, &mut transaction)
+            .unwrap();
+        for ticket in &tickets {
+            transaction.save(ticket).unwrap();
+        }
+        assert_eq!(tasks_md(&sync, &spec), before);
+        transaction.commit().unwrap();
+        sync.complete_specs(&specs).unwrap();
+        assert_eq!(tasks_md(&sync, &spec), TASKS.replace("[ ]", "[x]"));
+        assert_eq!(
+            sync.manager
+                .load_spec(&spec.metadata.id)
+                .unwrap()
+                .metadata
+                .progress
+                .current_phase(),
+            crate::specs::SpecPhase::Completed
+        );
+    }
+
+    #[test]
+    fn test_two_way_sync() {
+        let (_temp_dir, sync, spec) = setup();
+        sync.export(&spec).unwrap();
+        let path = sync
+            .manager
+            .get_document_path(&spec.metadata.id, SpecDocumentType::Tasks);
+
+        // Ticking T001 in tasks.md closes its ticket
+        fs::write(&path, TASKS.replace("[ ] T001", "[x] T001")).unwrap();
+        let report = sync.sync(&spec).unwrap();
+        assert_eq!(report.updated.len(), 1);
+        let mut first = sync
+            .storage
+            .find_ticket_by_slug(&report.updated[0])
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.status, Status::Done);
+
+        // Reopening the ticket clears the checkbox
+        first.status = Status::Doing;
+        assert!(sync.sync_ticket(&mut first).unwrap());
+        sync.storage.save_ticket(&first).unwrap();
+        assert!(tasks_md(&sync, &spec).contains("- [ ] T001 First"));
+        let report = sync.sync(&spec).unwrap();
+        assert!(report.updated.is_empty() && report.toggled.is_empty());
+    }
+}
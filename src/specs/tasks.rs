@@ -0,0 +1,276 @@
+//! Structured task model of `tasks.md`
+//!
+//! Tasks are Markdown task list items carrying an ID such as `T001`, in any
+//! list and at any nesting depth:
+//!
+//! ```markdown
+//! ## Phase 2: Core (REQ-001)
+//! - [ ] T004 [P] Implement the parser
+//!   - [x] **T005**: Wire up the CLI (depends on T004)
+//! ```
+//!
+//! Each task records its checked state, the `[P]` parallel marker, the
+//! heading it appears under, the tasks it depends on and the requirement IDs
+//! it references on its line or in its heading.
+
+use super::trace::requirement_refs;
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+static TASK_ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bT\d{3,}\b").expect("valid regex"));
+
+static DEPENDENCY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\(?\b(?:depends on|blocked by|after)\s*:?\s*(T\d{3,}(?:\s*(?:,|and|&)\s*T\d{3,})*)\)?")
+        .expect("valid regex")
+});
+
+/// Marker of tasks that can run in parallel with their siblings
+const PARALLEL_MARKER: &str = "[P]";
+
+/// A task defined in `tasks.md`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpecTask {
+    /// Task ID, e.g. `T001`
+    pub id: String,
+    /// Task description
+    pub title: String,
+    /// Whether the checkbox is ticked
+    pub completed: bool,
+    /// Whether the task is marked `[P]`
+    pub parallel: bool,
+    /// Heading the task appears under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+    /// IDs of the tasks this task depends on
+    pub depends_on: Vec<String>,
+    /// Requirement IDs the task implements
+    pub requirements: Vec<String>,
+    /// Zero-based line of the task's checkbox in the document
+    #[serde(skip)]
+    pub line: usize,
+}
+
+/// Task IDs referenced in a piece of text, in order of appearance
+#[must_use]
+pub fn task_refs(text: &str) -> Vec<String> {
+    let mut refs: Vec<String> = Vec::new();
+    for found in TASK_ID.find_iter(text) {
+        if !refs.iter().any(|r| r == found.as_str()) {
+            refs.push(found.as_str().to_string());
+        }
+    }
+    refs
+}
+
+/// Item being collected while walking the Markdown events
+struct OpenItem {
+    text: String,
+    checked: Option<bool>,
+    line: usize,
+}
+
+/// Parse the tasks of a `tasks.md` document
+///
+/// Only the first task with a given ID is kept.
+#[must_use]
+pub fn parse_tasks(markdown: &str) -> Vec<SpecTask> {
+    let mut tasks: Vec<SpecTask> = Vec::new();
+    // Headings by level, so that a `###` keeps its enclosing `##` context
+    let mut headings: Vec<(HeadingLevel, String)> = Vec::new();
+    let mut heading_text: Option<(HeadingLevel, String)> = None;
+    let mut items: Vec<OpenItem> = Vec::new();
+    let line_of = |offset: usize| markdown[..offset].matches('\n').count();
+
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TASKLISTS);
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                heading_text = Some((level, String::new()));
+            },
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, text)) = heading_text.take() {
+                    headings.retain(|(outer, _)| *outer < level);
+                    headings.push((level, text.trim().to_string()));
+                }
+            },
+            Event::Start(Tag::Item) => items.push(OpenItem {
+                text: String::new(),
+                checked: None,
+                line: line_of(range.start),
+            }),
+            Event::TaskListMarker(checked) => {
+                if let Some(item) = items.last_mut() {
+                    item.checked = Some(checked);
+                    item.line = line_of(range.start);
+                }
+            },
+            Event::End(TagEnd::Item) => {
+                let Some(item) = items.pop() else { continue };
+                let Some(checked) = item.checked else {
+                    continue;
+                };
+                let Some(task) = build_task(&item.text, checked, item.line, &headings) else {
+                    continue;
+                };
+                if tasks.iter().all(|existing| existing.id != task.id) {
+                    tasks.push(task);
+                }
+            },
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, heading)) = heading_text.as_mut() {
+                    heading.push_str(&text);
+                } else if let Some(item) = items.last_mut() {
+                    item.text.push_str(&text);
+                }
+            },
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(item) = items.last_mut() {
+                    item.text.push(' ');
+                }
+            },
+            _ => {},
+        }
+    }
+
+    tasks.sort_by_key(|task| task.line);
+    tasks
+}
+
+fn build_task(
+    text: &str,
+    completed: bool,
+    line: usize,
+    headings: &[(HeadingLevel, String)],
+) -> Option<SpecTask> {
+    let id = TASK_ID.find(text)?;
+
+    let mut depends_on = Vec::new();
+    let mut title = text.to_string();
+    if let Some(dependency) = DEPENDENCY.captures(text) {
+        depends_on = task_refs(&dependency[1]);
+        title = title.replace(&dependency[0], "");
+    }
+    depends_on.retain(|dependency| dependency != id.as_str());
+
+    let title = title
+        .replacen(id.as_str(), "", 1)
+        .replace(PARALLEL_MARKER, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches([':', '-', '–', '—', ' '])
+        .to_string();
+
+    let mut requirements = Vec::new();
+    for source in headings
+        .iter()
+        .map(|(_, heading)| heading.as_str())
+        .chain([text])
+    {
+        for requirement in requirement_refs(source) {
+            if !requirements.contains(&requirement) {
+                requirements.push(requirement);
+            }
+        }
+    }
+
+    Some(SpecTask {
+        id: id.as_str().to_string(),
+        title,
+        completed,
+        parallel: text.contains(PARALLEL_MARKER),
+        phase: headings.last().map(|(_, heading)| heading.clone()),
+        depends_on,
+        requirements,
+        line,
+    })
+}
+
+/// Set the checkbox of the task on `line`, returning the updated document
+///
+/// Returns `None` if the line holds no task checkbox.
+#[must_use]
+pub fn set_checked(markdown: &str, line: usize, checked: bool) -> Option<String> {
+    let mut lines: Vec<&str> = markdown.split('\n').collect();
+    let target = lines.get(line)?;
+    let start = ["[ ]", "[x]", "[X]"]
+        .iter()
+        .filter_map(|marker| target.find(marker))
+        .min()?;
+    let marker = if checked { "[x]" } else { "[ ]" };
+    let updated = format!("{}{marker}{}", &target[..start], &target[start + 3..]);
+    lines[line] = &updated;
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS: &str = "\
+# Tasks
+
+## Phase 1: Setup (REQ-001)
+- [ ] T001 Create project structure
+- [x] **T002** [P]: Configure linting
+  - [ ] T003 [P] Nested formatter setup (depends on T001, T002)
+
+### Parser
+1. [ ] T104: Handle `T100+` IDs
+* [X] T105 Wrap
+  long lines (blocked by T104)
+
+## Phase 2
+- [ ] Task without ID
+- [ ] T001 Duplicate ID
+
+```markdown
+- [ ] T999 Inside a code block
+```
+";
+
+    #[test]
+    fn test_parse_tasks() {
+        let tasks = parse_tasks(TASKS);
+        let ids: Vec<_> = tasks.iter().map(|task| task.id.as_str()).collect();
+        assert_eq!(ids, vec!["T001", "T002", "T003", "T104", "T105"]);
+
+        assert_eq!(tasks[0].title, "Create project structure");
+        assert_eq!(tasks[0].phase.as_deref(), Some("Phase 1: Setup (REQ-001)"));
+        assert_eq!(tasks[0].requirements, vec!["REQ-001"]);
+        assert_eq!(tasks[0].line, 3);
+        assert!(!tasks[0].completed);
+
+        assert_eq!(tasks[1].title, "Configure linting");
+        assert!(tasks[1].completed && tasks[1].parallel);
+
+        assert_eq!(tasks[2].title, "Nested formatter setup");
+        assert_eq!(tasks[2].depends_on, vec!["T001", "T002"]);
+        assert!(tasks[2].parallel);
+
+        assert_eq!(tasks[3].title, "Handle T100+ IDs");
+        assert_eq!(tasks[3].phase.as_deref(), Some("Parser"));
+        assert_eq!(tasks[3].requirements, vec!["REQ-001"]);
+
+        assert_eq!(tasks[4].title, "Wrap long lines");
+        assert_eq!(tasks[4].depends_on, vec!["T104"]);
+        assert!(tasks[4].completed);
+    }
+
+    #[test]
+    fn test_set_checked() {
+        let tasks = parse_tasks(TASKS);
+        let updated = set_checked(TASKS, tasks[2].line, true).unwrap();
+        let updated = set_checked(&updated, tasks[1].line, false).unwrap();
+        assert!(updated.contains("  - [x] T003 [P] Nested"));
+        assert!(updated.contains("- [ ] **T002** [P]: Configure"));
+
+        let reparsed = parse_tasks(&updated);
+        assert!(reparsed[2].completed);
+        assert!(!reparsed[1].completed);
+        assert!(set_checked(TASKS, 0, true).is_none());
+    }
+}
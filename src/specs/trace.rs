@@ -0,0 +1,409 @@
+//! Requirement traceability for specifications
+//!
+//! Links the requirements of a spec to the design sections, tasks and
+//! tickets that implement them:
+//!
+//! - Requirements are defined in `requirements.md` by list items or headings
+//!   starting with an ID such as `REQ-001`, `FR-001` or `NFR-001`
+//! - A design section covers every requirement ID mentioned in it
+//! - Tasks are parsed from `tasks.md` (see [`super::tasks`]). They implement
+//!   the requirement IDs on their line or in their section headings
+//! - Tickets belong to a spec when tagged with its ID (as done by
+//!   `spec tasks --export-tickets`). They implement the tasks and requirements
+//!   they reference by ID, or list in the `spec_task` and `requirements`
+//!   metadata keys
+//!
+//! [`TraceMatrix`] flags requirements without an implementing ticket and
+//! spec tickets that do not trace back to any requirement.
+
+use super::Specification;
+use super::tasks::{SpecTask, parse_tasks, task_refs};
+use crate::core::{Status, Ticket};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+/// Ticket metadata key holding the spec a ticket was exported from
+pub const SPEC_ID_KEY: &str = "spec_id";
+
+/// Ticket metadata key holding the spec task a ticket implements
+pub const SPEC_TASK_KEY: &str = "spec_task";
+
+/// Ticket metadata key holding the requirement IDs a ticket implements
+pub const REQUIREMENTS_KEY: &str = "requirements";
+
+static REQUIREMENT_ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:REQ|FR|NFR)-\d+\b").expect("valid regex"));
+
+static REQUIREMENT_DEFINITION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:#+\s*|[-*+]\s+|\d+[.)]\s+)?(?:\[[ xX]\]\s+)?\**((?:REQ|FR|NFR)-\d+)\**\s*[:.\-–—]?\s*(.*)$")
+        .expect("valid regex")
+});
+
+/// A requirement defined in `requirements.md`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Requirement {
+    /// Requirement ID, e.g. `REQ-001`
+    pub id: String,
+    /// Requirement text following the ID
+    pub text: String,
+}
+
+/// A section of `design.md` and the requirements it covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DesignSection {
+    /// Section heading
+    pub title: String,
+    /// Requirement IDs mentioned in the section
+    pub requirements: Vec<String>,
+}
+
+/// A ticket linked from the matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TicketLink {
+    /// Ticket ID
+    pub id: String,
+    /// Ticket slug
+    pub slug: String,
+    /// Ticket status
+    pub status: Status,
+}
+
+/// One requirement with everything that traces to it
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceRow {
+    /// The requirement
+    pub requirement: Requirement,
+    /// Titles of the design sections covering it
+    pub design: Vec<String>,
+    /// IDs of the tasks implementing it
+    pub tasks: Vec<String>,
+    /// Tickets implementing it
+    pub tickets: Vec<TicketLink>,
+}
+
+impl TraceRow {
+    /// Whether at least one ticket implements the requirement
+    #[must_use]
+    pub fn is_implemented(&self) -> bool {
+        !self.tickets.is_empty()
+    }
+}
+
+/// Requirement → design → task → ticket matrix of a spec
+#[derive(Debug, Clone, Serialize)]
+pub struct TraceMatrix {
+    /// Spec ID
+    pub spec_id: String,
+    /// One row per requirement, in definition order
+    pub rows: Vec<TraceRow>,
+    /// Tasks defined in `tasks.md`
+    pub tasks: Vec<SpecTask>,
+    /// Spec tickets that do not trace to any requirement
+    pub untraced_tickets: Vec<TicketLink>,
+}
+
+impl TraceMatrix {
+    /// Build the matrix of a spec from its documents and the project tickets
+    #[must_use]
+    pub fn build(spec: &Specification, tickets: &[Ticket]) -> Self {
+        let requirements = parse_requirements(spec.requirements.as_deref().unwrap_or_default());
+        let design = parse_design_sections(spec.design.as_deref().unwrap_or_default());
+        let tasks = parse_tasks(spec.tasks.as_deref().unwrap_or_default());
+        let spec_id = spec.metadata.id.as_str();
+
+        // Requirements implemented by each spec ticket
+        let spec_tickets: Vec<(TicketLink, BTreeSet<String>)> = tickets
+            .iter()
+            .filter(|ticket| belongs_to_spec(ticket, spec_id))
+            .map(|ticket| {
+                let mut implemented = ticket_requirements(ticket);
+                for task_id in ticket_tasks(ticket, spec_id) {
+                    if let Some(task) = tasks.iter().find(|task| task.id == task_id) {
+                        implemented.extend(task.requirements.iter().cloned());
+                    }
+                }
+                let link = TicketLink {
+                    id: ticket.id.to_string(),
+                    slug: ticket.slug.clone(),
+                    status: ticket.status,
+                };
+                (link, implemented)
+            })
+            .collect();
+
+        let rows = requirements
+            .into_iter()
+            .map(|requirement| TraceRow {
+                design: design
+                    .iter()
+                    .filter(|section| section.requirements.contains(&requirement.id))
+                    .map(|section| section.title.clone())
+                    .collect(),
+                tasks: tasks
+                    .iter()
+                    .filter(|task| task.requirements.contains(&requirement.id))
+                    .map(|task| task.id.clone())
+                    .collect(),
+                tickets: spec_tickets
+                    .iter()
+                    .filter(|(_, implemented)| implemented.contains(&requirement.id))
+                    .map(|(link, _)| link.clone())
+                    .collect(),
+                requirement,
+            })
+            .collect::<Vec<_>>();
+
+        let untraced_tickets = spec_tickets
+            .into_iter()
+            .filter(|(_, implemented)| {
+                !rows
+                    .iter()
+                    .any(|row| implemented.contains(&row.requirement.id))
+            })
+            .map(|(link, _)| link)
+            .collect();
+
+        Self {
+            spec_id: spec_id.to_string(),
+            rows,
+            tasks,
+            untraced_tickets,
+        }
+    }
+
+    /// Requirements without an implementing ticket
+    pub fn unimplemented(&self) -> impl Iterator<Item = &Requirement> {
+        self.rows
+            .iter()
+            .filter(|row| !row.is_implemented())
+            .map(|row| &row.requirement)
+    }
+
+    /// Number of unimplemented requirements plus untraced tickets
+    #[must_use]
+    pub fn gap_count(&self) -> usize {
+        self.unimplemented().count() + self.untraced_tickets.len()
+    }
+}
+
+/// Parse the requirements defined in `requirements.md`
+///
+/// Only the first definition of each ID counts; later mentions are references.
+#[must_use]
+pub fn parse_requirements(markdown: &str) -> Vec<Requirement> {
+    let mut requirements: Vec<Requirement> = Vec::new();
+    for line in prose_lines(markdown) {
+        let Some(captures) = REQUIREMENT_DEFINITION.captures(line.trim()) else {
+            continue;
+        };
+        let id = captures[1].to_string();
+        if requirements.iter().all(|r| r.id != id) {
+            requirements.push(Requirement {
+                id,
+                text: captures[2].trim().trim_matches('*').trim().to_string(),
+            });
+        }
+    }
+    requirements
+}
+
+/// Split `design.md` into sections and collect the requirements each mentions
+#[must_use]
+pub fn parse_design_sections(markdown: &str) -> Vec<DesignSection> {
+    let mut sections: Vec<DesignSection> = Vec::new();
+    for line in prose_lines(markdown) {
+        if let Some(title) = heading(line) {
+            sections.push(DesignSection {
+                title: title.to_string(),
+                requirements: Vec::new(),
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            for id in requirement_refs(line) {
+                if !section.requirements.contains(&id) {
+                    section.requirements.push(id);
+                }
+            }
+        }
+    }
+    sections.retain(|section| !section.requirements.is_empty());
+    sections
+}
+
+/// Requirement IDs referenced in a piece of text, in order of appearance
+#[must_use]
+pub fn requirement_refs(text: &str) -> Vec<String> {
+    let mut refs: Vec<String> = Vec::new();
+    for found in REQUIREMENT_ID.find_iter(text) {
+        if !refs.iter().any(|r| r == found.as_str()) {
+            refs.push(found.as_str().to_string());
+        }
+    }
+    refs
+}
+
+/// Whether a ticket was created for (or tagged with) the spec
+pub(crate) fn belongs_to_spec(ticket: &Ticket, spec_id: &str) -> bool {
+    ticket.tags.iter().any(|tag| tag == spec_id)
+        || ticket.slug.starts_with(&format!("{spec_id}-"))
+        || ticket.metadata.get(SPEC_ID_KEY).and_then(|v| v.as_str()) == Some(spec_id)
+}
+
+/// Task IDs a ticket implements
+fn ticket_tasks(ticket: &Ticket, spec_id: &str) -> BTreeSet<String> {
+    let mut tasks: BTreeSet<String> = task_refs(&ticket.title).into_iter().collect();
+    if let Some(task) = ticket.metadata.get(SPEC_TASK_KEY).and_then(|v| v.as_str()) {
+        tasks.insert(task.to_string());
+    }
+    if let Some(suffix) = ticket.slug.strip_prefix(&format!("{spec_id}-")) {
+        let suffix = suffix.to_uppercase();
+        if task_refs(&suffix) == [suffix.as_str()] {
+            tasks.insert(suffix);
+        }
+    }
+    tasks
+}
+
+/// Requirement IDs a ticket references directly
+fn ticket_requirements(ticket: &Ticket) -> BTreeSet<String> {
+    let mut requirements: BTreeSet<String> = requirement_refs(&ticket.title)
+        .into_iter()
+        .chain(requirement_refs(&ticket.description))
+        .chain(ticket.tags.iter().flat_map(|tag| requirement_refs(tag)))
+        .collect();
+    if let Some(listed) = ticket
+        .metadata
+        .get(REQUIREMENTS_KEY)
+        .and_then(|v| v.as_array())
+    {
+        requirements.extend(listed.iter().filter_map(|v| v.as_str()).map(str::to_string));
+    }
+    requirements
+}
+
+/// Lines outside fenced code blocks
+fn prose_lines(markdown: &str) -> impl Iterator<Item = &str> {
+    let mut in_fence = false;
+    markdown.lines().filter(move |line| {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            return false;
+        }
+        !in_fence
+    })
+}
+
+/// Heading text of a Markdown ATX heading line
+fn heading(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('#')?;
+    let text = rest.trim_start_matches('#');
+    (text.is_empty() || text.starts_with(' ')).then(|| text.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::Specification;
+
+    const REQUIREMENTS: &str = "\
+# Requirements
+
+## Functional
+- **REQ-001**: Users can log in
+- REQ-002: Users can reset their password
+### REQ-003 Audit log
+Every login is recorded (see REQ-001).
+
+```text
+REQ-999: not a requirement
+```
+";
+
+    const DESIGN: &str = "\
+# Design
+## Authentication flow
+Covers REQ-001 and REQ-002.
+## Storage
+Nothing traced here.
+";
+
+    const TASKS: &str = "\
+# Tasks
+## REQ-003
+- [ ] T001: Write audit records
+## Login
+- [x] **T002** [P] Implement login form (REQ-001)
+- [ ] T003: Reset password flow, REQ-002
+- [ ] Unnumbered task
+";
+
+    fn spec() -> Specification {
+        let mut spec = Specification::new("Auth".to_string(), String::new(), None, vec![]);
+        spec.requirements = Some(REQUIREMENTS.to_string());
+        spec.design = Some(DESIGN.to_string());
+        spec.tasks = Some(TASKS.to_string());
+        spec
+    }
+
+    #[test]
+    fn test_parse_requirements() {
+        let requirements = parse_requirements(REQUIREMENTS);
+        let ids: Vec<_> = requirements.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["REQ-001", "REQ-002", "REQ-003"]);
+        assert_eq!(requirements[0].text, "Users can log in");
+        assert_eq!(requirements[2].text, "Audit log");
+    }
+
+    #[test]
+    fn test_parse_design_and_tasks() {
+        let sections = parse_design_sections(DESIGN);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].title, "Authentication flow");
+        assert_eq!(sections[0].requirements, vec!["REQ-001", "REQ-002"]);
+
+        let tasks = parse_tasks(TASKS);
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].requirements, vec!["REQ-003"]);
+        assert_eq!(tasks[1].id, "T002");
+        assert_eq!(tasks[1].title, "Implement login form (REQ-001)");
+        assert!(tasks[1].completed);
+        assert_eq!(tasks[2].requirements, vec!["REQ-002"]);
+    }
+
+    #[test]
+    fn test_trace_matrix() {
+        let spec = spec();
+        let spec_id = spec.metadata.id.clone();
+
+        // Exported from a task, linked through the slug
+        let mut login = Ticket::new(format!("{spec_id}-t002"), "[T002] Implement login form");
+        login.tags = vec![spec_id.clone()];
+        login.status = Status::Done;
+        // Linked through a direct requirement reference
+        let mut reset = Ticket::new("reset", "Password reset for REQ-002");
+        reset.tags = vec![spec_id.clone()];
+        // Tagged with the spec but not traced to anything
+        let mut cleanup = Ticket::new("cleanup", "Refactor helpers");
+        cleanup.tags = vec![spec_id];
+        // Not part of the spec
+        let other = Ticket::new("other", "REQ-003 elsewhere");
+
+        let matrix = TraceMatrix::build(&spec, &[login, reset, cleanup, other]);
+        assert_eq!(matrix.rows.len(), 3);
+        assert_eq!(matrix.rows[0].design, vec!["Authentication flow"]);
+        assert_eq!(matrix.rows[0].tasks, vec!["T002"]);
+        assert_eq!(
+            matrix.rows[0].tickets[0].slug,
+            format!("{}-t002", spec.metadata.id)
+        );
+        assert_eq!(matrix.rows[1].tickets[0].slug, "reset");
+
+        let unimplemented: Vec<_> = matrix.unimplemented().map(|r| r.id.as_str()).collect();
+        assert_eq!(unimplemented, vec!["REQ-003"]);
+        assert_eq!(matrix.untraced_tickets.len(), 1);
+        assert_eq!(matrix.untraced_tickets[0].slug, "cleanup");
+        assert_eq!(matrix.gap_count(), 2);
+    }
+}
@@ -0,0 +1,469 @@
+//! Compressed cold storage for archived tickets and specifications
+//!
+//! Archived items are moved out of `tickets/` and `specs/` into
+//! `.vibe-ticket/archive/`, so regular loads never parse them:
+//!
+//! - `archive/tickets/<id>.yaml.gz` holds the ticket YAML, gzip-compressed
+//! - `archive/specs/<id>.json.gz` bundles the metadata and every file of the
+//!   spec directory into one gzip-compressed JSON document
+//!
+//! Restoring an item moves it back to where it came from.
+
+use super::write_project_file;
+use crate::core::{Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::{FileStorage, TicketRepository, Transaction};
+
+/// Ticket metadata key marking a ticket as archived
+pub const ARCHIVED_KEY: &str = "archived";
+
+/// Ticket metadata key recording when a ticket was archived
+pub const ARCHIVED_AT_KEY: &str = "archived_at";
+
+/// A specification bundled for the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSpec {
+    /// Metadata of the spec
+    pub metadata: SpecMetadata,
+
+    /// When the spec was archived
+    pub archived_at: DateTime<Utc>,
+
+    /// Contents of the spec directory by relative path
+    #[serde(with = "file_contents")]
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Spec files are bundled as text where they are UTF-8, which keeps bundles
+/// written before binary files were supported readable, and as byte arrays
+/// otherwise
+mod file_contents {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Contents {
+        Text(String),
+        Bytes(Vec<u8>),
+    }
+
+    pub fn serialize<S: Serializer>(
+        files: &BTreeMap<String, Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        files
+            .iter()
+            .map(|(name, bytes)| {
+                let contents = String::from_utf8(bytes.clone())
+                    .map_or_else(|e| Contents::Bytes(e.into_bytes()), Contents::Text);
+                (name, contents)
+            })
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Vec<u8>>, D::Error> {
+        Ok(BTreeMap::<String, Contents>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, contents)| match contents {
+                Contents::Text(text) => (name, text.into_bytes()),
+                Contents::Bytes(bytes) => (name, bytes),
+            })
+            .collect())
+    }
+}
+
+/// Compressed archive under `.vibe-ticket/archive/`
+pub struct ArchiveStore {
+    /// The `.vibe-ticket` directory
+    base_dir: PathBuf,
+}
+
+impl ArchiveStore {
+    /// Create an archive store for a `.vibe-ticket` directory
+    #[must_use]
+    pub fn new(vibe_ticket_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: vibe_ticket_dir.into(),
+        }
+    }
+
+    fn tickets_dir(&self) -> PathBuf {
+        self.base_dir.join("archive").join("tickets")
+    }
+
+    fn specs_dir(&self) -> PathBuf {
+        self.base_dir.join("archive").join("specs")
+    }
+
+    fn ticket_path(&self, id: &TicketId) -> PathBuf {
+        self.tickets_dir().join(format!("{id}.yaml.gz"))
+    }
+
+    fn spec_path(&self, spec_id: &str) -> PathBuf {
+        self.specs_dir().join(format!("{spec_id}.json.gz"))
+    }
+
+    /// Move a ticket from `tickets/` into the archive
+    pub fn archive_ticket(&self, storage: &FileStorage, ticket: Ticket) -> Result<Ticket> {
+        let mut transaction = storage.begin("archive")?;
+        let ticket = self.stage_ticket(&mut transaction, ticket)?;
+        transaction.commit()?;
+        Ok(ticket)
+    }
+
+    /// Stage moving a ticket into the archive as part of a larger transaction
+    pub fn stage_ticket(
+        &self,
+        transaction: &mut Transaction<'_>,
+        mut ticket: Ticket,
+    ) -> Result<Ticket> {
+        ticket
+            .metadata
+            .insert(ARCHIVED_KEY.to_string(), serde_json::Value::Bool(true));
+        ticket.metadata.insert(
+            ARCHIVED_AT_KEY.to_string(),
+            serde_json::Value::String(Utc::now().to_rfc3339()),
+        );
+        let yaml = serde_yaml::to_string(&ticket)?;
+        let path = self.ticket_path(&ticket.id);
+        transaction.write(&path, compress(&path, yaml.as_bytes())?);
+        transaction.delete(&ticket.id)?;
+        Ok(ticket)
+    }
+
+    /// Move an archived ticket back into `tickets/`
+    pub fn restore_ticket(&self, storage: &FileStorage, id: &TicketId) -> Result<Ticket> {
+        let mut ticket = self.load_ticket(id)?;
+        if storage.exists(id)? {
+            return Err(VibeTicketError::custom(format!(
+                "Cannot restore ticket {}: a ticket with the same ID exists",
+                ticket.slug
+            )));
+        }
+        ticket.metadata.remove(ARCHIVED_KEY);
+        ticket.metadata.remove(ARCHIVED_AT_KEY);
+        storage.save(&ticket)?;
+        let path = self.ticket_path(id);
+        fs::remove_file(&path).map_err(|e| VibeTicketError::io_error("delete", &path, e))?;
+        Ok(ticket)
+    }
+
+    /// Load an archived ticket
+    pub fn load_ticket(&self, id: &TicketId) -> Result<Ticket> {
+        let path = self.ticket_path(id);
+        if !path.exists() {
+            return Err(VibeTicketError::TicketNotFound { id: id.to_string() });
+        }
+        let yaml = read_compressed(&path)?;
+        Ok(serde_yaml::from_slice(&yaml)?)
+    }
+
+    /// Load all archived tickets
+    pub fn load_all_tickets(&self) -> Result<Vec<Ticket>> {
+        let mut tickets = Vec::new();
+        for path in archive_files(&self.tickets_dir(), ".yaml.gz")? {
+            let yaml = read_compressed(&path)?;
+            match serde_yaml::from_slice::<Ticket>(&yaml) {
+                Ok(ticket) => tickets.push(ticket),
+                Err(e) => eprintln!(
+                    "Warning: Failed to load archived ticket from {}: {e}",
+                    path.display()
+                ),
+            }
+        }
+        Ok(tickets)
+    }
+
+    /// Find an archived ticket by ID, ID prefix or slug
+    pub fn find_ticket(&self, reference: &str) -> Result<Option<Ticket>> {
+        Ok(self.load_all_tickets()?.into_iter().find(|ticket| {
+            ticket.slug == reference || ticket.id.to_string().starts_with(reference)
+        }))
+    }
+
+    /// Move a spec directory into the archive
+    pub fn archive_spec(&self, specs_dir: &Path, spec_id: &str) -> Result<ArchivedSpec> {
+        let source = specs_dir.join(spec_id);
+        let metadata_path = source.join("spec.json");
+        if !metadata_path.exists() {
+            return Err(VibeTicketError::SpecNotFound {
+                id: spec_id.to_string(),
+            });
+        }
+        let metadata = fs::read_to_string(&metadata_path)
+            .map_err(|e| VibeTicketError::io_error("read", &metadata_path, e))?;
+
+        let mut files = BTreeMap::new();
+        collect_files(&source, &source, &mut files)?;
+        let bundle = ArchivedSpec {
+            metadata: serde_json::from_str(&metadata)?,
+            archived_at: Utc::now(),
+            files,
+        };
+        write_compressed(
+            &self.spec_path(spec_id),
+            serde_json::to_string(&bundle)?.as_bytes(),
+        )?;
+        fs::remove_dir_all(&source).map_err(|e| VibeTicketError::io_error("delete", &source, e))?;
+        Ok(bundle)
+    }
+
+    /// Move an archived spec back into `specs/`
+    pub fn restore_spec(&self, specs_dir: &Path, spec_id: &str) -> Result<ArchivedSpec> {
+        let bundle = self.load_spec(spec_id)?;
+        let target = specs_dir.join(spec_id);
+        if target.exists() {
+            return Err(VibeTicketError::custom(format!(
+                "Cannot restore specification {spec_id}: {} exists",
+                target.display()
+            )));
+        }
+
+        // Write the files next to the archive and move the directory into
+        // place in one rename, so a failed restore leaves no partial spec
+        let staging = self.specs_dir().join(format!(".restore-{spec_id}"));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)
+                .map_err(|e| VibeTicketError::io_error("delete", &staging, e))?;
+        }
+        let restored = write_files(&staging, &bundle.files).and_then(|()| {
+            fs::create_dir_all(specs_dir)
+                .and_then(|()| fs::rename(&staging, &target))
+                .map_err(|e| VibeTicketError::io_error("move", &target, e))
+        });
+        if restored.is_err() {
+            let _ = fs::remove_dir_all(&staging);
+        }
+        restored?;
+
+        let path = self.spec_path(spec_id);
+        fs::remove_file(&path).map_err(|e| VibeTicketError::io_error("delete", &path, e))?;
+        Ok(bundle)
+    }
+
+    /// Load an archived spec
+    pub fn load_spec(&self, spec_id: &str) -> Result<ArchivedSpec> {
+        let path = self.spec_path(spec_id);
+        if !path.exists() {
+            return Err(VibeTicketError::SpecNotFound {
+                id: spec_id.to_string(),
+            });
+        }
+        Ok(serde_json::from_slice(&read_compressed(&path)?)?)
+    }
+
+    /// Load all archived specs
+    pub fn load_all_specs(&self) -> Result<Vec<ArchivedSpec>> {
+        archive_files(&self.specs_dir(), ".json.gz")?
+            .iter()
+            .map(|path| Ok(serde_json::from_slice(&read_compressed(path)?)?))
+            .collect()
+    }
+}
+
+/// Whether a ticket carries the archived flag
+#[must_use]
+pub fn is_archived(ticket: &Ticket) -> bool {
+    ticket
+        .metadata
+        .get(ARCHIVED_KEY)
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Files in `dir` ending in `suffix`, sorted by name
+fn archive_files(dir: &Path, suffix: &str) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| VibeTicketError::io_error("read", dir, e))? {
+        let path = entry
+            .map_err(|e| VibeTicketError::io_error("read", dir, e))?
+            .path();
+        if path.to_string_lossy().ends_with(suffix) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut BTreeMap<String, Vec<u8>>) -> Result<()> {
+    for entry in fs::read_dir(dir).map_err(|e| VibeTicketError::io_error("read", dir, e))? {
+        let path = entry
+            .map_err(|e| VibeTicketError::io_error("read", dir, e))?
+            .path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        }
+        let content = fs::read(&path).map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.insert(name, content);
+    }
+    Ok(())
+}
+
+/// Write bundled files into `dir` by relative path
+fn write_files(dir: &Path, files: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+    fs::create_dir_all(dir).map_err(|e| VibeTicketError::io_error("create", dir, e))?;
+    for (name, content) in files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| VibeTicketError::io_error("create", parent, e))?;
+        }
+        write_project_file(&path, content)?;
+    }
+    Ok(())
+}
+
+/// Write gzip-compressed data, replacing `path` only once it is complete
+fn write_compressed(path: &Path, data: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| VibeTicketError::custom("Invalid archive path"))?;
+    fs::create_dir_all(dir).map_err(|e| VibeTicketError::io_error("create", dir, e))?;
+
+    let compressed = compress(path, data)?;
+    write_project_file(path, compressed)
+}
+
+/// Gzip `data` to be written to `path`
+fn compress(path: &Path, data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .and_then(|()| encoder.finish())
+        .map_err(|e| VibeTicketError::io_error("compress", path, e))
+}
+
+fn read_compressed(path: &Path) -> Result<Vec<u8>> {
+    let file = fs::File::open(path).map_err(|e| VibeTicketError::io_error("read", path, e))?;
+    let mut data = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut data)
+        .map_err(|e| VibeTicketError::io_error("decompress", path, e))?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::specs::{SpecDocumentType, SpecManager};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ticket_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(temp_dir.path());
+        let archive = ArchiveStore::new(temp_dir.path());
+        let ticket = Ticket::new("old-bug".to_string(), "Old bug".to_string());
+        storage.save(&ticket).unwrap();
+
+        let archived = archive.archive_ticket(&storage, ticket.clone()).unwrap();
+        assert!(is_archived(&archived));
+        assert!(storage.load_all().unwrap().is_empty());
+        assert!(
+            temp_dir
+                .path()
+                .join("archive/tickets")
+                .read_dir()
+                .unwrap()
+                .count()
+                == 1
+        );
+        assert_eq!(
+            archive.find_ticket("old-bug").unwrap().unwrap().id,
+            ticket.id
+        );
+
+        let restored = archive.restore_ticket(&storage, &ticket.id).unwrap();
+        assert!(!is_archived(&restored));
+        assert_eq!(storage.load(&ticket.id).unwrap().title, "Old bug");
+        assert!(archive.load_all_tickets().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_spec_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let specs_dir = temp_dir.path().join("specs");
+        let manager = SpecManager::new(specs_dir.clone());
+        let spec_id = manager
+            .create_spec("Export".to_string(), String::new())
+            .unwrap()
+            .id;
+        manager
+            .save_document(&spec_id, SpecDocumentType::Requirements, "REQ-1")
+            .unwrap();
+        manager
+            .save_document(&spec_id, SpecDocumentType::Requirements, "REQ-1 changed")
+            .unwrap();
+        let archive = ArchiveStore::new(temp_dir.path());
+
+        let bundle = archive.archive_spec(&specs_dir, &spec_id).unwrap();
+        assert_eq!(bundle.metadata.title, "Export");
+        assert!(manager.list_specs().unwrap().is_empty());
+        assert_eq!(archive.load_all_specs().unwrap().len(), 1);
+
+        archive.restore_spec(&specs_dir, &spec_id).unwrap();
+        let spec = manager.load_spec(&spec_id).unwrap();
+        assert_eq!(spec.requirements.as_deref(), Some("REQ-1 changed"));
+        assert_eq!(manager.revisions(&spec_id).unwrap().len(), 1);
+        assert!(archive.load_all_specs().unwrap().is_empty());
+        assert_eq!(archive.specs_dir().read_dir().unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_spec_with_binary_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let specs_dir = temp_dir.path().join("specs");
+        let manager = SpecManager::new(specs_dir.clone());
+        let spec_id = manager
+            .create_spec("Logo".to_string(), String::new())
+            .unwrap()
+            .id;
+        let image = [0x89, b'P', b'N', b'G', 0xff, 0x00];
+        fs::write(specs_dir.join(&spec_id).join("logo.png"), image).unwrap();
+        let archive = ArchiveStore::new(temp_dir.path());
+
+        let bundle = archive.archive_spec(&specs_dir, &spec_id).unwrap();
+        assert_eq!(bundle.files["logo.png"], image);
+        archive.restore_spec(&specs_dir, &spec_id).unwrap();
+        assert_eq!(
+            fs::read(specs_dir.join(&spec_id).join("logo.png")).unwrap(),
+            image
+        );
+    }
+
+    #[test]
+    fn test_text_bundle_loads() {
+        let json = serde_json::json!({
+            "metadata": serde_json::to_value(SpecMetadata::new("Old".to_string(), String::new())).unwrap(),
+            "archived_at": Utc::now(),
+            "files": { "requirements.md": "REQ-1" },
+        });
+        let bundle: ArchivedSpec = serde_json::from_value(json).unwrap();
+        assert_eq!(bundle.files["requirements.md"], b"REQ-1");
+    }
+}
@@ -0,0 +1,117 @@
+//! Crash-safe file writes
+//!
+//! [`write_atomic`] writes to a temporary file next to the destination,
+//! flushes it to disk and renames it over the destination. Readers see either
+//! the old or the new contents, never a truncated file, even if the process
+//! dies or the disk fills up halfway through.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes temporary files written by threads of the same process
+static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+/// Atomically replace the contents of `path`, like [`fs::write`]
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be written or renamed; the
+/// destination is left untouched in that case.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let temp = temp_path(path)?;
+    let result = write_synced(&temp, contents.as_ref()).and_then(|()| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+    sync_parent(path);
+    Ok(())
+}
+
+/// Whether `path` is a temporary file left behind by an interrupted write
+#[must_use]
+pub fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        && path.extension().is_some_and(|ext| ext == "tmp")
+}
+
+/// Hidden file in the same directory, so the rename stays on one filesystem
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    Ok(path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id(),
+        NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+    )))
+}
+
+fn write_synced(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Persist the rename itself; not supported for directories on every platform
+fn sync_parent(path: &Path) {
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_replaces_contents() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.yaml");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        let names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names, ["state.yaml"]);
+    }
+
+    #[test]
+    fn test_failed_write_leaves_destination_untouched() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("state.yaml");
+        write_atomic(&path, "first").unwrap();
+
+        // Renaming a file over a non-empty directory fails
+        let blocked = dir.path().join("blocked");
+        fs::create_dir_all(blocked.join("inner")).unwrap();
+        assert!(write_atomic(&blocked, "second").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        assert!(
+            fs::read_dir(dir.path())
+                .unwrap()
+                .all(|entry| !is_temp_file(&entry.unwrap().path()))
+        );
+    }
+
+    #[test]
+    fn test_is_temp_file() {
+        assert!(is_temp_file(Path::new("tickets/.abc.yaml.42.0.tmp")));
+        assert!(!is_temp_file(Path::new("tickets/abc.yaml")));
+        assert!(!is_temp_file(Path::new("archive/tickets.gz.tmp")));
+    }
+}
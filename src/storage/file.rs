@@ -1,7 +1,10 @@
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
+use super::lock::default_lock_timeout;
+use super::{FileLock, LockMode, write_atomic};
 use crate::cache::TicketCache;
 use crate::core::{Ticket, TicketId};
 
@@ -17,6 +20,10 @@ pub struct FileStorage {
     base_dir: PathBuf,
     /// Cache for improved performance
     pub(crate) cache: Arc<TicketCache>,
+    /// How long to wait for locks held by other processes
+    lock_timeout: Duration,
+    /// Format version of the project, read before the first write
+    pub(crate) format: Arc<OnceLock<u32>>,
 }
 
 impl FileStorage {
@@ -25,11 +32,23 @@ impl FileStorage {
         Self {
             base_dir: base_dir.into(),
             cache: Arc::new(TicketCache::with_default_ttl()),
+            lock_timeout: default_lock_timeout(),
+            format: Arc::new(OnceLock::new()),
         }
     }
 
+    /// Sets how long to wait for locks held by other processes
+    ///
+    /// Defaults to 30 seconds, or the number of seconds in the
+    /// `VIBE_TICKET_LOCK_TIMEOUT` environment variable.
+    #[must_use]
+    pub const fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
     /// Returns the path to the tickets directory
-    fn tickets_dir(&self) -> PathBuf {
+    pub(crate) fn tickets_dir(&self) -> PathBuf {
         self.get_path("tickets")
     }
 
@@ -38,21 +57,59 @@ impl FileStorage {
         self.tickets_dir().join(format!("{id}.yaml"))
     }
 
+    /// Returns the path to the directory for per-worktree state
+    ///
+    /// The directory ignores itself in git, so state such as the active
+    /// ticket is never committed or merged between branches.
+    fn local_dir(&self) -> PathBuf {
+        self.get_path("local")
+    }
+
+    /// Returns the path to a per-worktree file
+    ///
+    /// A file left in the shared directory by an older version is moved into
+    /// the local directory first, in case the project was not migrated yet.
+    fn local_path(&self, name: &str) -> Result<PathBuf> {
+        let path = self.local_dir().join(name);
+        let legacy = self.get_path(name);
+        if legacy.exists() && !path.exists() {
+            self.ensure_local_dir()?;
+            fs::rename(&legacy, &path)
+                .with_context(|| format!("Failed to move {} into local state", legacy.display()))?;
+        }
+        Ok(path)
+    }
+
     /// Returns the path to the active ticket file
-    fn active_ticket_path(&self) -> PathBuf {
-        self.get_path("active_ticket")
+    fn active_ticket_path(&self) -> Result<PathBuf> {
+        self.local_path("active_ticket")
     }
 
     /// Returns the path to the active tickets file (new format supporting multiple tickets)
-    fn active_tickets_path(&self) -> PathBuf {
-        self.get_path("active_tickets.yaml")
+    fn active_tickets_path(&self) -> Result<PathBuf> {
+        self.local_path("active_tickets.yaml")
+    }
+
+    /// Returns the path to the directory holding lock files
+    pub(crate) fn locks_dir(&self) -> PathBuf {
+        self.local_dir().join("locks")
+    }
+
+    /// Returns the path to the journal of the transaction being committed
+    pub(crate) fn journal_dir(&self) -> PathBuf {
+        self.local_dir().join("journal")
     }
 
     /// Returns the path to the project state file
-    fn state_path(&self) -> PathBuf {
+    pub(crate) fn state_path(&self) -> PathBuf {
         self.get_path("state.yaml")
     }
 
+    /// Returns the `.vibe-ticket` directory the storage works in
+    pub(crate) fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
     /// Helper method to get a path relative to base directory
     fn get_path(&self, name: &str) -> PathBuf {
         self.base_dir.join(name)
@@ -64,6 +121,64 @@ impl FileStorage {
         Ok(())
     }
 
+    /// Ensures the per-worktree state directory exists and is ignored by git
+    pub(crate) fn ensure_local_dir(&self) -> Result<()> {
+        let dir = self.local_dir();
+        fs::create_dir_all(&dir).context("Failed to create local state directory")?;
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(&gitignore, "# Per-worktree state, never committed\n*\n")
+                .context("Failed to write local state .gitignore")?;
+        }
+        Ok(())
+    }
+
+    /// Acquires the lock named `name` in the locks directory
+    fn lock(&self, name: &str, mode: LockMode, operation: &str) -> Result<FileLock> {
+        self.ensure_local_dir()?;
+        let path = self.locks_dir().join(format!("{name}.lock"));
+        FileLock::acquire(&path, mode, operation, self.lock_timeout).map_err(|e| {
+            VibeTicketError::custom(format!("Failed to acquire lock for {operation}: {e:#}"))
+        })
+    }
+
+    /// Locks the whole store for an operation on many tickets
+    ///
+    /// Single-ticket writes by other threads and processes wait until the
+    /// returned lock is dropped, while those of the current thread go ahead.
+    /// Reads are not blocked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store is still locked by others after the
+    /// lock timeout.
+    pub fn lock_store(&self, operation: &str) -> Result<FileLock> {
+        self.lock("store", LockMode::Exclusive, operation)
+    }
+
+    /// Locks one ticket for writing, within a shared lock on the store
+    ///
+    /// Fails if the project was written by a newer version of vibe-ticket.
+    pub(crate) fn lock_ticket_for_write(
+        &self,
+        id: &TicketId,
+        operation: &str,
+    ) -> Result<[FileLock; 2]> {
+        self.check_format()?;
+        let store = self.lock("store", LockMode::Shared, operation)?;
+        let ticket = self.lock(&format!("ticket-{id}"), LockMode::Exclusive, operation)?;
+        Ok([store, ticket])
+    }
+
+    /// Serializes a ticket for saving, stamped with the time of the save
+    pub(crate) fn ticket_yaml(ticket: &Ticket) -> Result<String> {
+        let ticket = Ticket {
+            updated_at: Some(chrono::Utc::now()),
+            ..ticket.clone()
+        };
+        serde_yaml::to_string(&ticket).context("Failed to serialize ticket")
+    }
+
     /// Saves a ticket to storage with file locking for concurrent access protection
     pub fn save_ticket(&self, ticket: &Ticket) -> Result<()> {
         self.ensure_directories()?;
@@ -71,14 +186,10 @@ impl FileStorage {
         let path = self.ticket_path(&ticket.id);
 
         // Acquire lock before modifying the file
-        let _lock =
-            super::FileLock::acquire(&path, Some("save_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!("Failed to acquire lock for saving ticket: {e}"))
-            })?;
+        let _locks = self.lock_ticket_for_write(&ticket.id, "saving ticket")?;
 
-        let yaml = serde_yaml::to_string(ticket).context("Failed to serialize ticket")?;
-
-        fs::write(&path, yaml)
+        let yaml = Self::ticket_yaml(ticket)?;
+        write_atomic(&path, yaml)
             .with_context(|| format!("Failed to write ticket to {}", path.display()))?;
 
         // Invalidate cache for this ticket
@@ -100,11 +211,8 @@ impl FileStorage {
             return Err(VibeTicketError::TicketNotFound { id: id.to_string() });
         }
 
-        // Acquire lock for reading to ensure consistency
-        let _lock =
-            super::FileLock::acquire(&path, Some("load_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!("Failed to acquire lock for loading ticket: {e}"))
-            })?;
+        // Acquire a shared lock so no writer changes the ticket meanwhile
+        let _lock = self.lock(&format!("ticket-{id}"), LockMode::Shared, "loading ticket")?;
 
         let yaml = fs::read_to_string(&path)
             .with_context(|| format!("Failed to read ticket from {}", path.display()))?;
@@ -142,13 +250,8 @@ impl FileStorage {
 
                 match serde_yaml::from_str::<Ticket>(&yaml) {
                     Ok(ticket) => tickets.push(ticket),
-                    Err(e) => {
-                        // Log error but continue loading other tickets
-                        eprintln!(
-                            "Warning: Failed to load ticket from {}: {e}",
-                            path.display()
-                        );
-                    },
+                    // Log error but continue loading other tickets
+                    Err(e) => Self::warn_unreadable(&path, &e),
                 }
             }
         }
@@ -159,6 +262,15 @@ impl FileStorage {
         Ok(tickets)
     }
 
+    /// Warns about a ticket file that is skipped because it does not parse
+    pub(crate) fn warn_unreadable(path: &Path, error: &serde_yaml::Error) {
+        eprintln!(
+            "Warning: Skipping unreadable ticket {}: {error} \
+             (run `vibe-ticket doctor --fix` to quarantine it)",
+            path.display()
+        );
+    }
+
     /// Deletes a ticket from storage with locking
     pub fn delete_ticket(&self, id: &TicketId) -> Result<()> {
         let path = self.ticket_path(id);
@@ -168,10 +280,7 @@ impl FileStorage {
         }
 
         // Acquire lock before deleting
-        let _lock =
-            super::FileLock::acquire(&path, Some("delete_ticket".to_string())).map_err(|e| {
-                VibeTicketError::custom(format!("Failed to acquire lock for deleting ticket: {e}"))
-            })?;
+        let _locks = self.lock_ticket_for_write(id, "deleting ticket")?;
 
         fs::remove_file(&path)
             .with_context(|| format!("Failed to delete ticket at {}", path.display()))?;
@@ -184,23 +293,24 @@ impl FileStorage {
 
     /// Sets the active ticket with locking
     pub fn set_active_ticket(&self, id: &TicketId) -> Result<()> {
-        let path = self.active_ticket_path();
+        self.check_format()?;
+        self.ensure_local_dir()?;
+        let path = self.active_ticket_path()?;
 
         // Acquire lock for the active ticket file
-        let _lock = super::FileLock::acquire(&path, Some("set_active_ticket".to_string()))
-            .map_err(|e| {
-                VibeTicketError::custom(format!(
-                    "Failed to acquire lock for setting active ticket: {e}"
-                ))
-            })?;
-
-        fs::write(&path, id.to_string()).context("Failed to write active ticket")?;
+        let _lock = self.lock(
+            "active_ticket",
+            LockMode::Exclusive,
+            "setting active ticket",
+        )?;
+
+        write_atomic(&path, id.to_string()).context("Failed to write active ticket")?;
         Ok(())
     }
 
     /// Gets the active ticket ID
     pub fn get_active_ticket(&self) -> Result<Option<TicketId>> {
-        let path = self.active_ticket_path();
+        let path = self.active_ticket_path()?;
 
         if !path.exists() {
             return Ok(None);
@@ -215,16 +325,16 @@ impl FileStorage {
 
     /// Clears the active ticket with locking
     pub fn clear_active_ticket(&self) -> Result<()> {
-        let path = self.active_ticket_path();
+        self.check_format()?;
+        let path = self.active_ticket_path()?;
 
         if path.exists() {
             // Acquire lock before removing
-            let _lock = super::FileLock::acquire(&path, Some("clear_active_ticket".to_string()))
-                .map_err(|e| {
-                    VibeTicketError::custom(format!(
-                        "Failed to acquire lock for clearing active ticket: {e}"
-                    ))
-                })?;
+            let _lock = self.lock(
+                "active_ticket",
+                LockMode::Exclusive,
+                "clearing active ticket",
+            )?;
 
             fs::remove_file(&path).context("Failed to clear active ticket")?;
         }
@@ -235,17 +345,18 @@ impl FileStorage {
     /// Adds a ticket to the list of active tickets
     ///
     /// This method adds a ticket ID to the active tickets list stored in
-    /// `active_tickets.yaml`. If the file doesn't exist, it creates it.
+    /// `local/active_tickets.yaml`. If the file doesn't exist, it creates it.
     pub fn add_active_ticket(&self, id: &TicketId) -> Result<()> {
-        let path = self.active_tickets_path();
+        self.check_format()?;
+        self.ensure_local_dir()?;
+        let path = self.active_tickets_path()?;
 
         // Acquire lock for the active tickets file
-        let _lock = super::FileLock::acquire(&path, Some("add_active_ticket".to_string()))
-            .map_err(|e| {
-                VibeTicketError::custom(format!(
-                    "Failed to acquire lock for adding active ticket: {e}"
-                ))
-            })?;
+        let _lock = self.lock(
+            "active_tickets",
+            LockMode::Exclusive,
+            "adding active ticket",
+        )?;
 
         // Load existing active tickets
         let mut active_ids = if path.exists() {
@@ -273,26 +384,26 @@ impl FileStorage {
         )
         .context("Failed to serialize active tickets")?;
 
-        fs::write(&path, yaml).context("Failed to write active tickets")?;
+        write_atomic(&path, yaml).context("Failed to write active tickets")?;
 
         Ok(())
     }
 
     /// Removes a ticket from the list of active tickets
     pub fn remove_active_ticket(&self, id: &TicketId) -> Result<()> {
-        let path = self.active_tickets_path();
+        self.check_format()?;
+        let path = self.active_tickets_path()?;
 
         if !path.exists() {
             return Ok(()); // Nothing to remove
         }
 
         // Acquire lock for the active tickets file
-        let _lock = super::FileLock::acquire(&path, Some("remove_active_ticket".to_string()))
-            .map_err(|e| {
-                VibeTicketError::custom(format!(
-                    "Failed to acquire lock for removing active ticket: {e}"
-                ))
-            })?;
+        let _lock = self.lock(
+            "active_tickets",
+            LockMode::Exclusive,
+            "removing active ticket",
+        )?;
 
         // Load existing active tickets
         let content = fs::read_to_string(&path).context("Failed to read active tickets")?;
@@ -318,7 +429,7 @@ impl FileStorage {
             )
             .context("Failed to serialize active tickets")?;
 
-            fs::write(&path, yaml).context("Failed to write active tickets")?;
+            write_atomic(&path, yaml).context("Failed to write active tickets")?;
         }
 
         Ok(())
@@ -332,7 +443,7 @@ impl FileStorage {
         let mut active_ids = Vec::new();
 
         // Try new format first
-        let new_path = self.active_tickets_path();
+        let new_path = self.active_tickets_path()?;
         if new_path.exists() {
             let content = fs::read_to_string(&new_path).context("Failed to read active tickets")?;
             active_ids = serde_yaml::from_str::<Vec<String>>(&content)
@@ -352,14 +463,14 @@ impl FileStorage {
 
     /// Checks if a ticket with the given slug already exists
     pub fn ticket_exists_with_slug(&self, slug: &str) -> Result<bool> {
-        let tickets = self.load_all_tickets()?;
-        Ok(tickets.iter().any(|t| t.slug == slug))
+        Ok(self.find_ticket_id_by_slug(slug)?.is_some())
     }
 
     /// Finds a ticket by its slug
     pub fn find_ticket_by_slug(&self, slug: &str) -> Result<Option<Ticket>> {
-        let tickets = self.load_all_tickets()?;
-        Ok(tickets.into_iter().find(|t| t.slug == slug))
+        self.find_ticket_id_by_slug(slug)?
+            .map(|id| self.load_ticket(&id))
+            .transpose()
     }
 }
 
@@ -380,15 +491,22 @@ pub struct ProjectState {
 
     /// Total number of tickets created (for ID generation)
     pub ticket_count: u64,
+
+    /// Version of the on-disk format, see [`FORMAT_VERSION`]
+    ///
+    /// 0 for projects created before the format was versioned.
+    #[serde(default)]
+    pub format_version: u32,
 }
 
 impl FileStorage {
     /// Saves the project state
     pub fn save_state(&self, state: &ProjectState) -> Result<()> {
+        self.check_format()?;
         let path = self.state_path();
         let yaml = serde_yaml::to_string(state).context("Failed to serialize project state")?;
 
-        fs::write(&path, yaml).context("Failed to write project state")?;
+        write_atomic(&path, yaml).context("Failed to write project state")?;
 
         Ok(())
     }
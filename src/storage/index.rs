@@ -0,0 +1,349 @@
+//! Persisted index of ticket summaries
+//!
+//! Listing tickets used to read and parse every file in `tickets/`. The
+//! index in `.vibe-ticket/local/index.json` keeps a [`TicketSummary`] of each
+//! ticket together with the modification time and size of its file. Loading
+//! it only stats the ticket files and parses the ones that changed, so
+//! commands such as `list`, `board` and `check` and slug lookups stay fast
+//! in projects with thousands of tickets.
+//!
+//! The index is per worktree and never committed; deleting it only costs
+//! one full parse on the next load.
+
+use super::{FileStorage, write_atomic};
+use crate::core::{Priority, Status, Ticket, TicketId};
+use crate::error::{ErrorContext, Result};
+use crate::storage::archive::is_archived;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Version of the index layout; an index of another version is rebuilt
+const INDEX_VERSION: u32 = 1;
+
+/// Files modified this close to the last index write are parsed again
+///
+/// File systems with coarse timestamps give a file changed twice within one
+/// tick the same modification time, so only older entries are trusted.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// The fields of a ticket needed to list, filter, sort and look it up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketSummary {
+    /// Ticket ID
+    pub id: TicketId,
+    /// Ticket slug
+    pub slug: String,
+    /// Ticket title
+    pub title: String,
+    /// Current status
+    pub status: Status,
+    /// Priority
+    pub priority: Priority,
+    /// Tags
+    pub tags: Vec<String>,
+    /// Assignee, if any
+    pub assignee: Option<String>,
+    /// When the ticket was created
+    pub created_at: DateTime<Utc>,
+    /// When work on the ticket started
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the ticket was closed
+    pub closed_at: Option<DateTime<Utc>>,
+    /// When the ticket was last saved
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Number of tasks
+    pub tasks_total: usize,
+    /// Number of completed tasks
+    pub tasks_completed: usize,
+    /// Whether the ticket is flagged as archived
+    pub archived: bool,
+}
+
+impl From<&Ticket> for TicketSummary {
+    fn from(ticket: &Ticket) -> Self {
+        Self {
+            id: ticket.id.clone(),
+            slug: ticket.slug.clone(),
+            title: ticket.title.clone(),
+            status: ticket.status,
+            priority: ticket.priority,
+            tags: ticket.tags.clone(),
+            assignee: ticket.assignee.clone(),
+            created_at: ticket.created_at,
+            started_at: ticket.started_at,
+            closed_at: ticket.closed_at,
+            updated_at: ticket.updated_at,
+            tasks_total: ticket.total_tasks_count(),
+            tasks_completed: ticket.completed_tasks_count(),
+            archived: is_archived(ticket),
+        }
+    }
+}
+
+/// Contents of `index.json`
+#[derive(Debug, Serialize, Deserialize)]
+struct Index {
+    version: u32,
+    /// When the index was written
+    written_at: SystemTime,
+    /// Entries by ticket file name
+    entries: HashMap<String, IndexEntry>,
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            written_at: SystemTime::UNIX_EPOCH,
+            entries: HashMap::new(),
+        }
+    }
+}
+
+/// A ticket file as it was when it was indexed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    modified: SystemTime,
+    len: u64,
+    summary: TicketSummary,
+}
+
+impl FileStorage {
+    /// Returns the path to the ticket index
+    fn index_path(&self) -> PathBuf {
+        self.base_dir().join("local").join("index.json")
+    }
+
+    /// Loads a summary of every ticket, sorted by slug
+    ///
+    /// Only ticket files changed since the index was written are parsed;
+    /// the index is then updated for the next run. Files that do not parse
+    /// are skipped with a warning, as in [`FileStorage::load_all_tickets`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tickets directory cannot be read.
+    pub fn load_summaries(&self) -> Result<Vec<TicketSummary>> {
+        let (index, _) = self.refresh_index()?;
+        let mut summaries: Vec<_> = index
+            .entries
+            .into_values()
+            .map(|entry| entry.summary)
+            .collect();
+        summaries.sort_by(|a, b| a.slug.cmp(&b.slug));
+        Ok(summaries)
+    }
+
+    /// Finds the ID of the ticket with `slug` through the index
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tickets directory cannot be read.
+    pub fn find_ticket_id_by_slug(&self, slug: &str) -> Result<Option<TicketId>> {
+        let (index, _) = self.refresh_index()?;
+        Ok(index
+            .entries
+            .into_values()
+            .find(|entry| entry.summary.slug == slug)
+            .map(|entry| entry.summary.id))
+    }
+
+    /// Brings the index up to date with the ticket files
+    ///
+    /// Returns the index and the number of files parsed to update it.
+    fn refresh_index(&self) -> Result<(Index, usize)> {
+        let mut index = self.read_index();
+        let tickets_dir = self.tickets_dir();
+        if !tickets_dir.exists() {
+            return Ok((Index::default(), 0));
+        }
+
+        let trusted_before = index
+            .written_at
+            .checked_sub(RACY_WINDOW)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        // Entries older than this are trusted once the index is written now
+        let now = SystemTime::now();
+        let settled_before = now
+            .checked_sub(RACY_WINDOW)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut entries = HashMap::with_capacity(index.entries.len());
+        let mut parsed = 0;
+        let mut dirty = false;
+        for entry in fs::read_dir(&tickets_dir).context("Failed to read tickets directory")? {
+            let entry = entry.context("Failed to read directory entry")?;
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("yaml") {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            // A file removed meanwhile is no longer a ticket
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+
+            let known = match index.entries.remove(name) {
+                Some(known) if known.modified == modified && known.len == metadata.len() => {
+                    if modified < trusted_before {
+                        entries.insert(name.to_string(), known);
+                        continue;
+                    }
+                    // Too recent to trust when last indexed; once confirmed
+                    // below, writing the index again lets later runs skip it
+                    dirty |= modified < settled_before;
+                    Some(known)
+                },
+                known => known,
+            };
+
+            parsed += 1;
+            let Ok(yaml) = fs::read_to_string(&path) else {
+                dirty |= known.is_some();
+                continue;
+            };
+            let ticket = match serde_yaml::from_str::<Ticket>(&yaml) {
+                Ok(ticket) => ticket,
+                Err(e) => {
+                    Self::warn_unreadable(&path, &e);
+                    dirty |= known.is_some();
+                    continue;
+                },
+            };
+            let fresh = IndexEntry {
+                modified,
+                len: metadata.len(),
+                summary: TicketSummary::from(&ticket),
+            };
+            dirty |= known.is_none_or(|known| {
+                known.modified != fresh.modified
+                    || known.len != fresh.len
+                    || known.summary != fresh.summary
+            });
+            entries.insert(name.to_string(), fresh);
+        }
+        // Entries left over belong to deleted tickets
+        dirty |= !index.entries.is_empty();
+
+        index.entries = entries;
+        if dirty {
+            index.written_at = now;
+            self.write_index(&index);
+        }
+        Ok((index, parsed))
+    }
+
+    /// Reads the index, or starts an empty one if it is missing or unusable
+    fn read_index(&self) -> Index {
+        fs::read(self.index_path())
+            .ok()
+            .and_then(|json| serde_json::from_slice::<Index>(&json).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Writes the index; failing to is not an error, it is rebuilt next time
+    fn write_index(&self, index: &Index) {
+        if self.ensure_local_dir().is_err() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_vec(index) {
+            let _ = write_atomic(self.index_path(), json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    /// Makes every indexed entry older than the racy window
+    fn age_index(storage: &FileStorage) {
+        let mut index = storage.read_index();
+        index.written_at += RACY_WINDOW * 2;
+        storage.write_index(&index);
+    }
+
+    #[test]
+    fn test_index_parses_only_changed_files() {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+        let mut tickets: Vec<_> = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|slug| {
+                let ticket = Ticket::new(*slug, slug.to_uppercase());
+                storage.save(&ticket).unwrap();
+                ticket
+            })
+            .collect();
+
+        let (index, parsed) = storage.refresh_index().unwrap();
+        assert_eq!((index.entries.len(), parsed), (3, 3));
+        age_index(&storage);
+        assert_eq!(storage.refresh_index().unwrap().1, 0);
+
+        tickets[1].status = Status::Doing;
+        storage.save(&tickets[1]).unwrap();
+        storage.delete(&tickets[2].id).unwrap();
+        let (index, parsed) = storage.refresh_index().unwrap();
+        assert_eq!((index.entries.len(), parsed), (2, 1));
+
+        let summaries = storage.load_summaries().unwrap();
+        assert_eq!(summaries[1].slug, "beta");
+        assert_eq!(summaries[1].status, Status::Doing);
+        assert_eq!(
+            storage.find_ticket_id_by_slug("alpha").unwrap(),
+            Some(tickets[0].id.clone())
+        );
+        assert_eq!(storage.find_ticket_id_by_slug("gamma").unwrap(), None);
+    }
+
+    #[test]
+    fn test_fresh_entries_are_trusted_once_settled() {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+        for slug in ["alpha", "beta"] {
+            storage.save(&Ticket::new(slug, slug)).unwrap();
+        }
+
+        // Written just now, the files are parsed until their change settles
+        assert_eq!(storage.refresh_index().unwrap().1, 2);
+        assert_eq!(storage.refresh_index().unwrap().1, 2);
+        std::thread::sleep(RACY_WINDOW + Duration::from_millis(100));
+        assert_eq!(storage.refresh_index().unwrap().1, 2);
+        assert_eq!(storage.refresh_index().unwrap().1, 0);
+    }
+
+    #[test]
+    fn test_index_is_rebuilt_when_unreadable() {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+        let mut ticket = Ticket::new("login", "Login");
+        ticket
+            .tasks
+            .push(crate::core::Task::new("Write tests".to_string()));
+        storage.save(&ticket).unwrap();
+        storage.load_summaries().unwrap();
+
+        fs::write(storage.index_path(), "{ not json").unwrap();
+        let summaries = storage.load_summaries().unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].id, ticket.id);
+        assert_eq!(summaries[0].title, "Login");
+        assert_eq!(
+            (summaries[0].tasks_total, summaries[0].tasks_completed),
+            (1, 0)
+        );
+        assert!(serde_json::from_slice::<Index>(&fs::read(storage.index_path()).unwrap()).is_ok());
+    }
+}
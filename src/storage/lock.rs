@@ -1,29 +1,58 @@
+//! Advisory file locks for concurrent access protection
+//!
+//! Locks are operating system advisory locks (`flock` on Unix, `LockFileEx`
+//! on Windows) taken on dedicated lock files, either shared for reads or
+//! exclusive for writes. The operating system releases a lock when its
+//! holder exits, so a crashed process never blocks others, and a long
+//! operation is never mistaken for a stale one. Acquiring a lock blocks
+//! until it is free or the timeout expires.
+//!
+//! Locks are reentrant per thread: a thread that already holds a lock in a
+//! mode covering the request gets another guard for it without touching the
+//! file. This lets a multi-ticket operation holding the store lock
+//! exclusively call single-ticket operations that take it shared.
+
 use anyhow::{Context, Result, bail};
+use fs4::fs_std::FileExt;
 use serde::{Deserialize, Serialize};
-/// File locking mechanism for concurrent access protection
-///
-/// This module provides a simple file-based locking mechanism to prevent
-/// concurrent modifications to ticket files.
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write as IoWrite};
+use std::io::Write as IoWrite;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use uuid::Uuid;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default time to wait for a lock before giving up
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Environment variable overriding [`LOCK_TIMEOUT`], in seconds
+pub const LOCK_TIMEOUT_ENV: &str = "VIBE_TICKET_LOCK_TIMEOUT";
 
-/// Maximum time a lock can be held before it's considered stale
-const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+/// Longest pause between two attempts to take a busy lock
+const MAX_RETRY_DELAY: Duration = Duration::from_millis(50);
 
-/// Maximum number of retry attempts for acquiring a lock
-const MAX_RETRY_ATTEMPTS: u32 = 10;
+/// How a lock is shared with other holders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Any number of readers, no writer
+    Shared,
+    /// A single writer
+    Exclusive,
+}
 
-/// Delay between retry attempts
-const RETRY_DELAY: Duration = Duration::from_millis(100);
+impl LockMode {
+    /// Whether holding a lock in this mode allows what `requested` allows
+    const fn covers(self, requested: Self) -> bool {
+        matches!((self, requested), (Self::Exclusive, _) | (_, Self::Shared))
+    }
+}
 
-/// Information stored in a lock file
+/// Information about the process holding an exclusive lock
+///
+/// Written into the lock file while the lock is held, so that a process
+/// waiting for the lock can tell who holds it.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LockInfo {
-    /// Unique identifier for the lock holder
-    pub(crate) holder_id: String,
     /// Process ID of the lock holder
     pub(crate) pid: u32,
     /// Timestamp when the lock was acquired
@@ -32,113 +61,175 @@ pub struct LockInfo {
     pub(crate) operation: Option<String>,
 }
 
+impl LockInfo {
+    /// Reads the holder information from a lock file, if there is any
+    #[must_use]
+    pub fn read(lock_path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(lock_path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Whether the process that took the lock is still running
+    #[must_use]
+    pub fn is_holder_alive(&self) -> bool {
+        is_process_alive(self.pid)
+    }
+}
+
+/// A lock held by the current thread
+struct HeldLock {
+    /// Open lock file; closing it releases the operating system lock
+    file: File,
+    mode: LockMode,
+    /// Number of guards for this lock
+    count: usize,
+}
+
+thread_local! {
+    /// Locks held by the current thread, by lock file path
+    static HELD: RefCell<HashMap<PathBuf, HeldLock>> = RefCell::new(HashMap::new());
+}
+
 /// A file lock that automatically releases on drop
 pub struct FileLock {
     path: PathBuf,
-    holder_id: String,
+    mode: LockMode,
 }
 
 impl FileLock {
-    /// Attempts to acquire a lock for the given path
+    /// Acquires a lock on the given lock file, creating it if needed
     ///
     /// # Arguments
-    /// * `path` - The file path to lock
-    /// * `operation` - Optional description of the operation being performed
+    /// * `lock_path` - The lock file, which is never removed
+    /// * `mode` - Whether to lock for reading or writing
+    /// * `operation` - Description of the operation, recorded for exclusive locks
+    /// * `timeout` - How long to wait for other holders to release the lock
     ///
     /// # Returns
     /// A `FileLock` that will automatically release when dropped
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn acquire(path: &Path, operation: Option<String>) -> Result<Self> {
-        let lock_path = Self::lock_path(path);
-        let holder_id = Uuid::new_v4().to_string();
-
-        // Try to acquire the lock with retries
-        for attempt in 0..MAX_RETRY_ATTEMPTS {
-            match Self::try_acquire_once(&lock_path, &holder_id, operation.as_ref()) {
-                Ok(()) => {
-                    return Ok(Self {
-                        path: lock_path,
-                        holder_id,
-                    });
-                },
-                Err(e) => {
-                    if attempt == MAX_RETRY_ATTEMPTS - 1 {
-                        return Err(e).context("Failed to acquire lock after maximum retries");
-                    }
-
-                    // Check if the existing lock is stale
-                    if Self::is_lock_stale(&lock_path)? {
-                        // Try to remove stale lock
-                        let _ = fs::remove_file(&lock_path);
-                        continue;
-                    }
-
-                    // Wait before retrying
-                    std::thread::sleep(RETRY_DELAY);
-                },
-            }
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened, the lock is still
+    /// busy after `timeout`, or the thread holds it shared and asks for an
+    /// exclusive lock, which would wait for itself.
+    pub fn acquire(
+        lock_path: &Path,
+        mode: LockMode,
+        operation: &str,
+        timeout: Duration,
+    ) -> Result<Self> {
+        let reentered = HELD.with_borrow_mut(|held| match held.get_mut(lock_path) {
+            Some(lock) if lock.mode.covers(mode) => {
+                lock.count += 1;
+                Ok(true)
+            },
+            Some(_) => bail!(
+                "Cannot lock {} exclusively while holding a shared lock on it",
+                lock_path.display()
+            ),
+            None => Ok(false),
+        })?;
+
+        if !reentered {
+            let file = Self::lock_file(lock_path, mode, operation, timeout)?;
+            HELD.with_borrow_mut(|held| {
+                held.insert(
+                    lock_path.to_path_buf(),
+                    HeldLock {
+                        file,
+                        mode,
+                        count: 1,
+                    },
+                );
+            });
         }
 
-        bail!("Failed to acquire lock: maximum retries exceeded")
+        Ok(Self {
+            path: lock_path.to_path_buf(),
+            mode,
+        })
     }
 
-    /// Attempts to acquire a lock once without retrying
-    fn try_acquire_once(
+    /// Opens the lock file and waits until the operating system grants the lock
+    fn lock_file(
         lock_path: &Path,
-        holder_id: &str,
-        operation: Option<&String>,
-    ) -> Result<()> {
-        // Try to create the lock file exclusively
-        let mut file = OpenOptions::new()
+        mode: LockMode,
+        operation: &str,
+        timeout: Duration,
+    ) -> Result<File> {
+        if let Some(dir) = lock_path.parent() {
+            fs::create_dir_all(dir).context("Failed to create lock directory")?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
             .write(true)
-            .create_new(true)
+            .create(true)
+            .truncate(false)
             .open(lock_path)
-            .context("Lock file already exists")?;
-
-        let lock_info = LockInfo {
-            holder_id: holder_id.to_string(),
-            pid: std::process::id(),
-            acquired_at: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-            operation: operation.cloned(),
-        };
+            .with_context(|| format!("Failed to open lock file {}", lock_path.display()))?;
+
+        let deadline = Instant::now() + timeout;
+        let mut delay = Duration::from_millis(1);
+        while !Self::try_lock(&file, mode)
+            .with_context(|| format!("Failed to lock {}", lock_path.display()))?
+        {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                bail!(Self::busy_message(lock_path, timeout));
+            }
+            std::thread::sleep(delay.min(remaining));
+            delay = (delay * 2).min(MAX_RETRY_DELAY);
+        }
 
-        let json = serde_json::to_string_pretty(&lock_info)?;
-        file.write_all(json.as_bytes())?;
-        file.sync_all()?;
+        if mode == LockMode::Exclusive {
+            let lock_info = LockInfo {
+                pid: std::process::id(),
+                acquired_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                operation: Some(operation.to_string()),
+            };
+            let json = serde_json::to_string_pretty(&lock_info)?;
+            file.set_len(0)?;
+            (&file).write_all(json.as_bytes())?;
+        }
 
-        Ok(())
+        Ok(file)
     }
 
-    /// Checks if a lock file is stale (older than `LOCK_TIMEOUT`)
-    fn is_lock_stale(lock_path: &Path) -> Result<bool> {
-        if !lock_path.exists() {
-            return Ok(false);
+    /// Takes the operating system lock without waiting; `false` if it is busy
+    fn try_lock(file: &File, mode: LockMode) -> std::io::Result<bool> {
+        // Called through the trait: newer standard libraries have inherent
+        // methods of the same name with a different signature
+        match mode {
+            LockMode::Shared => FileExt::try_lock_shared(file),
+            LockMode::Exclusive => FileExt::try_lock_exclusive(file),
         }
+    }
 
-        let mut file = File::open(lock_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-
-        let lock_info: LockInfo = serde_json::from_str(&contents)?;
-
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        let age = now.saturating_sub(lock_info.acquired_at);
-        Ok(age > LOCK_TIMEOUT.as_secs())
+    /// Explains who holds a lock that could not be acquired in time
+    fn busy_message(lock_path: &Path, timeout: Duration) -> String {
+        let holder = match LockInfo::read(lock_path) {
+            Some(info) if info.is_holder_alive() => format!(
+                "process {} ({})",
+                info.pid,
+                info.operation.as_deref().unwrap_or("unknown operation")
+            ),
+            _ => "another process".to_string(),
+        };
+        format!(
+            "Timed out after {}s waiting for {}, which is held by {holder}",
+            timeout.as_secs_f32(),
+            lock_path.display()
+        )
     }
 
-    /// Gets the lock file path for a given file
-    fn lock_path(path: &Path) -> PathBuf {
-        let mut lock_path = path.to_path_buf();
-        let filename = format!("{}.lock", path.file_name().unwrap().to_str().unwrap());
-        lock_path.set_file_name(filename);
-        lock_path
+    /// The mode the lock was requested in
+    #[must_use]
+    pub const fn mode(&self) -> LockMode {
+        self.mode
     }
 
     /// Releases the lock explicitly
@@ -150,17 +241,22 @@ impl FileLock {
 
 impl Drop for FileLock {
     fn drop(&mut self) {
-        // Only remove the lock file if we are the holder
-        if let Ok(mut file) = File::open(&self.path) {
-            let mut contents = String::new();
-            if file.read_to_string(&mut contents).is_ok() {
-                if let Ok(lock_info) = serde_json::from_str::<LockInfo>(&contents) {
-                    #[allow(clippy::excessive_nesting)]
-                    if lock_info.holder_id == self.holder_id {
-                        let _ = fs::remove_file(&self.path);
-                    }
-                }
+        let released = HELD.with_borrow_mut(|held| {
+            let lock = held.get_mut(&self.path)?;
+            lock.count -= 1;
+            if lock.count == 0 {
+                held.remove(&self.path)
+            } else {
+                None
             }
+        });
+
+        if let Some(lock) = released {
+            if lock.mode == LockMode::Exclusive {
+                // The holder information is only meaningful while held
+                let _ = lock.file.set_len(0);
+            }
+            let _ = FileExt::unlock(&lock.file);
         }
     }
 }
@@ -182,46 +278,131 @@ impl LockGuard<'_> {
     }
 }
 
+/// Time to wait for a lock, from [`LOCK_TIMEOUT_ENV`] or [`LOCK_TIMEOUT`]
+#[must_use]
+pub fn default_lock_timeout() -> Duration {
+    std::env::var(LOCK_TIMEOUT_ENV)
+        .ok()
+        .and_then(|seconds| seconds.trim().parse::<f64>().ok())
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .unwrap_or(LOCK_TIMEOUT)
+}
+
+/// Whether a process with the given ID is running
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> bool {
+    let Some(pid) = i32::try_from(pid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+    else {
+        return false;
+    };
+    // Permission errors mean the process exists but belongs to someone else
+    match rustix::process::test_kill_process(pid) {
+        Ok(()) => true,
+        Err(errno) => errno == rustix::io::Errno::PERM,
+    }
+}
+
+/// Whether a process with the given ID is running
+///
+/// Liveness cannot be checked here, so every process counts as running.
+#[cfg(not(unix))]
+const fn is_process_alive(_pid: u32) -> bool {
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::mpsc;
     use tempfile::TempDir;
 
+    const SHORT: Duration = Duration::from_millis(50);
+
+    /// Tries to take a lock from another thread, which does not share locks
+    fn acquire_elsewhere(path: &Path, mode: LockMode) -> Result<()> {
+        let path = path.to_path_buf();
+        std::thread::spawn(move || FileLock::acquire(&path, mode, "other thread", SHORT).map(drop))
+            .join()
+            .unwrap()
+    }
+
     #[test]
     fn test_acquire_and_release_lock() {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("test.yaml");
+        let lock_path = temp_dir.path().join("locks/test.lock");
 
         // Acquire lock
-        let lock = FileLock::acquire(&file_path, Some("test operation".to_string())).unwrap();
+        let lock = FileLock::acquire(&lock_path, LockMode::Exclusive, "test", SHORT).unwrap();
+        let info = LockInfo::read(&lock_path).unwrap();
+        assert_eq!(info.pid, std::process::id());
+        assert!(info.is_holder_alive());
 
-        // Try to acquire again - should fail
-        assert!(FileLock::acquire(&file_path, None).is_err());
+        // Others cannot take it in either mode
+        let error = acquire_elsewhere(&lock_path, LockMode::Shared).unwrap_err();
+        assert!(error.to_string().contains("(test)"), "{error}");
+        assert!(acquire_elsewhere(&lock_path, LockMode::Exclusive).is_err());
 
         // Release lock
         drop(lock);
+        assert!(LockInfo::read(&lock_path).is_none());
 
         // Should be able to acquire again
-        let _lock2 = FileLock::acquire(&file_path, None).unwrap();
+        acquire_elsewhere(&lock_path, LockMode::Exclusive).unwrap();
     }
 
     #[test]
-    fn test_stale_lock_removal() {
+    fn test_shared_locks_exclude_writers_only() {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("test.yaml");
-        let lock_path = FileLock::lock_path(&file_path);
-
-        // Create a stale lock manually
-        let stale_info = LockInfo {
-            holder_id: "stale-holder".to_string(),
-            pid: 99999,
-            acquired_at: 0, // Very old timestamp
-            operation: None,
-        };
+        let lock_path = temp_dir.path().join("test.lock");
+
+        let _read = FileLock::acquire(&lock_path, LockMode::Shared, "read", SHORT).unwrap();
+        acquire_elsewhere(&lock_path, LockMode::Shared).unwrap();
+        assert!(acquire_elsewhere(&lock_path, LockMode::Exclusive).is_err());
+    }
 
-        fs::write(&lock_path, serde_json::to_string(&stale_info).unwrap()).unwrap();
+    #[test]
+    fn test_waits_for_holder_to_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("test.lock");
+        let lock = FileLock::acquire(&lock_path, LockMode::Exclusive, "hold", SHORT).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let path = lock_path.clone();
+        let waiter = std::thread::spawn(move || {
+            sender.send(()).unwrap();
+            FileLock::acquire(&path, LockMode::Exclusive, "wait", LOCK_TIMEOUT).map(drop)
+        });
+        receiver.recv().unwrap();
+        std::thread::sleep(SHORT);
+        drop(lock);
 
-        // Should be able to acquire lock despite stale lock file
-        let _lock = FileLock::acquire(&file_path, None).unwrap();
+        waiter.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_locks_are_reentrant_per_thread() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("store.lock");
+
+        let store = FileLock::acquire(&lock_path, LockMode::Exclusive, "bulk", SHORT).unwrap();
+        let nested = FileLock::acquire(&lock_path, LockMode::Shared, "save", SHORT).unwrap();
+        assert_eq!(nested.mode(), LockMode::Shared);
+        drop(nested);
+        // Still held after the nested guard is gone
+        assert!(acquire_elsewhere(&lock_path, LockMode::Shared).is_err());
+        drop(store);
+
+        let read = FileLock::acquire(&lock_path, LockMode::Shared, "read", SHORT).unwrap();
+        assert!(FileLock::acquire(&lock_path, LockMode::Exclusive, "write", SHORT).is_err());
+        drop(read);
+    }
+
+    #[test]
+    fn test_process_liveness() {
+        assert!(is_process_alive(std::process::id()));
+        #[cfg(unix)]
+        assert!(!is_process_alive(u32::MAX));
     }
 }
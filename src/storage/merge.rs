@@ -0,0 +1,484 @@
+//! Three-way merge of ticket files
+//!
+//! Used by `vibe-ticket merge-driver`, which git runs instead of its line
+//! based merge for files under `.vibe-ticket/` (see
+//! `vibe-ticket init --install-merge-driver`). Files are parsed and merged
+//! field by field:
+//!
+//! - A field changed on one side only takes that side's value
+//! - A field changed differently on both sides takes the value of the side
+//!   saved last, going by the tickets' `updated_at`
+//! - Tags are merged as sets, and tasks by ID, so additions from both sides
+//!   are kept
+//!
+//! Only fields changed on both sides of tickets saved at the same moment (or
+//! by versions too old to record it) are left with conflict markers.
+
+use crate::core::{Task, Ticket};
+use crate::error::{ErrorContext, Result};
+use crate::storage::ProjectState;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// Result of merging a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge {
+    /// The merged file, with conflict markers around any conflicting fields
+    pub content: String,
+    /// Names of the fields that could not be merged
+    pub conflicts: Vec<String>,
+}
+
+impl Merge {
+    /// Whether the merge completed without conflicts
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Side of a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Ours,
+    Theirs,
+}
+
+/// Merge three versions of a ticket or project state file
+///
+/// `base` is the common ancestor, which is empty when both sides added the
+/// file.
+///
+/// # Errors
+///
+/// Returns an error if a version is neither a ticket nor a project state.
+pub fn merge_yaml(base: &str, ours: &str, theirs: &str) -> Result<Merge> {
+    if let Ok(ours_ticket) = serde_yaml::from_str::<Ticket>(ours) {
+        let theirs: Ticket =
+            serde_yaml::from_str(theirs).context("Failed to parse their ticket")?;
+        let base = parse_base::<Ticket>(base).context("Failed to parse base ticket")?;
+        let newer = newer(ours_ticket.updated_at, theirs.updated_at);
+        return render(|tie| {
+            merge_ticket(base.as_ref(), &ours_ticket, &theirs, newer.unwrap_or(tie))
+        });
+    }
+
+    let ours: ProjectState = serde_yaml::from_str(ours).context("Failed to parse our file")?;
+    let theirs: ProjectState =
+        serde_yaml::from_str(theirs).context("Failed to parse their project state")?;
+    let base = parse_base::<ProjectState>(base).context("Failed to parse base project state")?;
+    let newer = newer(Some(ours.updated_at), Some(theirs.updated_at));
+    render(|tie| merge_state(base.as_ref(), &ours, &theirs, newer.unwrap_or(tie)))
+}
+
+fn parse_base<T: serde::de::DeserializeOwned>(base: &str) -> serde_yaml::Result<Option<T>> {
+    if base.trim().is_empty() {
+        Ok(None)
+    } else {
+        serde_yaml::from_str(base).map(Some)
+    }
+}
+
+/// The side saved last, if the timestamps tell them apart
+fn newer(ours: Option<DateTime<Utc>>, theirs: Option<DateTime<Utc>>) -> Option<Side> {
+    match ours.cmp(&theirs) {
+        std::cmp::Ordering::Greater => Some(Side::Ours),
+        std::cmp::Ordering::Less => Some(Side::Theirs),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Serialize a merge, marking the top-level fields that depend on which side
+/// wins a tie
+fn render<T: Serialize>(merge: impl Fn(Side) -> T) -> Result<Merge> {
+    let ours = serde_yaml::to_value(merge(Side::Ours)).context("Failed to serialize merge")?;
+    let theirs = serde_yaml::to_value(merge(Side::Theirs)).context("Failed to serialize merge")?;
+    if ours == theirs {
+        return Ok(Merge {
+            content: serde_yaml::to_string(&ours).context("Failed to serialize merge")?,
+            conflicts: Vec::new(),
+        });
+    }
+
+    let (serde_yaml::Value::Mapping(ours), serde_yaml::Value::Mapping(theirs)) = (ours, theirs)
+    else {
+        unreachable!("tickets and project states serialize to mappings");
+    };
+    let field = |key: &serde_yaml::Value, value: Option<&serde_yaml::Value>| -> Result<String> {
+        let mut mapping = serde_yaml::Mapping::new();
+        if let Some(value) = value {
+            mapping.insert(key.clone(), value.clone());
+        }
+        if mapping.is_empty() {
+            return Ok(String::new());
+        }
+        serde_yaml::to_string(&mapping).context("Failed to serialize merge")
+    };
+
+    let mut content = String::new();
+    let mut conflicts = Vec::new();
+    for (key, value) in &ours {
+        let other = theirs.get(key);
+        if other == Some(value) {
+            content.push_str(&field(key, Some(value))?);
+        } else {
+            conflicts.push(key.as_str().unwrap_or_default().to_string());
+            content.push_str("<<<<<<< ours\n");
+            content.push_str(&field(key, Some(value))?);
+            content.push_str("=======\n");
+            content.push_str(&field(key, other)?);
+            content.push_str(">>>>>>> theirs\n");
+        }
+    }
+    Ok(Merge { content, conflicts })
+}
+
+/// Merge one value: a side that changed it wins over one that did not, and
+/// `prefer` wins when both changed it
+fn merge_value<T: PartialEq + Clone>(base: Option<&T>, ours: &T, theirs: &T, prefer: Side) -> T {
+    if ours == theirs || base == Some(theirs) {
+        return ours.clone();
+    }
+    if base == Some(ours) {
+        return theirs.clone();
+    }
+    match prefer {
+        Side::Ours => ours.clone(),
+        Side::Theirs => theirs.clone(),
+    }
+}
+
+/// Merge two lists as sets: items added on either side are kept and items
+/// removed on either side are dropped
+fn merge_set<T: PartialEq + Clone>(base: Option<&[T]>, ours: &[T], theirs: &[T]) -> Vec<T> {
+    let in_base = |item: &T| base.is_some_and(|base| base.contains(item));
+    let mut merged: Vec<T> = ours
+        .iter()
+        .filter(|item| theirs.contains(item) || !in_base(item))
+        .cloned()
+        .collect();
+    for item in theirs {
+        if !merged.contains(item) && !in_base(item) {
+            merged.push(item.clone());
+        }
+    }
+    merged
+}
+
+fn merge_ticket(base: Option<&Ticket>, ours: &Ticket, theirs: &Ticket, prefer: Side) -> Ticket {
+    macro_rules! field {
+        ($name:ident) => {
+            merge_value(
+                base.map(|base| &base.$name),
+                &ours.$name,
+                &theirs.$name,
+                prefer,
+            )
+        };
+    }
+
+    // The timestamps only make sense with the status they were set for
+    let lifecycle = |ticket: &Ticket| (ticket.status, ticket.started_at, ticket.closed_at);
+    let (status, started_at, closed_at) = merge_value(
+        base.map(lifecycle).as_ref(),
+        &lifecycle(ours),
+        &lifecycle(theirs),
+        prefer,
+    );
+
+    Ticket {
+        id: field!(id),
+        slug: field!(slug),
+        title: field!(title),
+        description: field!(description),
+        priority: field!(priority),
+        status,
+        tags: merge_set(
+            base.map(|base| base.tags.as_slice()),
+            &ours.tags,
+            &theirs.tags,
+        ),
+        created_at: field!(created_at),
+        started_at,
+        closed_at,
+        updated_at: ours.updated_at.max(theirs.updated_at),
+        assignee: field!(assignee),
+        tasks: merge_tasks(
+            base.map(|base| base.tasks.as_slice()),
+            &ours.tasks,
+            &theirs.tasks,
+            prefer,
+        ),
+        metadata: merge_metadata(
+            base.map(|base| &base.metadata),
+            &ours.metadata,
+            &theirs.metadata,
+            prefer,
+        ),
+    }
+}
+
+/// Merge tasks by ID
+///
+/// Tasks added on either side are kept. A task removed on one side stays
+/// removed unless the other side changed it.
+fn merge_tasks(base: Option<&[Task]>, ours: &[Task], theirs: &[Task], prefer: Side) -> Vec<Task> {
+    let mut merged = Vec::new();
+    for task in ours {
+        let base_task = find_task(base, task);
+        match find_task(Some(theirs), task) {
+            Some(their_task) => merged.push(merge_task(base_task, task, their_task, prefer)),
+            None if base_task.is_none_or(|base_task| base_task != task) => {
+                merged.push(task.clone());
+            },
+            None => {},
+        }
+    }
+    for task in theirs {
+        if find_task(Some(ours), task).is_none()
+            && find_task(base, task).is_none_or(|base_task| base_task != task)
+        {
+            merged.push(task.clone());
+        }
+    }
+    merged
+}
+
+fn find_task<'a>(tasks: Option<&'a [Task]>, task: &Task) -> Option<&'a Task> {
+    tasks.and_then(|tasks| tasks.iter().find(|other| other.id == task.id))
+}
+
+fn merge_task(base: Option<&Task>, ours: &Task, theirs: &Task, prefer: Side) -> Task {
+    let (completed, completed_at) = merge_value(
+        base.map(|base| (base.completed, base.completed_at))
+            .as_ref(),
+        &(ours.completed, ours.completed_at),
+        &(theirs.completed, theirs.completed_at),
+        prefer,
+    );
+    Task {
+        id: ours.id.clone(),
+        title: merge_value(
+            base.map(|base| &base.title),
+            &ours.title,
+            &theirs.title,
+            prefer,
+        ),
+        completed,
+        created_at: merge_value(
+            base.map(|base| &base.created_at),
+            &ours.created_at,
+            &theirs.created_at,
+            prefer,
+        ),
+        completed_at,
+    }
+}
+
+/// Merge metadata key by key, treating a missing key as a value
+fn merge_metadata(
+    base: Option<&HashMap<String, serde_json::Value>>,
+    ours: &HashMap<String, serde_json::Value>,
+    theirs: &HashMap<String, serde_json::Value>,
+    prefer: Side,
+) -> HashMap<String, serde_json::Value> {
+    let keys: BTreeSet<&String> = ours.keys().chain(theirs.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            merge_value(
+                base.map(|base| base.get(key)).as_ref(),
+                &ours.get(key),
+                &theirs.get(key),
+                prefer,
+            )
+            .map(|value| (key.clone(), value.clone()))
+        })
+        .collect()
+}
+
+fn merge_state(
+    base: Option<&ProjectState>,
+    ours: &ProjectState,
+    theirs: &ProjectState,
+    prefer: Side,
+) -> ProjectState {
+    ProjectState {
+        name: merge_value(
+            base.map(|base| &base.name),
+            &ours.name,
+            &theirs.name,
+            prefer,
+        ),
+        description: merge_value(
+            base.map(|base| &base.description),
+            &ours.description,
+            &theirs.description,
+            prefer,
+        ),
+        created_at: merge_value(
+            base.map(|base| &base.created_at),
+            &ours.created_at,
+            &theirs.created_at,
+            prefer,
+        ),
+        updated_at: ours.updated_at.max(theirs.updated_at),
+        // Tickets created on both sides count towards the total
+        ticket_count: base.map_or_else(
+            || ours.ticket_count.max(theirs.ticket_count),
+            |base| (ours.ticket_count + theirs.ticket_count).saturating_sub(base.ticket_count),
+        ),
+        // A branch that was migrated stays migrated
+        format_version: ours.format_version.max(theirs.format_version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Priority, Status};
+    use crate::storage::FORMAT_VERSION;
+    use chrono::Duration;
+
+    fn yaml(ticket: &Ticket) -> String {
+        serde_yaml::to_string(ticket).unwrap()
+    }
+
+    fn parse(merge: &Merge) -> Ticket {
+        serde_yaml::from_str(&merge.content).unwrap()
+    }
+
+    /// A ticket and two copies of it, the second saved later than the first
+    fn sides() -> (Ticket, Ticket, Ticket) {
+        let mut base = Ticket::new("fix-login", "Fix login");
+        base.tags = vec!["bug".to_string(), "auth".to_string()];
+        base.add_task("Reproduce");
+        base.updated_at = Some(Utc::now() - Duration::hours(2));
+        let mut ours = base.clone();
+        ours.updated_at = Some(Utc::now() - Duration::hours(1));
+        let mut theirs = base.clone();
+        theirs.updated_at = Some(Utc::now());
+        (base, ours, theirs)
+    }
+
+    #[test]
+    fn test_merge_combines_changes_from_both_sides() {
+        let (base, mut ours, mut theirs) = sides();
+        ours.priority = Priority::High;
+        ours.tags.retain(|tag| tag != "auth");
+        ours.tags.push("urgent".to_string());
+        ours.add_task("Write test");
+        ours.metadata.insert("pr".to_string(), 12.into());
+        theirs.status = Status::Doing;
+        theirs.tags.push("login".to_string());
+        let task_id = theirs.tasks[0].id.clone();
+        theirs.complete_task(&task_id).unwrap();
+        theirs.add_task("Fix redirect");
+
+        let merge = merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap();
+        assert!(merge.is_clean());
+        let merged = parse(&merge);
+        assert_eq!(merged.priority, Priority::High);
+        assert_eq!(merged.status, Status::Doing);
+        assert_eq!(merged.tags, ["bug", "urgent", "login"]);
+        let tasks: Vec<_> = merged
+            .tasks
+            .iter()
+            .map(|task| task.title.as_str())
+            .collect();
+        assert_eq!(tasks, ["Reproduce", "Write test", "Fix redirect"]);
+        assert!(merged.tasks[0].completed);
+        assert_eq!(merged.metadata["pr"], 12);
+        assert_eq!(merged.updated_at, theirs.updated_at);
+    }
+
+    #[test]
+    fn test_merge_prefers_latest_save_when_both_change_a_field() {
+        let (base, mut ours, mut theirs) = sides();
+        ours.title = "Fix login on Safari".to_string();
+        theirs.title = "Fix login redirect".to_string();
+        let merged = parse(&merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap());
+        assert_eq!(merged.title, "Fix login redirect");
+
+        ours.updated_at = Some(Utc::now() + Duration::hours(1));
+        let merged = parse(&merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap());
+        assert_eq!(merged.title, "Fix login on Safari");
+    }
+
+    #[test]
+    fn test_merge_takes_status_and_timestamps_from_one_side() {
+        let (mut base, mut ours, mut theirs) = sides();
+        let started_at = Some(Utc::now() - Duration::hours(3));
+        for ticket in [&mut base, &mut ours, &mut theirs] {
+            ticket.status = Status::Doing;
+            ticket.started_at = started_at;
+        }
+        ours.set_status(Status::Done);
+        theirs.set_status(Status::Review);
+
+        let merged = parse(&merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap());
+        assert_eq!(merged.status, Status::Review);
+        assert_eq!(merged.started_at, started_at);
+        assert!(merged.closed_at.is_none());
+    }
+
+    #[test]
+    fn test_merge_removed_task_is_kept_only_if_changed() {
+        let (base, mut ours, mut theirs) = sides();
+        ours.tasks.clear();
+        let merged = parse(&merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap());
+        assert!(merged.tasks.is_empty());
+
+        let task_id = theirs.tasks[0].id.clone();
+        theirs.complete_task(&task_id).unwrap();
+        let merged = parse(&merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap());
+        assert_eq!(merged.tasks, theirs.tasks);
+    }
+
+    #[test]
+    fn test_merge_marks_conflicts_without_timestamps() {
+        let (mut base, mut ours, mut theirs) = sides();
+        base.updated_at = None;
+        ours.updated_at = None;
+        theirs.updated_at = None;
+        ours.title = "Fix login on Safari".to_string();
+        theirs.title = "Fix login redirect".to_string();
+        theirs.priority = Priority::Critical;
+
+        let merge = merge_yaml(&yaml(&base), &yaml(&ours), &yaml(&theirs)).unwrap();
+        assert_eq!(merge.conflicts, ["title"]);
+        assert!(merge.content.contains(
+            "<<<<<<< ours\ntitle: Fix login on Safari\n=======\ntitle: Fix login redirect\n>>>>>>> theirs\n"
+        ));
+        assert!(merge.content.contains("priority: critical\n"));
+    }
+
+    #[test]
+    fn test_merge_project_state_counts_tickets_from_both_sides() {
+        let state = |ticket_count, updated_at| ProjectState {
+            name: "project".to_string(),
+            description: None,
+            created_at: Utc::now() - Duration::days(1),
+            updated_at,
+            ticket_count,
+            format_version: FORMAT_VERSION,
+        };
+        let now = Utc::now();
+        let base = serde_yaml::to_string(&state(3, now - Duration::hours(2))).unwrap();
+        let ours = serde_yaml::to_string(&state(5, now - Duration::hours(1))).unwrap();
+        let theirs = serde_yaml::to_string(&state(4, now)).unwrap();
+
+        let merge = merge_yaml(&base, &ours, &theirs).unwrap();
+        let merged: ProjectState = serde_yaml::from_str(&merge.content).unwrap();
+        assert_eq!(merged.ticket_count, 6);
+        assert_eq!(merged.updated_at, now);
+        let merged: ProjectState =
+            serde_yaml::from_str(&merge_yaml("", &ours, &theirs).unwrap().content).unwrap();
+        assert_eq!(merged.ticket_count, 5);
+    }
+
+    #[test]
+    fn test_merge_rejects_other_files() {
+        assert!(merge_yaml("", "just: text", "just: other").is_err());
+    }
+}
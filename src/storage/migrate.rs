@@ -0,0 +1,482 @@
+//! Versioned on-disk format and the migrations between versions
+//!
+//! `state.yaml` records the version of the format the files in
+//! `.vibe-ticket` are written in. Each [`Migration`] upgrades a project from
+//! the version before it, and `vibe-ticket migrate` applies the pending ones
+//! in order, each in its own [`Transaction`], after backing up the
+//! directory to `.vibe-ticket/local/backups/`.
+//!
+//! Writes to a project whose format is newer than [`FORMAT_VERSION`] are
+//! refused, so an outdated binary cannot rewrite files it does not fully
+//! understand.
+
+use super::{FileStorage, Transaction, write_atomic};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use crate::specs::SpecMetadata;
+use crate::specs::review::migrate_legacy_approvals;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Version of the on-disk format this version of vibe-ticket writes
+pub const FORMAT_VERSION: u32 = 2;
+
+/// One step of the on-disk format
+pub struct Migration {
+    /// Version the project is at after this step
+    pub version: u32,
+    /// What the step changes
+    pub description: &'static str,
+    /// Stages the changes of the step
+    stage: fn(&FileStorage, &mut Transaction<'_>) -> Result<()>,
+}
+
+/// Every migration, in the order they are applied
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Move the active tickets into per-worktree state",
+        stage: move_local_state,
+    },
+    Migration {
+        version: 2,
+        description: "Convert spec approvals into reviewer sign-offs",
+        stage: convert_spec_approvals,
+    },
+];
+
+/// A migration step that was applied, or would be in a dry run
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStep {
+    /// Version the step upgrades to
+    pub version: u32,
+    /// What the step changes
+    pub description: String,
+    /// Files written or removed, relative to `.vibe-ticket`
+    pub changed: Vec<PathBuf>,
+}
+
+/// Outcome of [`FileStorage::migrate`]
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    /// Format version before migrating
+    pub from: u32,
+    /// Format version after migrating
+    pub to: u32,
+    /// Whether nothing was written
+    pub dry_run: bool,
+    /// Copy of the project taken before the first step
+    pub backup: Option<PathBuf>,
+    /// Steps applied, oldest first
+    pub steps: Vec<MigrationStep>,
+}
+
+/// The part of `state.yaml` every version can read
+#[derive(Deserialize)]
+struct Versioned {
+    #[serde(default)]
+    format_version: u32,
+}
+
+impl FileStorage {
+    /// Reads the format version of the project from `state.yaml`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project is not initialized or its state
+    /// cannot be read.
+    pub fn format_version(&self) -> Result<u32> {
+        read_format_version(&self.state_path())
+    }
+
+    /// Fails if the project was written by a newer version of vibe-ticket
+    ///
+    /// The version is read once per storage. A project without a readable
+    /// state is left to the commands that need it.
+    pub(crate) fn check_format(&self) -> Result<()> {
+        let found = *self
+            .format
+            .get_or_init(|| self.format_version().unwrap_or_default());
+        ensure_supported(found)
+    }
+
+    /// Upgrades the project to [`FORMAT_VERSION`]
+    ///
+    /// The project is backed up before the first step, and each step is
+    /// applied in a transaction together with the new version in
+    /// `state.yaml`, so an interrupted migration resumes where it stopped.
+    /// A dry run stages every step against the files as they are and
+    /// reports what would change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project is not initialized, was written by a
+    /// newer version, or cannot be backed up or migrated.
+    pub fn migrate(&self, dry_run: bool) -> Result<MigrationReport> {
+        let _lock = self.lock_store("migrate")?;
+        self.recover()?;
+        let from = self.format_version()?;
+        ensure_supported(from)?;
+
+        let pending: Vec<_> = MIGRATIONS.iter().filter(|m| m.version > from).collect();
+        let backup = if dry_run || pending.is_empty() {
+            None
+        } else {
+            Some(self.back_up(from)?)
+        };
+
+        let mut steps = Vec::with_capacity(pending.len());
+        for migration in pending {
+            let mut transaction = self.begin(&format!(
+                "migration to format version {}",
+                migration.version
+            ))?;
+            (migration.stage)(self, &mut transaction)?;
+            let changed = transaction.paths();
+
+            let mut state = self.load_state()?;
+            state.format_version = migration.version;
+            let yaml =
+                serde_yaml::to_string(&state).context("Failed to serialize project state")?;
+            transaction.write(&self.state_path(), yaml);
+            if dry_run {
+                transaction.rollback();
+            } else {
+                transaction.commit()?;
+            }
+
+            steps.push(MigrationStep {
+                version: migration.version,
+                description: migration.description.to_string(),
+                changed,
+            });
+        }
+
+        Ok(MigrationReport {
+            from,
+            to: if dry_run { from } else { FORMAT_VERSION },
+            dry_run,
+            backup,
+            steps,
+        })
+    }
+
+    /// Copies the project files into `local/backups/`, returning the copy
+    fn back_up(&self, version: u32) -> Result<PathBuf> {
+        let backup = self.base_dir().join("local").join("backups").join(format!(
+            "{}-format-{version}",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        copy_dir(self.base_dir(), &backup)?;
+        Ok(backup)
+    }
+}
+
+/// Writes a file of the project holding `path`
+///
+/// Like [`write_atomic`], but refuses to write if the project was written by
+/// a newer version of vibe-ticket. The project is the nearest `.vibe-ticket`
+/// directory above `path`; files outside one, and files of a project
+/// without a `state.yaml` yet, are written without a check. Every file in
+/// `.vibe-ticket` not written through [`FileStorage`] or a [`Transaction`]
+/// is written through here.
+///
+/// # Errors
+///
+/// Returns an error if the project's state cannot be read, its format is
+/// newer than [`FORMAT_VERSION`], or the file cannot be written.
+pub fn write_project_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    let state = path
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.file_name() == Some(OsStr::new(".vibe-ticket")))
+        .map(|root| root.join("state.yaml"));
+    if let Some(state) = state.filter(|state| state.exists()) {
+        ensure_supported(read_format_version(&state)?)?;
+    }
+    write_atomic(path, contents).map_err(|e| VibeTicketError::io_error("write", path, e))
+}
+
+/// Reads the format version from the `state.yaml` at `path`
+fn read_format_version(path: &Path) -> Result<u32> {
+    if !path.exists() {
+        return Err(VibeTicketError::ProjectNotInitialized);
+    }
+    let yaml = fs::read_to_string(path).map_err(|e| VibeTicketError::io_error("read", path, e))?;
+    let versioned: Versioned =
+        serde_yaml::from_str(&yaml).context("Failed to deserialize project state")?;
+    Ok(versioned.format_version)
+}
+
+/// Fails if `found` is newer than the format this version writes
+const fn ensure_supported(found: u32) -> Result<()> {
+    if found > FORMAT_VERSION {
+        return Err(VibeTicketError::UnsupportedFormat {
+            found,
+            supported: FORMAT_VERSION,
+        });
+    }
+    Ok(())
+}
+
+/// Copies `from` into `to`, leaving out per-worktree state and quarantine
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).map_err(|e| VibeTicketError::io_error("create", to, e))?;
+    for entry in fs::read_dir(from).map_err(|e| VibeTicketError::io_error("read", from, e))? {
+        let path = entry
+            .map_err(|e| VibeTicketError::io_error("read", from, e))?
+            .path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let target = to.join(name);
+        if path.is_dir() {
+            if name != "local" && name != "quarantine" {
+                copy_dir(&path, &target)?;
+            }
+        } else {
+            fs::copy(&path, &target).map_err(|e| VibeTicketError::io_error("copy", &path, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Version 1: the active ticket files moved from `.vibe-ticket/` to
+/// `.vibe-ticket/local/`, which git ignores
+fn move_local_state(storage: &FileStorage, transaction: &mut Transaction<'_>) -> Result<()> {
+    for name in ["active_ticket", "active_tickets.yaml"] {
+        let legacy = storage.base_dir().join(name);
+        if !legacy.is_file() {
+            continue;
+        }
+        let local = storage.base_dir().join("local").join(name);
+        // A file already in local state is newer than the one it replaced
+        if !local.exists() {
+            let contents =
+                fs::read(&legacy).map_err(|e| VibeTicketError::io_error("read", &legacy, e))?;
+            transaction.write(&local, contents);
+        }
+        transaction.remove(&legacy);
+    }
+    Ok(())
+}
+
+/// Version 2: untyped approvals in `spec.json` became sign-offs per phase
+///
+/// Specs that do not parse are left for `vibe-ticket doctor`.
+fn convert_spec_approvals(storage: &FileStorage, transaction: &mut Transaction<'_>) -> Result<()> {
+    let specs_dir = storage.base_dir().join("specs");
+    if !specs_dir.is_dir() {
+        return Ok(());
+    }
+    let mut paths: Vec<_> = fs::read_dir(&specs_dir)
+        .map_err(|e| VibeTicketError::io_error("read", &specs_dir, e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path().join("spec.json")))
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let json =
+            fs::read_to_string(&path).map_err(|e| VibeTicketError::io_error("read", &path, e))?;
+        let Ok(mut metadata) = serde_json::from_str::<SpecMetadata>(&json) else {
+            continue;
+        };
+        if migrate_legacy_approvals(&mut metadata) {
+            let json = serde_json::to_string_pretty(&metadata)
+                .context("Failed to serialize specification")?;
+            transaction.write(&path, json);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Ticket, TicketId};
+    use crate::specs::SpecDocumentType;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    /// A project written before the format was versioned
+    fn legacy_project() -> (TempDir, FileStorage) {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("state.yaml"),
+            "name: old\ndescription: null\ncreated_at: 2024-01-01T00:00:00Z\n\
+             updated_at: 2024-01-01T00:00:00Z\nticket_count: 3\n",
+        )
+        .unwrap();
+        let storage = FileStorage::new(dir.path());
+        (dir, storage)
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<_> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<_> = (1..=FORMAT_VERSION).collect();
+        assert_eq!(versions, expected);
+    }
+
+    #[test]
+    fn test_migrate_moves_active_tickets_into_local_state() {
+        let (dir, storage) = legacy_project();
+        let id = TicketId::new();
+        fs::write(dir.path().join("active_ticket"), id.to_string()).unwrap();
+
+        let report = storage.migrate(false).unwrap();
+        assert_eq!((report.from, report.to), (0, FORMAT_VERSION));
+        assert_eq!(
+            report.steps[0].changed,
+            [
+                PathBuf::from("local/active_ticket"),
+                PathBuf::from("active_ticket")
+            ]
+        );
+        assert!(!dir.path().join("active_ticket").exists());
+        assert_eq!(storage.get_active_ticket().unwrap(), Some(id));
+        let state = storage.load_state().unwrap();
+        assert_eq!(state.format_version, FORMAT_VERSION);
+        assert_eq!(state.ticket_count, 3);
+        // The backup holds the project as it was
+        let backup = report.backup.unwrap();
+        assert!(backup.join("active_ticket").exists());
+        assert!(!backup.join("local").exists());
+
+        // Nothing is left to do, so no second backup is taken
+        let report = storage.migrate(false).unwrap();
+        assert!(report.steps.is_empty());
+        assert!(report.backup.is_none());
+    }
+
+    #[test]
+    fn test_migrate_converts_spec_approvals() {
+        let (dir, storage) = legacy_project();
+        let metadata = SpecMetadata::new("Legacy".to_string(), String::new());
+        let mut json = serde_json::to_value(&metadata).unwrap();
+        json["progress"]["approval_status"] =
+            serde_json::json!({"Design": {"approved": true, "message": "LGTM"}});
+        let spec_dir = dir.path().join("specs").join(&metadata.id);
+        fs::create_dir_all(&spec_dir).unwrap();
+        fs::write(spec_dir.join("spec.json"), json.to_string()).unwrap();
+
+        let report = storage.migrate(false).unwrap();
+        assert_eq!(
+            report.steps[1].changed,
+            [PathBuf::from(format!("specs/{}/spec.json", metadata.id))]
+        );
+        let json = fs::read_to_string(spec_dir.join("spec.json")).unwrap();
+        assert!(!json.contains("approval_status"));
+        let migrated: SpecMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            migrated.progress.reviews[&SpecDocumentType::Design].sign_offs[0].reviewer,
+            "unknown"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_changes_nothing() {
+        let (dir, storage) = legacy_project();
+        fs::write(dir.path().join("active_tickets.yaml"), "- abc\n").unwrap();
+
+        let report = storage.migrate(true).unwrap();
+        assert_eq!(report.to, 0);
+        assert_eq!(report.steps.len(), MIGRATIONS.len());
+        assert_eq!(report.steps[0].changed.len(), 2);
+        assert!(report.backup.is_none());
+        assert!(dir.path().join("active_tickets.yaml").exists());
+        assert_eq!(storage.format_version().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_newer_format_refuses_writes() {
+        let (dir, storage) = legacy_project();
+        let state = fs::read_to_string(dir.path().join("state.yaml")).unwrap();
+        fs::write(
+            dir.path().join("state.yaml"),
+            format!(
+                "{state}format_version: {}\nnew_field: 1\n",
+                FORMAT_VERSION + 1
+            ),
+        )
+        .unwrap();
+
+        let error = storage.save(&Ticket::new("login", "Login")).unwrap_err();
+        assert!(matches!(
+            error,
+            VibeTicketError::UnsupportedFormat { found, supported }
+                if found == FORMAT_VERSION + 1 && supported == FORMAT_VERSION
+        ));
+        assert!(storage.begin("test").is_err());
+        assert!(storage.migrate(false).is_err());
+        assert!(!dir.path().join("tickets").read_dir().unwrap().any(|_| true));
+    }
+
+    #[test]
+    fn test_project_file_writes_check_only_their_project() {
+        let dir = TempDir::new().unwrap();
+        let newer = format!("format_version: {}\n", FORMAT_VERSION + 1);
+        fs::write(dir.path().join("state.yaml"), &newer).unwrap();
+
+        // A state above the project, or outside any project, is not used
+        let base_dir = dir.path().join(".vibe-ticket");
+        fs::create_dir_all(base_dir.join("specs")).unwrap();
+        write_project_file(base_dir.join("specs/tasks.md"), "").unwrap();
+        write_project_file(dir.path().join("config.yaml"), "").unwrap();
+
+        // An unreadable state refuses the write
+        fs::write(base_dir.join("state.yaml"), "format_version: [").unwrap();
+        assert!(write_project_file(base_dir.join("filters.yaml"), "").is_err());
+        assert!(!base_dir.join("filters.yaml").exists());
+    }
+
+    #[test]
+    fn test_newer_format_refuses_spec_and_side_file_writes() {
+        use crate::cli::handlers::{Hooks, SavedFilters};
+        use crate::specs::SpecManager;
+
+        let dir = TempDir::new().unwrap();
+        let base_dir = dir.path().join(".vibe-ticket");
+        let storage = FileStorage::new(&base_dir);
+        storage.ensure_directories().unwrap();
+        let manager = SpecManager::new(base_dir.join("specs"));
+        let spec_id = manager
+            .create_spec("Auth".to_string(), String::new())
+            .unwrap()
+            .id;
+        fs::write(
+            base_dir.join("state.yaml"),
+            format!("format_version: {}\n", FORMAT_VERSION + 1),
+        )
+        .unwrap();
+
+        let refused = |result: Result<()>| {
+            assert!(matches!(
+                result,
+                Err(VibeTicketError::UnsupportedFormat { .. })
+            ));
+        };
+        refused(manager.save_document(&spec_id, SpecDocumentType::Tasks, "- [ ] T001 Login\n"));
+        refused(
+            manager
+                .create_spec("Other".to_string(), String::new())
+                .map(drop),
+        );
+        refused(manager.set_active_spec(&spec_id));
+        let root = dir.path().to_str();
+        refused(SavedFilters::default().save(root));
+        refused(Hooks::default().save(root));
+        refused(crate::config::Config::default().save_to_path(base_dir.join("config.yaml")));
+        refused(storage.set_active_ticket(&TicketId::new()));
+
+        let spec_dir = manager.get_spec_dir(&spec_id);
+        assert!(!spec_dir.join("tasks.md").exists());
+        for file in ["filters.yaml", "hooks.yaml", "config.yaml", ".active_spec"] {
+            assert!(!base_dir.join(file).exists(), "{file} was written");
+        }
+        assert_eq!(manager.list_specs().unwrap().len(), 1);
+    }
+}
@@ -11,6 +11,26 @@
 //! - `PostgreSQL` (for production deployments) - feature gated
 //! - In-memory (for testing)
 //!
+//! Archived tickets and specifications live in a separate, gzip-compressed
+//! [`ArchiveStore`] under `.vibe-ticket/archive/` and are not part of
+//! regular loads.
+//!
+//! Changes to many tickets at once go through a [`Transaction`], which
+//! journals them in `.vibe-ticket/local/journal/` and applies all or none
+//! of them, even across a crash.
+//!
+//! Summaries of all tickets are kept in an index in
+//! `.vibe-ticket/local/index.json`, updated from the ticket files changed
+//! since it was written ([`FileStorage::load_summaries`]).
+//!
+//! `state.yaml` records the version of the on-disk format; [`MIGRATIONS`]
+//! upgrade older projects, and projects written in a newer format are never
+//! written to (see [`FileStorage::migrate`] and [`write_project_file`]).
+//!
+//! Ticket files are merged field by field when branches are merged in git
+//! (see [`merge`]); per-worktree state such as the active ticket lives in
+//! `.vibe-ticket/local/`, which git ignores.
+//!
 //! # Architecture
 //!
 //! The storage layer implements the repository pattern:
@@ -21,13 +41,19 @@
 //!
 //! # Concurrent Access Protection
 //!
-//! The file storage implementation includes built-in protection against concurrent
-//! modifications through a file-based locking mechanism:
+//! The file storage implementation protects against concurrent modifications
+//! with operating system advisory locks (`flock` on Unix):
 //!
-//! - **Automatic Locking**: All write operations acquire exclusive locks
-//! - **Lock Files**: Created as `<filename>.lock` with metadata
-//! - **Retry Logic**: Operations retry up to 10 times with 100ms delays
-//! - **Stale Lock Cleanup**: Locks older than 30 seconds are removed automatically
+//! - **Read/Write Locks**: Loading a ticket takes a shared lock, saving or
+//!   deleting it an exclusive one, so readers never block each other
+//! - **Store Lock**: Operations on many tickets, such as `bulk` and `import`,
+//!   hold [`FileStorage::lock_store`] so single-ticket writes wait for them
+//! - **Lock Files**: Kept in `.vibe-ticket/local/locks/`, with the process ID
+//!   and operation of an exclusive holder for diagnostics
+//! - **Timeouts**: Waiting for a lock gives up after 30 seconds, or
+//!   `VIBE_TICKET_LOCK_TIMEOUT` seconds
+//! - **No Stale Locks**: The operating system releases the locks of a process
+//!   that exits, however it exits
 //! - **RAII Pattern**: Locks are released automatically using Rust's Drop trait
 //!
 //! This ensures data integrity even when multiple users or processes access
@@ -54,10 +80,25 @@
 //! - Permission errors
 //! - Lock acquisition failures
 
+pub mod archive;
+mod atomic;
 mod file;
+mod index;
 mod lock;
+pub mod merge;
+mod migrate;
 mod repository;
+mod transaction;
 
+pub use archive::ArchiveStore;
+pub use atomic::{is_temp_file, write_atomic};
 pub use file::{FileStorage, ProjectState};
-pub use lock::{FileLock, LockGuard};
+pub use index::TicketSummary;
+pub use lock::{FileLock, LockGuard, LockMode};
+pub(crate) use lock::{LOCK_TIMEOUT, LockInfo};
+pub use merge::{Merge, merge_yaml};
+pub use migrate::{
+    FORMAT_VERSION, MIGRATIONS, Migration, MigrationReport, MigrationStep, write_project_file,
+};
 pub use repository::{ActiveTicketRepository, Repository, TicketRepository};
+pub use transaction::{Recovery, Transaction, TransactionReport};
@@ -0,0 +1,546 @@
+//! All-or-nothing changes to many tickets
+//!
+//! A [`Transaction`] stages ticket saves and deletes, and writes to other
+//! project files, in memory. Committing writes the new contents to a journal
+//! in `.vibe-ticket/local/journal/`, marks the journal complete with an
+//! atomic write of its manifest and only then changes the files themselves.
+//!
+//! - If changing a file fails, the files already changed are restored and
+//!   the journal is discarded.
+//! - If the process dies while changing files, the next run finishes the
+//!   job from the journal ([`FileStorage::recover`]).
+//! - A journal whose manifest was never written is discarded, leaving every
+//!   file as it was.
+
+use super::{FileLock, FileStorage, write_atomic};
+use crate::core::{Ticket, TicketId};
+use crate::error::{ErrorContext, Result, VibeTicketError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest that marks a journal complete
+const MANIFEST: &str = "journal.yaml";
+
+/// A staged change to one file
+struct Change {
+    /// Path relative to the `.vibe-ticket` directory, or absolute outside it
+    path: PathBuf,
+    /// New contents, or `None` to delete the file
+    contents: Option<Vec<u8>>,
+    /// Ticket stored in the file, if it is a ticket file
+    ticket: Option<TicketId>,
+}
+
+/// Manifest of a committed journal
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    /// Description of the operation, e.g. `bulk close`
+    operation: String,
+    /// When the transaction was committed
+    committed_at: DateTime<Utc>,
+    /// Changes in the order they are applied
+    changes: Vec<JournalEntry>,
+}
+
+/// One change in a journal
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    /// Path relative to the `.vibe-ticket` directory, or absolute outside it
+    path: PathBuf,
+    /// Journal file holding the new contents; `None` deletes the file
+    contents: Option<String>,
+}
+
+/// What a committed transaction changed
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct TransactionReport {
+    /// IDs of the tickets saved
+    pub saved: Vec<TicketId>,
+    /// IDs of the tickets deleted
+    pub deleted: Vec<TicketId>,
+    /// Other files written or removed, relative to `.vibe-ticket`
+    pub files: Vec<PathBuf>,
+}
+
+/// An interrupted transaction found by [`FileStorage::recover`]
+#[derive(Debug, Clone, Serialize)]
+pub struct Recovery {
+    /// Description of the interrupted operation, if its journal was complete
+    pub operation: Option<String>,
+    /// Number of files changed to finish the transaction; zero if it was
+    /// discarded
+    pub applied: usize,
+}
+
+/// Changes to many tickets that are applied all together or not at all
+///
+/// Holds the store lock from [`FileStorage::begin`] until it is committed
+/// or dropped, so no other writer sees or changes the tickets in between.
+/// Dropping a transaction without committing it discards its changes.
+pub struct Transaction<'a> {
+    storage: &'a FileStorage,
+    operation: String,
+    changes: Vec<Change>,
+    _lock: FileLock,
+}
+
+impl FileStorage {
+    /// Starts a transaction, locking the store until it ends
+    ///
+    /// An interrupted transaction of an earlier run is finished first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project was written by a newer version, the
+    /// store cannot be locked or an interrupted transaction cannot be
+    /// recovered.
+    pub fn begin(&self, operation: &str) -> Result<Transaction<'_>> {
+        self.check_format()?;
+        let lock = self.lock_store(operation)?;
+        self.recover()?;
+        Ok(Transaction {
+            storage: self,
+            operation: operation.to_string(),
+            changes: Vec::new(),
+            _lock: lock,
+        })
+    }
+
+    /// Finishes or discards a transaction interrupted by a crash
+    ///
+    /// Returns `None` if there was none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project was written by a newer version, or
+    /// the journal cannot be read or its changes cannot be applied; the
+    /// journal is kept for the next attempt.
+    pub fn recover(&self) -> Result<Option<Recovery>> {
+        let dir = self.journal_dir();
+        if !dir.exists() {
+            return Ok(None);
+        }
+        self.check_format()?;
+        // A transaction still committing holds the lock
+        let _lock = self.lock_store("recovering an interrupted transaction")?;
+        if !dir.exists() {
+            return Ok(None);
+        }
+
+        let manifest = dir.join(MANIFEST);
+        let recovery = if manifest.exists() {
+            let yaml = fs::read_to_string(&manifest)
+                .map_err(|e| VibeTicketError::io_error("read", &manifest, e))?;
+            let journal: Journal = serde_yaml::from_str(&yaml)
+                .with_context(|| format!("Failed to parse journal {}", manifest.display()))?;
+            for entry in &journal.changes {
+                let contents = entry
+                    .contents
+                    .as_ref()
+                    .map(|name| {
+                        let path = dir.join(name);
+                        fs::read(&path).map_err(|e| VibeTicketError::io_error("read", &path, e))
+                    })
+                    .transpose()?;
+                self.apply(&entry.path, contents.as_deref())?;
+            }
+            self.cache.invalidate_all();
+            Recovery {
+                operation: Some(journal.operation),
+                applied: journal.changes.len(),
+            }
+        } else {
+            Recovery {
+                operation: None,
+                applied: 0,
+            }
+        };
+
+        fs::remove_dir_all(&dir).map_err(|e| VibeTicketError::io_error("remove", &dir, e))?;
+        Ok(Some(recovery))
+    }
+
+    /// Writes or deletes one file of a transaction
+    fn apply(&self, path: &Path, contents: Option<&[u8]>) -> Result<()> {
+        let path = self.base_dir().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| VibeTicketError::io_error("create", parent, e))?;
+        }
+        match contents {
+            Some(contents) => write_atomic(&path, contents)
+                .map_err(|e| VibeTicketError::io_error("write", &path, e)),
+            None => match fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    Err(VibeTicketError::io_error("delete", &path, e))
+                },
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+impl Transaction<'_> {
+    /// Stages saving a ticket
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket cannot be serialized.
+    pub fn save(&mut self, ticket: &Ticket) -> Result<()> {
+        let yaml = FileStorage::ticket_yaml(ticket)?;
+        self.stage(Change {
+            path: self.relative(&self.storage.ticket_path(&ticket.id)),
+            contents: Some(yaml.into_bytes()),
+            ticket: Some(ticket.id.clone()),
+        });
+        Ok(())
+    }
+
+    /// Stages deleting a ticket
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ticket neither exists nor is staged for saving.
+    pub fn delete(&mut self, id: &TicketId) -> Result<()> {
+        let path = self.storage.ticket_path(id);
+        let relative = self.relative(&path);
+        let staged = self
+            .changes
+            .iter()
+            .any(|change| change.path == relative && change.contents.is_some());
+        if !staged && !path.exists() {
+            return Err(VibeTicketError::TicketNotFound { id: id.to_string() });
+        }
+        self.stage(Change {
+            path: relative,
+            contents: None,
+            ticket: Some(id.clone()),
+        });
+        Ok(())
+    }
+
+    /// Stages writing another file of the project, such as an archive entry
+    pub fn write(&mut self, path: &Path, contents: impl Into<Vec<u8>>) {
+        self.stage(Change {
+            path: self.relative(path),
+            contents: Some(contents.into()),
+            ticket: None,
+        });
+    }
+
+    /// Stages removing another file of the project
+    pub fn remove(&mut self, path: &Path) {
+        self.stage(Change {
+            path: self.relative(path),
+            contents: None,
+            ticket: None,
+        });
+    }
+
+    /// Paths of the staged changes, relative to `.vibe-ticket`
+    #[must_use]
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.changes
+            .iter()
+            .map(|change| change.path.clone())
+            .collect()
+    }
+
+    /// Number of staged changes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Whether nothing is staged
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Discards the staged changes and releases the store lock
+    pub fn rollback(self) {
+        drop(self);
+    }
+
+    /// Applies all staged changes, or none of them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal cannot be written or a change cannot
+    /// be applied. Files changed before the failure are restored; if even
+    /// that fails, the journal is kept and the next run completes the
+    /// transaction instead.
+    pub fn commit(self) -> Result<TransactionReport> {
+        let mut report = TransactionReport::default();
+        if self.changes.is_empty() {
+            return Ok(report);
+        }
+
+        let storage = self.storage;
+        let dir = storage.journal_dir();
+        self.write_journal(&dir)?;
+
+        let mut originals = Vec::with_capacity(self.changes.len());
+        for change in &self.changes {
+            let path = storage.base_dir().join(&change.path);
+            let original = match fs::read(&path) {
+                Ok(contents) => Some(contents),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    let error = VibeTicketError::io_error("read", &path, e);
+                    return Err(self.undo(&dir, &originals, error));
+                },
+            };
+            let result = self.apply_change(change);
+            originals.push((change, original));
+            if let Err(error) = result {
+                return Err(self.undo(&dir, &originals, error));
+            }
+        }
+
+        fs::remove_dir_all(&dir).map_err(|e| VibeTicketError::io_error("remove", &dir, e))?;
+        for change in &self.changes {
+            match (&change.ticket, &change.contents) {
+                (Some(id), Some(_)) => report.saved.push(id.clone()),
+                (Some(id), None) => report.deleted.push(id.clone()),
+                (None, _) => report.files.push(change.path.clone()),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Replaces an earlier change to the same file
+    fn stage(&mut self, change: Change) {
+        self.changes.retain(|staged| staged.path != change.path);
+        self.changes.push(change);
+    }
+
+    /// Path as recorded in the journal
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(self.storage.base_dir())
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+
+    /// Writes the new contents, then the manifest that marks them complete
+    fn write_journal(&self, dir: &Path) -> Result<()> {
+        if dir.exists() {
+            fs::remove_dir_all(dir).map_err(|e| VibeTicketError::io_error("remove", dir, e))?;
+        }
+        fs::create_dir_all(dir).map_err(|e| VibeTicketError::io_error("create", dir, e))?;
+
+        let mut changes = Vec::with_capacity(self.changes.len());
+        for (index, change) in self.changes.iter().enumerate() {
+            let name = change.contents.as_ref().map(|_| format!("{index}.new"));
+            if let (Some(name), Some(contents)) = (&name, &change.contents) {
+                let path = dir.join(name);
+                write_atomic(&path, contents)
+                    .map_err(|e| self.abandon(dir, VibeTicketError::io_error("write", &path, e)))?;
+            }
+            changes.push(JournalEntry {
+                path: change.path.clone(),
+                contents: name,
+            });
+        }
+
+        let journal = Journal {
+            operation: self.operation.clone(),
+            committed_at: Utc::now(),
+            changes,
+        };
+        let yaml = serde_yaml::to_string(&journal).map_err(|e| self.abandon(dir, e.into()))?;
+        let manifest = dir.join(MANIFEST);
+        write_atomic(&manifest, yaml)
+            .map_err(|e| self.abandon(dir, VibeTicketError::io_error("write", &manifest, e)))
+    }
+
+    /// Applies one change under the lock of the ticket it touches
+    fn apply_change(&self, change: &Change) -> Result<()> {
+        let _locks = change
+            .ticket
+            .as_ref()
+            .map(|id| self.storage.lock_ticket_for_write(id, &self.operation))
+            .transpose()?;
+        let result = self.storage.apply(&change.path, change.contents.as_deref());
+        if let Some(id) = &change.ticket {
+            self.storage.cache.invalidate_ticket(id);
+        }
+        result
+    }
+
+    /// Restores the files changed so far after `error`, then discards the journal
+    fn undo(
+        &self,
+        dir: &Path,
+        originals: &[(&Change, Option<Vec<u8>>)],
+        error: VibeTicketError,
+    ) -> VibeTicketError {
+        for (change, original) in originals.iter().rev() {
+            let restored = Change {
+                path: change.path.clone(),
+                contents: original.clone(),
+                ticket: change.ticket.clone(),
+            };
+            if let Err(undo_error) = self.apply_change(&restored) {
+                return VibeTicketError::custom(format!(
+                    "{} failed: {error}; restoring {} also failed ({undo_error}), \
+                     the next command will complete the {}",
+                    self.operation,
+                    change.path.display(),
+                    self.operation
+                ));
+            }
+        }
+        self.abandon(dir, error)
+    }
+
+    /// Discards the journal after `error`, so nothing is applied later
+    fn abandon(&self, dir: &Path, error: VibeTicketError) -> VibeTicketError {
+        let _ = fs::remove_dir_all(dir);
+        VibeTicketError::custom(format!(
+            "{} failed, no changes were made: {error}",
+            self.operation
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TicketRepository;
+    use tempfile::TempDir;
+
+    fn storage_with_tickets(titles: &[&str]) -> (TempDir, FileStorage, Vec<Ticket>) {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path());
+        let tickets: Vec<_> = titles
+            .iter()
+            .map(|title| {
+                let ticket = Ticket::new(title.to_lowercase(), *title);
+                storage.save(&ticket).unwrap();
+                ticket
+            })
+            .collect();
+        (dir, storage, tickets)
+    }
+
+    #[test]
+    fn test_commit_applies_all_changes() {
+        let (_dir, storage, tickets) = storage_with_tickets(&["Login", "Logout"]);
+        let mut tx = storage.begin("test").unwrap();
+        let mut login = tickets[0].clone();
+        login.title = "Fix login".to_string();
+        tx.save(&login).unwrap();
+        tx.delete(&tickets[1].id).unwrap();
+        let added = Ticket::new("signup", "Signup");
+        tx.save(&added).unwrap();
+        tx.write(&storage.base_dir().join("notes.txt"), "done");
+        assert_eq!(tx.len(), 4);
+        // Nothing changes before the commit
+        assert_eq!(storage.load(&tickets[0].id).unwrap().title, "Login");
+
+        let report = tx.commit().unwrap();
+        assert_eq!(report.saved, [login.id.clone(), added.id.clone()]);
+        assert_eq!(report.deleted, [tickets[1].id.clone()]);
+        assert_eq!(report.files, [PathBuf::from("notes.txt")]);
+        assert_eq!(storage.load(&login.id).unwrap().title, "Fix login");
+        assert!(!storage.exists(&tickets[1].id).unwrap());
+        assert!(storage.exists(&added.id).unwrap());
+        assert!(!storage.journal_dir().exists());
+    }
+
+    #[test]
+    fn test_failed_commit_restores_every_file() {
+        let (dir, storage, tickets) = storage_with_tickets(&["Login", "Logout"]);
+        let mut tx = storage.begin("test").unwrap();
+        for ticket in &tickets {
+            let mut changed = ticket.clone();
+            changed.title = format!("Changed {}", ticket.title);
+            tx.save(&changed).unwrap();
+        }
+        // Writing over a non-empty directory fails after the tickets changed
+        let blocked = dir.path().join("blocked");
+        fs::create_dir_all(blocked.join("inner")).unwrap();
+        tx.write(&blocked, "fails");
+
+        let error = tx.commit().unwrap_err();
+        assert!(
+            error.to_string().contains("no changes were made"),
+            "{error}"
+        );
+        let titles: Vec<_> = tickets
+            .iter()
+            .map(|ticket| storage.load(&ticket.id).unwrap().title)
+            .collect();
+        assert_eq!(titles, ["Login", "Logout"]);
+        assert!(!storage.journal_dir().exists());
+    }
+
+    #[test]
+    fn test_dropped_transaction_changes_nothing() {
+        let (_dir, storage, tickets) = storage_with_tickets(&["Login"]);
+        let mut tx = storage.begin("test").unwrap();
+        tx.delete(&tickets[0].id).unwrap();
+        assert!(tx.delete(&TicketId::new()).is_err());
+        tx.rollback();
+
+        assert!(storage.exists(&tickets[0].id).unwrap());
+        // The store lock was released
+        storage.begin("again").unwrap().commit().unwrap();
+    }
+
+    #[test]
+    fn test_recover_finishes_complete_journal_and_discards_partial_one() {
+        let (_dir, storage, tickets) = storage_with_tickets(&["Login", "Logout"]);
+        let mut changed = tickets[0].clone();
+        changed.title = "Fix login".to_string();
+
+        // A crash right after the manifest was written
+        let journal_dir = storage.journal_dir();
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(
+            journal_dir.join("0.new"),
+            serde_yaml::to_string(&changed).unwrap(),
+        )
+        .unwrap();
+        let journal = Journal {
+            operation: "bulk update".to_string(),
+            committed_at: Utc::now(),
+            changes: vec![
+                JournalEntry {
+                    path: PathBuf::from(format!("tickets/{}.yaml", changed.id)),
+                    contents: Some("0.new".to_string()),
+                },
+                JournalEntry {
+                    path: PathBuf::from(format!("tickets/{}.yaml", tickets[1].id)),
+                    contents: None,
+                },
+            ],
+        };
+        fs::write(
+            journal_dir.join(MANIFEST),
+            serde_yaml::to_string(&journal).unwrap(),
+        )
+        .unwrap();
+
+        let recovery = storage.recover().unwrap().unwrap();
+        assert_eq!(recovery.operation.as_deref(), Some("bulk update"));
+        assert_eq!(recovery.applied, 2);
+        assert_eq!(storage.load(&changed.id).unwrap().title, "Fix login");
+        assert!(!storage.exists(&tickets[1].id).unwrap());
+        assert!(storage.recover().unwrap().is_none());
+
+        // A crash before the manifest was written
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("0.new"), "title: Lost").unwrap();
+        let recovery = storage.recover().unwrap().unwrap();
+        assert_eq!(recovery.operation, None);
+        assert_eq!(recovery.applied, 0);
+        assert_eq!(storage.load(&changed.id).unwrap().title, "Fix login");
+        assert!(!journal_dir.exists());
+    }
+}
@@ -108,8 +108,9 @@ impl TestProject {
     ///
     /// Panics if writing the active ticket file fails
     pub fn set_active(&self, ticket_id: &TicketId) {
-        let active_path = self.tickets_dir.join("active_ticket");
-        std::fs::write(active_path, ticket_id.to_string()).expect("Failed to set active ticket");
+        self.storage
+            .set_active_ticket(ticket_id)
+            .expect("Failed to set active ticket");
     }
 }
 
@@ -135,6 +136,7 @@ pub fn create_test_ticket(title: &str, priority: Priority, status: Status) -> Ti
         } else {
             None
         },
+        updated_at: None,
         assignee: None,
         tasks: vec![],
         metadata: HashMap::new(),
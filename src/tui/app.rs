@@ -0,0 +1,583 @@
+//! State of the terminal UI and how it responds to keys
+//!
+//! [`App`] holds the tickets loaded from [`FileStorage`], the selection on the
+//! board and the saved filters. It is independent of the terminal, so it can
+//! be driven by key events in tests.
+
+use crate::cli::handlers::{SavedFilter, SavedFilters};
+use crate::config::Config;
+use crate::core::{Status, Ticket, TicketId};
+use crate::error::{Result, VibeTicketError};
+use crate::events::TicketWatcher;
+use crate::specs::TaskSync;
+use crate::storage::{FileStorage, TicketRepository};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What the keyboard currently drives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    /// Moving between columns and tickets
+    Board,
+    /// Moving between and toggling the tasks of the selected ticket
+    Tasks,
+    /// Choosing a saved filter in the filter bar
+    Filter,
+}
+
+/// State of the terminal UI
+pub struct App {
+    storage: FileStorage,
+    vibe_ticket_dir: PathBuf,
+    project_name: String,
+    columns: Vec<Status>,
+    tickets: Vec<Ticket>,
+    filters: Vec<SavedFilter>,
+    filter: Option<usize>,
+    filter_choice: Option<usize>,
+    column: usize,
+    rows: Vec<usize>,
+    task: usize,
+    detail: bool,
+    focus: Focus,
+    message: Option<String>,
+    watcher: TicketWatcher,
+    filters_modified: Option<SystemTime>,
+    quit: bool,
+}
+
+impl App {
+    /// Load the board of the project at `project_root`
+    pub fn new(project_root: &Path) -> Result<Self> {
+        let vibe_ticket_dir = project_root.join(".vibe-ticket");
+        let project_name = Config::load_from_path(vibe_ticket_dir.join("config.yaml"))
+            .map(|config| config.project.name)
+            .unwrap_or_default();
+        let columns = Status::all();
+        let storage = FileStorage::new(&vibe_ticket_dir);
+        let watcher = TicketWatcher::new(&storage)?;
+        let mut app = Self {
+            storage,
+            vibe_ticket_dir,
+            project_name,
+            rows: vec![0; columns.len()],
+            columns,
+            tickets: Vec::new(),
+            filters: Vec::new(),
+            filter: None,
+            filter_choice: None,
+            column: 0,
+            task: 0,
+            detail: false,
+            focus: Focus::Board,
+            message: None,
+            watcher,
+            filters_modified: None,
+            quit: false,
+        };
+        app.load()?;
+        Ok(app)
+    }
+
+    /// Apply the saved filter called `name`
+    pub fn select_filter(&mut self, name: &str) -> Result<()> {
+        let index = self
+            .filters
+            .iter()
+            .position(|filter| filter.name == name)
+            .ok_or_else(|| VibeTicketError::custom(format!("Filter '{name}' not found")))?;
+        self.apply_filter(Some(index));
+        Ok(())
+    }
+
+    /// Name of the project shown in the header
+    #[must_use]
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    /// Statuses shown as columns, in board order
+    #[must_use]
+    pub fn columns(&self) -> &[Status] {
+        &self.columns
+    }
+
+    /// Index of the selected column
+    #[must_use]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Index of the selected ticket in `column`
+    #[must_use]
+    pub fn row(&self, column: usize) -> usize {
+        self.rows[column]
+    }
+
+    /// Tickets shown in `column`, most urgent first
+    #[must_use]
+    pub fn column_tickets(&self, column: usize) -> Vec<&Ticket> {
+        let filter = self.filter.map(|index| &self.filters[index]);
+        let mut tickets: Vec<&Ticket> = self
+            .tickets
+            .iter()
+            .filter(|ticket| ticket.status == self.columns[column])
+            .filter(|ticket| filter.is_none_or(|filter| filter.matches(ticket)))
+            .collect();
+        tickets.sort_by(|a, b| {
+            b.priority
+                .value()
+                .cmp(&a.priority.value())
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        tickets
+    }
+
+    /// The selected ticket
+    #[must_use]
+    pub fn selected(&self) -> Option<&Ticket> {
+        self.column_tickets(self.column)
+            .get(self.rows[self.column])
+            .copied()
+    }
+
+    /// Whether the detail pane is open
+    #[must_use]
+    pub const fn detail(&self) -> bool {
+        self.detail
+    }
+
+    /// What the keyboard currently drives
+    #[must_use]
+    pub const fn focus(&self) -> Focus {
+        self.focus
+    }
+
+    /// Index of the selected task in the detail pane
+    #[must_use]
+    pub const fn task(&self) -> usize {
+        self.task
+    }
+
+    /// Saved filters, by name
+    #[must_use]
+    pub fn filters(&self) -> &[SavedFilter] {
+        &self.filters
+    }
+
+    /// The applied filter
+    #[must_use]
+    pub fn filter(&self) -> Option<&SavedFilter> {
+        self.filter.map(|index| &self.filters[index])
+    }
+
+    /// The filter highlighted in the filter bar
+    #[must_use]
+    pub const fn filter_choice(&self) -> Option<usize> {
+        self.filter_choice
+    }
+
+    /// Result of the last action, shown in the footer
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Whether the user asked to quit
+    #[must_use]
+    pub const fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Respond to a key press
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        self.message = None;
+        let result = match self.focus {
+            Focus::Board => self.board_key(key),
+            Focus::Tasks => self.tasks_key(key),
+            Focus::Filter => {
+                self.filter_key(key);
+                Ok(())
+            },
+        };
+        if let Err(error) = result {
+            self.message = Some(error.user_message());
+        }
+    }
+
+    /// Reload the board if ticket or filter files changed on disk
+    pub fn refresh_if_changed(&mut self) {
+        let tickets_changed = match self.watcher.poll() {
+            Ok(changes) => !changes.is_empty(),
+            Err(error) => {
+                self.message = Some(error.user_message());
+                false
+            },
+        };
+        if !tickets_changed && self.filters_modified() == self.filters_modified {
+            return;
+        }
+        if let Err(error) = self.load() {
+            self.message = Some(error.user_message());
+        }
+    }
+
+    fn board_key(&mut self, key: KeyEvent) -> Result<()> {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('H' | '<') => self.move_ticket(false)?,
+            KeyCode::Char('L' | '>') => self.move_ticket(true)?,
+            KeyCode::Left if shift => self.move_ticket(false)?,
+            KeyCode::Right if shift => self.move_ticket(true)?,
+            KeyCode::Left | KeyCode::Char('h') => self.select_column(self.column.saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => self.select_column(self.column + 1),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_row(self.rows[self.column].checked_sub(1));
+            },
+            KeyCode::Down | KeyCode::Char('j') => self.select_row(Some(self.rows[self.column] + 1)),
+            KeyCode::Enter => {
+                self.detail = !self.detail && self.selected().is_some();
+                self.task = 0;
+            },
+            KeyCode::Tab if self.detail => self.focus = Focus::Tasks,
+            KeyCode::Esc => self.detail = false,
+            KeyCode::Char('/' | 'f') => {
+                self.filter_choice = self.filter;
+                self.focus = Focus::Filter;
+            },
+            KeyCode::Char('r') => {
+                self.load()?;
+                self.message = Some("Reloaded".to_string());
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    fn tasks_key(&mut self, key: KeyEvent) -> Result<()> {
+        let tasks = self.selected().map_or(0, |ticket| ticket.tasks.len());
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.task = self.task.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.task + 1 < tasks => self.task += 1,
+            KeyCode::Char(' ' | 'x') => self.toggle_task()?,
+            KeyCode::Tab | KeyCode::Esc => self.focus = Focus::Board,
+            KeyCode::Enter => {
+                self.detail = false;
+                self.focus = Focus::Board;
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    fn filter_key(&mut self, key: KeyEvent) {
+        // Slot 0 is "all tickets", slot i + 1 is filter i
+        let slots = self.filters.len() + 1;
+        let slot = self.filter_choice.map_or(0, |index| index + 1);
+        let slot = match key.code {
+            KeyCode::Left | KeyCode::Up | KeyCode::Char('h' | 'k') | KeyCode::BackTab => {
+                (slot + slots - 1) % slots
+            },
+            KeyCode::Right | KeyCode::Down | KeyCode::Char('l' | 'j') | KeyCode::Tab => {
+                (slot + 1) % slots
+            },
+            KeyCode::Enter => {
+                self.apply_filter(self.filter_choice);
+                self.focus = Focus::Board;
+                return;
+            },
+            KeyCode::Esc => {
+                self.focus = Focus::Board;
+                return;
+            },
+            _ => slot,
+        };
+        self.filter_choice = slot.checked_sub(1);
+    }
+
+    fn apply_filter(&mut self, filter: Option<usize>) {
+        self.filter = filter;
+        self.rows.fill(0);
+        self.task = 0;
+        self.message = Some(self.filter().map_or_else(
+            || "Showing all tickets".to_string(),
+            |filter| format!("Filter: @{}", filter.name),
+        ));
+    }
+
+    fn select_column(&mut self, column: usize) {
+        self.column = column.min(self.columns.len() - 1);
+        self.task = 0;
+    }
+
+    fn select_row(&mut self, row: Option<usize>) {
+        let len = self.column_tickets(self.column).len();
+        if let Some(row) = row.filter(|row| *row < len) {
+            self.rows[self.column] = row;
+            self.task = 0;
+        }
+    }
+
+    /// Move the selected ticket to the previous or next column
+    fn move_ticket(&mut self, forward: bool) -> Result<()> {
+        let column = if forward {
+            self.column + 1
+        } else {
+            self.column.wrapping_sub(1)
+        };
+        let (Some(ticket), Some(&status)) = (self.selected(), self.columns.get(column)) else {
+            return Ok(());
+        };
+        let mut ticket = ticket.clone();
+        let old_status = ticket.status;
+        TaskSync::new(&self.vibe_ticket_dir).set_status(&mut ticket, status)?;
+        self.storage.save(&ticket)?;
+        self.load()?;
+        self.select_ticket(&ticket.id);
+        self.message = Some(format!("{}: {old_status} → {status}", ticket.slug));
+        Ok(())
+    }
+
+    /// Toggle completion of the selected task
+    fn toggle_task(&mut self) -> Result<()> {
+        let Some(mut ticket) = self.selected().cloned() else {
+            return Ok(());
+        };
+        let Some(task) = ticket.tasks.get_mut(self.task) else {
+            return Ok(());
+        };
+        if task.completed {
+            task.uncomplete();
+        } else {
+            task.complete();
+        }
+        // Saved through the same path as status changes, so a spec task left
+        // out of sync by an earlier edit catches up
+        let status = ticket.status;
+        TaskSync::new(&self.vibe_ticket_dir).set_status(&mut ticket, status)?;
+        self.storage.save(&ticket)?;
+        self.load()?;
+        self.select_ticket(&ticket.id);
+        Ok(())
+    }
+
+    /// Select `id` on the board, if it is shown
+    fn select_ticket(&mut self, id: &TicketId) {
+        for column in 0..self.columns.len() {
+            if let Some(row) = self
+                .column_tickets(column)
+                .iter()
+                .position(|ticket| &ticket.id == id)
+            {
+                self.column = column;
+                self.rows[column] = row;
+                return;
+            }
+        }
+    }
+
+    /// Load tickets and saved filters, keeping the selection where possible
+    fn load(&mut self) -> Result<()> {
+        let selected = self.selected().map(|ticket| ticket.id.clone());
+        let filter = self.filter().map(|filter| filter.name.clone());
+        let project_root = self.vibe_ticket_dir.parent().map(Path::to_string_lossy);
+
+        self.filters_modified = self.filters_modified();
+        self.storage.cache.invalidate_all();
+        self.tickets = self.storage.load_all()?;
+        self.filters = SavedFilters::load(project_root.as_deref())?
+            .filters
+            .into_values()
+            .collect();
+        self.filters.sort_by(|a, b| a.name.cmp(&b.name));
+        self.filter = filter.and_then(|name| self.filters.iter().position(|f| f.name == name));
+
+        for column in 0..self.columns.len() {
+            let len = self.column_tickets(column).len();
+            self.rows[column] = self.rows[column].min(len.saturating_sub(1));
+        }
+        if let Some(id) = selected {
+            self.select_ticket(&id);
+        }
+        let tasks = self.selected().map_or(0, |ticket| ticket.tasks.len());
+        self.task = self.task.min(tasks.saturating_sub(1));
+        if self.selected().is_none() {
+            self.detail = false;
+            self.focus = Focus::Board;
+        }
+        Ok(())
+    }
+
+    fn filters_modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(self.vibe_ticket_dir.join("filters.yaml"))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Priority;
+    use tempfile::TempDir;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn project() -> (TempDir, FileStorage) {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+
+        let mut login = Ticket::new("fix-login", "Fix login");
+        login.priority = Priority::High;
+        login.add_task("Reproduce");
+        login.add_task("Patch");
+        storage.save(&login).unwrap();
+        let mut docs = Ticket::new("write-docs", "Write docs");
+        docs.tags = vec!["docs".to_string()];
+        storage.save(&docs).unwrap();
+        (dir, storage)
+    }
+
+    #[test]
+    fn test_move_ticket_between_columns() {
+        let (dir, storage) = project();
+        let mut app = App::new(dir.path()).unwrap();
+        assert_eq!(app.column_tickets(0).len(), 2);
+        assert_eq!(app.selected().unwrap().slug, "fix-login");
+
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(app.selected().unwrap().slug, "write-docs");
+        app.handle_key(key(KeyCode::Char('L')));
+
+        assert_eq!(app.column(), 1);
+        assert_eq!(app.selected().unwrap().slug, "write-docs");
+        let saved = storage.find_ticket_by_slug("write-docs").unwrap().unwrap();
+        assert_eq!(saved.status, Status::Doing);
+        assert!(saved.started_at.is_some());
+
+        // Moving left of the first column does nothing
+        app.handle_key(key(KeyCode::Char('h')));
+        app.handle_key(key(KeyCode::Char('H')));
+        assert_eq!(app.column(), 0);
+        assert_eq!(app.column_tickets(0).len(), 1);
+    }
+
+    #[test]
+    fn test_move_ticket_syncs_spec_task() {
+        use crate::specs::{SpecDocumentType, SpecManager};
+
+        let dir = TempDir::new().unwrap();
+        let vibe_ticket_dir = dir.path().join(".vibe-ticket");
+        FileStorage::new(&vibe_ticket_dir)
+            .ensure_directories()
+            .unwrap();
+        let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+        let spec_id = manager
+            .create_spec("Auth".to_string(), String::new())
+            .unwrap()
+            .id;
+        manager
+            .save_document(&spec_id, SpecDocumentType::Tasks, "- [ ] T001 Login\n")
+            .unwrap();
+        TaskSync::new(&vibe_ticket_dir)
+            .export(&manager.load_spec(&spec_id).unwrap())
+            .unwrap();
+        let tasks_md = || {
+            std::fs::read_to_string(manager.get_document_path(&spec_id, SpecDocumentType::Tasks))
+                .unwrap()
+        };
+
+        let mut app = App::new(dir.path()).unwrap();
+        app.handle_key(key(KeyCode::Char('L')));
+        app.handle_key(key(KeyCode::Char('L')));
+        assert_eq!(app.selected().unwrap().status, Status::Done);
+        assert_eq!(tasks_md(), "- [x] T001 Login\n");
+
+        app.handle_key(key(KeyCode::Char('H')));
+        assert_eq!(tasks_md(), "- [ ] T001 Login\n");
+    }
+
+    #[test]
+    fn test_toggle_task_in_detail_pane() {
+        let (dir, storage) = project();
+        let mut app = App::new(dir.path()).unwrap();
+
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.detail());
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.focus(), Focus::Tasks);
+        app.handle_key(key(KeyCode::Char('j')));
+        app.handle_key(key(KeyCode::Char(' ')));
+
+        let saved = storage.find_ticket_by_slug("fix-login").unwrap().unwrap();
+        assert!(!saved.tasks[0].completed);
+        assert!(saved.tasks[1].completed);
+        assert_eq!(app.selected().unwrap().completed_tasks_count(), 1);
+
+        app.handle_key(key(KeyCode::Char(' ')));
+        storage.cache.invalidate_all();
+        let saved = storage.find_ticket_by_slug("fix-login").unwrap().unwrap();
+        assert!(!saved.tasks[1].completed);
+    }
+
+    #[test]
+    fn test_saved_filter_bar() {
+        let (dir, _storage) = project();
+        let project_dir = dir.path().to_string_lossy();
+        let mut filters = SavedFilters::default();
+        filters.add(SavedFilter {
+            name: "docs".to_string(),
+            expression: "tag:docs".to_string(),
+            description: None,
+            created_at: chrono::Utc::now(),
+        });
+        filters.save(Some(&project_dir)).unwrap();
+        let mut app = App::new(dir.path()).unwrap();
+
+        app.handle_key(key(KeyCode::Char('/')));
+        assert_eq!(app.focus(), Focus::Filter);
+        app.handle_key(key(KeyCode::Right));
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.filter().unwrap().name, "docs");
+        assert_eq!(app.column_tickets(0).len(), 1);
+        assert_eq!(app.selected().unwrap().slug, "write-docs");
+
+        app.handle_key(key(KeyCode::Char('/')));
+        app.handle_key(key(KeyCode::Right));
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.filter().is_none());
+        assert!(app.select_filter("missing").is_err());
+    }
+
+    #[test]
+    fn test_refresh_when_files_change() {
+        let (dir, storage) = project();
+        let mut app = App::new(dir.path()).unwrap();
+        app.handle_key(key(KeyCode::Char('j')));
+
+        let mut urgent = Ticket::new("outage", "Outage");
+        urgent.priority = Priority::Critical;
+        storage.save(&urgent).unwrap();
+        for _ in 0..50 {
+            app.refresh_if_changed();
+            if app.column_tickets(0).len() == 3 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        assert_eq!(app.column_tickets(0).len(), 3);
+        // The selection stays on the same ticket
+        assert_eq!(app.selected().unwrap().slug, "write-docs");
+    }
+}
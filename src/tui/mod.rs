@@ -0,0 +1,66 @@
+//! Full-screen terminal UI for triaging tickets
+//!
+//! `vibe-ticket tui` shows a kanban board with one column per status. Tickets
+//! can be moved between columns, their tasks toggled in a detail pane and the
+//! board narrowed with saved filters. The board reloads when ticket or filter
+//! files change on disk, so edits made from another terminal show up without
+//! restarting.
+//!
+//! # Keys
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | `←` `→` / `h` `l` | Select column |
+//! | `↑` `↓` / `k` `j` | Select ticket |
+//! | `H` `L` / `<` `>` / `Shift+←` `Shift+→` | Move ticket to the previous or next column |
+//! | `Enter` | Open or close the detail pane |
+//! | `Tab` | Switch between the board and the tasks in the detail pane |
+//! | `Space` / `x` | Toggle the selected task |
+//! | `/` / `f` | Choose a saved filter |
+//! | `r` | Reload |
+//! | `q` / `Ctrl+C` | Quit |
+
+pub mod app;
+mod ui;
+
+pub use app::{App, Focus};
+
+use crate::error::Result;
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::path::Path;
+use std::time::Duration;
+
+/// How long to wait for a key before checking for changes on disk
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Run the terminal UI for the project at `project_root` until the user quits
+///
+/// `filter` names a saved filter to apply on start.
+pub fn run(project_root: &Path, filter: Option<&str>) -> Result<()> {
+    let mut app = App::new(project_root)?;
+    if let Some(name) = filter {
+        app.select_filter(name)?;
+    }
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::try_restore()?;
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if event::poll(POLL_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key);
+                }
+            }
+        }
+        app.refresh_if_changed();
+    }
+    Ok(())
+}
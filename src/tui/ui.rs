@@ -0,0 +1,322 @@
+//! Rendering of the terminal UI
+//!
+//! Draws the header with the filter bar, one column per status, the detail
+//! pane of the selected ticket and a footer with key hints.
+
+use super::app::{App, Focus};
+use crate::core::Ticket;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, BorderType, List, ListItem, ListState, Paragraph, Wrap};
+
+/// Draw the whole screen
+pub fn draw(frame: &mut Frame<'_>, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    draw_header(frame, app, header);
+    if app.detail() {
+        let [board, detail] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(body);
+        draw_board(frame, app, board);
+        draw_detail(frame, app, detail);
+    } else {
+        draw_board(frame, app, body);
+    }
+    draw_footer(frame, app, footer);
+}
+
+/// Terminal color for a color name used by `Status` and `Priority`
+fn color(name: &str) -> Color {
+    name.parse().unwrap_or(Color::Reset)
+}
+
+fn draw_header(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let mut spans = vec![
+        Span::from(format!(" {} ", app.project_name()))
+            .bold()
+            .reversed(),
+        Span::from("  Filter: "),
+    ];
+    if app.focus() == Focus::Filter {
+        let choice = app.filter_choice();
+        let names = std::iter::once((None, "all".to_string())).chain(
+            app.filters()
+                .iter()
+                .enumerate()
+                .map(|(index, filter)| (Some(index), format!("@{}", filter.name))),
+        );
+        for (index, name) in names {
+            let span = Span::from(format!(" {name} "));
+            spans.push(if index == choice {
+                span.reversed()
+            } else {
+                span
+            });
+        }
+    } else {
+        spans.push(app.filter().map_or_else(
+            || Span::from("all").dim(),
+            |filter| Span::from(format!("@{} ({})", filter.name, filter.expression)),
+        ));
+    }
+    frame.render_widget(Line::from(spans), area);
+}
+
+fn draw_board(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let columns = app.columns();
+    let constraints = columns
+        .iter()
+        .map(|_| Constraint::Ratio(1, u32::try_from(columns.len()).unwrap_or(1)));
+    let areas = Layout::horizontal(constraints).split(area);
+
+    for (index, status) in columns.iter().enumerate() {
+        let tickets = app.column_tickets(index);
+        let selected = index == app.column();
+        let mut block = Block::bordered()
+            .title(format!(
+                " {} {} ({}) ",
+                status.emoji(),
+                status,
+                tickets.len()
+            ))
+            .border_style(Style::new().fg(color(status.color())));
+        if selected && app.focus() == Focus::Board {
+            block = block
+                .border_type(BorderType::Thick)
+                .title_style(Modifier::BOLD);
+        }
+
+        let width = usize::from(areas[index].width.saturating_sub(2));
+        let items: Vec<ListItem<'_>> = tickets
+            .iter()
+            .map(|ticket| ticket_item(ticket, width))
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let mut state = ListState::default();
+        if selected {
+            state.select(Some(app.row(index)));
+        }
+        frame.render_stateful_widget(list, areas[index], &mut state);
+    }
+}
+
+/// Lines of a ticket title shown on its card
+const TITLE_LINES: usize = 2;
+
+/// Word-wrap `text` to `width` columns, ending with `…` if it needs more
+/// than `max_lines`
+fn wrap(text: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let joined = if line.is_empty() {
+            word.to_string()
+        } else {
+            format!("{line} {word}")
+        };
+        if Span::raw(joined.as_str()).width() <= width || line.is_empty() {
+            line = joined;
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+        }
+    }
+    lines.push(line);
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = &mut lines[max_lines - 1];
+        while !last.is_empty() && Span::raw(last.as_str()).width() + 1 > width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+/// A ticket card: the title wrapped to the column, then slug, tasks and assignee
+fn ticket_item(ticket: &Ticket, width: usize) -> ListItem<'_> {
+    let title = format!("{} {}", ticket.priority.emoji(), ticket.title);
+    let mut lines: Vec<Line<'_>> = wrap(&title, width, TITLE_LINES)
+        .into_iter()
+        .map(Line::from)
+        .collect();
+
+    let mut meta = vec![Span::from(ticket.slug.clone()).dim()];
+    if !ticket.tasks.is_empty() {
+        meta.push(Span::from(format!(
+            " ✓{}/{}",
+            ticket.completed_tasks_count(),
+            ticket.total_tasks_count()
+        )));
+    }
+    if let Some(assignee) = &ticket.assignee {
+        meta.push(Span::from(format!(" @{assignee}")).fg(Color::Cyan));
+    }
+    lines.push(Line::from(meta));
+    ListItem::new(Text::from(lines))
+}
+
+fn draw_detail(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let Some(ticket) = app.selected() else {
+        return;
+    };
+    let block = Block::bordered().title(format!(" {} ", ticket.slug));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let label = |text: &'static str| Span::from(text).bold();
+    let mut lines = vec![
+        Line::from(ticket.title.clone()).bold(),
+        Line::from(vec![
+            label("Status:   "),
+            Span::from(format!("{} {}", ticket.status.emoji(), ticket.status))
+                .fg(color(ticket.status.color())),
+        ]),
+        Line::from(vec![
+            label("Priority: "),
+            Span::from(format!("{} {}", ticket.priority.emoji(), ticket.priority))
+                .fg(color(ticket.priority.color())),
+        ]),
+    ];
+    if let Some(assignee) = &ticket.assignee {
+        lines.push(Line::from(vec![
+            label("Assignee: "),
+            Span::from(assignee.clone()),
+        ]));
+    }
+    if !ticket.tags.is_empty() {
+        lines.push(Line::from(vec![
+            label("Tags:     "),
+            Span::from(ticket.tags.join(", ")),
+        ]));
+    }
+    if !ticket.description.is_empty() {
+        lines.push(Line::default());
+        lines.extend(
+            ticket
+                .description
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+    }
+
+    let [info, tasks] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(u16::try_from(ticket.tasks.len() + 2).unwrap_or(u16::MAX)),
+    ])
+    .areas(inner);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), info);
+
+    let items: Vec<ListItem<'_>> = ticket
+        .tasks
+        .iter()
+        .map(|task| {
+            let mark = if task.completed { "[x]" } else { "[ ]" };
+            let item = ListItem::new(format!("{mark} {}", task.title));
+            if task.completed { item.dim() } else { item }
+        })
+        .collect();
+    let title = format!(
+        " Tasks {}/{} ",
+        ticket.completed_tasks_count(),
+        ticket.total_tasks_count()
+    );
+    let mut block = Block::bordered().title(title);
+    if app.focus() == Focus::Tasks {
+        block = block.border_type(BorderType::Thick);
+    }
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default();
+    if app.focus() == Focus::Tasks {
+        state.select(Some(app.task()));
+    }
+    frame.render_stateful_widget(list, tasks, &mut state);
+}
+
+fn draw_footer(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let line = app.message().map_or_else(
+        || {
+            let hints = match app.focus() {
+                Focus::Board => {
+                    "←→/hl column  ↑↓/jk ticket  H/L move  enter details  tab tasks  / filter  r reload  q quit"
+                },
+                Focus::Tasks => "↑↓/jk task  space toggle  tab board  enter close  q quit",
+                Focus::Filter => "←→ choose  enter apply  esc cancel",
+            };
+            Line::from(hints).dim()
+        },
+        |message| Line::from(message.to_string()).fg(Color::Yellow),
+    );
+    frame.render_widget(line, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{FileStorage, TicketRepository};
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use tempfile::TempDir;
+
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, app)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(120)
+            .map(|row| {
+                row.iter()
+                    .map(ratatui::buffer::Cell::symbol)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("fix the login", 8, 2), vec!["fix the", "login"]);
+        assert_eq!(wrap("one two three four", 7, 2), vec!["one two", "three…"]);
+        assert_eq!(wrap("", 5, 2), vec![""]);
+    }
+
+    #[test]
+    fn test_draw_board_and_detail() {
+        let dir = TempDir::new().unwrap();
+        let storage = FileStorage::new(dir.path().join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        let mut ticket = Ticket::new("fix-login", "Fix the login crash on slow networks");
+        ticket.add_task("Reproduce");
+        storage.save(&ticket).unwrap();
+        let mut app = App::new(dir.path()).unwrap();
+
+        let screen = render(&app);
+        assert!(screen.contains("Todo (1)"));
+        assert!(screen.contains("Review (0)"));
+        // Long titles wrap within the column instead of being cut off
+        assert!(screen.contains("Fix the login"));
+        assert!(screen.contains("networks"));
+        assert!(screen.contains("✓0/1"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let screen = render(&app);
+        assert!(screen.contains("Tasks 0/1"));
+        assert!(screen.contains("[ ] Reproduce"));
+    }
+}
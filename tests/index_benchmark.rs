@@ -0,0 +1,160 @@
+//! Benchmarks of the ticket index with 10,000 tickets
+//!
+//! Times loading every ticket file against loading the index, cold and warm,
+//! and the `list`, `board` and `check` commands and a slug lookup on a warm
+//! index. The benchmark is ignored by default because it is slow in debug
+//! builds; run it with
+//! `cargo test --release --test index_benchmark -- --ignored --nocapture`
+//! to see the timings. The assertions only check results, not timings.
+
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+use tempfile::TempDir;
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{handle_board_command, handle_check_command, handle_list_command};
+use vibe_ticket::core::{Priority, Status, Task, Ticket};
+use vibe_ticket::storage::{FORMAT_VERSION, FileStorage, ProjectState};
+
+const TICKETS: usize = 10_000;
+
+/// Writes a project with `TICKETS` tickets last changed an hour ago, as
+/// after a fresh checkout
+fn project_with_tickets() -> (TempDir, FileStorage, Vec<Ticket>) {
+    let dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = dir.path().join(".vibe-ticket");
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    storage.ensure_directories().unwrap();
+    storage
+        .save_state(&ProjectState {
+            name: "Benchmark".to_string(),
+            description: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            ticket_count: TICKETS as u64,
+            format_version: FORMAT_VERSION,
+        })
+        .unwrap();
+
+    let statuses = [
+        Status::Todo,
+        Status::Doing,
+        Status::Review,
+        Status::Blocked,
+        Status::Done,
+    ];
+    let priorities = [
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+        Priority::Critical,
+    ];
+    let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+    let tickets: Vec<_> = (0..TICKETS)
+        .map(|i| {
+            let mut ticket = Ticket::new(format!("ticket-{i:05}"), format!("Ticket number {i}"));
+            ticket.description = "A longer description of the work to do. ".repeat(10);
+            ticket.status = statuses[i % statuses.len()];
+            ticket.priority = priorities[i % priorities.len()];
+            ticket.tags = vec!["bench".to_string(), format!("group-{}", i % 20)];
+            ticket.assignee = Some(format!("dev-{}", i % 7));
+            ticket.tasks = (0..3).map(|t| Task::new(format!("Task {t}"))).collect();
+            let path = vibe_ticket_dir
+                .join("tickets")
+                .join(format!("{}.yaml", ticket.id));
+            fs::write(&path, serde_yaml::to_string(&ticket).unwrap()).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(an_hour_ago)
+                .unwrap();
+            ticket
+        })
+        .collect();
+    (dir, storage, tickets)
+}
+
+/// Runs `f`, printing how long it took
+fn time<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    println!("{label:<40} {:>12.2?}", start.elapsed());
+    result
+}
+
+fn root(dir: &Path) -> &str {
+    dir.to_str().unwrap()
+}
+
+#[test]
+#[ignore = "slow; run with `cargo test --release -- --ignored`"]
+fn bench_index_with_10k_tickets() {
+    let (dir, storage, tickets) = project_with_tickets();
+    let base_dir = dir.path().join(".vibe-ticket");
+    let fresh = || FileStorage::new(&base_dir);
+
+    let all = time("parse every ticket file", || {
+        fresh().load_all_tickets().unwrap()
+    });
+    assert_eq!(all.len(), TICKETS);
+    let summaries = time("build the index", || fresh().load_summaries().unwrap());
+    assert_eq!(summaries.len(), TICKETS);
+    let summaries = time("load the index", || fresh().load_summaries().unwrap());
+    assert_eq!(summaries.len(), TICKETS);
+
+    let last = &tickets[TICKETS - 1];
+    let id = time("find a ticket by slug", || {
+        fresh().find_ticket_id_by_slug(&last.slug).unwrap()
+    });
+    assert_eq!(id, Some(last.id.clone()));
+
+    // Commands on the warm index
+    let output = OutputFormatter::capture();
+    time("list --limit 20", || {
+        handle_list_command(
+            None,
+            None,
+            None,
+            "priority",
+            false,
+            Some(20),
+            false,
+            false,
+            None,
+            None,
+            false,
+            Some(root(dir.path())),
+            &output,
+        )
+        .unwrap();
+    });
+    let listed = output.take_captured().json;
+    assert_eq!(listed[0]["count"], 20);
+    time("board", || {
+        handle_board_command(None, false, true, Some(root(dir.path())), &output).unwrap();
+    });
+    let board = output.take_captured().json;
+    let on_board: usize = board[0]
+        .as_object()
+        .unwrap()
+        .values()
+        .map(|column| column.as_array().unwrap().len())
+        .sum();
+    assert_eq!(on_board, TICKETS);
+    time("check --stats", || {
+        handle_check_command(true, true, Some(root(dir.path())), &output).unwrap();
+    });
+    let check = output.take_captured().json;
+    assert_eq!(check[0]["statistics"]["total"], TICKETS);
+
+    // A changed ticket is picked up without a rebuild
+    let mut changed = tickets[0].clone();
+    changed.status = Status::Done;
+    storage.save_ticket(&changed).unwrap();
+    let summaries = time("load the index after one change", || {
+        fresh().load_summaries().unwrap()
+    });
+    let summary = summaries.iter().find(|s| s.id == changed.id).unwrap();
+    assert_eq!(summary.status, Status::Done);
+}
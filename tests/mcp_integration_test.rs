@@ -2,6 +2,7 @@
 
 #[cfg(feature = "mcp")]
 mod mcp_tests {
+    use serde_json::json;
     use serial_test::serial;
     use std::time::Duration;
     use tempfile::TempDir;
@@ -10,6 +11,7 @@ mod mcp_tests {
         cli::{OutputFormatter, handlers::handle_new_command},
         core::{Priority, Status},
         integration::IntegrationEvent,
+        mcp::service::VibeTicketService,
         storage::FileStorage,
     };
 
@@ -32,6 +34,7 @@ mod mcp_tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             ticket_count: 0,
+            format_version: vibe_ticket::storage::FORMAT_VERSION,
         };
         storage.save_state(&state).unwrap();
         storage.ensure_directories().unwrap();
@@ -94,6 +97,7 @@ mod mcp_tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             ticket_count: 0,
+            format_version: vibe_ticket::storage::FORMAT_VERSION,
         };
         storage.save_state(&state).unwrap();
         storage.ensure_directories().unwrap();
@@ -152,4 +156,323 @@ mod mcp_tests {
             Err(e) => panic!("Failed to receive second event: {e:?}"),
         }
     }
+
+    /// Initialize a project in `dir` and return its storage
+    fn init_project(dir: &std::path::Path) -> FileStorage {
+        let storage = FileStorage::new(dir.join(".vibe-ticket"));
+        storage.ensure_directories().unwrap();
+        storage
+            .save_state(&vibe_ticket::storage::ProjectState {
+                name: "Test Project".to_string(),
+                description: None,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+                ticket_count: 0,
+                format_version: vibe_ticket::storage::FORMAT_VERSION,
+            })
+            .unwrap();
+        storage
+    }
+
+    /// Restores the working directory when dropped
+    struct CwdGuard(std::path::PathBuf);
+
+    impl CwdGuard {
+        fn enter(dir: &std::path::Path) -> Self {
+            let previous = std::env::current_dir().unwrap();
+            std::env::set_current_dir(dir).unwrap();
+            Self(previous)
+        }
+    }
+
+    impl Drop for CwdGuard {
+        fn drop(&mut self) {
+            let _ = std::env::set_current_dir(&self.0);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_spec_tools_use_project_root_from_other_cwd() {
+        let project = TempDir::new().unwrap();
+        let elsewhere = TempDir::new().unwrap();
+        let storage = init_project(project.path());
+        let service = VibeTicketService::new(storage, project.path().to_path_buf());
+        let _cwd = CwdGuard::enter(elsewhere.path());
+
+        let created = service
+            .call(
+                "vibe-ticket_spec_specify",
+                json!({ "requirements": "Audit logging\nRecord every login" }),
+            )
+            .unwrap();
+        let spec_id = created["spec_id"].as_str().unwrap().to_string();
+        let spec_dir = project
+            .path()
+            .join(".vibe-ticket")
+            .join("specs")
+            .join(&spec_id);
+        assert!(spec_dir.join("spec.json").exists());
+        assert!(!elsewhere.path().join(".vibe-ticket").exists());
+
+        for tool in [
+            "vibe-ticket_spec_plan",
+            "vibe-ticket_spec_generate_tasks",
+            "vibe-ticket_spec_validate",
+            "vibe-ticket_spec_progress",
+        ] {
+            service
+                .call(tool, json!({ "spec": spec_id }))
+                .unwrap_or_else(|e| panic!("{tool} failed: {e}"));
+        }
+        assert!(spec_dir.join("plan.md").exists());
+        assert!(spec_dir.join("tasks.md").exists());
+    }
+
+    #[test]
+    #[serial]
+    fn test_project_argument_selects_project() {
+        let workspace = TempDir::new().unwrap();
+        let main = workspace.path().join("main");
+        let other = workspace.path().join("other");
+        let main_storage = init_project(&main);
+        let other_storage = init_project(&other);
+        let service = VibeTicketService::new(main_storage.clone(), main);
+        let _cwd = CwdGuard::enter(workspace.path());
+
+        service
+            .call(
+                "vibe-ticket_new",
+                json!({ "slug": "absolute", "title": "Absolute", "project": other.to_str() }),
+            )
+            .unwrap();
+        service
+            .call(
+                "vibe-ticket_new",
+                json!({ "slug": "relative", "title": "Relative", "project": "../other" }),
+            )
+            .unwrap();
+        service
+            .call("vibe-ticket_new", json!({ "slug": "own", "title": "Own" }))
+            .unwrap();
+
+        let slugs = |storage: &FileStorage| {
+            let mut slugs: Vec<_> = storage
+                .load_all_tickets()
+                .unwrap()
+                .into_iter()
+                .map(|ticket| ticket.slug)
+                .collect();
+            slugs.sort();
+            slugs
+        };
+        assert_eq!(slugs(&other_storage), vec!["absolute", "relative"]);
+        assert_eq!(slugs(&main_storage), vec!["own"]);
+
+        let error = service
+            .call(
+                "vibe-ticket_list",
+                json!({ "project": workspace.path().join("missing").to_str() }),
+            )
+            .unwrap_err();
+        assert!(error.contains("No vibe-ticket project"));
+    }
+
+    #[test]
+    fn test_every_tool_accepts_project() {
+        for tool in VibeTicketService::get_tools() {
+            assert!(
+                tool.input_schema["properties"].get("project").is_some(),
+                "{} has no project argument",
+                tool.name
+            );
+        }
+    }
+
+    /// Paths of every runnable CLI command, e.g. `bulk update`
+    fn cli_commands(command: &clap::Command, prefix: &str, paths: &mut Vec<String>) {
+        for sub in command.get_subcommands() {
+            let path = format!("{prefix}{}", sub.get_name());
+            if sub.has_subcommands() {
+                cli_commands(sub, &format!("{path} "), paths);
+            } else {
+                paths.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_command_has_a_tool_or_exclusion() {
+        use clap::CommandFactory;
+        use vibe_ticket::mcp::handlers::cli::{EXCLUDED_COMMANDS, RENAMED_COMMANDS, tool_name};
+
+        let mut commands = Vec::new();
+        cli_commands(&vibe_ticket::cli::Cli::command(), "", &mut commands);
+        let tools: Vec<String> = VibeTicketService::get_tools()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+        let mut unique = tools.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), tools.len(), "duplicate tool names");
+
+        let excluded = |command: &str| {
+            EXCLUDED_COMMANDS.iter().any(|(excluded, _)| {
+                command == *excluded || command.starts_with(&format!("{excluded} "))
+            })
+        };
+        let missing: Vec<_> = commands
+            .iter()
+            .filter(|command| !excluded(command))
+            .filter(|command| {
+                let name = RENAMED_COMMANDS
+                    .iter()
+                    .find(|(renamed, _)| renamed == command)
+                    .map_or_else(
+                        || tool_name(&command.split(' ').collect::<Vec<_>>()),
+                        |(_, tool)| (*tool).to_string(),
+                    );
+                !tools.contains(&name)
+            })
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "commands without an MCP tool or entry in EXCLUDED_COMMANDS: {missing:?}"
+        );
+
+        for (excluded, _) in EXCLUDED_COMMANDS.iter().chain(RENAMED_COMMANDS) {
+            assert!(
+                commands
+                    .iter()
+                    .any(|command| command == excluded
+                        || command.starts_with(&format!("{excluded} "))),
+                "`{excluded}` is not a command"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cli_tool_schemas_match_command_arguments() {
+        use clap::CommandFactory;
+        use vibe_ticket::mcp::handlers::cli::cli_tools;
+
+        let cli = vibe_ticket::cli::Cli::command();
+        let globals = [
+            "verbose", "json", "no_color", "project", "set", "help", "version",
+        ];
+        for tool in cli_tools() {
+            let command = tool
+                .command
+                .iter()
+                .try_fold(&cli, |command, name| command.find_subcommand(name))
+                .unwrap_or_else(|| panic!("{} is not a command", tool.name()));
+            let mut arguments: Vec<String> = command
+                .get_arguments()
+                .map(|arg| arg.get_id().to_string())
+                .filter(|id| !globals.contains(&id.as_str()))
+                .collect();
+            arguments.sort();
+            let schema = tool.input_schema();
+            let mut properties: Vec<String> = schema["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+            properties.sort();
+            assert_eq!(
+                properties,
+                arguments,
+                "{} drifted from the CLI",
+                tool.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cli_tools_run_commands() {
+        let project = TempDir::new().unwrap();
+        let storage = init_project(project.path());
+        let service = VibeTicketService::new(storage, project.path().to_path_buf());
+
+        service
+            .call(
+                "vibe-ticket_new",
+                json!({ "slug": "login", "title": "Login" }),
+            )
+            .unwrap();
+        let reviewed = service
+            .call("vibe-ticket_review", json!({ "ticket": "login" }))
+            .unwrap();
+        assert_eq!(reviewed["status"], "success");
+        service
+            .call(
+                "vibe-ticket_handoff",
+                json!({ "ticket": "login", "assignee": "reviewer-bot" }),
+            )
+            .unwrap();
+
+        // The service sees what the command wrote
+        let shown = service
+            .call("vibe-ticket_show", json!({ "ticket": "login" }))
+            .unwrap();
+        assert_eq!(shown["status"], "review");
+        assert_eq!(shown["assignee"], "reviewer-bot");
+
+        service
+            .call(
+                "vibe-ticket_filter_create",
+                json!({ "name": "in-review", "expression": "status:review" }),
+            )
+            .unwrap();
+        let filters = service.call("vibe-ticket_filter_list", json!({})).unwrap();
+        assert!(filters.to_string().contains("in-review"));
+
+        let error = service
+            .call("vibe-ticket_request_changes", json!({ "ticket": "login" }))
+            .unwrap_err();
+        assert!(error.starts_with("Invalid arguments"), "{error}");
+    }
+
+    #[test]
+    #[serial]
+    fn test_cli_spec_tools_leave_cwd_alone() {
+        let elsewhere = TempDir::new().unwrap();
+        let _cwd = CwdGuard::enter(elsewhere.path());
+        let cwd = std::env::current_dir().unwrap();
+
+        let projects: Vec<_> = ["Audit logging", "Rate limits"]
+            .into_iter()
+            .map(|title| {
+                let dir = TempDir::new().unwrap();
+                let service =
+                    VibeTicketService::new(init_project(dir.path()), dir.path().to_path_buf());
+                let created = service
+                    .call("vibe-ticket_spec_specify", json!({ "requirements": title }))
+                    .unwrap();
+                let spec_id = created["spec_id"].as_str().unwrap().to_string();
+                (dir, service, spec_id)
+            })
+            .collect();
+
+        for (_dir, service, spec_id) in &projects {
+            service
+                .call("vibe-ticket_spec_activate", json!({ "spec": spec_id }))
+                .unwrap();
+            let listed = service.call("vibe-ticket_spec_list", json!({})).unwrap();
+            assert!(listed.to_string().contains(spec_id.as_str()));
+            service.call("vibe-ticket_spec_status", json!({})).unwrap();
+            service
+                .call("vibe-ticket_spec_show", json!({ "spec": spec_id }))
+                .unwrap();
+            assert_eq!(std::env::current_dir().unwrap(), cwd);
+        }
+        let listed = projects[0]
+            .1
+            .call("vibe-ticket_spec_list", json!({}))
+            .unwrap();
+        assert!(!listed.to_string().contains(projects[1].2.as_str()));
+        assert!(!elsewhere.path().join(".vibe-ticket").exists());
+    }
 }
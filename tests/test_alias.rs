@@ -0,0 +1,122 @@
+//! Integration tests for command aliases
+//!
+//! Runs the binary with project and user aliases, checking that aliases are
+//! expanded in-process with quoting, placeholders and chaining.
+
+// Alias placeholders look like format arguments
+#![allow(clippy::literal_string_with_formatting_args)]
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+struct Project {
+    dir: TempDir,
+    config_home: TempDir,
+}
+
+impl Project {
+    fn new() -> Self {
+        let project = Self {
+            dir: TempDir::new().unwrap(),
+            config_home: TempDir::new().unwrap(),
+        };
+        project.run(&["init", "--name", "aliases"]).success();
+        project
+    }
+
+    #[allow(deprecated)]
+    fn run(&self, args: &[&str]) -> assert_cmd::assert::Assert {
+        Command::cargo_bin("vibe-ticket")
+            .unwrap()
+            .current_dir(self.dir.path())
+            .env("XDG_CONFIG_HOME", self.config_home.path())
+            .args(args)
+            .assert()
+    }
+
+    fn write_user_aliases(&self, yaml: &str) {
+        let dir = self.config_home.path().join("vibe-ticket");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("aliases.yaml"), yaml).unwrap();
+    }
+}
+
+#[test]
+fn test_alias_expands_in_process() {
+    let project = Project::new();
+    project
+        .run(&[
+            "alias",
+            "create",
+            "fix",
+            r#"new "$1" --title "Fix ${what:-the bug}""#,
+        ])
+        .success();
+
+    project.run(&["fix", "login"]).success();
+    project
+        .run(&["fix", "crash", "the crash on start", "--start"])
+        .success();
+    project
+        .run(&["--json", "list"])
+        .success()
+        .stdout(predicate::str::contains("Fix the crash on start"))
+        .stdout(predicate::str::contains("Fix the bug"));
+
+    // `${ticket:-active}` falls back to the active ticket
+    project
+        .run(&["alias", "create", "current", "show ${ticket:-active}"])
+        .success();
+    project
+        .run(&["--json", "current"])
+        .success()
+        .stdout(predicate::str::contains("Fix the crash on start"));
+    project
+        .run(&["--json", "alias", "run", "current"])
+        .success()
+        .stdout(predicate::str::contains("Fix the crash on start"));
+}
+
+#[test]
+fn test_user_aliases_chain_and_cycles() {
+    let project = Project::new();
+    project.write_user_aliases(
+        "aliases:
+  todo:
+    name: todo
+    command: list --status todo
+    description: null
+    created_at: 2025-01-01T00:00:00Z
+  ping:
+    name: ping
+    command: pong
+    description: null
+    created_at: 2025-01-01T00:00:00Z
+",
+    );
+    project
+        .run(&["new", "docs", "--title", "Write docs"])
+        .success();
+    project.run(&["alias", "create", "pong", "ping"]).success();
+    project
+        .run(&["alias", "create", "todo-json", "todo --json"])
+        .success();
+
+    project
+        .run(&["todo-json"])
+        .success()
+        .stdout(predicate::str::contains("Write docs"));
+    project
+        .run(&["alias", "list"])
+        .success()
+        .stdout(predicate::str::contains("todo (user)"));
+    project
+        .run(&["ping"])
+        .failure()
+        .stderr(predicate::str::contains("ping -> pong -> ping"));
+    project
+        .run(&["alias", "delete", "todo"])
+        .failure()
+        .stderr(predicate::str::contains("user alias"));
+}
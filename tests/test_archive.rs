@@ -0,0 +1,143 @@
+//! Integration tests for the archive store
+//!
+//! Checks that archived tickets and specs leave the regular stores, are
+//! kept compressed under `.vibe-ticket/archive/`, and can be restored.
+
+use chrono::{Duration, Utc};
+use std::path::Path;
+use tempfile::TempDir;
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{ArchiveParams, handle_archive_command, handle_search_command};
+use vibe_ticket::core::{Status, Ticket};
+use vibe_ticket::specs::SpecManager;
+use vibe_ticket::storage::{ArchiveStore, FileStorage, TicketRepository};
+
+fn params(target: Option<&str>) -> ArchiveParams {
+    ArchiveParams {
+        target: target.map(str::to_string),
+        unarchive: false,
+        spec: false,
+        older_than: None,
+        status: None,
+        dry_run: false,
+    }
+}
+
+fn ticket(slug: &str, status: Status, age_days: i64) -> Ticket {
+    let mut ticket = Ticket::new(slug, slug.replace('-', " "));
+    ticket.status = status;
+    ticket.created_at = Utc::now() - Duration::days(age_days + 1);
+    if status == Status::Done {
+        ticket.closed_at = Some(Utc::now() - Duration::days(age_days));
+    }
+    ticket.updated_at = ticket.closed_at.or(Some(ticket.created_at));
+    ticket
+}
+
+/// Write a ticket as last saved at its `updated_at`, which a save through
+/// the storage would stamp with the current time
+fn save_dated(vibe_ticket_dir: &Path, ticket: &Ticket) {
+    let tickets_dir = vibe_ticket_dir.join("tickets");
+    std::fs::create_dir_all(&tickets_dir).unwrap();
+    std::fs::write(
+        tickets_dir.join(format!("{}.yaml", ticket.id)),
+        serde_yaml::to_string(ticket).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_archive_old_done_tickets_and_restore() {
+    let temp_dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    let project = temp_dir.path().to_str();
+    let formatter = OutputFormatter::new(false, true);
+    let old_done = ticket("old-done", Status::Done, 120);
+    for ticket in [
+        &old_done,
+        &ticket("recent-done", Status::Done, 10),
+        &ticket("old-todo", Status::Todo, 120),
+    ] {
+        save_dated(&vibe_ticket_dir, ticket);
+    }
+
+    let bulk = ArchiveParams {
+        older_than: Some("90d".to_string()),
+        status: Some("done".to_string()),
+        dry_run: true,
+        ..params(None)
+    };
+    handle_archive_command(&bulk, project, &formatter).unwrap();
+    // Each storage instance caches tickets, so read through a fresh one
+    let load_all = || FileStorage::new(&vibe_ticket_dir).load_all().unwrap();
+    assert_eq!(load_all().len(), 3);
+
+    let bulk = ArchiveParams {
+        dry_run: false,
+        ..bulk
+    };
+    handle_archive_command(&bulk, project, &formatter).unwrap();
+    let mut remaining: Vec<_> = load_all().into_iter().map(|t| t.slug).collect();
+    remaining.sort();
+    assert_eq!(remaining, vec!["old-todo", "recent-done"]);
+
+    let archived_file = vibe_ticket_dir
+        .join("archive/tickets")
+        .join(format!("{}.yaml.gz", old_done.id));
+    let bytes = std::fs::read(&archived_file).unwrap();
+    assert_eq!(&bytes[..2], &[0x1f, 0x8b], "archive entries are gzip");
+    let archived = ArchiveStore::new(&vibe_ticket_dir)
+        .find_ticket("old-done")
+        .unwrap()
+        .unwrap();
+    assert_eq!(archived.title, "old done");
+    handle_search_command(
+        "old done", false, false, false, false, true, project, &formatter,
+    )
+    .unwrap();
+
+    // Archiving again is refused, unarchiving restores the ticket
+    assert!(handle_archive_command(&params(Some("old-done")), project, &formatter).is_err());
+    let unarchive = ArchiveParams {
+        unarchive: true,
+        ..params(Some("old-done"))
+    };
+    handle_archive_command(&unarchive, project, &formatter).unwrap();
+    let restored = FileStorage::new(&vibe_ticket_dir)
+        .load(&old_done.id)
+        .unwrap();
+    assert!(!restored.metadata.contains_key("archived"));
+    assert!(!archived_file.exists());
+}
+
+#[test]
+fn test_archive_spec() {
+    let temp_dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    let project = temp_dir.path().to_str();
+    let formatter = OutputFormatter::new(false, true);
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = manager
+        .create_spec("Export".to_string(), String::new())
+        .unwrap()
+        .id;
+    manager.set_active_spec(&spec_id).unwrap();
+
+    let archive = ArchiveParams {
+        spec: true,
+        ..params(Some(&spec_id))
+    };
+    handle_archive_command(&archive, project, &formatter).unwrap();
+    assert!(manager.list_specs().unwrap().is_empty());
+    assert_eq!(manager.get_active_spec().unwrap(), None);
+
+    let unarchive = ArchiveParams {
+        unarchive: true,
+        ..archive
+    };
+    handle_archive_command(&unarchive, project, &formatter).unwrap();
+    assert_eq!(
+        manager.load_spec(&spec_id).unwrap().metadata.title,
+        "Export"
+    );
+}
@@ -0,0 +1,88 @@
+//! Integration tests for layered configuration
+//!
+//! Runs the binary with values from the user file, the project file,
+//! environment variables and `--set`, and checks precedence and origins.
+
+use assert_cmd::Command;
+use serde_json::Value;
+use tempfile::TempDir;
+
+#[allow(deprecated)]
+fn vibe_ticket(project: &TempDir, config_home: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("vibe-ticket").unwrap();
+    cmd.current_dir(project.path())
+        .env("XDG_CONFIG_HOME", config_home.path());
+    cmd
+}
+
+/// Origin of `key` in `config show --origin` JSON
+fn origin(shown: &Value, key: &str) -> String {
+    shown[key]["origin"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_layered_config_and_origins() {
+    let project = TempDir::new().unwrap();
+    let config_home = TempDir::new().unwrap();
+    vibe_ticket(&project, &config_home)
+        .args(["init", "--name", "layers"])
+        .assert()
+        .success();
+
+    let user_dir = config_home.path().join("vibe-ticket");
+    std::fs::create_dir_all(&user_dir).unwrap();
+    std::fs::write(
+        user_dir.join("config.yaml"),
+        "ui:\n  page_size: 50\n  date_format: '%d.%m.%Y'\n",
+    )
+    .unwrap();
+
+    vibe_ticket(&project, &config_home)
+        .args(["config", "set", "git.worktree_prefix", "../wt-"])
+        .assert()
+        .success();
+    vibe_ticket(&project, &config_home)
+        .args(["config", "set", "git.worktree_enabled", "maybe"])
+        .assert()
+        .failure();
+
+    let output = vibe_ticket(&project, &config_home)
+        .env("VIBE_TICKET_UI__EMOJI", "false")
+        .env("VIBE_TICKET_UI__THEME", "light")
+        .args([
+            "--json",
+            "--set",
+            "ui.theme=dark",
+            "config",
+            "show",
+            "--origin",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let shown: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(shown["git.worktree_prefix"]["value"], "../wt-");
+    assert!(origin(&shown, "git.worktree_prefix").starts_with("project file"));
+    // The project file written by `init` sets page_size, overriding the user file
+    assert!(origin(&shown, "ui.page_size").starts_with("project file"));
+    assert_eq!(shown["ui.emoji"]["value"], false);
+    assert_eq!(
+        origin(&shown, "ui.emoji"),
+        "environment variable VIBE_TICKET_UI__EMOJI"
+    );
+    assert_eq!(shown["ui.theme"]["value"], "dark");
+    assert_eq!(origin(&shown, "ui.theme"), "--set flag");
+
+    // Values not in the project file come from the user file
+    let project_file = project.path().join(".vibe-ticket/config.yaml");
+    std::fs::write(&project_file, "project:\n  name: layers\n").unwrap();
+    let output = vibe_ticket(&project, &config_home)
+        .args(["--json", "config", "show", "ui", "--origin"])
+        .output()
+        .unwrap();
+    let shown: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(shown["ui.page_size"]["value"], 50);
+    assert!(origin(&shown, "ui.page_size").starts_with("user file"));
+    assert_eq!(origin(&shown, "ui.emoji"), "default");
+}
@@ -22,6 +22,7 @@ fn setup_test_project() -> (TempDir, OutputFormatter) {
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
         ticket_count: 0,
+        format_version: vibe_ticket::storage::FORMAT_VERSION,
     };
 
     let storage = FileStorage::new(&vibe_ticket_dir);
@@ -86,6 +87,7 @@ fn test_import_json_array() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -149,6 +151,7 @@ fn test_import_json_object() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         None, // Test auto-detection
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -199,6 +202,7 @@ fn test_import_yaml() {
     let result = handle_import_command(
         yaml_file.to_str().unwrap(),
         Some("yaml"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -236,6 +240,7 @@ fn test_import_csv() {
     let result = handle_import_command(
         csv_file.to_str().unwrap(),
         Some("csv"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -293,6 +298,7 @@ fn test_dry_run_import() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         false,
         true, // dry_run = true
         Some(temp_dir.path().to_str().unwrap()),
@@ -326,6 +332,7 @@ fn test_skip_existing_tickets() {
         created_at: chrono::Utc::now(),
         started_at: None,
         closed_at: None,
+        updated_at: None,
         assignee: None,
         tasks: vec![],
         metadata: std::collections::HashMap::new(),
@@ -375,6 +382,7 @@ fn test_skip_existing_tickets() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -448,6 +456,7 @@ fn test_validation_duplicate_ids() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         true, // skip_validation = true
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -474,6 +483,7 @@ fn test_invalid_json_format() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -495,6 +505,7 @@ fn test_auto_format_detection() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         None, // Let it auto-detect
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -528,6 +539,7 @@ tickets:
     let result = handle_import_command(
         unknown_file.to_str().unwrap(),
         None, // Let it auto-detect
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -592,6 +604,7 @@ fn test_import_with_complex_metadata() {
     let result = handle_import_command(
         json_file.to_str().unwrap(),
         Some("json"),
+        None,
         false,
         false,
         Some(temp_dir.path().to_str().unwrap()),
@@ -618,3 +631,110 @@ fn test_import_with_complex_metadata() {
     assert!(ticket.metadata.contains_key("custom_field"));
     assert!(ticket.metadata.contains_key("nested"));
 }
+
+#[test]
+fn test_github_reimport_is_idempotent() {
+    let (temp_dir, formatter) = setup_test_project();
+    let project = temp_dir.path().to_str().unwrap();
+
+    let issues = |state: &str| {
+        format!(
+            r#"[
+            {{
+                "number": 7,
+                "title": "Crash when saving",
+                "body": "Steps to reproduce",
+                "state": "{state}",
+                "url": "https://github.com/owner/repo/issues/7",
+                "labels": [{{"name": "bug"}}, {{"name": "priority: high"}}],
+                "assignees": [{{"login": "alice"}}],
+                "comments": [{{"author": {{"login": "bob"}}, "body": "Confirmed"}}],
+                "createdAt": "2025-07-28T10:00:00Z",
+                "closedAt": null
+            }}
+        ]"#
+        )
+    };
+    let json_file = temp_dir.path().join("issues.json");
+    fs::write(&json_file, issues("OPEN")).unwrap();
+
+    for _ in 0..2 {
+        let result = handle_import_command(
+            json_file.to_str().unwrap(),
+            None,
+            None,
+            false,
+            false,
+            Some(project),
+            &formatter,
+        );
+        assert!(result.is_ok());
+    }
+
+    let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+    let tickets = storage.load_all_tickets().unwrap();
+    assert_eq!(tickets.len(), 1);
+    let ticket = &tickets[0];
+    assert_eq!(ticket.slug, "github-7-crash-when-saving");
+    assert_eq!(ticket.priority, Priority::High);
+    assert_eq!(ticket.assignee.as_deref(), Some("alice"));
+    assert_eq!(ticket.metadata["external_id"], "github:owner/repo#7");
+    assert_eq!(ticket.metadata["comments"][0]["body"], "Confirmed");
+
+    // Closing the issue upstream updates the same ticket
+    fs::write(&json_file, issues("CLOSED")).unwrap();
+    handle_import_command(
+        json_file.to_str().unwrap(),
+        Some("github"),
+        None,
+        false,
+        false,
+        Some(project),
+        &formatter,
+    )
+    .unwrap();
+
+    let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+    let tickets = storage.load_all_tickets().unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].id, ticket.id);
+    assert_eq!(tickets[0].status, Status::Done);
+}
+
+#[test]
+fn test_jira_csv_with_mapping_file() {
+    let (temp_dir, formatter) = setup_test_project();
+
+    let csv_content = "Summary,Issue key,Issue id,Status,Priority,Assignee,Reporter,Created,Labels,Labels\n\
+        Slow search,PROJ-1,10001,In QA,Nice to have,alice,bob,28/Jul/25 10:00 AM,perf,search\n";
+    let csv_file = temp_dir.path().join("jira.csv");
+    fs::write(&csv_file, csv_content).unwrap();
+
+    let mapping_file = temp_dir.path().join("mapping.yaml");
+    fs::write(
+        &mapping_file,
+        "status:\n  In QA: review\npriority:\n  Nice to have: low\n",
+    )
+    .unwrap();
+
+    let result = handle_import_command(
+        csv_file.to_str().unwrap(),
+        Some("jira"),
+        Some(mapping_file.to_str().unwrap()),
+        false,
+        false,
+        Some(temp_dir.path().to_str().unwrap()),
+        &formatter,
+    );
+    assert!(result.is_ok());
+
+    let storage = FileStorage::new(temp_dir.path().join(".vibe-ticket"));
+    let ticket = storage
+        .find_ticket_by_slug("jira-proj-1-slow-search")
+        .unwrap()
+        .unwrap();
+    assert_eq!(ticket.status, Status::Review);
+    assert_eq!(ticket.priority, Priority::Low);
+    assert_eq!(ticket.tags, vec!["perf", "search"]);
+    assert_eq!(ticket.metadata["reporter"], "bob");
+}
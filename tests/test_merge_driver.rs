@@ -0,0 +1,143 @@
+//! Integration tests for the git merge driver
+//!
+//! Installs the driver with `init --install-merge-driver`, changes the same
+//! ticket on two branches and merges them with git.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+use vibe_ticket::core::{Priority, Status, Ticket};
+
+const BINARY: &str = env!("CARGO_BIN_EXE_vibe-ticket");
+
+/// `PATH` with the directory of the built binary first, so git finds the driver
+fn path_with_binary() -> std::ffi::OsString {
+    let binary_dir = Path::new(BINARY).parent().unwrap().to_path_buf();
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::join_paths(std::iter::once(binary_dir).chain(std::env::split_paths(&path))).unwrap()
+}
+
+fn run(dir: &Path, program: &str, args: &[&str]) -> Output {
+    let output = Command::new(program)
+        .current_dir(dir)
+        .args(args)
+        .env("PATH", path_with_binary())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{program} {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn git(dir: &Path, args: &[&str]) -> Output {
+    run(dir, "git", args)
+}
+
+fn vibe_ticket(dir: &Path, args: &[&str]) -> Output {
+    run(dir, BINARY, args)
+}
+
+fn ticket_file(dir: &Path) -> PathBuf {
+    std::fs::read_dir(dir.join(".vibe-ticket/tickets"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .unwrap()
+}
+
+fn read_ticket(dir: &Path) -> Ticket {
+    serde_yaml::from_str(&std::fs::read_to_string(ticket_file(dir)).unwrap()).unwrap()
+}
+
+fn commit(dir: &Path, message: &str) {
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-q", "-m", message]);
+}
+
+#[test]
+fn test_merge_driver_merges_ticket_changes_from_both_branches() {
+    let project = TempDir::new().unwrap();
+    let dir = project.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.name", "Test"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    vibe_ticket(dir, &["init", "--name", "merge", "--install-merge-driver"]);
+
+    let attributes = std::fs::read_to_string(dir.join(".gitattributes")).unwrap();
+    assert!(attributes.contains(".vibe-ticket/tickets/*.yaml merge=vibe-ticket"));
+    let driver = git(dir, &["config", "merge.vibe-ticket.driver"]);
+    assert_eq!(
+        String::from_utf8_lossy(&driver.stdout).trim(),
+        "vibe-ticket merge-driver %O %A %B %P"
+    );
+
+    vibe_ticket(
+        dir,
+        &["new", "login", "--title", "Fix login", "--tags", "bug"],
+    );
+    let slug = read_ticket(dir).slug;
+    vibe_ticket(dir, &["task", "add", "Reproduce", "--ticket", &slug]);
+    commit(dir, "Add ticket");
+
+    git(dir, &["checkout", "-q", "-b", "feature"]);
+    vibe_ticket(
+        dir,
+        &["edit", &slug, "--priority", "high", "--add-tags", "auth"],
+    );
+    vibe_ticket(dir, &["task", "add", "Write test", "--ticket", &slug]);
+    commit(dir, "Triage login");
+
+    git(dir, &["checkout", "-q", "main"]);
+    vibe_ticket(
+        dir,
+        &["edit", &slug, "--status", "doing", "--add-tags", "urgent"],
+    );
+    vibe_ticket(dir, &["task", "add", "Fix redirect", "--ticket", &slug]);
+    commit(dir, "Start login");
+
+    git(dir, &["merge", "-q", "--no-edit", "feature"]);
+    let merged = read_ticket(dir);
+    assert_eq!(merged.priority, Priority::High);
+    assert_eq!(merged.status, Status::Doing);
+    assert_eq!(merged.tags, ["bug", "urgent", "auth"]);
+    let tasks: Vec<_> = merged
+        .tasks
+        .iter()
+        .map(|task| task.title.as_str())
+        .collect();
+    assert_eq!(tasks, ["Reproduce", "Fix redirect", "Write test"]);
+}
+
+#[test]
+fn test_merge_driver_reports_true_conflicts() {
+    let project = TempDir::new().unwrap();
+    let dir = project.path();
+    let mut base = Ticket::new("login", "Fix login");
+    base.updated_at = None;
+    let mut ours = base.clone();
+    ours.title = "Fix login on Safari".to_string();
+    let mut theirs = base.clone();
+    theirs.title = "Fix login redirect".to_string();
+    for (name, ticket) in [("base", &base), ("ours", &ours), ("theirs", &theirs)] {
+        std::fs::write(dir.join(name), serde_yaml::to_string(ticket).unwrap()).unwrap();
+    }
+
+    let status = Command::new(BINARY)
+        .current_dir(dir)
+        .args([
+            "merge-driver",
+            "base",
+            "ours",
+            "theirs",
+            "tickets/login.yaml",
+        ])
+        .output()
+        .unwrap();
+    assert!(!status.status.success());
+    assert!(String::from_utf8_lossy(&status.stderr).contains("title"));
+    let content = std::fs::read_to_string(dir.join("ours")).unwrap();
+    assert!(content.contains("<<<<<<< ours\ntitle: Fix login on Safari\n=======\n"));
+}
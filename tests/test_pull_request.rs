@@ -0,0 +1,297 @@
+//! Integration tests for opening pull requests when closing tickets
+//!
+//! `close --pr` runs against a small in-process mock of the forge REST APIs.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::TempDir;
+use tiny_http::{Header, Response, Server};
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::handle_close_command;
+use vibe_ticket::config::Config;
+use vibe_ticket::core::{Status, Task, Ticket};
+use vibe_ticket::forge::ForgeKind;
+use vibe_ticket::storage::{FileStorage, TicketRepository};
+
+/// A request received by the mock forge
+#[derive(Debug, Clone)]
+struct Recorded {
+    method: String,
+    url: String,
+    body: serde_json::Value,
+}
+
+/// Start a mock forge that answers every request with `respond`
+fn start_mock(
+    requests: Arc<Mutex<Vec<Recorded>>>,
+    respond: fn(&str, &str) -> (u16, serde_json::Value),
+) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let method = request.method().to_string();
+            let url = request.url().to_string();
+            let (status, json) = respond(&method, &url);
+            requests.lock().unwrap().push(Recorded {
+                method,
+                url,
+                body: serde_json::from_str(&body).unwrap_or_default(),
+            });
+            let response = Response::from_string(json.to_string())
+                .with_status_code(status)
+                .with_header("Content-Type: application/json".parse::<Header>().unwrap());
+            let _ = request.respond(response);
+        }
+    });
+    format!("http://127.0.0.1:{port}")
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Create a Git project on branch `ticket/fix-login` with a ticket to close
+fn setup(configure: impl FnOnce(&mut Config)) -> (TempDir, FileStorage, Ticket) {
+    let temp_dir = TempDir::new().unwrap();
+    git(temp_dir.path(), &["init", "-q"]);
+    git(
+        temp_dir.path(),
+        &["checkout", "-q", "-b", "ticket/fix-login"],
+    );
+    git(
+        temp_dir.path(),
+        &["remote", "add", "origin", "git@github.com:owner/repo.git"],
+    );
+
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    std::fs::create_dir_all(vibe_ticket_dir.join("tickets")).unwrap();
+    let mut config = Config::default();
+    config.forge.token_env = Some("VIBE_TICKET_TEST_UNSET_TOKEN".to_string());
+    configure(&mut config);
+    config
+        .save_to_path(vibe_ticket_dir.join("config.yaml"))
+        .unwrap();
+
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    let mut ticket = Ticket::new("fix-login", "Fix login");
+    ticket.description = "Users cannot log in".to_string();
+    ticket.status = Status::Doing;
+    ticket.assignee = Some("alice".to_string());
+    ticket.tasks = vec![Task::new("Reproduce")];
+    storage.save(&ticket).unwrap();
+    (temp_dir, storage, ticket)
+}
+
+fn close_with_pr(temp_dir: &TempDir, draft: bool) -> vibe_ticket::error::Result<()> {
+    handle_close_command(
+        Some("fix-login".to_string()),
+        None,
+        false,
+        true,
+        draft,
+        Some(temp_dir.path().to_str().unwrap()),
+        &OutputFormatter::new(false, true),
+    )
+}
+
+#[test]
+fn test_github_pull_request_from_template() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api_url = start_mock(requests.clone(), |_, url| {
+        if url.ends_with("/requested_reviewers") {
+            (201, serde_json::json!({}))
+        } else {
+            (
+                201,
+                serde_json::json!({
+                    "number": 7,
+                    "html_url": "https://github.com/owner/repo/pull/7",
+                }),
+            )
+        }
+    });
+    let (temp_dir, storage, ticket) = setup(|config| {
+        config.forge.api_url = Some(api_url);
+        config.forge.reviewers = vec!["alice".to_string(), "bob".to_string()];
+    });
+    let templates = temp_dir.path().join(".vibe-ticket/templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(
+        templates.join("pr.md"),
+        "---\ntitle: {{ ticket.title }} ({{ ticket.priority }})\n---\n\
+         {% for task in tasks %}* {{ task.title }}\n{% endfor %}From {{ branch }} into {{ base }}\n",
+    )
+    .unwrap();
+
+    close_with_pr(&temp_dir, true).unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].url, "/repos/owner/repo/pulls");
+    assert_eq!(requests[0].body["title"], "Fix login (medium)");
+    assert_eq!(
+        requests[0].body["body"],
+        "* Reproduce\nFrom ticket/fix-login into main\n"
+    );
+    assert_eq!(requests[0].body["head"], "ticket/fix-login");
+    assert_eq!(requests[0].body["draft"], true);
+
+    // The assignee is not asked to review their own work
+    assert_eq!(
+        requests[1].url,
+        "/repos/owner/repo/pulls/7/requested_reviewers"
+    );
+    assert_eq!(requests[1].body["reviewers"], serde_json::json!(["bob"]));
+
+    let closed = storage.load(&ticket.id).unwrap();
+    assert_eq!(closed.status, Status::Done);
+    assert_eq!(
+        closed.metadata["pr_url"],
+        "https://github.com/owner/repo/pull/7"
+    );
+    assert_eq!(closed.metadata["pr_number"], 7);
+}
+
+#[test]
+fn test_gitlab_draft_merge_request() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api_url = start_mock(requests.clone(), |_, url| {
+        if url.starts_with("/users") {
+            (200, serde_json::json!([{ "id": 42 }]))
+        } else {
+            (
+                201,
+                serde_json::json!({
+                    "iid": 3,
+                    "web_url": "https://gitlab.example.com/group/sub/app/-/merge_requests/3",
+                }),
+            )
+        }
+    });
+    let (temp_dir, storage, ticket) = setup(|config| {
+        config.forge.kind = ForgeKind::GitLab;
+        config.forge.api_url = Some(api_url);
+        config.forge.repository = Some("group/sub/app".to_string());
+        config.forge.base_branch = "develop".to_string();
+        config.forge.draft = true;
+        config.forge.reviewers = vec!["bob".to_string()];
+    });
+
+    close_with_pr(&temp_dir, false).unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests[0].url, "/users?username=bob");
+    let create = &requests[1];
+    assert_eq!(create.url, "/projects/group%2Fsub%2Fapp/merge_requests");
+    assert_eq!(create.body["title"], "Draft: [fix-login] Fix login");
+    assert_eq!(create.body["target_branch"], "develop");
+    assert_eq!(create.body["reviewer_ids"], serde_json::json!([42]));
+    assert!(
+        create.body["description"]
+            .as_str()
+            .unwrap()
+            .contains("- [ ] Reproduce")
+    );
+
+    let closed = storage.load(&ticket.id).unwrap();
+    assert_eq!(
+        closed.metadata["pr_url"],
+        "https://gitlab.example.com/group/sub/app/-/merge_requests/3"
+    );
+}
+
+#[test]
+fn test_failed_pull_request_still_closes_ticket() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api_url = start_mock(requests, |_, _| {
+        (
+            422,
+            serde_json::json!({ "message": "No commits between main and ticket/fix-login" }),
+        )
+    });
+    let (temp_dir, storage, ticket) = setup(|config| config.forge.api_url = Some(api_url));
+
+    let error = close_with_pr(&temp_dir, false).unwrap_err().to_string();
+    assert!(error.contains("No commits between"), "{error}");
+
+    let closed = storage.load(&ticket.id).unwrap();
+    assert_eq!(closed.status, Status::Done);
+    assert!(!closed.metadata.contains_key("pr_url"));
+}
+
+#[test]
+fn test_failed_review_request_keeps_pull_request() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api_url = start_mock(requests, |_, url| {
+        if url.ends_with("/requested_reviewers") {
+            (
+                422,
+                serde_json::json!({ "message": "Reviews may only be requested from collaborators" }),
+            )
+        } else {
+            (
+                201,
+                serde_json::json!({
+                    "number": 7,
+                    "html_url": "https://github.com/owner/repo/pull/7",
+                }),
+            )
+        }
+    });
+    let (temp_dir, storage, ticket) = setup(|config| {
+        config.forge.api_url = Some(api_url);
+        config.forge.reviewers = vec!["mallory".to_string()];
+    });
+
+    close_with_pr(&temp_dir, false).unwrap();
+
+    let closed = storage.load(&ticket.id).unwrap();
+    assert_eq!(closed.metadata["pr_number"], 7);
+}
+
+#[test]
+fn test_unknown_gitlab_reviewer_keeps_merge_request() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api_url = start_mock(requests.clone(), |_, url| {
+        if url == "/users?username=bob" {
+            (200, serde_json::json!([{ "id": 42 }]))
+        } else if url.starts_with("/users") {
+            (200, serde_json::json!([]))
+        } else {
+            (
+                201,
+                serde_json::json!({
+                    "iid": 3,
+                    "web_url": "https://gitlab.example.com/group/app/-/merge_requests/3",
+                }),
+            )
+        }
+    });
+    let (temp_dir, storage, ticket) = setup(|config| {
+        config.forge.kind = ForgeKind::GitLab;
+        config.forge.api_url = Some(api_url);
+        config.forge.repository = Some("group/app".to_string());
+        config.forge.reviewers = vec!["nobody".to_string(), "bob".to_string()];
+    });
+
+    close_with_pr(&temp_dir, false).unwrap();
+
+    let requests = requests.lock().unwrap().clone();
+    let create = requests.last().unwrap();
+    assert_eq!(create.url, "/projects/group%2Fapp/merge_requests");
+    assert_eq!(create.body["reviewer_ids"], serde_json::json!([42]));
+    let closed = storage.load(&ticket.id).unwrap();
+    assert_eq!(closed.metadata["pr_number"], 3);
+}
@@ -0,0 +1,81 @@
+//! Integration tests for the spec ambiguity linter
+//!
+//! Checks that `spec lint` and `spec validate --ambiguities` fail on error
+//! findings only, and that validating does not edit the document.
+
+use std::fs;
+use tempfile::TempDir;
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{handle_spec_lint, handle_spec_validate};
+use vibe_ticket::specs::lint::{Rule, Severity};
+use vibe_ticket::specs::{Linter, SpecManager};
+
+const SPEC: &str = "\
+# Feature Specification: Export
+
+## Requirements
+- REQ-001: Export some tickets quickly
+- REQ-002: Include handsome charts
+
+## Acceptance Criteria
+- Given tickets, when exported, then a CSV file is written
+- Open question: TBD
+";
+
+#[test]
+fn test_lint_reports_without_mutating() {
+    let temp_dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    fs::create_dir_all(&vibe_ticket_dir).unwrap();
+    let project = temp_dir.path().to_str().unwrap();
+    let formatter = OutputFormatter::new(false, true);
+
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = manager
+        .create_spec("Export".to_string(), String::new())
+        .unwrap()
+        .id;
+    let spec_file = manager.get_spec_dir(&spec_id).join("spec.md");
+    fs::write(&spec_file, SPEC).unwrap();
+
+    let findings = Linter::for_project(&vibe_ticket_dir)
+        .lint_dir(&manager.get_spec_dir(&spec_id))
+        .unwrap();
+    let rules: Vec<_> = findings.iter().map(|f| (f.rule, f.line)).collect();
+    assert_eq!(
+        rules,
+        vec![
+            (Rule::VagueQuantifier, 4),
+            (Rule::UntestableAdjective, 4),
+            (Rule::TbdMarker, 9),
+        ]
+    );
+    assert_eq!(findings[2].severity, Severity::Error);
+
+    for format in ["text", "json", "sarif"] {
+        let error =
+            handle_spec_lint(Some(&spec_id), format, Some(project), &formatter).unwrap_err();
+        assert!(error.to_string().contains("1 lint error"));
+    }
+
+    let validate = || {
+        let output = OutputFormatter::capture();
+        handle_spec_validate(
+            Some(spec_id.clone()),
+            false,
+            true,
+            false,
+            Some(project),
+            &output,
+        )
+        .unwrap();
+        output.take_captured().messages.pop().unwrap()
+    };
+    assert!(validate().contains("validation issues"));
+    assert_eq!(fs::read_to_string(&spec_file).unwrap(), SPEC);
+
+    // Once the placeholder is resolved, only warnings remain and both pass
+    fs::write(&spec_file, SPEC.replace("TBD", "CSV only")).unwrap();
+    handle_spec_lint(Some(&spec_id), "sarif", Some(project), &formatter).unwrap();
+    assert!(validate().contains("passed all validation checks"));
+}
@@ -0,0 +1,132 @@
+//! Integration tests for spec review sign-offs
+//!
+//! Checks that a phase needs the configured quorum and required reviewers,
+//! that change requests block approval, and that editing a document
+//! withdraws its approvals.
+
+use std::path::Path;
+use tempfile::TempDir;
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{
+    handle_spec_approve, handle_spec_plan, handle_spec_request_changes, handle_spec_reviewers,
+};
+use vibe_ticket::config::Config;
+use vibe_ticket::specs::{SpecDocumentType, SpecManager, Verdict};
+
+/// Create a project needing two requirements approvals and a spec with
+/// requirements
+fn init_project(vibe_ticket_dir: &Path) -> (SpecManager, String) {
+    std::fs::create_dir_all(vibe_ticket_dir).unwrap();
+    let mut config = Config::default();
+    config
+        .spec
+        .review
+        .quorum
+        .insert(SpecDocumentType::Requirements, 2);
+    config.spec.review.enforce_order = true;
+    config
+        .save_to_path(vibe_ticket_dir.join("config.yaml"))
+        .unwrap();
+
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = manager
+        .create_spec("Export".to_string(), String::new())
+        .unwrap()
+        .id;
+    manager
+        .save_document(&spec_id, SpecDocumentType::Requirements, "REQ-001: Export")
+        .unwrap();
+    (manager, spec_id)
+}
+
+#[test]
+fn test_design_needs_two_approvals() {
+    let temp_dir = TempDir::new().unwrap();
+    let (manager, spec_id) = init_project(&temp_dir.path().join(".vibe-ticket"));
+    let project = Some(temp_dir.path().to_str().unwrap());
+    let formatter = OutputFormatter::new(false, true);
+    let plan = || {
+        handle_spec_plan(
+            Some(spec_id.clone()),
+            None,
+            None,
+            false,
+            None,
+            project,
+            &formatter,
+        )
+    };
+    let approve = |who: &str| {
+        handle_spec_approve(
+            &spec_id,
+            "requirements",
+            None,
+            Some(who),
+            project,
+            &formatter,
+        )
+    };
+
+    approve("alice").unwrap();
+    let error = plan().unwrap_err();
+    assert!(error.to_string().contains("1/2 approvals"));
+
+    handle_spec_request_changes(
+        &spec_id,
+        "requirements",
+        "Name the export format",
+        Some("bob"),
+        project,
+        &formatter,
+    )
+    .unwrap();
+    assert!(plan().is_err());
+
+    approve("bob").unwrap();
+    handle_spec_reviewers(
+        Some(&spec_id),
+        "requirements",
+        &["carol".to_string()],
+        &[],
+        project,
+        &formatter,
+    )
+    .unwrap();
+    assert!(plan().unwrap_err().to_string().contains("waiting on carol"));
+
+    approve("carol").unwrap();
+    plan().unwrap();
+    let metadata = manager.load_spec(&spec_id).unwrap().metadata;
+    assert!(metadata.progress.requirements_approved);
+    let review = &metadata.progress.reviews[&SpecDocumentType::Requirements];
+    let verdicts: Vec<_> = review
+        .sign_offs
+        .iter()
+        .map(|s| (s.reviewer.as_str(), s.verdict))
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![
+            ("alice", Verdict::Approved),
+            ("bob", Verdict::ChangesRequested),
+            ("bob", Verdict::Approved),
+            ("carol", Verdict::Approved),
+        ]
+    );
+    assert_eq!(
+        review.sign_offs[1].comment.as_deref(),
+        Some("Name the export format")
+    );
+
+    // Editing the requirements withdraws their approvals
+    manager
+        .save_document(
+            &spec_id,
+            SpecDocumentType::Requirements,
+            "REQ-001: Export CSV",
+        )
+        .unwrap();
+    let metadata = manager.load_spec(&spec_id).unwrap().metadata;
+    assert!(!metadata.progress.requirements_approved);
+    assert!(plan().unwrap_err().to_string().contains("0/2 approvals"));
+}
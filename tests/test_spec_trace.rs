@@ -0,0 +1,77 @@
+//! Integration tests for spec requirement traceability
+//!
+//! Checks that `spec trace` renders and that `spec validate` fails while a
+//! requirement has no implementing ticket.
+
+use std::fs;
+use tempfile::TempDir;
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{handle_spec_trace, handle_spec_validate};
+use vibe_ticket::core::{Status, Ticket};
+use vibe_ticket::specs::{SpecDocumentType, SpecManager, TraceMatrix};
+use vibe_ticket::storage::{FileStorage, TicketRepository};
+
+#[test]
+fn test_validate_fails_until_requirements_are_traced() {
+    let temp_dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    fs::create_dir_all(vibe_ticket_dir.join("tickets")).unwrap();
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    let project = temp_dir.path().to_str().unwrap();
+    let formatter = OutputFormatter::new(false, true);
+
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let spec_id = manager
+        .create_spec("Auth".to_string(), String::new())
+        .unwrap()
+        .id;
+    manager
+        .save_document(
+            &spec_id,
+            SpecDocumentType::Requirements,
+            "- REQ-001: Log in\n- REQ-002: Log out\n",
+        )
+        .unwrap();
+    manager
+        .save_document(
+            &spec_id,
+            SpecDocumentType::Tasks,
+            "## REQ-001\n- [ ] T001: Login form\n",
+        )
+        .unwrap();
+
+    let mut login = Ticket::new(format!("{spec_id}-t001"), "[T001] Login form");
+    login.tags = vec![spec_id.clone()];
+    login.status = Status::Doing;
+    storage.save(&login).unwrap();
+
+    handle_spec_trace(Some(&spec_id), true, Some(project), &formatter).unwrap();
+    let error = handle_spec_validate(
+        Some(spec_id.clone()),
+        false,
+        false,
+        false,
+        Some(project),
+        &formatter,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("1 traceability gap"));
+
+    let mut logout = Ticket::new("logout", "Implement REQ-002");
+    logout.tags = vec![spec_id.clone()];
+    storage.save(&logout).unwrap();
+
+    let spec = manager.load_spec(&spec_id).unwrap();
+    let matrix = TraceMatrix::build(&spec, &storage.load_all().unwrap());
+    assert_eq!(matrix.gap_count(), 0);
+    assert_eq!(matrix.rows[0].tickets[0].status, Status::Doing);
+    handle_spec_validate(
+        Some(spec_id),
+        false,
+        false,
+        false,
+        Some(project),
+        &formatter,
+    )
+    .unwrap();
+}
@@ -0,0 +1,320 @@
+//! Integration tests for GitHub issue sync
+//!
+//! These tests run `sync push` and `sync pull` against a small in-process
+//! mock of the GitHub issues REST API.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tempfile::TempDir;
+use tiny_http::{Header, Method, Response, Server};
+use vibe_ticket::cli::OutputFormatter;
+use vibe_ticket::cli::handlers::{handle_sync_pull, handle_sync_push};
+use vibe_ticket::config::Config;
+use vibe_ticket::core::{Priority, Status, Task, Ticket};
+use vibe_ticket::specs::{SpecDocumentType, SpecManager, TaskSync};
+use vibe_ticket::storage::{FileStorage, TicketRepository};
+
+/// In-memory GitHub issues of one repository
+#[derive(Default)]
+struct MockState {
+    issues: Vec<serde_json::Value>,
+    writes: usize,
+    clock: i64,
+}
+
+impl MockState {
+    fn tick(&mut self) -> String {
+        self.clock += 1;
+        chrono::DateTime::from_timestamp(1_700_000_000 + self.clock, 0)
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    fn apply(&mut self, index: usize, patch: &serde_json::Value) {
+        let updated_at = self.tick();
+        let issue = &mut self.issues[index];
+        for field in ["title", "body", "state"] {
+            if let Some(value) = patch.get(field) {
+                issue[field] = value.clone();
+            }
+        }
+        if let Some(labels) = patch.get("labels").and_then(|l| l.as_array()) {
+            issue["labels"] = labels
+                .iter()
+                .map(|name| serde_json::json!({ "name": name }))
+                .collect();
+        }
+        if let Some(assignees) = patch.get("assignees").and_then(|a| a.as_array()) {
+            issue["assignees"] = assignees
+                .iter()
+                .map(|login| serde_json::json!({ "login": login }))
+                .collect();
+        }
+        issue["updated_at"] = updated_at.into();
+    }
+
+    /// Simulate an edit made on GitHub
+    fn edit_remote(&mut self, number: u64, patch: &serde_json::Value) {
+        let index = self.index(number).unwrap();
+        self.apply(index, patch);
+    }
+
+    fn index(&self, number: u64) -> Option<usize> {
+        self.issues
+            .iter()
+            .position(|issue| issue["number"].as_u64() == Some(number))
+    }
+
+    fn handle(&mut self, method: &Method, url: &str, body: &str) -> (u16, serde_json::Value) {
+        let path = url.split('?').next().unwrap_or_default();
+        let Some(rest) = path.strip_prefix("/repos/owner/repo/issues") else {
+            return (404, serde_json::json!({ "message": "Not Found" }));
+        };
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+
+        match (method, rest.trim_start_matches('/').parse::<u64>().ok()) {
+            (Method::Get, None) => (200, self.issues.clone().into()),
+            (Method::Post, None) => {
+                self.writes += 1;
+                let number = self.issues.len() as u64 + 1;
+                self.issues.push(serde_json::json!({
+                    "number": number,
+                    "html_url": format!("https://github.com/owner/repo/issues/{number}"),
+                    "title": "",
+                    "body": null,
+                    "state": "open",
+                    "labels": [],
+                    "assignees": [],
+                    "updated_at": "",
+                }));
+                let index = self.issues.len() - 1;
+                self.apply(
+                    index,
+                    &serde_json::json!({
+                        "title": payload["title"],
+                        "body": payload["body"],
+                        "labels": payload["labels"],
+                        "assignees": payload["assignees"],
+                    }),
+                );
+                (201, self.issues[index].clone())
+            },
+            (Method::Get, Some(number)) => match self.index(number) {
+                Some(index) => (200, self.issues[index].clone()),
+                None => (404, serde_json::json!({ "message": "Not Found" })),
+            },
+            (Method::Patch, Some(number)) => match self.index(number) {
+                Some(index) => {
+                    self.writes += 1;
+                    self.apply(index, &payload);
+                    (200, self.issues[index].clone())
+                },
+                None => (404, serde_json::json!({ "message": "Not Found" })),
+            },
+            _ => (405, serde_json::json!({ "message": "Method Not Allowed" })),
+        }
+    }
+}
+
+/// Start the mock API server and return its base URL
+fn start_mock(state: Arc<Mutex<MockState>>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let (status, json) =
+                state
+                    .lock()
+                    .unwrap()
+                    .handle(request.method(), request.url(), &body);
+            let response = Response::from_string(json.to_string())
+                .with_status_code(status)
+                .with_header("Content-Type: application/json".parse::<Header>().unwrap());
+            let _ = request.respond(response);
+        }
+    });
+    format!("http://127.0.0.1:{port}")
+}
+
+/// Create a project configured to sync with the mock server
+fn setup(api_url: &str) -> (TempDir, FileStorage, OutputFormatter) {
+    let temp_dir = TempDir::new().unwrap();
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+    std::fs::create_dir_all(vibe_ticket_dir.join("tickets")).unwrap();
+
+    let mut config = Config::default();
+    config.remote.github.repository = Some("owner/repo".to_string());
+    config.remote.github.api_url = api_url.to_string();
+    config.remote.github.token_env = "VIBE_TICKET_TEST_UNSET_TOKEN".to_string();
+    config
+        .save_to_path(vibe_ticket_dir.join("config.yaml"))
+        .unwrap();
+
+    let storage = FileStorage::new(&vibe_ticket_dir);
+    (temp_dir, storage, OutputFormatter::new(false, true))
+}
+
+fn reload(temp_dir: &TempDir) -> Vec<Ticket> {
+    FileStorage::new(temp_dir.path().join(".vibe-ticket"))
+        .load_all()
+        .unwrap()
+}
+
+#[test]
+fn test_push_then_pull_round_trip() {
+    let state = Arc::new(Mutex::new(MockState::default()));
+    let api_url = start_mock(state.clone());
+    let (temp_dir, storage, formatter) = setup(&api_url);
+    let project = Some(temp_dir.path().to_str().unwrap());
+
+    let mut ticket = Ticket::new("fix-login", "Fix login");
+    ticket.description = "Users cannot log in".to_string();
+    ticket.status = Status::Doing;
+    ticket.tags = vec!["bug".to_string()];
+    ticket.tasks = vec![Task::new("Reproduce"), Task::new("Write fix")];
+    storage.save(&ticket).unwrap();
+
+    // First push creates the issue and links it
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+    {
+        let issues = state.lock().unwrap().issues.clone();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue["title"], "Fix login");
+        assert_eq!(issue["state"], "open");
+        assert!(issue["body"].as_str().unwrap().contains("- [ ] Reproduce"));
+        let labels: Vec<&str> = issue["labels"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["bug", "priority:medium", "status:doing"]);
+    }
+    let tickets = reload(&temp_dir);
+    assert_eq!(tickets[0].metadata["github_issue"], "1");
+
+    // Pushing again without local changes does not write
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+    assert_eq!(state.lock().unwrap().writes, 1);
+
+    // A remote edit is pulled into the ticket
+    state.lock().unwrap().edit_remote(
+        1,
+        &serde_json::json!({
+            "state": "closed",
+            "body": "Users cannot log in\n\n<!-- vibe-ticket:tasks -->\n## Tasks\n\n- [x] Reproduce\n- [x] Write fix\n",
+        }),
+    );
+    handle_sync_pull(false, false, project, &formatter).unwrap();
+    let pulled = &reload(&temp_dir)[0];
+    assert_eq!(pulled.id, ticket.id);
+    assert_eq!(pulled.status, Status::Done);
+    assert!(pulled.tasks.iter().all(|task| task.completed));
+    assert_eq!(pulled.tasks[0].id, ticket.tasks[0].id);
+
+    // Nothing changed locally since the pull, so a push is a no-op
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+    assert_eq!(state.lock().unwrap().writes, 1);
+}
+
+#[test]
+fn test_conflicts_are_detected() {
+    let state = Arc::new(Mutex::new(MockState::default()));
+    let api_url = start_mock(state.clone());
+    let (temp_dir, storage, formatter) = setup(&api_url);
+    let project = Some(temp_dir.path().to_str().unwrap());
+
+    storage.save(&Ticket::new("docs", "Write docs")).unwrap();
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+
+    // Both sides change
+    state
+        .lock()
+        .unwrap()
+        .edit_remote(1, &serde_json::json!({ "title": "Write the docs" }));
+    let mut ticket = reload(&temp_dir).remove(0);
+    ticket.title = "Write user docs".to_string();
+    storage.save(&ticket).unwrap();
+
+    // Neither push nor pull overwrites the other side without --force
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+    handle_sync_pull(false, false, project, &formatter).unwrap();
+    assert_eq!(state.lock().unwrap().issues[0]["title"], "Write the docs");
+    assert_eq!(reload(&temp_dir)[0].title, "Write user docs");
+
+    handle_sync_push(None, true, false, project, &formatter).unwrap();
+    assert_eq!(state.lock().unwrap().issues[0]["title"], "Write user docs");
+}
+
+#[test]
+fn test_pull_imports_new_issues() {
+    let state = Arc::new(Mutex::new(MockState::default()));
+    let api_url = start_mock(state.clone());
+    let (temp_dir, _storage, formatter) = setup(&api_url);
+    let project = Some(temp_dir.path().to_str().unwrap());
+
+    {
+        let mut state = state.lock().unwrap();
+        state.handle(
+            &Method::Post,
+            "/repos/owner/repo/issues",
+            r#"{"title": "Crash on start", "body": "Trace", "labels": ["priority:high", "ui"], "assignees": ["alice"]}"#,
+        );
+    }
+
+    // Dry run does not create tickets
+    handle_sync_pull(false, true, project, &formatter).unwrap();
+    assert!(reload(&temp_dir).is_empty());
+
+    handle_sync_pull(false, false, project, &formatter).unwrap();
+    let tickets = reload(&temp_dir);
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].slug, "github-1-crash-on-start");
+    assert_eq!(tickets[0].tags, vec!["ui"]);
+    assert_eq!(tickets[0].priority, Priority::High);
+    assert_eq!(tickets[0].assignee.as_deref(), Some("alice"));
+
+    // Pulling again finds nothing new
+    handle_sync_pull(false, false, project, &formatter).unwrap();
+    assert_eq!(reload(&temp_dir).len(), 1);
+}
+
+#[test]
+fn test_pull_updates_spec_tasks() {
+    let state = Arc::new(Mutex::new(MockState::default()));
+    let api_url = start_mock(state.clone());
+    let (temp_dir, _storage, formatter) = setup(&api_url);
+    let project = Some(temp_dir.path().to_str().unwrap());
+    let vibe_ticket_dir = temp_dir.path().join(".vibe-ticket");
+
+    let manager = SpecManager::new(vibe_ticket_dir.join("specs"));
+    let metadata = manager
+        .create_spec("Login".to_string(), String::new())
+        .unwrap();
+    manager
+        .save_document(
+            &metadata.id,
+            SpecDocumentType::Tasks,
+            "- [ ] T001 Fix login\n",
+        )
+        .unwrap();
+    TaskSync::new(&vibe_ticket_dir)
+        .export(&manager.load_spec(&metadata.id).unwrap())
+        .unwrap();
+    handle_sync_push(None, false, false, project, &formatter).unwrap();
+
+    state
+        .lock()
+        .unwrap()
+        .edit_remote(1, &serde_json::json!({ "state": "closed" }));
+    handle_sync_pull(false, false, project, &formatter).unwrap();
+
+    assert_eq!(reload(&temp_dir)[0].status, Status::Done);
+    let tasks =
+        std::fs::read_to_string(manager.get_document_path(&metadata.id, SpecDocumentType::Tasks))
+            .unwrap();
+    assert_eq!(tasks, "- [x] T001 Fix login\n");
+}
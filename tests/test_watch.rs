@@ -0,0 +1,115 @@
+//! Integration tests for watch mode
+//!
+//! Runs `vibe-ticket watch` in the background, changes tickets from other
+//! processes and checks the JSON lines it streams.
+
+use assert_cmd::Command;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[allow(deprecated)]
+fn vibe_ticket(project: &TempDir, args: &[&str]) {
+    Command::cargo_bin("vibe-ticket")
+        .unwrap()
+        .current_dir(project.path())
+        .args(args)
+        .assert()
+        .success();
+}
+
+/// Watch process that is killed when dropped
+struct Watch {
+    child: Child,
+    lines: mpsc::Receiver<Value>,
+}
+
+impl Watch {
+    fn start(project: &TempDir, args: &[&str]) -> Self {
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_vibe-ticket"))
+            .current_dir(project.path())
+            .arg("watch")
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let line = line.unwrap();
+                if sender.send(serde_json::from_str(&line).unwrap()).is_err() {
+                    return;
+                }
+            }
+        });
+        // Give the watcher time to start watching
+        std::thread::sleep(Duration::from_millis(500));
+        Self { child, lines }
+    }
+
+    fn next(&self) -> Value {
+        self.lines.recv_timeout(Duration::from_secs(10)).unwrap()
+    }
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_watch_streams_changes_from_other_processes() {
+    let project = TempDir::new().unwrap();
+    vibe_ticket(&project, &["init", "--name", "watch"]);
+    let watch = Watch::start(
+        &project,
+        &[
+            "--filter",
+            "tag:bug",
+            "--exec",
+            "echo \"$VIBE_TICKET_EVENT $VIBE_TICKET_SLUG\" >> exec.log",
+        ],
+    );
+
+    vibe_ticket(&project, &["new", "docs", "--title", "Write docs"]);
+    vibe_ticket(
+        &project,
+        &["new", "crash", "--title", "Fix crash", "--tags", "bug"],
+    );
+    let created = watch.next();
+    assert_eq!(created["event"], "created");
+    assert_eq!(created["title"], "Fix crash");
+    let slug = created["slug"].as_str().unwrap().to_string();
+
+    vibe_ticket(&project, &["edit", &slug, "--status", "doing"]);
+    let mut events = Vec::new();
+    while !events.contains(&"status_changed".to_string()) {
+        let line = watch.next();
+        assert_eq!(line["slug"], slug.as_str());
+        if line["event"] == "status_changed" {
+            assert_eq!(line["old_status"], "Todo");
+            assert_eq!(line["new_status"], "Doing");
+        }
+        events.push(line["event"].as_str().unwrap().to_string());
+    }
+
+    // Changes are printed before the --exec command runs
+    let status_changed = format!("status_changed {slug}\n");
+    let mut log = String::new();
+    for _ in 0..50 {
+        log = std::fs::read_to_string(project.path().join("exec.log")).unwrap_or_default();
+        if log.contains(&status_changed) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert!(log.starts_with(&format!("created {slug}\n")));
+    assert!(log.contains(&status_changed));
+    assert!(!log.contains("docs"));
+}